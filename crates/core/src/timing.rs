@@ -159,6 +159,17 @@ impl TimingMeasurement {
         self.checkpoints.push((label.into(), self.clock.now()));
     }
 
+    /// Record a checkpoint at a timestamp taken earlier
+    #[inline]
+    pub fn checkpoint_at<S: Into<String>>(&mut self, label: S, at: Timestamp) {
+        self.checkpoints.push((label.into(), at));
+    }
+
+    /// Remove every checkpoint with the given label
+    pub fn remove_checkpoint(&mut self, label: &str) {
+        self.checkpoints.retain(|(l, _)| l != label);
+    }
+
     /// Get the start timestamp
     #[inline]
     pub fn start_time(&self) -> Timestamp {
@@ -170,6 +181,24 @@ impl TimingMeasurement {
         &self.checkpoints
     }
 
+    /// Get the timestamp of the first checkpoint with the given label
+    pub fn checkpoint_time(&self, label: &str) -> Option<Timestamp> {
+        self.checkpoints
+            .iter()
+            .find(|(l, _)| l == label)
+            .map(|(_, ts)| *ts)
+    }
+
+    /// Duration between two labelled checkpoints, if both were recorded
+    pub fn between(&self, from: &str, to: &str) -> Option<Duration> {
+        Some(self.checkpoint_time(to)?.duration_since(self.checkpoint_time(from)?))
+    }
+
+    /// Duration from the start to a labelled checkpoint, if it was recorded
+    pub fn since_start(&self, label: &str) -> Option<Duration> {
+        Some(self.checkpoint_time(label)?.duration_since(self.start))
+    }
+
     /// Calculate total duration since start
     #[inline]
     pub fn total_duration(&self) -> Duration {
@@ -309,6 +338,21 @@ mod tests {
         assert!(result.get_checkpoint("checkpoint2").is_some());
     }
 
+    #[test]
+    fn test_checkpoint_lookup() {
+        let engine = TimingEngine::new();
+        let mut measurement = engine.start();
+
+        measurement.checkpoint("connect_start");
+        thread::sleep(Duration::from_micros(100));
+        measurement.checkpoint("connect_end");
+
+        assert!(measurement.checkpoint_time("connect_start").is_some());
+        assert!(measurement.between("connect_start", "connect_end").unwrap().as_micros() >= 100);
+        assert!(measurement.since_start("connect_end").unwrap().as_micros() >= 100);
+        assert!(measurement.between("connect_start", "missing").is_none());
+    }
+
     #[test]
     fn test_timing_precision() {
         let clock = Clock::new();
@@ -76,6 +76,8 @@ pub enum Provider {
     AzureOpenAI,
     /// Generic OpenAI-compatible endpoint
     Generic,
+    /// Local command speaking the exec protocol
+    Exec,
 }
 
 impl Provider {
@@ -88,6 +90,7 @@ impl Provider {
             Provider::AwsBedrock => "aws-bedrock",
             Provider::AzureOpenAI => "azure-openai",
             Provider::Generic => "generic",
+            Provider::Exec => "exec",
         }
     }
 }
@@ -116,6 +119,19 @@ pub struct RequestConfig {
     pub max_retries: u32,
 }
 
+/// Kind of content carried by a token event
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenKind {
+    /// Visible response content
+    #[default]
+    Content,
+    /// Thinking/reasoning content produced before the visible response
+    Thinking,
+    /// Tool call start or tool argument fragment
+    ToolCall,
+}
+
 /// Streaming token event
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TokenEvent {
@@ -123,6 +139,9 @@ pub struct TokenEvent {
     pub request_id: RequestId,
     /// Token sequence number (0 = first token)
     pub sequence: u64,
+    /// Kind of content this token carries
+    #[serde(default)]
+    pub kind: TokenKind,
     /// Token content (if available)
     pub content: Option<String>,
     /// Timestamp when token was received
@@ -224,6 +243,7 @@ mod tests {
         assert_eq!(Provider::OpenAI.to_string(), "openai");
         assert_eq!(Provider::Anthropic.to_string(), "anthropic");
         assert_eq!(Provider::Google.to_string(), "google");
+        assert_eq!(Provider::Exec.to_string(), "exec");
     }
 
     #[test]
@@ -250,6 +270,7 @@ mod tests {
         let event = TokenEvent {
             request_id: RequestId::new(),
             sequence: 0,
+            kind: TokenKind::Content,
             content: Some("Hello".to_string()),
             timestamp_nanos: 1000000,
             time_since_start: Duration::from_millis(10),
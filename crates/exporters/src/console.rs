@@ -120,6 +120,50 @@ impl ConsoleExporter {
             }
         }
 
+        // Events per read above 1 means events arrived buffered together
+        if let Some(mean) = metrics.stream_reads.mean_events_per_read() {
+            builder.push_record([
+                "Events per Read",
+                &format!(
+                    "{:.2} (max {})",
+                    mean, metrics.stream_reads.max_events_per_read
+                ),
+            ]);
+        }
+
+        // Retried initial requests; their backoff is part of the latencies
+        if metrics.retries.retried_requests > 0 {
+            builder.push_record([
+                "Retried Requests",
+                &format!(
+                    "{} ({} retries, {:.2}s backoff)",
+                    metrics.retries.retried_requests,
+                    metrics.retries.total_retries,
+                    metrics.retries.total_backoff.as_secs_f64()
+                ),
+            ]);
+        }
+
+        // Embeddings throughput, when embeddings requests were run
+        let embeddings = &metrics.embeddings;
+        if let Some(batch_size) = embeddings.mean_batch_size() {
+            builder.push_record([
+                "Vectors/sec",
+                &format!(
+                    "{:.1} (p95 {:.1})",
+                    embeddings.mean_vectors_per_second, embeddings.p95_vectors_per_second
+                ),
+            ]);
+            builder.push_record([
+                "Inputs/sec",
+                &format!("{:.1}", embeddings.mean_inputs_per_second),
+            ]);
+            builder.push_record(["Batch Size", &format!("{:.1}", batch_size)]);
+            if let Some(dimensions) = embeddings.dimensions {
+                builder.push_record(["Dimensions", &dimensions.to_string()]);
+            }
+        }
+
         let mut table = builder.build();
         table.with(Style::rounded());
 
@@ -149,6 +193,24 @@ impl ConsoleExporter {
         // Header
         builder.push_record(["Metric", "Min", "Mean", "P50", "P95", "P99", "Max"]);
 
+        // Response headers, first stream event and realtime session timings,
+        // which precede the first token
+        let lead_rows = [
+            ("Time to First Byte", &metrics.time_to_first_byte_distribution),
+            ("Time to First Event", &metrics.time_to_first_event_distribution),
+            ("Session Setup", &metrics.session_setup_distribution),
+            ("Response TTFT", &metrics.response_ttft_distribution),
+        ];
+        for (label, dist) in lead_rows {
+            if dist.is_empty() {
+                continue;
+            }
+            let values = Self::format_latency_dist(dist);
+            let mut row = vec![label];
+            row.extend(values.iter().map(|s| s.as_str()));
+            builder.push_record(row);
+        }
+
         // TTFT row
         let ttft_values = Self::format_latency_dist(&metrics.ttft_distribution);
         let mut ttft_row = vec!["Time to First Token"];
@@ -167,6 +229,40 @@ impl ConsoleExporter {
         total_row.extend(total_values.iter().map(|s| s.as_str()));
         builder.push_record(total_row);
 
+        // Thinking-phase rows, only when extended thinking was used
+        if !metrics.thinking_ttft_distribution.is_empty() {
+            let phases = [
+                ("Time to First Thinking", &metrics.thinking_ttft_distribution),
+                ("Thinking Duration", &metrics.thinking_duration_distribution),
+                ("Time to First Visible", &metrics.visible_ttft_distribution),
+            ];
+            for (label, dist) in phases {
+                let values = Self::format_latency_dist(dist);
+                let mut row = vec![label];
+                row.extend(values.iter().map(|s| s.as_str()));
+                builder.push_record(row);
+            }
+        }
+
+        // Reasoning, tool-call and prompt-cache rows, only when some requests had them
+        let optional_rows = [
+            ("Reasoning Time", &metrics.reasoning_time_distribution),
+            ("Time to First Tool Call", &metrics.tool_call_ttft_distribution),
+            ("Tool Argument Generation", &metrics.tool_call_duration_distribution),
+            ("TTFT (Cache Hit)", &metrics.ttft_cache_hit_distribution),
+            ("TTFT (Cache Miss)", &metrics.ttft_cache_miss_distribution),
+            ("Server Processing", &metrics.server_processing_distribution),
+        ];
+        for (label, dist) in optional_rows {
+            if dist.is_empty() {
+                continue;
+            }
+            let values = Self::format_latency_dist(dist);
+            let mut row = vec![label];
+            row.extend(values.iter().map(|s| s.as_str()));
+            builder.push_record(row);
+        }
+
         let mut table = builder.build();
         table.with(Style::rounded());
 
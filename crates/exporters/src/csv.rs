@@ -71,6 +71,11 @@ impl CsvExporter {
         nanos as f64 / 1_000_000.0
     }
 
+    /// Format an optional duration in milliseconds, empty when absent
+    fn optional_ms(duration: Option<std::time::Duration>) -> String {
+        duration.map_or(String::new(), |d| format!("{:.3}", Self::duration_to_ms(d.as_nanos())))
+    }
+
     /// Export latency statistics to CSV rows
     fn export_latency_stats_csv(&self, metrics: &AggregatedMetrics) -> Result<String> {
         let mut output = String::new();
@@ -111,6 +116,23 @@ impl CsvExporter {
             ]
         };
 
+        // Response headers, first stream event, realtime session timings and
+        // server-reported processing time, when recorded
+        let lead = [
+            ("time_to_first_byte", &metrics.time_to_first_byte_distribution),
+            ("time_to_first_event", &metrics.time_to_first_event_distribution),
+            ("session_setup", &metrics.session_setup_distribution),
+            ("response_ttft", &metrics.response_ttft_distribution),
+            ("server_processing", &metrics.server_processing_distribution),
+        ];
+        for (name, dist) in lead {
+            if dist.is_empty() {
+                continue;
+            }
+            writeln!(output, "{}", self.join_fields(&export_dist(name, dist)))
+                .map_err(|e| crate::ExportError::Format(e.to_string()))?;
+        }
+
         // TTFT
         writeln!(
             output,
@@ -173,6 +195,15 @@ impl Exporter for CsvExporter {
                     "tokens_per_second".to_string(),
                     "cost_usd".to_string(),
                     "error".to_string(),
+                    "time_to_first_byte_ms".to_string(),
+                    "time_to_first_event_ms".to_string(),
+                    "provider_request_id".to_string(),
+                    "server_processing_ms".to_string(),
+                    "embedding_inputs".to_string(),
+                    "vectors_per_second".to_string(),
+                    "inputs_per_second".to_string(),
+                    "session_setup_ms".to_string(),
+                    "response_ttft_ms".to_string(),
                 ])
             )
             .map_err(|e| crate::ExportError::Format(e.to_string()))?;
@@ -196,7 +227,16 @@ impl Exporter for CsvExporter {
                     req.thinking_tokens.map_or(String::new(), |t| t.to_string()),
                     format!("{:.3}", req.tokens_per_second),
                     req.cost_usd.map_or(String::new(), |c| format!("{:.4}", c)),
-                    self.escape_field(&req.error.as_deref().unwrap_or("")),
+                    self.escape_field(req.error.as_deref().unwrap_or("")),
+                    Self::optional_ms(req.time_to_first_byte),
+                    Self::optional_ms(req.time_to_first_event),
+                    self.escape_field(req.provider_request_id.as_deref().unwrap_or("")),
+                    Self::optional_ms(req.server_processing_time),
+                    req.embedding_inputs.map_or(String::new(), |n| n.to_string()),
+                    req.vectors_per_second().map_or(String::new(), |r| format!("{:.3}", r)),
+                    req.inputs_per_second().map_or(String::new(), |r| format!("{:.3}", r)),
+                    Self::optional_ms(req.session_setup_time),
+                    Self::optional_ms(req.response_ttft),
                 ])
             )
             .map_err(|e| crate::ExportError::Format(e.to_string()))?;
@@ -220,6 +260,8 @@ mod tests {
         assert!(result.contains("metric,min_ms"));
         assert!(result.contains("ttft,"));
         assert!(result.contains("inter_token,"));
+        // Distributions without samples are left out
+        assert!(!result.contains("time_to_first_byte,"));
     }
 
     #[test]
@@ -233,6 +275,12 @@ mod tests {
         assert!(result.contains("claude-3-opus"));
         assert!(result.contains("openai"));
         assert!(result.contains("anthropic"));
+        assert!(result.contains("success,ttft_ms"));
+        assert!(result.contains(
+            "error,time_to_first_byte_ms,time_to_first_event_ms,provider_request_id,\
+             server_processing_ms,embedding_inputs,vectors_per_second,inputs_per_second,\
+             session_setup_ms,response_ttft_ms"
+        ));
     }
 
     #[test]
@@ -95,7 +95,10 @@ pub trait Exporter {
 mod tests {
     use super::*;
     use llm_latency_lens_core::{Provider, RequestId, SessionId};
-    use llm_latency_lens_metrics::{LatencyDistribution, ThroughputStats};
+    use llm_latency_lens_metrics::{
+        EmbeddingStats, LatencyDistribution, ReasoningTokenStats, RetryStats, StreamReadStats,
+        ThroughputStats, TokenCountSource,
+    };
     use std::time::Duration;
 
     pub(crate) fn create_test_metrics() -> AggregatedMetrics {
@@ -115,7 +118,8 @@ mod tests {
                 p90: Duration::from_millis(250),
                 p95: Duration::from_millis(280),
                 p99: Duration::from_millis(295),
-                p999: Duration::from_millis(299),
+                p99_9: Duration::from_millis(299),
+                sample_count: 9,
             },
             inter_token_distribution: LatencyDistribution {
                 min: Duration::from_millis(5),
@@ -126,7 +130,8 @@ mod tests {
                 p90: Duration::from_millis(15),
                 p95: Duration::from_millis(18),
                 p99: Duration::from_millis(19),
-                p999: Duration::from_millis(20),
+                p99_9: Duration::from_millis(20),
+                sample_count: 9,
             },
             total_latency_distribution: LatencyDistribution {
                 min: Duration::from_secs(1),
@@ -137,12 +142,31 @@ mod tests {
                 p90: Duration::from_millis(2500),
                 p95: Duration::from_millis(2800),
                 p99: Duration::from_millis(2950),
-                p999: Duration::from_millis(2990),
+                p99_9: Duration::from_millis(2990),
+                sample_count: 9,
             },
+            thinking_ttft_distribution: LatencyDistribution::empty(),
+            thinking_duration_distribution: LatencyDistribution::empty(),
+            visible_ttft_distribution: LatencyDistribution::empty(),
+            reasoning_time_distribution: LatencyDistribution::empty(),
+            reasoning_tokens: ReasoningTokenStats::empty(),
+            tool_call_ttft_distribution: LatencyDistribution::empty(),
+            tool_call_duration_distribution: LatencyDistribution::empty(),
+            ttft_cache_hit_distribution: LatencyDistribution::empty(),
+            ttft_cache_miss_distribution: LatencyDistribution::empty(),
+            time_to_first_byte_distribution: LatencyDistribution::empty(),
+            time_to_first_event_distribution: LatencyDistribution::empty(),
+            session_setup_distribution: LatencyDistribution::empty(),
+            response_ttft_distribution: LatencyDistribution::empty(),
+            server_processing_distribution: LatencyDistribution::empty(),
+            stream_reads: StreamReadStats::empty(),
+            retries: RetryStats::empty(),
+            embeddings: EmbeddingStats::empty(),
             throughput: ThroughputStats {
                 mean_tokens_per_second: 50.0,
                 min_tokens_per_second: 30.0,
                 max_tokens_per_second: 70.0,
+                std_dev_tokens_per_second: 10.0,
                 p50_tokens_per_second: 50.0,
                 p95_tokens_per_second: 65.0,
                 p99_tokens_per_second: 68.0,
@@ -150,6 +174,9 @@ mod tests {
             total_input_tokens: 1000,
             total_output_tokens: 2000,
             total_thinking_tokens: Some(100),
+            total_cache_creation_tokens: None,
+            total_cache_read_tokens: None,
+            estimated_token_requests: 0,
             total_cost_usd: Some(5.50),
             provider_breakdown: vec![
                 (Provider::OpenAI, 5),
@@ -180,6 +207,39 @@ mod tests {
                 input_tokens: 100,
                 output_tokens: 200,
                 thinking_tokens: None,
+                input_token_source: TokenCountSource::Reported,
+                output_token_source: TokenCountSource::Reported,
+                cache_creation_tokens: None,
+                cache_read_tokens: None,
+                cache_hit: None,
+                time_to_first_thinking_token: None,
+                thinking_duration: None,
+                time_to_first_visible_token: None,
+                reasoning_time: None,
+                time_to_first_tool_call: None,
+                tool_call_duration: None,
+                dns_time: None,
+                tcp_connect_time: None,
+                tls_handshake_time: None,
+                time_to_first_byte: None,
+                time_to_first_event: None,
+                session_setup_time: None,
+                response_ttft: None,
+                provider_request_id: None,
+                server_processing_time: None,
+                rate_limit_remaining_requests: None,
+                rate_limit_remaining_tokens: None,
+                attempts: None,
+                retry_backoff: None,
+                network_reads: None,
+                stream_events: None,
+                max_events_per_read: None,
+                response_body_bytes: None,
+                body_download_time: None,
+                body_parse_time: None,
+                embedding_inputs: None,
+                embedding_vectors: None,
+                embedding_dimensions: None,
                 tokens_per_second: 50.0,
                 cost_usd: Some(0.50),
                 success: true,
@@ -201,6 +261,39 @@ mod tests {
                 input_tokens: 150,
                 output_tokens: 300,
                 thinking_tokens: Some(20),
+                input_token_source: TokenCountSource::Reported,
+                output_token_source: TokenCountSource::Reported,
+                cache_creation_tokens: None,
+                cache_read_tokens: None,
+                cache_hit: None,
+                time_to_first_thinking_token: None,
+                thinking_duration: None,
+                time_to_first_visible_token: None,
+                reasoning_time: None,
+                time_to_first_tool_call: None,
+                tool_call_duration: None,
+                dns_time: None,
+                tcp_connect_time: None,
+                tls_handshake_time: None,
+                time_to_first_byte: None,
+                time_to_first_event: None,
+                session_setup_time: None,
+                response_ttft: None,
+                provider_request_id: None,
+                server_processing_time: None,
+                rate_limit_remaining_requests: None,
+                rate_limit_remaining_tokens: None,
+                attempts: None,
+                retry_backoff: None,
+                network_reads: None,
+                stream_events: None,
+                max_events_per_read: None,
+                response_body_bytes: None,
+                body_download_time: None,
+                body_parse_time: None,
+                embedding_inputs: None,
+                embedding_vectors: None,
+                embedding_dimensions: None,
                 tokens_per_second: 55.0,
                 cost_usd: Some(0.75),
                 success: true,
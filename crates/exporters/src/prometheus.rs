@@ -155,6 +155,25 @@ impl PrometheusExporter {
 
         Ok(())
     }
+
+    /// Export a gauge metric
+    fn export_gauge(
+        &self,
+        output: &mut String,
+        metric_name: &str,
+        help_text: &str,
+        value: f64,
+    ) -> Result<()> {
+        let full_metric_name = format!("{}_{}", self.prefix, metric_name);
+
+        self.write_help(output, &full_metric_name, help_text)?;
+        self.write_type(output, &full_metric_name, "gauge")?;
+
+        writeln!(output, "{} {}", full_metric_name, value)
+            .map_err(|e| crate::ExportError::Format(e.to_string()))?;
+
+        Ok(())
+    }
 }
 
 impl Default for PrometheusExporter {
@@ -191,6 +210,57 @@ impl Exporter for PrometheusExporter {
             metrics.failed_requests,
         )?;
 
+        // Response headers and first stream event statistics, when recorded
+        if !metrics.time_to_first_byte_distribution.is_empty() {
+            self.export_summary(
+                &mut output,
+                "time_to_first_byte_milliseconds",
+                "Time to response headers in milliseconds",
+                &metrics.time_to_first_byte_distribution,
+                &[],
+            )?;
+        }
+        if !metrics.time_to_first_event_distribution.is_empty() {
+            self.export_summary(
+                &mut output,
+                "time_to_first_event_milliseconds",
+                "Time to first stream event in milliseconds",
+                &metrics.time_to_first_event_distribution,
+                &[],
+            )?;
+        }
+
+        // Realtime session timings (WebSocket transports only)
+        if !metrics.session_setup_distribution.is_empty() {
+            self.export_summary(
+                &mut output,
+                "session_setup_milliseconds",
+                "Realtime session setup time in milliseconds",
+                &metrics.session_setup_distribution,
+                &[],
+            )?;
+        }
+        if !metrics.response_ttft_distribution.is_empty() {
+            self.export_summary(
+                &mut output,
+                "response_ttft_milliseconds",
+                "Time to first token from the response request on an open session in milliseconds",
+                &metrics.response_ttft_distribution,
+                &[],
+            )?;
+        }
+
+        // Server-reported processing time, when the provider reports it
+        if !metrics.server_processing_distribution.is_empty() {
+            self.export_summary(
+                &mut output,
+                "server_processing_milliseconds",
+                "Server-reported processing time in milliseconds",
+                &metrics.server_processing_distribution,
+                &[],
+            )?;
+        }
+
         // Time to first token statistics
         self.export_summary(
             &mut output,
@@ -218,6 +288,28 @@ impl Exporter for PrometheusExporter {
             &[],
         )?;
 
+        // Embeddings throughput, when embeddings requests were run
+        if metrics.embeddings.request_count > 0 {
+            self.export_counter(
+                &mut output,
+                "embedding_vectors_total",
+                "Total number of embedding vectors returned",
+                metrics.embeddings.total_vectors,
+            )?;
+            self.export_gauge(
+                &mut output,
+                "embedding_vectors_per_second",
+                "Mean embedding vectors per second",
+                metrics.embeddings.mean_vectors_per_second,
+            )?;
+            self.export_gauge(
+                &mut output,
+                "embedding_inputs_per_second",
+                "Mean embedding inputs per second",
+                metrics.embeddings.mean_inputs_per_second,
+            )?;
+        }
+
         Ok(output)
     }
 
@@ -283,6 +375,22 @@ mod tests {
         assert!(!result.contains("llm_latency_lens"));
     }
 
+    #[test]
+    fn test_prometheus_export_embeddings() {
+        let mut metrics = create_test_metrics();
+        let exporter = PrometheusExporter::new();
+        assert!(!exporter.export(&metrics).unwrap().contains("embedding"));
+
+        metrics.embeddings.request_count = 2;
+        metrics.embeddings.total_vectors = 64;
+        metrics.embeddings.mean_vectors_per_second = 160.0;
+
+        let result = exporter.export(&metrics).unwrap();
+        assert!(result.contains("llm_latency_lens_embedding_vectors_total 64"));
+        assert!(result.contains("# TYPE llm_latency_lens_embedding_vectors_per_second gauge"));
+        assert!(result.contains("llm_latency_lens_embedding_vectors_per_second 160"));
+    }
+
     #[test]
     fn test_prometheus_export_requests() {
         let requests = create_test_requests();
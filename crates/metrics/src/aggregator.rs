@@ -4,7 +4,10 @@
 //! statistical distributions with percentile calculations.
 
 use crate::collector::{MetricsCollector, MetricsError};
-use crate::types::{AggregatedMetrics, LatencyDistribution, ThroughputStats};
+use crate::types::{
+    AggregatedMetrics, EmbeddingStats, LatencyDistribution, ReasoningTokenStats, RetryStats,
+    StreamReadStats, ThroughputStats,
+};
 use hdrhistogram::Histogram;
 use llm_latency_lens_core::Provider;
 use std::collections::HashMap;
@@ -69,6 +72,36 @@ impl MetricsAggregator {
             Self::calculate_latency_distribution(&snapshot.global_histograms.inter_token)?;
         let total_latency_distribution =
             Self::calculate_latency_distribution(&snapshot.global_histograms.total_latency)?;
+        let thinking_ttft_distribution =
+            Self::calculate_latency_distribution(&snapshot.global_histograms.thinking_ttft)?;
+        let thinking_duration_distribution =
+            Self::calculate_latency_distribution(&snapshot.global_histograms.thinking_duration)?;
+        let visible_ttft_distribution =
+            Self::calculate_latency_distribution(&snapshot.global_histograms.visible_ttft)?;
+        let reasoning_time_distribution =
+            Self::calculate_latency_distribution(&snapshot.global_histograms.reasoning_time)?;
+        let reasoning_tokens = Self::calculate_reasoning_token_stats(&snapshot.request_metrics);
+        let stream_reads = Self::calculate_stream_read_stats(&snapshot.request_metrics);
+        let retries = Self::calculate_retry_stats(&snapshot.request_metrics);
+        let embeddings = Self::calculate_embedding_stats(&snapshot.request_metrics);
+        let tool_call_ttft_distribution =
+            Self::calculate_latency_distribution(&snapshot.global_histograms.tool_call_ttft)?;
+        let tool_call_duration_distribution =
+            Self::calculate_latency_distribution(&snapshot.global_histograms.tool_call_duration)?;
+        let ttft_cache_hit_distribution =
+            Self::calculate_latency_distribution(&snapshot.global_histograms.ttft_cache_hit)?;
+        let ttft_cache_miss_distribution =
+            Self::calculate_latency_distribution(&snapshot.global_histograms.ttft_cache_miss)?;
+        let time_to_first_byte_distribution =
+            Self::calculate_latency_distribution(&snapshot.global_histograms.time_to_first_byte)?;
+        let time_to_first_event_distribution =
+            Self::calculate_latency_distribution(&snapshot.global_histograms.time_to_first_event)?;
+        let session_setup_distribution =
+            Self::calculate_latency_distribution(&snapshot.global_histograms.session_setup)?;
+        let response_ttft_distribution =
+            Self::calculate_latency_distribution(&snapshot.global_histograms.response_ttft)?;
+        let server_processing_distribution =
+            Self::calculate_latency_distribution(&snapshot.global_histograms.server_processing)?;
 
         // Calculate throughput statistics
         let throughput = Self::calculate_throughput_stats(&snapshot.global_histograms.throughput)?;
@@ -102,10 +135,34 @@ impl MetricsAggregator {
             ttft_distribution,
             inter_token_distribution,
             total_latency_distribution,
+            thinking_ttft_distribution,
+            thinking_duration_distribution,
+            visible_ttft_distribution,
+            reasoning_time_distribution,
+            reasoning_tokens,
+            tool_call_ttft_distribution,
+            tool_call_duration_distribution,
+            ttft_cache_hit_distribution,
+            ttft_cache_miss_distribution,
+            time_to_first_byte_distribution,
+            time_to_first_event_distribution,
+            session_setup_distribution,
+            response_ttft_distribution,
+            server_processing_distribution,
+            stream_reads,
+            retries,
+            embeddings,
             throughput,
             total_input_tokens: snapshot.total_input_tokens,
             total_output_tokens: snapshot.total_output_tokens,
             total_thinking_tokens,
+            total_cache_creation_tokens: Self::sum_reported(&snapshot.request_metrics, |m| {
+                m.cache_creation_tokens
+            }),
+            total_cache_read_tokens: Self::sum_reported(&snapshot.request_metrics, |m| {
+                m.cache_read_tokens
+            }),
+            estimated_token_requests: Self::count_estimated(&snapshot.request_metrics),
             total_cost_usd,
             provider_breakdown,
             model_breakdown,
@@ -175,6 +232,109 @@ impl MetricsAggregator {
         })
     }
 
+    /// Calculate reasoning token statistics over successful requests that
+    /// reported reasoning tokens
+    fn calculate_reasoning_token_stats(
+        metrics: &[crate::types::RequestMetrics],
+    ) -> ReasoningTokenStats {
+        let counts: Vec<u64> = metrics
+            .iter()
+            .filter(|m| m.success)
+            .filter_map(|m| m.thinking_tokens)
+            .filter(|&t| t > 0)
+            .collect();
+
+        ReasoningTokenStats::from_counts(&counts)
+    }
+
+    /// Sum a token count over successful requests, or None if no request
+    /// reported it
+    fn sum_reported(
+        metrics: &[crate::types::RequestMetrics],
+        count: impl Fn(&crate::types::RequestMetrics) -> Option<u64>,
+    ) -> Option<u64> {
+        metrics
+            .iter()
+            .filter(|m| m.success)
+            .filter_map(count)
+            .reduce(|total, tokens| total + tokens)
+    }
+
+    /// Count successful requests whose token counts were not all reported
+    fn count_estimated(metrics: &[crate::types::RequestMetrics]) -> u64 {
+        metrics
+            .iter()
+            .filter(|m| m.success && !m.tokens_reported())
+            .count() as u64
+    }
+
+    /// Sum network reads and stream events over successful requests that
+    /// recorded them
+    fn calculate_stream_read_stats(metrics: &[crate::types::RequestMetrics]) -> StreamReadStats {
+        metrics
+            .iter()
+            .filter(|m| m.success)
+            .filter_map(|m| Some((m.network_reads?, m.stream_events?, m.max_events_per_read)))
+            .fold(StreamReadStats::empty(), |mut stats, (reads, events, max)| {
+                stats.request_count += 1;
+                stats.total_reads += reads;
+                stats.total_events += events;
+                stats.max_events_per_read = stats.max_events_per_read.max(max.unwrap_or(0));
+                stats
+            })
+    }
+
+    /// Sum retries and backoff over successful requests that recorded their
+    /// attempts
+    fn calculate_retry_stats(metrics: &[crate::types::RequestMetrics]) -> RetryStats {
+        metrics
+            .iter()
+            .filter(|m| m.success)
+            .filter_map(|m| Some((m.attempts?, m.retry_backoff.unwrap_or_default())))
+            .fold(RetryStats::empty(), |mut stats, (attempts, backoff)| {
+                let retries = u64::from(attempts.saturating_sub(1));
+                stats.request_count += 1;
+                stats.retried_requests += u64::from(retries > 0);
+                stats.total_retries += retries;
+                stats.total_backoff += backoff;
+                stats
+            })
+    }
+
+    /// Calculate embeddings throughput over successful embeddings requests
+    fn calculate_embedding_stats(metrics: &[crate::types::RequestMetrics]) -> EmbeddingStats {
+        let requests: Vec<_> = metrics
+            .iter()
+            .filter(|m| m.success && m.embedding_inputs.is_some())
+            .collect();
+        if requests.is_empty() {
+            return EmbeddingStats::empty();
+        }
+
+        let mean = |rates: &[f64]| rates.iter().sum::<f64>() / rates.len().max(1) as f64;
+        let input_rates: Vec<f64> = requests.iter().filter_map(|m| m.inputs_per_second()).collect();
+        let mut vector_rates: Vec<f64> =
+            requests.iter().filter_map(|m| m.vectors_per_second()).collect();
+        vector_rates.sort_by(|a, b| a.total_cmp(b));
+        let percentile = |q: f64| {
+            let idx = ((vector_rates.len() as f64 * q).ceil() as usize).saturating_sub(1);
+            vector_rates.get(idx).copied().unwrap_or(0.0)
+        };
+
+        let dimensions = requests[0].embedding_dimensions;
+        EmbeddingStats {
+            request_count: requests.len() as u64,
+            total_inputs: requests.iter().filter_map(|m| m.embedding_inputs).sum(),
+            total_vectors: requests.iter().filter_map(|m| m.embedding_vectors).sum(),
+            dimensions: dimensions
+                .filter(|_| requests.iter().all(|m| m.embedding_dimensions == dimensions)),
+            mean_inputs_per_second: mean(&input_rates),
+            mean_vectors_per_second: mean(&vector_rates),
+            p50_vectors_per_second: percentile(0.50),
+            p95_vectors_per_second: percentile(0.95),
+        }
+    }
+
     /// Aggregate metrics for a specific provider
     ///
     /// This filters the collector's metrics to only include those from the specified provider
@@ -234,6 +394,32 @@ impl MetricsAggregator {
             .map_err(|e| MetricsError::HistogramCreation(e.to_string()))?;
         let mut throughput_hist = Histogram::<u64>::new(3)
             .map_err(|e| MetricsError::HistogramCreation(e.to_string()))?;
+        let mut thinking_ttft_hist = Histogram::<u64>::new(3)
+            .map_err(|e| MetricsError::HistogramCreation(e.to_string()))?;
+        let mut thinking_duration_hist = Histogram::<u64>::new(3)
+            .map_err(|e| MetricsError::HistogramCreation(e.to_string()))?;
+        let mut visible_ttft_hist = Histogram::<u64>::new(3)
+            .map_err(|e| MetricsError::HistogramCreation(e.to_string()))?;
+        let mut reasoning_time_hist = Histogram::<u64>::new(3)
+            .map_err(|e| MetricsError::HistogramCreation(e.to_string()))?;
+        let mut tool_call_ttft_hist = Histogram::<u64>::new(3)
+            .map_err(|e| MetricsError::HistogramCreation(e.to_string()))?;
+        let mut tool_call_duration_hist = Histogram::<u64>::new(3)
+            .map_err(|e| MetricsError::HistogramCreation(e.to_string()))?;
+        let mut ttft_cache_hit_hist = Histogram::<u64>::new(3)
+            .map_err(|e| MetricsError::HistogramCreation(e.to_string()))?;
+        let mut ttft_cache_miss_hist = Histogram::<u64>::new(3)
+            .map_err(|e| MetricsError::HistogramCreation(e.to_string()))?;
+        let mut time_to_first_byte_hist = Histogram::<u64>::new(3)
+            .map_err(|e| MetricsError::HistogramCreation(e.to_string()))?;
+        let mut time_to_first_event_hist = Histogram::<u64>::new(3)
+            .map_err(|e| MetricsError::HistogramCreation(e.to_string()))?;
+        let mut session_setup_hist = Histogram::<u64>::new(3)
+            .map_err(|e| MetricsError::HistogramCreation(e.to_string()))?;
+        let mut response_ttft_hist = Histogram::<u64>::new(3)
+            .map_err(|e| MetricsError::HistogramCreation(e.to_string()))?;
+        let mut server_processing_hist = Histogram::<u64>::new(3)
+            .map_err(|e| MetricsError::HistogramCreation(e.to_string()))?;
 
         let mut successful_requests = 0u64;
         let mut failed_requests = 0u64;
@@ -269,6 +455,31 @@ impl MetricsAggregator {
                     .record(throughput_scaled)
                     .map_err(|e| MetricsError::HistogramRecord(e.to_string()))?;
 
+                let cache_ttft =
+                    |hit: bool| metric.cache_hit.filter(|h| *h == hit).map(|_| metric.ttft);
+                let phases = [
+                    (&mut thinking_ttft_hist, metric.time_to_first_thinking_token),
+                    (&mut thinking_duration_hist, metric.thinking_duration),
+                    (&mut visible_ttft_hist, metric.time_to_first_visible_token),
+                    (&mut reasoning_time_hist, metric.reasoning_time),
+                    (&mut tool_call_ttft_hist, metric.time_to_first_tool_call),
+                    (&mut tool_call_duration_hist, metric.tool_call_duration),
+                    (&mut ttft_cache_hit_hist, cache_ttft(true)),
+                    (&mut ttft_cache_miss_hist, cache_ttft(false)),
+                    (&mut time_to_first_byte_hist, metric.time_to_first_byte),
+                    (&mut time_to_first_event_hist, metric.time_to_first_event),
+                    (&mut session_setup_hist, metric.session_setup_time),
+                    (&mut response_ttft_hist, metric.response_ttft),
+                    (&mut server_processing_hist, metric.server_processing_time),
+                ];
+                for (histogram, value) in phases {
+                    if let Some(value) = value {
+                        histogram
+                            .record(value.as_nanos() as u64)
+                            .map_err(|e| MetricsError::HistogramRecord(e.to_string()))?;
+                    }
+                }
+
                 // Accumulate tokens
                 total_input_tokens += metric.input_tokens;
                 total_output_tokens += metric.output_tokens;
@@ -291,6 +502,31 @@ impl MetricsAggregator {
         let ttft_distribution = Self::calculate_latency_distribution(&ttft_hist)?;
         let inter_token_distribution = Self::calculate_latency_distribution(&inter_token_hist)?;
         let total_latency_distribution = Self::calculate_latency_distribution(&total_latency_hist)?;
+        let thinking_ttft_distribution = Self::calculate_latency_distribution(&thinking_ttft_hist)?;
+        let thinking_duration_distribution =
+            Self::calculate_latency_distribution(&thinking_duration_hist)?;
+        let visible_ttft_distribution = Self::calculate_latency_distribution(&visible_ttft_hist)?;
+        let reasoning_time_distribution =
+            Self::calculate_latency_distribution(&reasoning_time_hist)?;
+        let reasoning_tokens = Self::calculate_reasoning_token_stats(metrics);
+        let stream_reads = Self::calculate_stream_read_stats(metrics);
+        let retries = Self::calculate_retry_stats(metrics);
+        let embeddings = Self::calculate_embedding_stats(metrics);
+        let tool_call_ttft_distribution =
+            Self::calculate_latency_distribution(&tool_call_ttft_hist)?;
+        let tool_call_duration_distribution =
+            Self::calculate_latency_distribution(&tool_call_duration_hist)?;
+        let ttft_cache_hit_distribution = Self::calculate_latency_distribution(&ttft_cache_hit_hist)?;
+        let ttft_cache_miss_distribution =
+            Self::calculate_latency_distribution(&ttft_cache_miss_hist)?;
+        let time_to_first_byte_distribution =
+            Self::calculate_latency_distribution(&time_to_first_byte_hist)?;
+        let time_to_first_event_distribution =
+            Self::calculate_latency_distribution(&time_to_first_event_hist)?;
+        let session_setup_distribution = Self::calculate_latency_distribution(&session_setup_hist)?;
+        let response_ttft_distribution = Self::calculate_latency_distribution(&response_ttft_hist)?;
+        let server_processing_distribution =
+            Self::calculate_latency_distribution(&server_processing_hist)?;
         let throughput = Self::calculate_throughput_stats(&throughput_hist)?;
 
         // Calculate time range
@@ -325,10 +561,30 @@ impl MetricsAggregator {
             ttft_distribution,
             inter_token_distribution,
             total_latency_distribution,
+            thinking_ttft_distribution,
+            thinking_duration_distribution,
+            visible_ttft_distribution,
+            reasoning_time_distribution,
+            reasoning_tokens,
+            tool_call_ttft_distribution,
+            tool_call_duration_distribution,
+            ttft_cache_hit_distribution,
+            ttft_cache_miss_distribution,
+            time_to_first_byte_distribution,
+            time_to_first_event_distribution,
+            session_setup_distribution,
+            response_ttft_distribution,
+            server_processing_distribution,
+            stream_reads,
+            retries,
+            embeddings,
             throughput,
             total_input_tokens,
             total_output_tokens,
             total_thinking_tokens: total_thinking_tokens_opt,
+            total_cache_creation_tokens: Self::sum_reported(metrics, |m| m.cache_creation_tokens),
+            total_cache_read_tokens: Self::sum_reported(metrics, |m| m.cache_read_tokens),
+            estimated_token_requests: Self::count_estimated(metrics),
             total_cost_usd: total_cost_usd_opt,
             provider_breakdown,
             model_breakdown,
@@ -454,8 +710,7 @@ pub struct DistributionChange {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::collector::CollectorConfig;
-    use crate::types::RequestMetrics;
+    use crate::types::{RequestMetrics, TokenCountSource};
     use chrono::Utc;
     use llm_latency_lens_core::{Provider, RequestId, SessionId};
 
@@ -476,6 +731,39 @@ mod tests {
             input_tokens: 100,
             output_tokens: 50,
             thinking_tokens: None,
+            input_token_source: TokenCountSource::Reported,
+            output_token_source: TokenCountSource::Reported,
+            cache_creation_tokens: None,
+            cache_read_tokens: None,
+            cache_hit: None,
+            time_to_first_thinking_token: None,
+            thinking_duration: None,
+            time_to_first_visible_token: None,
+            reasoning_time: None,
+            time_to_first_tool_call: None,
+            tool_call_duration: None,
+            dns_time: None,
+            tcp_connect_time: None,
+            tls_handshake_time: None,
+            time_to_first_byte: None,
+            time_to_first_event: None,
+            session_setup_time: None,
+            response_ttft: None,
+            provider_request_id: None,
+            server_processing_time: None,
+            rate_limit_remaining_requests: None,
+            rate_limit_remaining_tokens: None,
+            attempts: None,
+            retry_backoff: None,
+            network_reads: None,
+            stream_events: None,
+            max_events_per_read: None,
+            response_body_bytes: None,
+            body_download_time: None,
+            body_parse_time: None,
+            embedding_inputs: None,
+            embedding_vectors: None,
+            embedding_dimensions: None,
             tokens_per_second: tokens_per_sec,
             cost_usd: Some(0.05),
             success: true,
@@ -553,6 +841,302 @@ mod tests {
         assert_eq!(aggregated.success_rate(), 90.0);
     }
 
+    #[test]
+    fn test_aggregate_thinking_phases() {
+        let session_id = SessionId::new();
+        let collector = MetricsCollector::with_defaults(session_id).unwrap();
+
+        // Requests with extended thinking
+        for i in 0..10 {
+            let mut metrics = create_test_metrics(100 + i, 3000 + i, 50.0);
+            metrics.provider = Provider::Anthropic;
+            metrics.time_to_first_thinking_token = Some(Duration::from_millis(100 + i));
+            metrics.thinking_duration = Some(Duration::from_millis(1500));
+            metrics.time_to_first_visible_token = Some(Duration::from_millis(1700 + i));
+            collector.record(metrics).unwrap();
+        }
+
+        // Requests without thinking
+        for i in 0..5 {
+            let mut metrics = create_test_metrics(100 + i, 1000 + i, 50.0);
+            metrics.time_to_first_visible_token = Some(Duration::from_millis(100 + i));
+            collector.record(metrics).unwrap();
+        }
+
+        let aggregated = MetricsAggregator::aggregate(&collector).unwrap();
+        assert_eq!(aggregated.thinking_ttft_distribution.sample_count, 10);
+        assert_eq!(aggregated.thinking_duration_distribution.sample_count, 10);
+        assert_eq!(aggregated.visible_ttft_distribution.sample_count, 15);
+
+        let anthropic =
+            MetricsAggregator::aggregate_by_provider(&collector, Provider::Anthropic).unwrap();
+        assert_eq!(anthropic.thinking_ttft_distribution.sample_count, 10);
+        assert_eq!(anthropic.visible_ttft_distribution.sample_count, 10);
+        assert!(anthropic.visible_ttft_distribution.p50 >= Duration::from_millis(1700));
+    }
+
+    #[test]
+    fn test_aggregate_reasoning() {
+        let session_id = SessionId::new();
+        let collector = MetricsCollector::with_defaults(session_id).unwrap();
+
+        for i in 1..=10 {
+            let mut metrics = create_test_metrics(4000 + i, 5000 + i, 50.0);
+            metrics.model = "o3-mini".to_string();
+            metrics.thinking_tokens = Some(i * 100);
+            metrics.time_to_first_visible_token = Some(Duration::from_millis(4000 + i));
+            // Kept clear of 4000ms: the histogram reports min to 3 significant digits
+            metrics.reasoning_time = Some(Duration::from_millis(4010 + i));
+            collector.record(metrics).unwrap();
+        }
+        collector.record(create_test_metrics(100, 1000, 50.0)).unwrap();
+
+        let aggregated = MetricsAggregator::aggregate(&collector).unwrap();
+        assert_eq!(aggregated.reasoning_time_distribution.sample_count, 10);
+        assert!(aggregated.reasoning_time_distribution.min >= Duration::from_millis(4000));
+        assert_eq!(aggregated.reasoning_tokens.request_count, 10);
+        assert_eq!(aggregated.reasoning_tokens.min, 100);
+        assert_eq!(aggregated.reasoning_tokens.max, 1000);
+        assert_eq!(aggregated.reasoning_tokens.p50, 500);
+        assert!((aggregated.reasoning_tokens.mean - 550.0).abs() < f64::EPSILON);
+        assert_eq!(aggregated.total_thinking_tokens, Some(5500));
+
+        let by_model = MetricsAggregator::aggregate_by_model(&collector, "o3-mini").unwrap();
+        assert_eq!(by_model.reasoning_time_distribution.sample_count, 10);
+        assert_eq!(by_model.reasoning_tokens.request_count, 10);
+    }
+
+    #[test]
+    fn test_aggregate_tool_calls() {
+        let session_id = SessionId::new();
+        let collector = MetricsCollector::with_defaults(session_id).unwrap();
+
+        for i in 0..8 {
+            let mut metrics = create_test_metrics(200 + i, 900 + i, 50.0);
+            metrics.time_to_first_tool_call = Some(Duration::from_millis(200 + i));
+            // Kept clear of 400ms: the histogram reports min to 3 significant digits
+            metrics.tool_call_duration = Some(Duration::from_millis(401 + i));
+            collector.record(metrics).unwrap();
+        }
+        collector.record(create_test_metrics(100, 1000, 50.0)).unwrap();
+
+        let aggregated = MetricsAggregator::aggregate(&collector).unwrap();
+        assert_eq!(aggregated.tool_call_ttft_distribution.sample_count, 8);
+        assert_eq!(aggregated.tool_call_duration_distribution.sample_count, 8);
+        assert!(aggregated.tool_call_duration_distribution.min >= Duration::from_millis(400));
+
+        let by_provider =
+            MetricsAggregator::aggregate_by_provider(&collector, Provider::OpenAI).unwrap();
+        assert_eq!(by_provider.tool_call_ttft_distribution.sample_count, 8);
+    }
+
+    #[test]
+    fn test_aggregate_cache_hits() {
+        let session_id = SessionId::new();
+        let collector = MetricsCollector::with_defaults(session_id).unwrap();
+
+        for i in 0..6 {
+            let mut metrics = create_test_metrics(80 + i, 900 + i, 50.0);
+            metrics.cache_read_tokens = Some(2048);
+            metrics.cache_hit = Some(true);
+            collector.record(metrics).unwrap();
+        }
+        for i in 0..4 {
+            // Kept clear of 400ms: the histogram reports min to 3 significant digits
+            let mut metrics = create_test_metrics(401 + i, 1200 + i, 50.0);
+            metrics.cache_read_tokens = Some(0);
+            metrics.cache_hit = Some(false);
+            collector.record(metrics).unwrap();
+        }
+        // Providers that do not report cache usage land in neither population
+        collector.record(create_test_metrics(200, 1000, 50.0)).unwrap();
+
+        let aggregated = MetricsAggregator::aggregate(&collector).unwrap();
+        assert_eq!(aggregated.ttft_distribution.sample_count, 11);
+        assert_eq!(aggregated.ttft_cache_hit_distribution.sample_count, 6);
+        assert_eq!(aggregated.ttft_cache_miss_distribution.sample_count, 4);
+        assert!(aggregated.ttft_cache_hit_distribution.max < Duration::from_millis(100));
+        assert!(aggregated.ttft_cache_miss_distribution.min >= Duration::from_millis(400));
+        assert_eq!(aggregated.total_cache_read_tokens, Some(6 * 2048));
+        assert_eq!(aggregated.total_cache_creation_tokens, None);
+
+        let by_provider =
+            MetricsAggregator::aggregate_by_provider(&collector, Provider::OpenAI).unwrap();
+        assert_eq!(by_provider.ttft_cache_hit_distribution.sample_count, 6);
+        assert_eq!(by_provider.ttft_cache_miss_distribution.sample_count, 4);
+    }
+
+    #[test]
+    fn test_aggregate_estimated_token_counts() {
+        let session_id = SessionId::new();
+        let collector = MetricsCollector::with_defaults(session_id).unwrap();
+
+        collector.record(create_test_metrics(100, 1000, 50.0)).unwrap();
+        let mut counted = create_test_metrics(100, 1000, 50.0);
+        counted.input_token_source = TokenCountSource::Tokenizer;
+        counted.output_token_source = TokenCountSource::Tokenizer;
+        collector.record(counted).unwrap();
+        let mut events = create_test_metrics(100, 1000, 50.0);
+        events.input_token_source = TokenCountSource::Unknown;
+        events.output_token_source = TokenCountSource::StreamEvents;
+        collector.record(events).unwrap();
+
+        let aggregated = MetricsAggregator::aggregate(&collector).unwrap();
+        assert_eq!(aggregated.estimated_token_requests, 2);
+    }
+
+    #[test]
+    fn test_aggregate_first_byte_and_event() {
+        let session_id = SessionId::new();
+        let collector = MetricsCollector::with_defaults(session_id).unwrap();
+
+        for i in 0..5 {
+            let mut metrics = create_test_metrics(300 + i, 1000 + i, 50.0);
+            metrics.time_to_first_byte = Some(Duration::from_millis(100 + i));
+            metrics.time_to_first_event = Some(Duration::from_millis(200 + i));
+            collector.record(metrics).unwrap();
+        }
+
+        let aggregated = MetricsAggregator::aggregate(&collector).unwrap();
+        assert_eq!(aggregated.time_to_first_byte_distribution.sample_count, 5);
+        assert_eq!(aggregated.time_to_first_event_distribution.sample_count, 5);
+        assert!(aggregated.time_to_first_byte_distribution.max < Duration::from_millis(200));
+        assert!(aggregated.time_to_first_event_distribution.max < aggregated.ttft_distribution.min);
+
+        let by_provider =
+            MetricsAggregator::aggregate_by_provider(&collector, Provider::OpenAI).unwrap();
+        assert_eq!(by_provider.time_to_first_byte_distribution.sample_count, 5);
+        assert_eq!(by_provider.time_to_first_event_distribution.sample_count, 5);
+    }
+
+    #[test]
+    fn test_aggregate_realtime_session() {
+        let session_id = SessionId::new();
+        let collector = MetricsCollector::with_defaults(session_id).unwrap();
+
+        for i in 0..4 {
+            let mut metrics = create_test_metrics(500 + i, 1000 + i, 50.0);
+            metrics.session_setup_time = Some(Duration::from_millis(250 + i));
+            metrics.response_ttft = Some(Duration::from_millis(200 + i));
+            collector.record(metrics).unwrap();
+        }
+        // Requests on other transports report neither
+        collector.record(create_test_metrics(500, 1000, 50.0)).unwrap();
+
+        let aggregated = MetricsAggregator::aggregate(&collector).unwrap();
+        assert_eq!(aggregated.session_setup_distribution.sample_count, 4);
+        assert_eq!(aggregated.response_ttft_distribution.sample_count, 4);
+        assert!(aggregated.response_ttft_distribution.max < aggregated.ttft_distribution.min);
+
+        let by_provider =
+            MetricsAggregator::aggregate_by_provider(&collector, Provider::OpenAI).unwrap();
+        assert_eq!(by_provider.session_setup_distribution.sample_count, 4);
+        assert_eq!(by_provider.response_ttft_distribution.sample_count, 4);
+    }
+
+    #[test]
+    fn test_aggregate_server_processing() {
+        let session_id = SessionId::new();
+        let collector = MetricsCollector::with_defaults(session_id).unwrap();
+
+        for i in 0..3 {
+            let mut metrics = create_test_metrics(300 + i, 1000 + i, 50.0);
+            metrics.provider_request_id = Some(format!("req_{}", i));
+            // Kept clear of 250ms: the histogram reports min to 3 significant digits
+            metrics.server_processing_time = Some(Duration::from_millis(251 + i));
+            collector.record(metrics).unwrap();
+        }
+        // Providers that do not report processing time are left out
+        collector.record(create_test_metrics(300, 1000, 50.0)).unwrap();
+
+        let aggregated = MetricsAggregator::aggregate(&collector).unwrap();
+        assert_eq!(aggregated.server_processing_distribution.sample_count, 3);
+        assert!(aggregated.server_processing_distribution.min >= Duration::from_millis(250));
+
+        let by_provider =
+            MetricsAggregator::aggregate_by_provider(&collector, Provider::OpenAI).unwrap();
+        assert_eq!(by_provider.server_processing_distribution.sample_count, 3);
+    }
+
+    #[test]
+    fn test_aggregate_retries() {
+        let session_id = SessionId::new();
+        let collector = MetricsCollector::with_defaults(session_id).unwrap();
+
+        for attempts in [1, 1, 2, 3] {
+            let mut metrics = create_test_metrics(300, 1000, 50.0);
+            metrics.attempts = Some(attempts);
+            metrics.retry_backoff = Some(Duration::from_millis(500) * (attempts - 1));
+            collector.record(metrics).unwrap();
+        }
+        // Requests without recorded attempts don't count
+        collector.record(create_test_metrics(300, 1000, 50.0)).unwrap();
+
+        let aggregated = MetricsAggregator::aggregate(&collector).unwrap();
+        assert_eq!(aggregated.retries.request_count, 4);
+        assert_eq!(aggregated.retries.retried_requests, 2);
+        assert_eq!(aggregated.retries.total_retries, 3);
+        assert_eq!(aggregated.retries.total_backoff, Duration::from_millis(1500));
+        assert_eq!(aggregated.retries.retry_rate(), Some(0.5));
+
+        let by_provider =
+            MetricsAggregator::aggregate_by_provider(&collector, Provider::OpenAI).unwrap();
+        assert_eq!(by_provider.retries.total_retries, 3);
+    }
+
+    #[test]
+    fn test_aggregate_stream_reads() {
+        let session_id = SessionId::new();
+        let collector = MetricsCollector::with_defaults(session_id).unwrap();
+
+        for i in 0..4 {
+            let mut metrics = create_test_metrics(300 + i, 1000 + i, 50.0);
+            metrics.network_reads = Some(10);
+            metrics.stream_events = Some(10 + i);
+            metrics.max_events_per_read = Some(1 + i);
+            collector.record(metrics).unwrap();
+        }
+        // Requests without read stats don't count
+        collector.record(create_test_metrics(300, 1000, 50.0)).unwrap();
+
+        let aggregated = MetricsAggregator::aggregate(&collector).unwrap();
+        assert_eq!(aggregated.stream_reads.request_count, 4);
+        assert_eq!(aggregated.stream_reads.total_reads, 40);
+        assert_eq!(aggregated.stream_reads.total_events, 46);
+        assert_eq!(aggregated.stream_reads.max_events_per_read, 4);
+        assert_eq!(aggregated.stream_reads.mean_events_per_read(), Some(1.15));
+
+        let by_provider =
+            MetricsAggregator::aggregate_by_provider(&collector, Provider::OpenAI).unwrap();
+        assert_eq!(by_provider.stream_reads.total_events, 46);
+    }
+
+    #[test]
+    fn test_aggregate_embeddings() {
+        let session_id = SessionId::new();
+        let collector = MetricsCollector::with_defaults(session_id).unwrap();
+
+        for total_ms in [250, 500] {
+            let mut metrics = create_test_metrics(total_ms, total_ms, 0.0);
+            metrics.embedding_inputs = Some(8);
+            metrics.embedding_vectors = Some(8);
+            metrics.embedding_dimensions = Some(1536);
+            collector.record(metrics).unwrap();
+        }
+        // Completions don't count
+        collector.record(create_test_metrics(300, 1000, 50.0)).unwrap();
+
+        let aggregated = MetricsAggregator::aggregate(&collector).unwrap();
+        let embeddings = &aggregated.embeddings;
+        assert_eq!(embeddings.request_count, 2);
+        assert_eq!(embeddings.total_vectors, 16);
+        assert_eq!(embeddings.dimensions, Some(1536));
+        assert_eq!(embeddings.mean_batch_size(), Some(8.0));
+        assert!((embeddings.mean_vectors_per_second - 24.0).abs() < 1e-9);
+        assert!((embeddings.mean_inputs_per_second - 24.0).abs() < 1e-9);
+        assert!((embeddings.p95_vectors_per_second - 32.0).abs() < 1e-9);
+    }
+
     #[test]
     fn test_aggregate_by_provider() {
         let session_id = SessionId::new();
@@ -84,6 +84,45 @@ pub struct HistogramSet {
 
     /// Token throughput histogram (stored as tokens/sec * 1000 for precision)
     pub(crate) throughput: Histogram<u64>,
+
+    /// Time to first thinking token histogram
+    pub(crate) thinking_ttft: Histogram<u64>,
+
+    /// Thinking phase duration histogram
+    pub(crate) thinking_duration: Histogram<u64>,
+
+    /// Time to first visible token histogram
+    pub(crate) visible_ttft: Histogram<u64>,
+
+    /// Reasoning time histogram
+    pub(crate) reasoning_time: Histogram<u64>,
+
+    /// Time to first tool-call byte histogram
+    pub(crate) tool_call_ttft: Histogram<u64>,
+
+    /// Tool-argument generation time histogram
+    pub(crate) tool_call_duration: Histogram<u64>,
+
+    /// TTFT histogram for prompt-cache hits
+    pub(crate) ttft_cache_hit: Histogram<u64>,
+
+    /// TTFT histogram for prompt-cache misses
+    pub(crate) ttft_cache_miss: Histogram<u64>,
+
+    /// Time to response headers histogram
+    pub(crate) time_to_first_byte: Histogram<u64>,
+
+    /// Time to first stream event histogram
+    pub(crate) time_to_first_event: Histogram<u64>,
+
+    /// Realtime session setup histogram
+    pub(crate) session_setup: Histogram<u64>,
+
+    /// Per-response TTFT histogram on open realtime sessions
+    pub(crate) response_ttft: Histogram<u64>,
+
+    /// Server-reported processing time histogram
+    pub(crate) server_processing: Histogram<u64>,
 }
 
 impl HistogramSet {
@@ -101,6 +140,19 @@ impl HistogramSet {
             // For throughput, we track up to 1M tokens/sec
             throughput: Histogram::new_with_max(1_000_000_000, config.significant_digits)
                 .map_err(|e| MetricsError::HistogramCreation(e.to_string()))?,
+            thinking_ttft: create_histogram()?,
+            thinking_duration: create_histogram()?,
+            visible_ttft: create_histogram()?,
+            reasoning_time: create_histogram()?,
+            tool_call_ttft: create_histogram()?,
+            tool_call_duration: create_histogram()?,
+            ttft_cache_hit: create_histogram()?,
+            ttft_cache_miss: create_histogram()?,
+            time_to_first_byte: create_histogram()?,
+            time_to_first_event: create_histogram()?,
+            session_setup: create_histogram()?,
+            response_ttft: create_histogram()?,
+            server_processing: create_histogram()?,
         })
     }
 
@@ -129,6 +181,35 @@ impl HistogramSet {
             .record(throughput_scaled)
             .map_err(|e| MetricsError::HistogramRecord(e.to_string()))?;
 
+        // Record thinking/reasoning and tool-call phase timings (only present
+        // for requests that reasoned or called tools), and TTFT split by
+        // prompt-cache status when the provider reported it, plus the
+        // response-header and first-event times that precede the first token,
+        // the realtime session timings and the server-reported processing time
+        let cache_ttft = |hit: bool| metrics.cache_hit.filter(|h| *h == hit).map(|_| metrics.ttft);
+        let phases = [
+            (&mut self.thinking_ttft, metrics.time_to_first_thinking_token),
+            (&mut self.thinking_duration, metrics.thinking_duration),
+            (&mut self.visible_ttft, metrics.time_to_first_visible_token),
+            (&mut self.reasoning_time, metrics.reasoning_time),
+            (&mut self.tool_call_ttft, metrics.time_to_first_tool_call),
+            (&mut self.tool_call_duration, metrics.tool_call_duration),
+            (&mut self.ttft_cache_hit, cache_ttft(true)),
+            (&mut self.ttft_cache_miss, cache_ttft(false)),
+            (&mut self.time_to_first_byte, metrics.time_to_first_byte),
+            (&mut self.time_to_first_event, metrics.time_to_first_event),
+            (&mut self.session_setup, metrics.session_setup_time),
+            (&mut self.response_ttft, metrics.response_ttft),
+            (&mut self.server_processing, metrics.server_processing_time),
+        ];
+        for (histogram, value) in phases {
+            if let Some(value) = value {
+                histogram
+                    .record(value.as_nanos() as u64)
+                    .map_err(|e| MetricsError::HistogramRecord(e.to_string()))?;
+            }
+        }
+
         Ok(())
     }
 }
@@ -293,7 +374,7 @@ impl CollectorState {
 /// # Example
 ///
 /// ```no_run
-/// use llm_latency_lens_metrics::{MetricsCollector, CollectorConfig};
+/// use llm_latency_lens_metrics::{MetricsAggregator, MetricsCollector, CollectorConfig};
 /// use llm_latency_lens_core::SessionId;
 ///
 /// let collector = MetricsCollector::new(
@@ -306,7 +387,7 @@ impl CollectorState {
 /// // collector.record(metrics).unwrap();
 ///
 /// // Get aggregated results
-/// let aggregated = collector.aggregate().unwrap();
+/// let aggregated = MetricsAggregator::aggregate(&collector).unwrap();
 /// println!("Mean TTFT: {:?}", aggregated.ttft_distribution.mean);
 /// ```
 #[derive(Clone)]
@@ -515,8 +596,10 @@ pub enum MetricsError {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::TokenCountSource;
     use chrono::Utc;
     use llm_latency_lens_core::{Provider, RequestId, SessionId};
+    use std::time::Duration;
 
     fn create_test_metrics(
         provider: Provider,
@@ -541,6 +624,39 @@ mod tests {
             input_tokens: 100,
             output_tokens: 50,
             thinking_tokens: None,
+            input_token_source: TokenCountSource::Reported,
+            output_token_source: TokenCountSource::Reported,
+            cache_creation_tokens: None,
+            cache_read_tokens: None,
+            cache_hit: None,
+            time_to_first_thinking_token: None,
+            thinking_duration: None,
+            time_to_first_visible_token: None,
+            reasoning_time: None,
+            time_to_first_tool_call: None,
+            tool_call_duration: None,
+            dns_time: None,
+            tcp_connect_time: None,
+            tls_handshake_time: None,
+            time_to_first_byte: None,
+            time_to_first_event: None,
+            session_setup_time: None,
+            response_ttft: None,
+            provider_request_id: None,
+            server_processing_time: None,
+            rate_limit_remaining_requests: None,
+            rate_limit_remaining_tokens: None,
+            attempts: None,
+            retry_backoff: None,
+            network_reads: None,
+            stream_events: None,
+            max_events_per_read: None,
+            response_body_bytes: None,
+            body_download_time: None,
+            body_parse_time: None,
+            embedding_inputs: None,
+            embedding_vectors: None,
+            embedding_dimensions: None,
             tokens_per_second: 50.0,
             cost_usd: Some(0.05),
             success,
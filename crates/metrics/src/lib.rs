@@ -22,7 +22,7 @@
 //!
 //! ```no_run
 //! use llm_latency_lens_metrics::{
-//!     MetricsCollector, MetricsAggregator, CollectorConfig, RequestMetrics
+//!     MetricsCollector, MetricsAggregator, CollectorConfig, RequestMetrics, TokenCountSource
 //! };
 //! use llm_latency_lens_core::{SessionId, RequestId, Provider};
 //! use chrono::Utc;
@@ -53,6 +53,39 @@
 //!     input_tokens: 100,
 //!     output_tokens: 50,
 //!     thinking_tokens: None,
+//!     input_token_source: TokenCountSource::Reported,
+//!     output_token_source: TokenCountSource::Reported,
+//!     cache_creation_tokens: None,
+//!     cache_read_tokens: None,
+//!     cache_hit: None,
+//!     time_to_first_thinking_token: None,
+//!     thinking_duration: None,
+//!     time_to_first_visible_token: None,
+//!     reasoning_time: None,
+//!     time_to_first_tool_call: None,
+//!     tool_call_duration: None,
+//!     dns_time: None,
+//!     tcp_connect_time: None,
+//!     tls_handshake_time: None,
+//!     time_to_first_byte: None,
+//!     time_to_first_event: None,
+//!     session_setup_time: None,
+//!     response_ttft: None,
+//!     provider_request_id: None,
+//!     server_processing_time: None,
+//!     rate_limit_remaining_requests: None,
+//!     rate_limit_remaining_tokens: None,
+//!     attempts: None,
+//!     retry_backoff: None,
+//!     network_reads: None,
+//!     stream_events: None,
+//!     max_events_per_read: None,
+//!     response_body_bytes: None,
+//!     body_download_time: None,
+//!     body_parse_time: None,
+//!     embedding_inputs: None,
+//!     embedding_vectors: None,
+//!     embedding_dimensions: None,
 //!     tokens_per_second: 25.0,
 //!     cost_usd: Some(0.05),
 //!     success: true,
@@ -128,7 +161,8 @@ pub mod types;
 pub use aggregator::{DistributionChange, MetricsAggregator, MetricsComparison};
 pub use collector::{CollectorConfig, MetricsCollector, MetricsError};
 pub use types::{
-    AggregatedMetrics, LatencyDistribution, RequestMetrics, ThroughputStats,
+    AggregatedMetrics, EmbeddingStats, LatencyDistribution, ReasoningTokenStats, RequestMetrics,
+    RetryStats, StreamReadStats, ThroughputStats, TokenCountSource,
 };
 
 // Re-export core types that are commonly used with metrics
@@ -163,6 +197,39 @@ mod integration_tests {
             input_tokens: 100,
             output_tokens: 50,
             thinking_tokens: None,
+            input_token_source: TokenCountSource::Reported,
+            output_token_source: TokenCountSource::Reported,
+            cache_creation_tokens: None,
+            cache_read_tokens: None,
+            cache_hit: None,
+            time_to_first_thinking_token: None,
+            thinking_duration: None,
+            time_to_first_visible_token: None,
+            reasoning_time: None,
+            time_to_first_tool_call: None,
+            tool_call_duration: None,
+            dns_time: None,
+            tcp_connect_time: None,
+            tls_handshake_time: None,
+            time_to_first_byte: None,
+            time_to_first_event: None,
+            session_setup_time: None,
+            response_ttft: None,
+            provider_request_id: None,
+            server_processing_time: None,
+            rate_limit_remaining_requests: None,
+            rate_limit_remaining_tokens: None,
+            attempts: None,
+            retry_backoff: None,
+            network_reads: None,
+            stream_events: None,
+            max_events_per_read: None,
+            response_body_bytes: None,
+            body_download_time: None,
+            body_parse_time: None,
+            embedding_inputs: None,
+            embedding_vectors: None,
+            embedding_dimensions: None,
             tokens_per_second: 50.0,
             cost_usd: Some(0.05),
             success: true,
@@ -369,7 +436,7 @@ mod integration_tests {
         }
         let baseline = MetricsAggregator::aggregate(&collector1).unwrap();
 
-        // Improved version (20% faster)
+        // Improved version (20ms faster)
         let session2 = SessionId::new();
         let collector2 = MetricsCollector::with_defaults(session2).unwrap();
         for i in 0..100 {
@@ -384,9 +451,9 @@ mod integration_tests {
         assert!(comparison.ttft_change.mean_change < 0.0);
         assert!(comparison.total_latency_change.mean_change < 0.0);
 
-        // Verify percentage is approximately -20%
-        assert!(comparison.ttft_change.mean_change > -25.0);
-        assert!(comparison.ttft_change.mean_change < -15.0);
+        // 20ms off a 149.5ms mean TTFT is about -13.4%
+        assert!(comparison.ttft_change.mean_change > -14.0);
+        assert!(comparison.ttft_change.mean_change < -12.5);
     }
 
     #[test]
@@ -36,6 +36,10 @@ pub struct RequestMetrics {
     pub timestamp: DateTime<Utc>,
 
     /// Time to first token (TTFT) in nanoseconds
+    ///
+    /// Measured to the first event that carried text, after the response
+    /// headers ([`Self::time_to_first_byte`]) and any empty stream events
+    /// ([`Self::time_to_first_event`]).
     #[serde(with = "duration_nanos")]
     pub ttft: Duration,
 
@@ -53,9 +57,152 @@ pub struct RequestMetrics {
     /// Number of output tokens generated
     pub output_tokens: u64,
 
-    /// Number of thinking tokens (if applicable, e.g., Claude extended thinking)
+    /// Number of thinking/reasoning tokens, when reported separately from
+    /// `output_tokens` (e.g., OpenAI o-series `reasoning_tokens`)
     pub thinking_tokens: Option<u64>,
 
+    /// Where `input_tokens` came from
+    #[serde(default)]
+    pub input_token_source: TokenCountSource,
+
+    /// Where `output_tokens` and `thinking_tokens` came from
+    #[serde(default)]
+    pub output_token_source: TokenCountSource,
+
+    /// Input tokens written to the prompt cache
+    #[serde(default)]
+    pub cache_creation_tokens: Option<u64>,
+
+    /// Input tokens served from the prompt cache
+    #[serde(default)]
+    pub cache_read_tokens: Option<u64>,
+
+    /// Whether the prompt hit the provider's prompt cache (None when the
+    /// provider did not report cache usage)
+    #[serde(default)]
+    pub cache_hit: Option<bool>,
+
+    /// Time to first thinking token (None when no thinking was streamed)
+    #[serde(default, with = "option_duration_nanos")]
+    pub time_to_first_thinking_token: Option<Duration>,
+
+    /// Time from the first to the last thinking token
+    #[serde(default, with = "option_duration_nanos")]
+    pub thinking_duration: Option<Duration>,
+
+    /// Time to first visible (non-thinking) token
+    #[serde(default, with = "option_duration_nanos")]
+    pub time_to_first_visible_token: Option<Duration>,
+
+    /// Reasoning phase before the first visible token (None for requests
+    /// that did not reason)
+    #[serde(default, with = "option_duration_nanos")]
+    pub reasoning_time: Option<Duration>,
+
+    /// Time to the first tool-call byte (None when no tool was called)
+    #[serde(default, with = "option_duration_nanos")]
+    pub time_to_first_tool_call: Option<Duration>,
+
+    /// Tool-argument generation time, from the first to the last tool-call event
+    #[serde(default, with = "option_duration_nanos")]
+    pub tool_call_duration: Option<Duration>,
+
+    /// DNS resolution time (None when no lookup was made)
+    #[serde(default, with = "option_duration_nanos")]
+    pub dns_time: Option<Duration>,
+
+    /// TCP connect time (None on a reused connection)
+    #[serde(default, with = "option_duration_nanos")]
+    pub tcp_connect_time: Option<Duration>,
+
+    /// TLS handshake time (None on a reused connection or plain HTTP)
+    #[serde(default, with = "option_duration_nanos")]
+    pub tls_handshake_time: Option<Duration>,
+
+    /// Time from the request start to the response headers
+    #[serde(default, with = "option_duration_nanos")]
+    pub time_to_first_byte: Option<Duration>,
+
+    /// Time from the request start to the first stream event, including
+    /// role-only and other empty events
+    #[serde(default, with = "option_duration_nanos")]
+    pub time_to_first_event: Option<Duration>,
+
+    /// Time from the request start until a realtime session was ready
+    /// (WebSocket transports only)
+    #[serde(default, with = "option_duration_nanos")]
+    pub session_setup_time: Option<Duration>,
+
+    /// Time to first token from the moment the response was requested on an
+    /// open realtime session, excluding connection and session setup
+    #[serde(default, with = "option_duration_nanos")]
+    pub response_ttft: Option<Duration>,
+
+    /// Request ID assigned by the provider (from the response headers)
+    #[serde(default)]
+    pub provider_request_id: Option<String>,
+
+    /// Server-reported processing time (e.g., `openai-processing-ms`)
+    #[serde(default, with = "option_duration_nanos")]
+    pub server_processing_time: Option<Duration>,
+
+    /// Requests left in the provider's rate-limit window after this request
+    #[serde(default)]
+    pub rate_limit_remaining_requests: Option<u64>,
+
+    /// Tokens left in the provider's rate-limit window after this request
+    #[serde(default)]
+    pub rate_limit_remaining_tokens: Option<u64>,
+
+    /// Attempts made to send the request, 1 when it was not retried
+    #[serde(default)]
+    pub attempts: Option<u32>,
+
+    /// Time spent waiting between attempts, included in the latencies above
+    #[serde(default, with = "option_duration_nanos")]
+    pub retry_backoff: Option<Duration>,
+
+    /// Network reads of the response body
+    #[serde(default)]
+    pub network_reads: Option<u64>,
+
+    /// Stream events delivered by those reads
+    #[serde(default)]
+    pub stream_events: Option<u64>,
+
+    /// Most stream events delivered by a single read
+    ///
+    /// Values above 1 mean events were buffered upstream (e.g., by a proxy)
+    /// and arrived together, so their token timings collapse.
+    #[serde(default)]
+    pub max_events_per_read: Option<u64>,
+
+    /// Size of the response body of a non-streaming request (None when the
+    /// response was streamed)
+    #[serde(default)]
+    pub response_body_bytes: Option<u64>,
+
+    /// Time from the response headers to the last body byte (non-streaming
+    /// requests only)
+    #[serde(default, with = "option_duration_nanos")]
+    pub body_download_time: Option<Duration>,
+
+    /// Time spent parsing the response body (non-streaming requests only)
+    #[serde(default, with = "option_duration_nanos")]
+    pub body_parse_time: Option<Duration>,
+
+    /// Inputs sent in an embeddings request (None for completions)
+    #[serde(default)]
+    pub embedding_inputs: Option<u64>,
+
+    /// Vectors returned by an embeddings request
+    #[serde(default)]
+    pub embedding_vectors: Option<u64>,
+
+    /// Size of the returned vectors
+    #[serde(default)]
+    pub embedding_dimensions: Option<u64>,
+
     /// Token generation throughput (tokens per second)
     pub tokens_per_second: f64,
 
@@ -70,6 +217,24 @@ pub struct RequestMetrics {
 }
 
 impl RequestMetrics {
+    /// Whether the response was streamed (non-streaming requests record a
+    /// response body size)
+    pub fn is_streamed(&self) -> bool {
+        self.response_body_bytes.is_none()
+    }
+
+    /// Embedding inputs processed per second of latency (embeddings
+    /// requests only)
+    pub fn inputs_per_second(&self) -> Option<f64> {
+        per_second(self.embedding_inputs?, self.total_latency)
+    }
+
+    /// Embedding vectors returned per second of latency (embeddings requests
+    /// only)
+    pub fn vectors_per_second(&self) -> Option<f64> {
+        per_second(self.embedding_vectors?, self.total_latency)
+    }
+
     /// Calculate the mean inter-token latency
     pub fn mean_inter_token_latency(&self) -> Option<Duration> {
         if self.inter_token_latencies.is_empty() {
@@ -120,6 +285,34 @@ impl RequestMetrics {
     pub fn total_tokens(&self) -> u64 {
         self.input_tokens + self.output_tokens + self.thinking_tokens.unwrap_or(0)
     }
+
+    /// Whether the provider reported both the input and output token counts
+    pub fn tokens_reported(&self) -> bool {
+        self.input_token_source.is_reported() && self.output_token_source.is_reported()
+    }
+}
+
+/// Where a request's token counts came from
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenCountSource {
+    /// Reported by the provider
+    #[default]
+    Reported,
+    /// Counted locally with a tokenizer
+    Tokenizer,
+    /// Number of streamed token events (undercounts when a provider packs
+    /// several tokens into one event)
+    StreamEvents,
+    /// Neither reported nor counted (recorded as 0)
+    Unknown,
+}
+
+impl TokenCountSource {
+    /// Whether the count was reported by the provider
+    pub fn is_reported(self) -> bool {
+        self == Self::Reported
+    }
 }
 
 /// Aggregated metrics across multiple requests
@@ -158,6 +351,76 @@ pub struct AggregatedMetrics {
     /// Total request latency distribution
     pub total_latency_distribution: LatencyDistribution,
 
+    /// Time to first thinking token distribution
+    #[serde(default = "LatencyDistribution::empty")]
+    pub thinking_ttft_distribution: LatencyDistribution,
+
+    /// Thinking phase duration distribution
+    #[serde(default = "LatencyDistribution::empty")]
+    pub thinking_duration_distribution: LatencyDistribution,
+
+    /// Time to first visible token distribution
+    #[serde(default = "LatencyDistribution::empty")]
+    pub visible_ttft_distribution: LatencyDistribution,
+
+    /// Reasoning time distribution (requests that reasoned only)
+    #[serde(default = "LatencyDistribution::empty")]
+    pub reasoning_time_distribution: LatencyDistribution,
+
+    /// Reasoning token statistics (requests that reported reasoning tokens only)
+    #[serde(default = "ReasoningTokenStats::empty")]
+    pub reasoning_tokens: ReasoningTokenStats,
+
+    /// Time to first tool-call byte distribution
+    #[serde(default = "LatencyDistribution::empty")]
+    pub tool_call_ttft_distribution: LatencyDistribution,
+
+    /// Tool-argument generation time distribution
+    #[serde(default = "LatencyDistribution::empty")]
+    pub tool_call_duration_distribution: LatencyDistribution,
+
+    /// TTFT distribution for prompt-cache hits
+    #[serde(default = "LatencyDistribution::empty")]
+    pub ttft_cache_hit_distribution: LatencyDistribution,
+
+    /// TTFT distribution for prompt-cache misses (requests that reported
+    /// cache status only)
+    #[serde(default = "LatencyDistribution::empty")]
+    pub ttft_cache_miss_distribution: LatencyDistribution,
+
+    /// Time to response headers distribution
+    #[serde(default = "LatencyDistribution::empty")]
+    pub time_to_first_byte_distribution: LatencyDistribution,
+
+    /// Time to first stream event distribution
+    #[serde(default = "LatencyDistribution::empty")]
+    pub time_to_first_event_distribution: LatencyDistribution,
+
+    /// Realtime session setup distribution (WebSocket transports only)
+    #[serde(default = "LatencyDistribution::empty")]
+    pub session_setup_distribution: LatencyDistribution,
+
+    /// Per-response TTFT distribution on open realtime sessions
+    #[serde(default = "LatencyDistribution::empty")]
+    pub response_ttft_distribution: LatencyDistribution,
+
+    /// Server-reported processing time distribution (requests whose provider
+    /// reported it only)
+    #[serde(default = "LatencyDistribution::empty")]
+    pub server_processing_distribution: LatencyDistribution,
+
+    /// Stream events per network read (streamed requests only)
+    #[serde(default = "StreamReadStats::empty")]
+    pub stream_reads: StreamReadStats,
+
+    /// Retries of the initial requests
+    #[serde(default = "RetryStats::empty")]
+    pub retries: RetryStats,
+
+    /// Embeddings throughput (embeddings requests only)
+    #[serde(default = "EmbeddingStats::empty")]
+    pub embeddings: EmbeddingStats,
+
     /// Token throughput statistics
     pub throughput: ThroughputStats,
 
@@ -170,6 +433,19 @@ pub struct AggregatedMetrics {
     /// Total thinking tokens (if applicable)
     pub total_thinking_tokens: Option<u64>,
 
+    /// Total input tokens written to the prompt cache (if reported)
+    #[serde(default)]
+    pub total_cache_creation_tokens: Option<u64>,
+
+    /// Total input tokens served from the prompt cache (if reported)
+    #[serde(default)]
+    pub total_cache_read_tokens: Option<u64>,
+
+    /// Successful requests whose token counts were not all reported by the
+    /// provider (counted locally or from stream events)
+    #[serde(default)]
+    pub estimated_token_requests: u64,
+
     /// Total cost in USD (if available)
     pub total_cost_usd: Option<f64>,
 
@@ -285,6 +561,184 @@ impl LatencyDistribution {
     }
 }
 
+/// Reasoning token count statistics
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReasoningTokenStats {
+    /// Number of requests that reported reasoning tokens
+    pub request_count: u64,
+
+    /// Mean reasoning tokens per request
+    pub mean: f64,
+
+    /// Minimum reasoning tokens observed
+    pub min: u64,
+
+    /// Maximum reasoning tokens observed
+    pub max: u64,
+
+    /// 50th percentile reasoning tokens
+    pub p50: u64,
+
+    /// 95th percentile reasoning tokens
+    pub p95: u64,
+}
+
+impl ReasoningTokenStats {
+    /// Create new empty reasoning token stats
+    pub fn empty() -> Self {
+        Self {
+            request_count: 0,
+            mean: 0.0,
+            min: 0,
+            max: 0,
+            p50: 0,
+            p95: 0,
+        }
+    }
+
+    /// Calculate statistics from per-request reasoning token counts
+    pub fn from_counts(counts: &[u64]) -> Self {
+        if counts.is_empty() {
+            return Self::empty();
+        }
+
+        let mut sorted = counts.to_vec();
+        sorted.sort_unstable();
+        let percentile = |q: f64| {
+            let idx = ((sorted.len() as f64 * q).ceil() as usize).saturating_sub(1);
+            sorted[idx.min(sorted.len() - 1)]
+        };
+
+        Self {
+            request_count: sorted.len() as u64,
+            mean: sorted.iter().sum::<u64>() as f64 / sorted.len() as f64,
+            min: sorted[0],
+            max: sorted[sorted.len() - 1],
+            p50: percentile(0.50),
+            p95: percentile(0.95),
+        }
+    }
+}
+
+/// Retries of the initial requests and the backoff between them
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetryStats {
+    /// Number of requests that recorded their attempts
+    pub request_count: u64,
+
+    /// Requests that needed more than one attempt
+    pub retried_requests: u64,
+
+    /// Retries over all requests
+    pub total_retries: u64,
+
+    /// Time spent waiting between attempts over all requests
+    #[serde(with = "duration_nanos")]
+    pub total_backoff: Duration,
+}
+
+impl RetryStats {
+    /// Create new empty retry stats
+    pub fn empty() -> Self {
+        Self {
+            request_count: 0,
+            retried_requests: 0,
+            total_retries: 0,
+            total_backoff: Duration::ZERO,
+        }
+    }
+
+    /// Fraction of requests that were retried
+    pub fn retry_rate(&self) -> Option<f64> {
+        (self.request_count > 0).then(|| self.retried_requests as f64 / self.request_count as f64)
+    }
+}
+
+/// How streamed responses arrived over the network
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StreamReadStats {
+    /// Number of requests that recorded reads
+    pub request_count: u64,
+
+    /// Total network reads of response bodies
+    pub total_reads: u64,
+
+    /// Total stream events delivered by those reads
+    pub total_events: u64,
+
+    /// Most events delivered by a single read
+    pub max_events_per_read: u64,
+}
+
+impl StreamReadStats {
+    /// Create new empty read stats
+    pub fn empty() -> Self {
+        Self {
+            request_count: 0,
+            total_reads: 0,
+            total_events: 0,
+            max_events_per_read: 0,
+        }
+    }
+
+    /// Mean stream events per network read
+    ///
+    /// Close to 1 when every event arrives on its own; higher values mean
+    /// events were buffered upstream and share an arrival time.
+    pub fn mean_events_per_read(&self) -> Option<f64> {
+        (self.total_reads > 0).then(|| self.total_events as f64 / self.total_reads as f64)
+    }
+}
+
+/// Embeddings throughput statistics
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmbeddingStats {
+    /// Number of embeddings requests
+    pub request_count: u64,
+
+    /// Total inputs sent
+    pub total_inputs: u64,
+
+    /// Total vectors returned
+    pub total_vectors: u64,
+
+    /// Size of the returned vectors (None when requests disagreed)
+    pub dimensions: Option<u64>,
+
+    /// Mean inputs per second, per request
+    pub mean_inputs_per_second: f64,
+
+    /// Mean vectors per second, per request
+    pub mean_vectors_per_second: f64,
+
+    /// 50th percentile vectors per second
+    pub p50_vectors_per_second: f64,
+
+    /// 95th percentile vectors per second
+    pub p95_vectors_per_second: f64,
+}
+
+impl EmbeddingStats {
+    /// Create new empty embeddings stats
+    pub fn empty() -> Self {
+        Self {
+            request_count: 0,
+            total_inputs: 0,
+            total_vectors: 0,
+            dimensions: None,
+            mean_inputs_per_second: 0.0,
+            mean_vectors_per_second: 0.0,
+            p50_vectors_per_second: 0.0,
+            p95_vectors_per_second: 0.0,
+        }
+    }
+
+    /// Mean inputs per request (the batch size)
+    pub fn mean_batch_size(&self) -> Option<f64> {
+        (self.request_count > 0).then(|| self.total_inputs as f64 / self.request_count as f64)
+    }
+}
+
 /// Token throughput statistics
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ThroughputStats {
@@ -325,6 +779,12 @@ impl ThroughputStats {
     }
 }
 
+/// Count per second over a duration (None for a zero duration)
+fn per_second(count: u64, duration: Duration) -> Option<f64> {
+    let secs = duration.as_secs_f64();
+    (secs > 0.0).then(|| count as f64 / secs)
+}
+
 /// Serde module for Duration serialization to nanoseconds
 mod duration_nanos {
     use serde::{Deserialize, Deserializer, Serializer};
@@ -346,12 +806,33 @@ mod duration_nanos {
     }
 }
 
+/// Serde module for Option<Duration> serialization to nanoseconds
+mod option_duration_nanos {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::time::Duration;
+
+    pub fn serialize<S>(duration: &Option<Duration>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        duration.map(|d| d.as_nanos() as u64).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<Duration>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let nanos = Option::<u64>::deserialize(deserializer)?;
+        Ok(nanos.map(Duration::from_nanos))
+    }
+}
+
 /// Serde module for Vec<Duration> serialization to nanoseconds
 mod duration_vec_nanos {
     use serde::{Deserialize, Deserializer, Serialize, Serializer};
     use std::time::Duration;
 
-    pub fn serialize<S>(durations: &Vec<Duration>, serializer: S) -> Result<S::Ok, S::Error>
+    pub fn serialize<S>(durations: &[Duration], serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
@@ -391,6 +872,39 @@ mod tests {
             input_tokens: 100,
             output_tokens: 50,
             thinking_tokens: None,
+            input_token_source: TokenCountSource::Reported,
+            output_token_source: TokenCountSource::Reported,
+            cache_creation_tokens: None,
+            cache_read_tokens: None,
+            cache_hit: None,
+            time_to_first_thinking_token: None,
+            thinking_duration: None,
+            time_to_first_visible_token: None,
+            reasoning_time: None,
+            time_to_first_tool_call: None,
+            tool_call_duration: None,
+            dns_time: None,
+            tcp_connect_time: None,
+            tls_handshake_time: None,
+            time_to_first_byte: None,
+            time_to_first_event: None,
+            session_setup_time: None,
+            response_ttft: None,
+            provider_request_id: None,
+            server_processing_time: None,
+            rate_limit_remaining_requests: None,
+            rate_limit_remaining_tokens: None,
+            attempts: None,
+            retry_backoff: None,
+            network_reads: None,
+            stream_events: None,
+            max_events_per_read: None,
+            response_body_bytes: None,
+            body_download_time: None,
+            body_parse_time: None,
+            embedding_inputs: None,
+            embedding_vectors: None,
+            embedding_dimensions: None,
             tokens_per_second: 50.0,
             cost_usd: Some(0.05),
             success: true,
@@ -427,6 +941,39 @@ mod tests {
             input_tokens: 100,
             output_tokens: 3,
             thinking_tokens: None,
+            input_token_source: TokenCountSource::Reported,
+            output_token_source: TokenCountSource::Reported,
+            cache_creation_tokens: None,
+            cache_read_tokens: None,
+            cache_hit: None,
+            time_to_first_thinking_token: None,
+            thinking_duration: None,
+            time_to_first_visible_token: None,
+            reasoning_time: None,
+            time_to_first_tool_call: None,
+            tool_call_duration: None,
+            dns_time: None,
+            tcp_connect_time: None,
+            tls_handshake_time: None,
+            time_to_first_byte: None,
+            time_to_first_event: None,
+            session_setup_time: None,
+            response_ttft: None,
+            provider_request_id: None,
+            server_processing_time: None,
+            rate_limit_remaining_requests: None,
+            rate_limit_remaining_tokens: None,
+            attempts: None,
+            retry_backoff: None,
+            network_reads: None,
+            stream_events: None,
+            max_events_per_read: None,
+            response_body_bytes: None,
+            body_download_time: None,
+            body_parse_time: None,
+            embedding_inputs: None,
+            embedding_vectors: None,
+            embedding_dimensions: None,
             tokens_per_second: 3.0,
             cost_usd: None,
             success: true,
@@ -457,6 +1004,39 @@ mod tests {
             input_tokens: 100,
             output_tokens: 5,
             thinking_tokens: None,
+            input_token_source: TokenCountSource::Reported,
+            output_token_source: TokenCountSource::Reported,
+            cache_creation_tokens: None,
+            cache_read_tokens: None,
+            cache_hit: None,
+            time_to_first_thinking_token: None,
+            thinking_duration: None,
+            time_to_first_visible_token: None,
+            reasoning_time: None,
+            time_to_first_tool_call: None,
+            tool_call_duration: None,
+            dns_time: None,
+            tcp_connect_time: None,
+            tls_handshake_time: None,
+            time_to_first_byte: None,
+            time_to_first_event: None,
+            session_setup_time: None,
+            response_ttft: None,
+            provider_request_id: None,
+            server_processing_time: None,
+            rate_limit_remaining_requests: None,
+            rate_limit_remaining_tokens: None,
+            attempts: None,
+            retry_backoff: None,
+            network_reads: None,
+            stream_events: None,
+            max_events_per_read: None,
+            response_body_bytes: None,
+            body_download_time: None,
+            body_parse_time: None,
+            embedding_inputs: None,
+            embedding_vectors: None,
+            embedding_dimensions: None,
             tokens_per_second: 5.0,
             cost_usd: None,
             success: true,
@@ -481,6 +1061,39 @@ mod tests {
             input_tokens: 100,
             output_tokens: 50,
             thinking_tokens: Some(200),
+            input_token_source: TokenCountSource::Reported,
+            output_token_source: TokenCountSource::Reported,
+            cache_creation_tokens: None,
+            cache_read_tokens: None,
+            cache_hit: None,
+            time_to_first_thinking_token: None,
+            thinking_duration: None,
+            time_to_first_visible_token: None,
+            reasoning_time: None,
+            time_to_first_tool_call: None,
+            tool_call_duration: None,
+            dns_time: None,
+            tcp_connect_time: None,
+            tls_handshake_time: None,
+            time_to_first_byte: None,
+            time_to_first_event: None,
+            session_setup_time: None,
+            response_ttft: None,
+            provider_request_id: None,
+            server_processing_time: None,
+            rate_limit_remaining_requests: None,
+            rate_limit_remaining_tokens: None,
+            attempts: None,
+            retry_backoff: None,
+            network_reads: None,
+            stream_events: None,
+            max_events_per_read: None,
+            response_body_bytes: None,
+            body_download_time: None,
+            body_parse_time: None,
+            embedding_inputs: None,
+            embedding_vectors: None,
+            embedding_dimensions: None,
             tokens_per_second: 50.0,
             cost_usd: None,
             success: true,
@@ -502,10 +1115,30 @@ mod tests {
             ttft_distribution: LatencyDistribution::empty(),
             inter_token_distribution: LatencyDistribution::empty(),
             total_latency_distribution: LatencyDistribution::empty(),
+            thinking_ttft_distribution: LatencyDistribution::empty(),
+            thinking_duration_distribution: LatencyDistribution::empty(),
+            visible_ttft_distribution: LatencyDistribution::empty(),
+            reasoning_time_distribution: LatencyDistribution::empty(),
+            reasoning_tokens: ReasoningTokenStats::empty(),
+            tool_call_ttft_distribution: LatencyDistribution::empty(),
+            tool_call_duration_distribution: LatencyDistribution::empty(),
+            ttft_cache_hit_distribution: LatencyDistribution::empty(),
+            ttft_cache_miss_distribution: LatencyDistribution::empty(),
+            time_to_first_byte_distribution: LatencyDistribution::empty(),
+            time_to_first_event_distribution: LatencyDistribution::empty(),
+            session_setup_distribution: LatencyDistribution::empty(),
+            response_ttft_distribution: LatencyDistribution::empty(),
+            server_processing_distribution: LatencyDistribution::empty(),
+            stream_reads: StreamReadStats::empty(),
+            retries: RetryStats::empty(),
+            embeddings: EmbeddingStats::empty(),
             throughput: ThroughputStats::empty(),
             total_input_tokens: 10000,
             total_output_tokens: 5000,
             total_thinking_tokens: None,
+            total_cache_creation_tokens: None,
+            total_cache_read_tokens: None,
+            estimated_token_requests: 0,
             total_cost_usd: Some(10.0),
             provider_breakdown: vec![],
             model_breakdown: vec![],
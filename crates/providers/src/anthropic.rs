@@ -3,18 +3,26 @@
 //! This module provides a production-ready adapter for Anthropic's Messages API
 //! with support for:
 //! - Server-Sent Events (SSE) streaming
-//! - Extended thinking mode (Claude thinking tokens)
+//! - Extended thinking mode (`thinking_delta` events emitted as thinking tokens)
+//! - Tool use (`tool_use` blocks and `input_json_delta` fragments streamed as
+//!   timed tool-call events)
+//! - Token usage reporting from `message_start`/`message_delta` events
 //! - Fine-grained timing measurements
 //! - Automatic retries with exponential backoff
 //! - Cost calculation for all Claude models
 
 use crate::error::{parse_api_error, ProviderError, Result};
+use crate::headers::ServerSignals;
+use crate::models::{self, ModelCache, ModelList};
+use crate::network::NetworkProbe;
+use crate::retry::{self, RetryPolicy};
 use crate::traits::{
-    MessageRole, Provider, ResponseMetadata, StreamingRequest, StreamingResponse,
+    CompletionResult, ContentPart, Message, MessageRole, Provider, ResponseMetadata, SharedUsage,
+    StreamingRequest, StreamingResponse, TokenUsage, ToolChoice,
 };
 use async_trait::async_trait;
 use futures::StreamExt;
-use llm_latency_lens_core::{TimingEngine, Timestamp, TokenEvent};
+use llm_latency_lens_core::{TimingEngine, Timestamp, TokenEvent, TokenKind};
 use reqwest::header::{HeaderMap, HeaderValue, CONTENT_TYPE};
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
@@ -27,11 +35,12 @@ pub struct AnthropicProvider {
     api_key: String,
     /// Base URL (allows custom endpoints)
     base_url: String,
-    /// Maximum retry attempts
-    #[allow(dead_code)]
+    /// Retries of the initial request
     max_retries: u32,
     /// Anthropic API version
     api_version: String,
+    /// Models discovered from `/models`
+    models: ModelCache,
 }
 
 impl AnthropicProvider {
@@ -50,11 +59,12 @@ impl AnthropicProvider {
     /// ```
     pub fn new(api_key: impl Into<String>) -> Self {
         Self {
-            client: Self::build_client(),
+            client: Self::build_client(crate::network::DEFAULT_TIMEOUT),
             api_key: api_key.into(),
             base_url: "https://api.anthropic.com/v1".to_string(),
             max_retries: 3,
             api_version: "2023-06-01".to_string(),
+            models: ModelCache::default(),
         }
     }
 
@@ -63,10 +73,69 @@ impl AnthropicProvider {
         AnthropicProviderBuilder::default()
     }
 
+    /// Build the Messages API payload, streamed or not
+    fn messages_request(&self, request: &StreamingRequest, stream: bool) -> MessagesRequest {
+        // Extract system message if present
+        let system_message = request
+            .messages
+            .iter()
+            .find(|m| m.role == MessageRole::System)
+            .map(system_content);
+
+        // Build messages (excluding system)
+        let messages: Vec<AnthropicMessage> = request
+            .messages
+            .iter()
+            .filter(|m| m.role != MessageRole::System)
+            .map(|m| AnthropicMessage {
+                role: match m.role {
+                    MessageRole::User => "user".to_string(),
+                    MessageRole::Assistant => "assistant".to_string(),
+                    MessageRole::System => "user".to_string(), // Fallback, should be filtered
+                },
+                content: message_content(m),
+            })
+            .collect();
+
+        // The thinking budget counts towards max_tokens, so it is added on top
+        // of the requested output length. Thinking does not accept a custom
+        // temperature.
+        let thinking = request.thinking_budget.map(ThinkingConfig::enabled);
+        let max_tokens = request.max_tokens.unwrap_or(4096)
+            + thinking.as_ref().map_or(0, |t| t.budget_tokens);
+        let temperature = if thinking.is_some() {
+            None
+        } else {
+            request.temperature
+        };
+
+        MessagesRequest {
+            model: request.model.clone(),
+            messages,
+            max_tokens,
+            stream,
+            system: system_message,
+            temperature,
+            top_p: request.top_p,
+            stop_sequences: request.stop.clone(),
+            thinking,
+            tools: request
+                .tools
+                .iter()
+                .map(|t| AnthropicTool {
+                    name: t.name.clone(),
+                    description: t.description.clone(),
+                    input_schema: t.parameters.clone(),
+                })
+                .collect(),
+            tool_choice: request.tool_choice.as_ref().map(tool_choice_value),
+        }
+    }
+
     /// Build HTTP client with optimized settings
-    fn build_client() -> reqwest::Client {
-        reqwest::Client::builder()
-            .timeout(Duration::from_secs(120))
+    fn build_client(timeout: Duration) -> reqwest::Client {
+        crate::network::instrument(reqwest::Client::builder())
+            .timeout(timeout)
             .tcp_keepalive(Duration::from_secs(60))
             .pool_idle_timeout(Duration::from_secs(90))
             .build()
@@ -88,51 +157,6 @@ impl AnthropicProvider {
 
         headers
     }
-
-    /// Execute request with retries
-    #[allow(dead_code)]
-    async fn execute_with_retries<F, Fut, T>(&self, operation: F) -> Result<T>
-    where
-        F: Fn() -> Fut,
-        Fut: std::future::Future<Output = Result<T>>,
-    {
-        let mut attempts = 0;
-        let mut last_error = None;
-
-        while attempts < self.max_retries {
-            match operation().await {
-                Ok(result) => return Ok(result),
-                Err(e) => {
-                    if !e.is_retryable() {
-                        return Err(e);
-                    }
-
-                    last_error = Some(e.clone());
-                    attempts += 1;
-
-                    if attempts < self.max_retries {
-                        let delay = if let Some(retry_after) = e.retry_delay() {
-                            Duration::from_secs(retry_after)
-                        } else {
-                            Duration::from_secs(2_u64.pow(attempts - 1))
-                        };
-
-                        tracing::warn!(
-                            "Request failed (attempt {}/{}), retrying after {:?}: {}",
-                            attempts,
-                            self.max_retries,
-                            delay,
-                            e
-                        );
-
-                        tokio::time::sleep(delay).await;
-                    }
-                }
-            }
-        }
-
-        Err(last_error.unwrap_or_else(|| ProviderError::Other("Max retries exceeded".to_string())))
-    }
 }
 
 /// Builder for Anthropic provider
@@ -141,6 +165,7 @@ pub struct AnthropicProviderBuilder {
     api_key: Option<String>,
     base_url: Option<String>,
     max_retries: Option<u32>,
+    timeout: Option<Duration>,
     api_version: Option<String>,
 }
 
@@ -157,12 +182,18 @@ impl AnthropicProviderBuilder {
         self
     }
 
-    /// Set maximum retry attempts
+    /// Set how many times a failed initial request is retried (3 by default)
     pub fn max_retries(mut self, retries: u32) -> Self {
         self.max_retries = Some(retries);
         self
     }
 
+    /// Set the HTTP request timeout (120 seconds by default)
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
     /// Set API version
     pub fn api_version(mut self, version: impl Into<String>) -> Self {
         self.api_version = Some(version.into());
@@ -172,7 +203,9 @@ impl AnthropicProviderBuilder {
     /// Build the provider
     pub fn build(self) -> AnthropicProvider {
         AnthropicProvider {
-            client: AnthropicProvider::build_client(),
+            client: AnthropicProvider::build_client(
+                self.timeout.unwrap_or(crate::network::DEFAULT_TIMEOUT),
+            ),
             api_key: self.api_key.expect("API key is required"),
             base_url: self
                 .base_url
@@ -181,6 +214,7 @@ impl AnthropicProviderBuilder {
             api_version: self
                 .api_version
                 .unwrap_or_else(|| "2023-06-01".to_string()),
+            models: ModelCache::default(),
         }
     }
 }
@@ -191,6 +225,10 @@ impl Provider for AnthropicProvider {
         "anthropic"
     }
 
+    fn provider_kind(&self) -> llm_latency_lens_core::Provider {
+        llm_latency_lens_core::Provider::Anthropic
+    }
+
     async fn health_check(&self) -> Result<()> {
         // Anthropic doesn't have a dedicated health endpoint
         // We'll make a minimal request to validate the API key
@@ -200,7 +238,7 @@ impl Provider for AnthropicProvider {
             model: "claude-3-5-sonnet-20241022".to_string(),
             messages: vec![AnthropicMessage {
                 role: "user".to_string(),
-                content: "Hello".to_string(),
+                content: serde_json::json!("Hello"),
             }],
             max_tokens: 1,
             stream: false,
@@ -208,6 +246,9 @@ impl Provider for AnthropicProvider {
             temperature: None,
             top_p: None,
             stop_sequences: None,
+            thinking: None,
+            tools: Vec::new(),
+            tool_choice: None,
         };
 
         let response = self
@@ -236,41 +277,11 @@ impl Provider for AnthropicProvider {
 
         // Start timing measurement
         let mut timing = timing_engine.start();
+        let network = NetworkProbe::new(timing_engine.clock());
         timing.checkpoint("request_start");
 
-        // Extract system message if present
-        let system_message = request
-            .messages
-            .iter()
-            .find(|m| m.role == MessageRole::System)
-            .map(|m| m.content.clone());
-
-        // Build messages (excluding system)
-        let messages: Vec<AnthropicMessage> = request
-            .messages
-            .iter()
-            .filter(|m| m.role != MessageRole::System)
-            .map(|m| AnthropicMessage {
-                role: match m.role {
-                    MessageRole::User => "user".to_string(),
-                    MessageRole::Assistant => "assistant".to_string(),
-                    MessageRole::System => "user".to_string(), // Fallback, should be filtered
-                },
-                content: m.content.clone(),
-            })
-            .collect();
-
         // Build request payload
-        let payload = MessagesRequest {
-            model: request.model.clone(),
-            messages,
-            max_tokens: request.max_tokens.unwrap_or(4096),
-            stream: true,
-            system: system_message,
-            temperature: request.temperature,
-            top_p: request.top_p,
-            stop_sequences: request.stop.clone(),
-        };
+        let payload = self.messages_request(&request, true);
 
         timing.checkpoint("payload_built");
 
@@ -279,7 +290,7 @@ impl Provider for AnthropicProvider {
 
         timing.checkpoint("headers_built");
 
-        // Create event source for SSE streaming
+        // Build the SSE request
         let request_id = request.request_id;
         let req_builder = self
             .client
@@ -289,32 +300,109 @@ impl Provider for AnthropicProvider {
 
         timing.checkpoint("http_request_built");
 
-        let event_source = reqwest_eventsource::EventSource::new(req_builder)
-            .map_err(|e| ProviderError::streaming(format!("Failed to create event source: {}", e)))?;
+        let response = retry::send(
+            RetryPolicy::new(self.max_retries),
+            &network,
+            req_builder,
+            parse_api_error,
+        )
+        .await?;
+
+        timing.checkpoint("response_received");
+
+        let response_headers =
+            crate::headers::capture(response.headers(), &request.redact_headers);
+        let server = ServerSignals::from_headers(response.headers());
+        let event_source = crate::sse::events(
+            response.bytes_stream(),
+            timing_engine.clock().clone(),
+            network.clone(),
+        );
 
         timing.checkpoint("event_source_created");
 
         // Create token stream
-        let clock = timing_engine.clock().clone();
         let request_start = timing.start_time();
+        let usage = SharedUsage::new();
+        let stream_usage = usage.clone();
         let mut sequence = 0u64;
         let mut last_token_time: Option<Timestamp> = None;
 
         let token_stream = event_source
             .map(move |event_result| {
                 match event_result {
-                    Ok(reqwest_eventsource::Event::Open) => {
-                        tracing::debug!("SSE stream opened");
-                        return None;
-                    }
-                    Ok(reqwest_eventsource::Event::Message(message)) => {
+                    Ok(message) => {
                         // Parse event type
                         let event_type = &message.event;
 
                         match event_type.as_str() {
-                            "message_start" | "content_block_start" | "content_block_stop" => {
+                            "message_start" => {
+                                // Input tokens are known up front
+                                match serde_json::from_str::<MessageStart>(&message.data) {
+                                    Ok(start) => {
+                                        let reported = start.message.usage;
+                                        stream_usage.update(|u| {
+                                            u.input_tokens = reported.input_tokens.or(u.input_tokens);
+                                            u.output_tokens =
+                                                reported.output_tokens.or(u.output_tokens);
+                                            u.cache_creation_tokens = reported
+                                                .cache_creation_input_tokens
+                                                .or(u.cache_creation_tokens);
+                                            u.cache_read_tokens = reported
+                                                .cache_read_input_tokens
+                                                .or(u.cache_read_tokens);
+                                        });
+                                        None
+                                    }
+                                    Err(e) => {
+                                        tracing::error!("Failed to parse message_start: {}", e);
+                                        Some(Err(ProviderError::sse_parse(format!(
+                                            "Invalid message_start JSON: {}",
+                                            e
+                                        ))))
+                                    }
+                                }
+                            }
+                            "content_block_start" => {
+                                // A tool_use block marks the first tool-call byte
+                                let start: ContentBlockStart =
+                                    match serde_json::from_str(&message.data) {
+                                        Ok(s) => s,
+                                        Err(e) => {
+                                            tracing::error!("Failed to parse block start: {}", e);
+                                            return Some(Err(ProviderError::sse_parse(format!(
+                                                "Invalid content_block_start JSON: {}",
+                                                e
+                                            ))));
+                                        }
+                                    };
+                                if start.content_block.block_type != "tool_use" {
+                                    return None;
+                                }
+
+                                let now = message.received_at;
+                                let time_since_start = now.duration_since(request_start);
+                                let inter_token_latency =
+                                    last_token_time.map(|t| now.duration_since(t));
+                                last_token_time = Some(now);
+
+                                let event = TokenEvent {
+                                    request_id,
+                                    sequence,
+                                    kind: TokenKind::ToolCall,
+                                    content: Some(String::new()),
+                                    timestamp_nanos: now.as_nanos(),
+                                    time_since_start,
+                                    inter_token_latency,
+                                };
+
+                                sequence += 1;
+
+                                Some(Ok(event))
+                            }
+                            "content_block_stop" | "ping" => {
                                 // Skip metadata events
-                                return None;
+                                None
                             }
                             "content_block_delta" => {
                                 // Parse delta event
@@ -330,18 +418,21 @@ impl Provider for AnthropicProvider {
                                         }
                                     };
 
-                                // Extract text content
-                                let content = match delta.delta.delta_type.as_str() {
-                                    "text_delta" => delta.delta.text,
-                                    _ => None,
+                                // Extract text, thinking or tool-input content;
+                                // signature deltas carry no generated text
+                                let (kind, content) = match delta.delta.delta_type.as_str() {
+                                    "text_delta" => (TokenKind::Content, delta.delta.text?),
+                                    "thinking_delta" => {
+                                        (TokenKind::Thinking, delta.delta.thinking?)
+                                    }
+                                    "input_json_delta" => {
+                                        (TokenKind::ToolCall, delta.delta.partial_json?)
+                                    }
+                                    _ => return None,
                                 };
 
-                                if content.is_none() {
-                                    return None;
-                                }
-
-                                // Record timing
-                                let now = clock.now();
+                                // Record timing at the arrival of the read carrying the event
+                                let now = message.received_at;
                                 let time_since_start = now.duration_since(request_start);
                                 let inter_token_latency =
                                     last_token_time.map(|t| now.duration_since(t));
@@ -350,7 +441,8 @@ impl Provider for AnthropicProvider {
                                 let event = TokenEvent {
                                     request_id,
                                     sequence,
-                                    content,
+                                    kind,
+                                    content: Some(content),
                                     timestamp_nanos: now.as_nanos(),
                                     time_since_start,
                                     inter_token_latency,
@@ -361,31 +453,52 @@ impl Provider for AnthropicProvider {
                                 Some(Ok(event))
                             }
                             "message_delta" => {
-                                // Final message with usage stats
-                                tracing::debug!("Message delta received");
-                                return None;
+                                // Final message with cumulative output usage
+                                match serde_json::from_str::<MessageDelta>(&message.data) {
+                                    Ok(delta) => {
+                                        if let Some(reported) = delta.usage {
+                                            stream_usage.update(|u| {
+                                                u.input_tokens =
+                                                    reported.input_tokens.or(u.input_tokens);
+                                                u.output_tokens =
+                                                    reported.output_tokens.or(u.output_tokens);
+                                                u.cache_creation_tokens = reported
+                                                    .cache_creation_input_tokens
+                                                    .or(u.cache_creation_tokens);
+                                                u.cache_read_tokens = reported
+                                                    .cache_read_input_tokens
+                                                    .or(u.cache_read_tokens);
+                                            });
+                                        }
+                                        None
+                                    }
+                                    Err(e) => {
+                                        tracing::error!("Failed to parse message_delta: {}", e);
+                                        Some(Err(ProviderError::sse_parse(format!(
+                                            "Invalid message_delta JSON: {}",
+                                            e
+                                        ))))
+                                    }
+                                }
                             }
                             "message_stop" => {
                                 tracing::debug!("SSE stream completed");
-                                return None;
+                                None
                             }
                             "error" => {
                                 tracing::error!("Error event received: {}", message.data);
-                                return Some(Err(ProviderError::streaming(format!(
+                                Some(Err(ProviderError::streaming(format!(
                                     "API error: {}",
                                     message.data
-                                ))));
+                                ))))
                             }
                             _ => {
                                 tracing::warn!("Unknown event type: {}", event_type);
-                                return None;
+                                None
                             }
                         }
                     }
-                    Err(e) => {
-                        tracing::error!("SSE stream error: {}", e);
-                        Some(Err(ProviderError::streaming(format!("SSE error: {}", e))))
-                    }
+                    Err(e) => Some(Err(e)),
                 }
             })
             .filter_map(|x| async move { x })
@@ -398,45 +511,91 @@ impl Provider for AnthropicProvider {
             token_stream: Box::pin(token_stream),
             metadata: ResponseMetadata {
                 model: request.model,
-                input_tokens: None,
+                input_tokens: None, // Reported in message_start/message_delta
                 output_tokens: None,
                 thinking_tokens: None,
+                cache_creation_tokens: None,
+                cache_read_tokens: None,
                 estimated_cost: None,
-                headers: vec![],
+                headers: response_headers,
+                server,
             },
+            usage,
+            network,
         })
     }
 
-    fn calculate_cost(&self, model: &str, input_tokens: u64, output_tokens: u64) -> Option<f64> {
-        // Pricing per 1M tokens (as of 2024)
-        let (input_price, output_price) = match model {
-            // Claude 3.5 Sonnet
-            "claude-3-5-sonnet-20241022" | "claude-3-5-sonnet-20240620" => (3.0, 15.0),
-
-            // Claude 3.5 Haiku
-            "claude-3-5-haiku-20241022" => (0.80, 4.0),
+    async fn complete_non_streaming(
+        &self,
+        request: StreamingRequest,
+        timing_engine: &TimingEngine,
+    ) -> Result<CompletionResult> {
+        self.validate_model(&request.model)?;
 
-            // Claude 3 Opus
-            "claude-3-opus-20240229" => (15.0, 75.0),
+        let timing = timing_engine.start();
+        let network = NetworkProbe::new(timing_engine.clock());
 
-            // Claude 3 Sonnet
-            "claude-3-sonnet-20240229" => (3.0, 15.0),
+        let payload = self.messages_request(&request, false);
+        let url = format!("{}/messages", self.base_url);
+        let req_builder = self
+            .client
+            .post(&url)
+            .headers(self.build_headers())
+            .json(&payload);
 
-            // Claude 3 Haiku
-            "claude-3-haiku-20240307" => (0.25, 1.25),
+        let response = retry::send(
+            RetryPolicy::new(self.max_retries),
+            &network,
+            req_builder,
+            parse_api_error,
+        )
+        .await?;
+
+        let response_headers =
+            crate::headers::capture(response.headers(), &request.redact_headers);
+        let server = ServerSignals::from_headers(response.headers());
+        let (message, body): (MessagesResponse, _) =
+            crate::body::read_json(response, timing_engine.clock(), timing.start_time()).await?;
+
+        // Text and thinking blocks; tool_use blocks carry no generated text
+        let mut content = String::new();
+        let mut thinking = String::new();
+        for block in message.content {
+            match block.block_type.as_str() {
+                "text" => content.push_str(block.text.as_deref().unwrap_or_default()),
+                "thinking" => thinking.push_str(block.thinking.as_deref().unwrap_or_default()),
+                _ => {}
+            }
+        }
 
-            // Unknown model
-            _ => return None,
+        let mut metadata = ResponseMetadata {
+            model: request.model.clone(),
+            input_tokens: None,
+            output_tokens: None,
+            thinking_tokens: None,
+            cache_creation_tokens: None,
+            cache_read_tokens: None,
+            estimated_cost: None,
+            headers: response_headers,
+            server,
         };
-
-        let input_cost = (input_tokens as f64 / 1_000_000.0) * input_price;
-        let output_cost = (output_tokens as f64 / 1_000_000.0) * output_price;
-
-        Some(input_cost + output_cost)
+        metadata.apply_usage(message.usage.token_usage());
+        metadata.estimate_cost(|usage| self.usage_cost(&request.model, usage));
+
+        Ok(CompletionResult::from_body(
+            request.request_id,
+            content,
+            thinking,
+            metadata,
+            &network,
+            body,
+        ))
     }
 
     fn supported_models(&self) -> Vec<String> {
         vec![
+            // Claude 3.7 Sonnet
+            "claude-3-7-sonnet-20250219".to_string(),
             // Claude 3.5 Sonnet
             "claude-3-5-sonnet-20241022".to_string(),
             "claude-3-5-sonnet-20240620".to_string(),
@@ -450,6 +609,41 @@ impl Provider for AnthropicProvider {
             "claude-3-haiku-20240307".to_string(),
         ]
     }
+
+    async fn list_models(&self) -> Result<Vec<String>> {
+        let mut ids = Vec::new();
+        let mut after_id: Option<String> = None;
+        loop {
+            let mut url = format!("{}/models?limit=1000", self.base_url);
+            if let Some(ref id) = after_id {
+                url.push_str("&after_id=");
+                url.push_str(id);
+            }
+            let response = self
+                .client
+                .get(&url)
+                .headers(self.build_headers())
+                .send()
+                .await
+                .map_err(ProviderError::from_reqwest)?;
+
+            if !response.status().is_success() {
+                return Err(parse_api_error(response).await);
+            }
+            let list: ModelList = models::read_list(response).await?;
+            after_id = list.last_id.clone().filter(|_| list.has_more);
+            ids.extend(list.ids());
+            if after_id.is_none() {
+                break;
+            }
+        }
+        ids.sort();
+        Ok(ids)
+    }
+
+    fn model_cache(&self) -> Option<&ModelCache> {
+        Some(&self.models)
+    }
 }
 
 // Anthropic API request/response types
@@ -461,19 +655,117 @@ struct MessagesRequest {
     max_tokens: u32,
     stream: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
-    system: Option<String>,
+    system: Option<serde_json::Value>,
     #[serde(skip_serializing_if = "Option::is_none")]
     temperature: Option<f32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     top_p: Option<f32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     stop_sequences: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    thinking: Option<ThinkingConfig>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tools: Vec<AnthropicTool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_choice: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Serialize)]
+struct AnthropicTool {
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    input_schema: serde_json::Value,
+}
+
+/// `cache_control` marker for a prompt-cache breakpoint
+fn cache_control() -> serde_json::Value {
+    serde_json::json!({"type": "ephemeral"})
+}
+
+/// Messages API `system` value
+///
+/// A cache breakpoint on the system message requires the block form.
+fn system_content(message: &Message) -> serde_json::Value {
+    if message.cache_breakpoint {
+        serde_json::json!([{
+            "type": "text",
+            "text": message.text(),
+            "cache_control": cache_control()
+        }])
+    } else {
+        serde_json::json!(message.text())
+    }
+}
+
+/// Messages API `content` value
+///
+/// Text-only messages stay a plain string; attachments become `image` and
+/// `document` blocks with a `base64` or `url` source. A cache breakpoint
+/// marks the last block with `cache_control`.
+fn message_content(message: &Message) -> serde_json::Value {
+    if message.is_text_only() && !message.cache_breakpoint {
+        return serde_json::json!(message.text());
+    }
+
+    let mut blocks: Vec<serde_json::Value> = message
+        .content
+        .iter()
+        .map(|part| match part {
+            ContentPart::Text { text } => serde_json::json!({"type": "text", "text": text}),
+            ContentPart::ImageUrl { url } => serde_json::json!({
+                "type": "image",
+                "source": {"type": "url", "url": url}
+            }),
+            ContentPart::Image { media_type, data } => serde_json::json!({
+                "type": "image",
+                "source": {"type": "base64", "media_type": media_type, "data": data}
+            }),
+            ContentPart::Document { media_type, data } => serde_json::json!({
+                "type": "document",
+                "source": {"type": "base64", "media_type": media_type, "data": data}
+            }),
+        })
+        .collect();
+    if message.cache_breakpoint {
+        if let Some(last) = blocks.last_mut() {
+            last["cache_control"] = cache_control();
+        }
+    }
+    serde_json::json!(blocks)
+}
+
+/// Messages API `tool_choice` value
+fn tool_choice_value(choice: &ToolChoice) -> serde_json::Value {
+    match choice {
+        ToolChoice::Auto => serde_json::json!({"type": "auto"}),
+        ToolChoice::None => serde_json::json!({"type": "none"}),
+        ToolChoice::Required => serde_json::json!({"type": "any"}),
+        ToolChoice::Tool(name) => serde_json::json!({"type": "tool", "name": name}),
+    }
+}
+
+/// Extended thinking configuration
+#[derive(Debug, Serialize)]
+struct ThinkingConfig {
+    #[serde(rename = "type")]
+    thinking_type: &'static str,
+    budget_tokens: u32,
+}
+
+impl ThinkingConfig {
+    fn enabled(budget_tokens: u32) -> Self {
+        Self {
+            thinking_type: "enabled",
+            budget_tokens,
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 struct AnthropicMessage {
     role: String,
-    content: String,
+    content: serde_json::Value,
 }
 
 #[derive(Debug, Deserialize)]
@@ -491,11 +783,93 @@ struct Delta {
     delta_type: String,
     #[serde(default)]
     text: Option<String>,
+    #[serde(default)]
+    thinking: Option<String>,
+    #[serde(default)]
+    partial_json: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ContentBlockStart {
+    content_block: ContentBlock,
+}
+
+#[derive(Debug, Deserialize)]
+struct ContentBlock {
+    #[serde(rename = "type")]
+    block_type: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct MessageStart {
+    message: MessageStartBody,
+}
+
+#[derive(Debug, Deserialize)]
+struct MessageStartBody {
+    #[serde(default)]
+    usage: Usage,
+}
+
+/// Non-streaming Messages API response
+#[derive(Debug, Deserialize)]
+struct MessagesResponse {
+    #[serde(default)]
+    content: Vec<ResponseBlock>,
+    #[serde(default)]
+    usage: Usage,
+}
+
+#[derive(Debug, Deserialize)]
+struct ResponseBlock {
+    #[serde(rename = "type")]
+    block_type: String,
+    #[serde(default)]
+    text: Option<String>,
+    #[serde(default)]
+    thinking: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MessageDelta {
+    #[serde(default)]
+    usage: Option<Usage>,
+}
+
+/// Usage counts; `message_delta` reports cumulative output tokens
+///
+/// `input_tokens` excludes the cached prefix, which is reported in the
+/// `cache_*` fields instead.
+#[derive(Debug, Default, Deserialize)]
+struct Usage {
+    #[serde(default)]
+    input_tokens: Option<u64>,
+    #[serde(default)]
+    output_tokens: Option<u64>,
+    #[serde(default)]
+    cache_creation_input_tokens: Option<u64>,
+    #[serde(default)]
+    cache_read_input_tokens: Option<u64>,
+}
+
+impl Usage {
+    /// Usage counts as reported (thinking is not counted separately)
+    fn token_usage(&self) -> TokenUsage {
+        TokenUsage {
+            input_tokens: self.input_tokens,
+            output_tokens: self.output_tokens,
+            thinking_tokens: None,
+            cache_creation_tokens: self.cache_creation_input_tokens,
+            cache_read_tokens: self.cache_read_input_tokens,
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use wiremock::matchers::{body_partial_json, header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
 
     #[test]
     fn test_provider_name() {
@@ -580,4 +954,286 @@ mod tests {
         assert_eq!(headers.get("x-api-key").unwrap(), "test-key");
         assert_eq!(headers.get("anthropic-version").unwrap(), "2024-01-01");
     }
+
+    #[test]
+    fn test_message_content_blocks() {
+        let text = Message::new(MessageRole::User, "Hi");
+        assert_eq!(message_content(&text), serde_json::json!("Hi"));
+
+        let message = Message {
+            role: MessageRole::User,
+            content: vec![
+                ContentPart::Image {
+                    media_type: "image/png".to_string(),
+                    data: "AAAA".to_string(),
+                },
+                ContentPart::image_url("https://example.com/a.png"),
+                ContentPart::Document {
+                    media_type: "application/pdf".to_string(),
+                    data: "JVBE".to_string(),
+                },
+                ContentPart::text("Summarize"),
+            ],
+            cache_breakpoint: false,
+        };
+        assert_eq!(
+            message_content(&message),
+            serde_json::json!([
+                {"type": "image", "source": {"type": "base64", "media_type": "image/png", "data": "AAAA"}},
+                {"type": "image", "source": {"type": "url", "url": "https://example.com/a.png"}},
+                {"type": "document", "source": {"type": "base64", "media_type": "application/pdf", "data": "JVBE"}},
+                {"type": "text", "text": "Summarize"}
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_stream_reports_usage() {
+        let server = MockServer::start().await;
+        let body = [
+            ("message_start", r#"{"type":"message_start","message":{"id":"msg_1","type":"message","role":"assistant","content":[],"model":"claude-3-5-haiku-20241022","stop_reason":null,"usage":{"input_tokens":1000,"output_tokens":1}}}"#),
+            ("content_block_start", r#"{"type":"content_block_start","index":0,"content_block":{"type":"text","text":""}}"#),
+            ("ping", r#"{"type":"ping"}"#),
+            ("content_block_delta", r#"{"type":"content_block_delta","index":0,"delta":{"type":"text_delta","text":"Hello"}}"#),
+            ("content_block_delta", r#"{"type":"content_block_delta","index":0,"delta":{"type":"text_delta","text":" world"}}"#),
+            ("content_block_stop", r#"{"type":"content_block_stop","index":0}"#),
+            ("message_delta", r#"{"type":"message_delta","delta":{"stop_reason":"end_turn","stop_sequence":null},"usage":{"output_tokens":1000}}"#),
+            ("message_stop", r#"{"type":"message_stop"}"#),
+        ]
+        .iter()
+        .map(|(event, data)| format!("event: {}\ndata: {}\n\n", event, data))
+        .collect::<String>();
+
+        Mock::given(method("POST"))
+            .and(path("/messages"))
+            .and(header("x-api-key", "test-key"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(body, "text/event-stream"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = AnthropicProvider::builder()
+            .api_key("test-key")
+            .base_url(server.uri())
+            .build();
+        let request = StreamingRequest::builder()
+            .model("claude-3-5-haiku-20241022")
+            .message(MessageRole::User, "Hi")
+            .build();
+
+        let result = provider.complete(request, &TimingEngine::new()).await.unwrap();
+
+        assert_eq!(result.content, "Hello world");
+        assert_eq!(result.token_events.len(), 2);
+        assert_eq!(result.metadata.input_tokens, Some(1000));
+        assert_eq!(result.metadata.output_tokens, Some(1000));
+        // Claude 3.5 Haiku: $0.80/1M input, $4.00/1M output
+        let cost = result.metadata.estimated_cost.unwrap();
+        assert!((cost - 0.0048).abs() < 0.000001);
+    }
+
+    #[tokio::test]
+    async fn test_complete_non_streaming() {
+        let server = MockServer::start().await;
+        let body = r#"{"id":"msg_2","type":"message","role":"assistant","model":"claude-3-5-haiku-20241022","content":[{"type":"thinking","thinking":"Greet back.","signature":"sig"},{"type":"text","text":"Hello world"}],"stop_reason":"end_turn","usage":{"input_tokens":1000,"output_tokens":1000}}"#;
+
+        Mock::given(method("POST"))
+            .and(path("/messages"))
+            .and(body_partial_json(serde_json::json!({"stream": false})))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(body, "application/json"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = AnthropicProvider::builder()
+            .api_key("test-key")
+            .base_url(server.uri())
+            .build();
+        let request = StreamingRequest::builder()
+            .model("claude-3-5-haiku-20241022")
+            .message(MessageRole::User, "Hi")
+            .build();
+
+        let result = provider
+            .complete_non_streaming(request, &TimingEngine::new())
+            .await
+            .unwrap();
+
+        assert_eq!(result.content, "Hello world");
+        assert_eq!(result.thinking, "Greet back.");
+        assert!(result.token_events.is_empty());
+        assert_eq!(result.metadata.input_tokens, Some(1000));
+        let cost = result.metadata.estimated_cost.unwrap();
+        assert!((cost - 0.0048).abs() < 0.000001);
+        assert_eq!(result.body.unwrap().size_bytes, body.len() as u64);
+    }
+
+    #[tokio::test]
+    async fn test_stream_prompt_cache() {
+        let server = MockServer::start().await;
+        let body = [
+            ("message_start", r#"{"type":"message_start","message":{"id":"msg_1","type":"message","role":"assistant","content":[],"model":"claude-3-5-haiku-20241022","stop_reason":null,"usage":{"input_tokens":12,"cache_creation_input_tokens":0,"cache_read_input_tokens":2048,"output_tokens":1}}}"#),
+            ("content_block_start", r#"{"type":"content_block_start","index":0,"content_block":{"type":"text","text":""}}"#),
+            ("content_block_delta", r#"{"type":"content_block_delta","index":0,"delta":{"type":"text_delta","text":"Hi"}}"#),
+            ("content_block_stop", r#"{"type":"content_block_stop","index":0}"#),
+            ("message_delta", r#"{"type":"message_delta","delta":{"stop_reason":"end_turn","stop_sequence":null},"usage":{"output_tokens":1}}"#),
+            ("message_stop", r#"{"type":"message_stop"}"#),
+        ]
+        .iter()
+        .map(|(event, data)| format!("event: {}\ndata: {}\n\n", event, data))
+        .collect::<String>();
+
+        Mock::given(method("POST"))
+            .and(path("/messages"))
+            .and(body_partial_json(serde_json::json!({
+                "system": [{
+                    "type": "text",
+                    "text": "Long shared instructions",
+                    "cache_control": {"type": "ephemeral"}
+                }],
+                "messages": [{"role": "user", "content": "Hi"}]
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(body, "text/event-stream"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = AnthropicProvider::builder()
+            .api_key("test-key")
+            .base_url(server.uri())
+            .build();
+        let request = StreamingRequest::builder()
+            .model("claude-3-5-haiku-20241022")
+            .message(MessageRole::System, "Long shared instructions")
+            .cache_breakpoint()
+            .message(MessageRole::User, "Hi")
+            .build();
+
+        let result = provider.complete(request, &TimingEngine::new()).await.unwrap();
+
+        assert_eq!(result.metadata.input_tokens, Some(12));
+        assert_eq!(result.metadata.cache_creation_tokens, Some(0));
+        assert_eq!(result.metadata.cache_read_tokens, Some(2048));
+        assert_eq!(result.metadata.cache_hit(), Some(true));
+    }
+
+    #[tokio::test]
+    async fn test_stream_extended_thinking() {
+        let server = MockServer::start().await;
+        let body = [
+            ("message_start", r#"{"type":"message_start","message":{"id":"msg_1","type":"message","role":"assistant","content":[],"model":"claude-3-7-sonnet-20250219","stop_reason":null,"usage":{"input_tokens":20,"output_tokens":1}}}"#),
+            ("content_block_start", r#"{"type":"content_block_start","index":0,"content_block":{"type":"thinking","thinking":""}}"#),
+            ("content_block_delta", r#"{"type":"content_block_delta","index":0,"delta":{"type":"thinking_delta","thinking":"Let me think."}}"#),
+            ("content_block_delta", r#"{"type":"content_block_delta","index":0,"delta":{"type":"thinking_delta","thinking":" Done."}}"#),
+            ("content_block_delta", r#"{"type":"content_block_delta","index":0,"delta":{"type":"signature_delta","signature":"EqQBCgIYAhIM"}}"#),
+            ("content_block_stop", r#"{"type":"content_block_stop","index":0}"#),
+            ("content_block_start", r#"{"type":"content_block_start","index":1,"content_block":{"type":"text","text":""}}"#),
+            ("content_block_delta", r#"{"type":"content_block_delta","index":1,"delta":{"type":"text_delta","text":"Answer"}}"#),
+            ("content_block_stop", r#"{"type":"content_block_stop","index":1}"#),
+            ("message_delta", r#"{"type":"message_delta","delta":{"stop_reason":"end_turn","stop_sequence":null},"usage":{"output_tokens":42}}"#),
+            ("message_stop", r#"{"type":"message_stop"}"#),
+        ]
+        .iter()
+        .map(|(event, data)| format!("event: {}\ndata: {}\n\n", event, data))
+        .collect::<String>();
+
+        Mock::given(method("POST"))
+            .and(path("/messages"))
+            .and(body_partial_json(serde_json::json!({
+                "max_tokens": 1124,
+                "thinking": {"type": "enabled", "budget_tokens": 1024}
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(body, "text/event-stream"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = AnthropicProvider::builder()
+            .api_key("test-key")
+            .base_url(server.uri())
+            .build();
+        let request = StreamingRequest::builder()
+            .model("claude-3-7-sonnet-20250219")
+            .message(MessageRole::User, "Hi")
+            .max_tokens(100)
+            .temperature(0.2)
+            .thinking_budget(1024)
+            .build();
+
+        let result = provider.complete(request, &TimingEngine::new()).await.unwrap();
+
+        assert_eq!(result.thinking, "Let me think. Done.");
+        assert_eq!(result.content, "Answer");
+        let kinds: Vec<TokenKind> = result.token_events.iter().map(|e| e.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![TokenKind::Thinking, TokenKind::Thinking, TokenKind::Content]
+        );
+        assert!(
+            result.time_to_first_thinking_token().unwrap()
+                <= result.time_to_first_visible_token().unwrap()
+        );
+        assert_eq!(result.metadata.output_tokens, Some(42));
+    }
+
+    #[tokio::test]
+    async fn test_stream_tool_use() {
+        let server = MockServer::start().await;
+        let body = [
+            ("message_start", r#"{"type":"message_start","message":{"id":"msg_1","type":"message","role":"assistant","content":[],"model":"claude-3-5-sonnet-20241022","stop_reason":null,"usage":{"input_tokens":50,"output_tokens":1}}}"#),
+            ("content_block_start", r#"{"type":"content_block_start","index":0,"content_block":{"type":"text","text":""}}"#),
+            ("content_block_delta", r#"{"type":"content_block_delta","index":0,"delta":{"type":"text_delta","text":"Checking."}}"#),
+            ("content_block_stop", r#"{"type":"content_block_stop","index":0}"#),
+            ("content_block_start", r#"{"type":"content_block_start","index":1,"content_block":{"type":"tool_use","id":"toolu_1","name":"get_weather","input":{}}}"#),
+            ("content_block_delta", r#"{"type":"content_block_delta","index":1,"delta":{"type":"input_json_delta","partial_json":""}}"#),
+            ("content_block_delta", r#"{"type":"content_block_delta","index":1,"delta":{"type":"input_json_delta","partial_json":"{\"city\": \"Paris\"}"}}"#),
+            ("content_block_stop", r#"{"type":"content_block_stop","index":1}"#),
+            ("message_delta", r#"{"type":"message_delta","delta":{"stop_reason":"tool_use","stop_sequence":null},"usage":{"output_tokens":30}}"#),
+            ("message_stop", r#"{"type":"message_stop"}"#),
+        ]
+        .iter()
+        .map(|(event, data)| format!("event: {}\ndata: {}\n\n", event, data))
+        .collect::<String>();
+
+        Mock::given(method("POST"))
+            .and(path("/messages"))
+            .and(body_partial_json(serde_json::json!({
+                "tools": [{"name": "get_weather", "input_schema": {"type": "object"}}],
+                "tool_choice": {"type": "tool", "name": "get_weather"}
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(body, "text/event-stream"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = AnthropicProvider::builder()
+            .api_key("test-key")
+            .base_url(server.uri())
+            .build();
+        let request = StreamingRequest::builder()
+            .model("claude-3-5-sonnet-20241022")
+            .message(MessageRole::User, "Weather in Paris?")
+            .tool(crate::traits::Tool::new(
+                "get_weather",
+                serde_json::json!({"type": "object"}),
+            ))
+            .tool_choice(ToolChoice::Tool("get_weather".to_string()))
+            .build();
+
+        let result = provider.complete(request, &TimingEngine::new()).await.unwrap();
+
+        assert_eq!(result.content, "Checking.");
+        let kinds: Vec<TokenKind> = result.token_events.iter().map(|e| e.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                TokenKind::Content,
+                TokenKind::ToolCall,
+                TokenKind::ToolCall,
+                TokenKind::ToolCall
+            ]
+        );
+        assert!(result.time_to_first_tool_call().unwrap() >= result.ttft().unwrap());
+        assert!(result.tool_call_duration().is_some());
+    }
 }
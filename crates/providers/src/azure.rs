@@ -0,0 +1,1172 @@
+//! Azure OpenAI provider implementation
+//!
+//! This module provides an adapter for the Azure OpenAI Service Chat Completions
+//! API with support for:
+//! - Deployment-based routing (`{endpoint}/openai/deployments/{deployment}/...`)
+//! - `api-key` header or Microsoft Entra ID bearer token authentication
+//! - Mapping deployment names to underlying models for cost calculation
+//! - Token usage reporting via `stream_options.include_usage`
+//! - Reasoning models (`reasoning_tokens`, `reasoning_content` deltas)
+//! - Tool calling (`tool_calls` deltas streamed as timed tool-call events)
+//! - Surfacing Azure content filter annotations as content filter errors
+
+use crate::error::{error_from_body, ProviderError, Result};
+use crate::headers::ServerSignals;
+use crate::network::NetworkProbe;
+use crate::openai::{chat_tools, message_content, tool_choice_value, ChatTool, Delta};
+use crate::pricing::{PriceTable, PricingMode};
+use crate::retry::{self, RetryPolicy};
+use crate::traits::{
+    CompletionResult, MessageRole, Provider, ResponseMetadata, SharedUsage, StreamingRequest,
+    StreamingResponse, TokenUsage,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::StreamExt;
+use llm_latency_lens_core::{TimingEngine, Timestamp, TokenEvent};
+use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION, CONTENT_TYPE};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Default Azure OpenAI API version (latest GA)
+const DEFAULT_API_VERSION: &str = "2024-10-21";
+
+/// Authentication scheme for Azure OpenAI
+#[derive(Debug, Clone)]
+pub enum AzureAuth {
+    /// Resource key sent in the `api-key` header
+    ApiKey(String),
+    /// Microsoft Entra ID access token sent as `Authorization: Bearer`
+    BearerToken(String),
+}
+
+/// Azure OpenAI provider adapter
+///
+/// Requests name a deployment rather than a model: `StreamingRequest::model`
+/// is used as the deployment name.
+pub struct AzureOpenAIProvider {
+    /// HTTP client
+    client: reqwest::Client,
+    /// Authentication scheme
+    auth: AzureAuth,
+    /// Resource endpoint (e.g. `https://my-resource.openai.azure.com`)
+    endpoint: String,
+    /// API version query parameter
+    api_version: String,
+    /// Deployment name to underlying model name
+    deployments: HashMap<String, String>,
+    /// Retries of the initial request
+    max_retries: u32,
+}
+
+impl AzureOpenAIProvider {
+    /// Create a new Azure OpenAI provider using an `api-key`
+    ///
+    /// # Arguments
+    ///
+    /// * `endpoint` - Azure OpenAI resource endpoint
+    /// * `api_key` - Azure OpenAI resource key
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use llm_latency_lens_providers::azure::AzureOpenAIProvider;
+    ///
+    /// let provider = AzureOpenAIProvider::new("https://my-resource.openai.azure.com", "...");
+    /// ```
+    pub fn new(endpoint: impl Into<String>, api_key: impl Into<String>) -> Self {
+        Self::builder()
+            .endpoint(endpoint)
+            .api_key(api_key)
+            .build()
+    }
+
+    /// Create a provider with custom configuration
+    pub fn builder() -> AzureOpenAIProviderBuilder {
+        AzureOpenAIProviderBuilder::default()
+    }
+
+    /// Build HTTP client with optimized settings
+    fn build_client(timeout: Duration) -> reqwest::Client {
+        crate::network::instrument(reqwest::Client::builder())
+            .timeout(timeout)
+            .tcp_keepalive(Duration::from_secs(60))
+            .pool_idle_timeout(Duration::from_secs(90))
+            .build()
+            .expect("Failed to build HTTP client")
+    }
+
+    /// Build headers for API request
+    fn build_headers(&self) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+
+        match self.auth {
+            AzureAuth::ApiKey(ref key) => {
+                headers.insert(
+                    "api-key",
+                    HeaderValue::from_str(key).expect("Invalid API key format"),
+                );
+            }
+            AzureAuth::BearerToken(ref token) => {
+                headers.insert(
+                    AUTHORIZATION,
+                    HeaderValue::from_str(&format!("Bearer {}", token))
+                        .expect("Invalid bearer token format"),
+                );
+            }
+        }
+
+        headers
+    }
+
+    /// Build the chat completions URL for a deployment
+    fn chat_completions_url(&self, deployment: &str) -> String {
+        format!(
+            "{}/openai/deployments/{}/chat/completions?api-version={}",
+            self.endpoint, deployment, self.api_version
+        )
+    }
+
+    /// Resolve the model served by a deployment
+    ///
+    /// Falls back to the deployment name, since deployments are commonly named
+    /// after the model they serve.
+    pub fn model_for_deployment<'a>(&'a self, deployment: &'a str) -> &'a str {
+        self.deployments
+            .get(deployment)
+            .map(|m| m.as_str())
+            .unwrap_or(deployment)
+    }
+}
+
+/// Builder for Azure OpenAI provider
+#[derive(Default)]
+pub struct AzureOpenAIProviderBuilder {
+    endpoint: Option<String>,
+    auth: Option<AzureAuth>,
+    api_version: Option<String>,
+    deployments: HashMap<String, String>,
+    max_retries: Option<u32>,
+    timeout: Option<Duration>,
+}
+
+impl AzureOpenAIProviderBuilder {
+    /// Set the resource endpoint
+    pub fn endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        self.endpoint = Some(endpoint.into());
+        self
+    }
+
+    /// Authenticate with a resource key (`api-key` header)
+    pub fn api_key(mut self, key: impl Into<String>) -> Self {
+        self.auth = Some(AzureAuth::ApiKey(key.into()));
+        self
+    }
+
+    /// Authenticate with a Microsoft Entra ID bearer token
+    pub fn bearer_token(mut self, token: impl Into<String>) -> Self {
+        self.auth = Some(AzureAuth::BearerToken(token.into()));
+        self
+    }
+
+    /// Set the API version
+    pub fn api_version(mut self, version: impl Into<String>) -> Self {
+        self.api_version = Some(version.into());
+        self
+    }
+
+    /// Map a deployment name to the model it serves
+    pub fn deployment(mut self, deployment: impl Into<String>, model: impl Into<String>) -> Self {
+        self.deployments.insert(deployment.into(), model.into());
+        self
+    }
+
+    /// Set how many times a failed initial request is retried (3 by default)
+    pub fn max_retries(mut self, retries: u32) -> Self {
+        self.max_retries = Some(retries);
+        self
+    }
+
+    /// Set the HTTP request timeout (120 seconds by default)
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Build the provider
+    pub fn build(self) -> AzureOpenAIProvider {
+        AzureOpenAIProvider {
+            client: AzureOpenAIProvider::build_client(
+                self.timeout.unwrap_or(crate::network::DEFAULT_TIMEOUT),
+            ),
+            auth: self.auth.expect("API key or bearer token is required"),
+            endpoint: self
+                .endpoint
+                .expect("Endpoint is required")
+                .trim_end_matches('/')
+                .to_string(),
+            api_version: self
+                .api_version
+                .unwrap_or_else(|| DEFAULT_API_VERSION.to_string()),
+            deployments: self.deployments,
+            max_retries: self.max_retries.unwrap_or(3),
+        }
+    }
+}
+
+#[async_trait]
+impl Provider for AzureOpenAIProvider {
+    fn name(&self) -> &'static str {
+        "azure-openai"
+    }
+
+    fn provider_kind(&self) -> llm_latency_lens_core::Provider {
+        llm_latency_lens_core::Provider::AzureOpenAI
+    }
+
+    async fn health_check(&self) -> Result<()> {
+        let url = format!("{}/openai/models?api-version={}", self.endpoint, self.api_version);
+        let response = self
+            .client
+            .get(&url)
+            .headers(self.build_headers())
+            .send()
+            .await
+            .map_err(ProviderError::from_reqwest)?;
+
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(parse_azure_error(response).await)
+        }
+    }
+
+    async fn stream(
+        &self,
+        request: StreamingRequest,
+        timing_engine: &TimingEngine,
+    ) -> Result<StreamingResponse> {
+        // Validate deployment
+        self.validate_model(&request.model)?;
+
+        // Start timing measurement
+        let mut timing = timing_engine.start();
+        let network = NetworkProbe::new(timing_engine.clock());
+        timing.checkpoint("request_start");
+
+        // Build request payload (the deployment determines the model)
+        let payload = chat_request(&request, true);
+
+        timing.checkpoint("payload_built");
+
+        let url = self.chat_completions_url(&request.model);
+        let headers = self.build_headers();
+
+        timing.checkpoint("headers_built");
+
+        // Build the SSE request
+        let request_id = request.request_id;
+        let req_builder = self
+            .client
+            .post(&url)
+            .headers(headers)
+            .json(&payload);
+
+        timing.checkpoint("http_request_built");
+
+        // HTTP errors, including prompt filtering, surface from stream() itself
+        let response = retry::send(
+            RetryPolicy::new(self.max_retries),
+            &network,
+            req_builder,
+            parse_azure_error,
+        )
+        .await?;
+
+        timing.checkpoint("response_received");
+
+        let response_headers =
+            crate::headers::capture(response.headers(), &request.redact_headers);
+        let server = ServerSignals::from_headers(response.headers());
+        let event_source = crate::sse::events(
+            response.bytes_stream(),
+            timing_engine.clock().clone(),
+            network.clone(),
+        );
+
+        timing.checkpoint("event_source_created");
+
+        // Create token stream
+        let request_start = timing.start_time();
+        let usage = SharedUsage::new();
+        let stream_usage = usage.clone();
+        let mut sequence = 0u64;
+        let mut last_token_time: Option<Timestamp> = None;
+
+        let token_stream = event_source
+            .map(move |event_result| {
+                match event_result {
+                    Ok(message) => {
+                        if message.data == "[DONE]" {
+                            tracing::debug!("SSE stream completed");
+                            return None;
+                        }
+
+                        // Parse SSE chunk
+                        let chunk: AzureChatCompletionChunk =
+                            match serde_json::from_str(&message.data) {
+                                Ok(c) => c,
+                                Err(e) => {
+                                    tracing::error!("Failed to parse SSE chunk: {}", e);
+                                    return Some(Err(ProviderError::sse_parse(format!(
+                                        "Invalid JSON in SSE event: {}",
+                                        e
+                                    ))));
+                                }
+                            };
+
+                        if let Some(err) = chunk.filtered() {
+                            return Some(Err(err));
+                        }
+
+                        // The final chunk carries usage and no choices
+                        if let Some(ref reported) = chunk.usage {
+                            stream_usage.update(|u| *u = reported.token_usage());
+                        }
+
+                        // Extract visible, reasoning or tool-call text; the first
+                        // chunk only carries prompt filter results and has no choices
+                        let (kind, content) = chunk
+                            .choices
+                            .first()
+                            .and_then(|c| c.delta.as_ref())
+                            .and_then(Delta::text)?;
+
+                        // Record timing at the arrival of the read carrying the event
+                        let now = message.received_at;
+                        let time_since_start = now.duration_since(request_start);
+                        let inter_token_latency = last_token_time.map(|t| now.duration_since(t));
+                        last_token_time = Some(now);
+
+                        let event = TokenEvent {
+                            request_id,
+                            sequence,
+                            kind,
+                            content: Some(content),
+                            timestamp_nanos: now.as_nanos(),
+                            time_since_start,
+                            inter_token_latency,
+                        };
+
+                        sequence += 1;
+
+                        Some(Ok(event))
+                    }
+                    Err(e) => Some(Err(e)),
+                }
+            })
+            .filter_map(|x| async move { x })
+            .boxed();
+
+        timing.checkpoint("stream_initialized");
+
+        Ok(StreamingResponse {
+            request_id: request.request_id,
+            token_stream: Box::pin(token_stream),
+            metadata: ResponseMetadata {
+                model: self.model_for_deployment(&request.model).to_string(),
+                input_tokens: None, // Reported in the final chunk
+                output_tokens: None,
+                thinking_tokens: None,
+                cache_creation_tokens: None,
+                cache_read_tokens: None,
+                estimated_cost: None,
+                headers: response_headers,
+                server,
+            },
+            usage,
+            network,
+        })
+    }
+
+    async fn complete_non_streaming(
+        &self,
+        request: StreamingRequest,
+        timing_engine: &TimingEngine,
+    ) -> Result<CompletionResult> {
+        self.validate_model(&request.model)?;
+
+        let timing = timing_engine.start();
+        let network = NetworkProbe::new(timing_engine.clock());
+
+        let payload = chat_request(&request, false);
+        let url = self.chat_completions_url(&request.model);
+        let req_builder = self
+            .client
+            .post(&url)
+            .headers(self.build_headers())
+            .json(&payload);
+
+        let response = retry::send(
+            RetryPolicy::new(self.max_retries),
+            &network,
+            req_builder,
+            parse_azure_error,
+        )
+        .await?;
+
+        let response_headers =
+            crate::headers::capture(response.headers(), &request.redact_headers);
+        let server = ServerSignals::from_headers(response.headers());
+        let (completion, body): (AzureChatCompletionChunk, _) =
+            crate::body::read_json(response, timing_engine.clock(), timing.start_time()).await?;
+
+        if let Some(err) = completion.filtered() {
+            return Err(err);
+        }
+
+        let message = completion.choices.first().and_then(|c| c.message.as_ref());
+        let content = message.and_then(|m| m.content.clone()).unwrap_or_default();
+        let thinking = message
+            .and_then(|m| m.reasoning_content.clone())
+            .unwrap_or_default();
+
+        let mut metadata = ResponseMetadata {
+            model: self.model_for_deployment(&request.model).to_string(),
+            input_tokens: None,
+            output_tokens: None,
+            thinking_tokens: None,
+            cache_creation_tokens: None,
+            cache_read_tokens: None,
+            estimated_cost: None,
+            headers: response_headers,
+            server,
+        };
+        if let Some(ref usage) = completion.usage {
+            metadata.apply_usage(usage.token_usage());
+        }
+        metadata.estimate_cost(|usage| self.usage_cost(&request.model, usage));
+
+        Ok(CompletionResult::from_body(
+            request.request_id,
+            content,
+            thinking,
+            metadata,
+            &network,
+            body,
+        ))
+    }
+
+    fn price_usage(
+        &self,
+        table: &PriceTable,
+        model: &str,
+        usage: &TokenUsage,
+        at: DateTime<Utc>,
+        mode: PricingMode,
+    ) -> Option<f64> {
+        // Azure list prices match OpenAI's for global deployments
+        table.cost(self.name(), self.model_for_deployment(model), usage, at, mode)
+    }
+
+    fn supported_models(&self) -> Vec<String> {
+        // Deployments are user-defined; with none configured, any name is accepted
+        let mut deployments: Vec<String> = self.deployments.keys().cloned().collect();
+        deployments.sort();
+        deployments
+    }
+}
+
+/// Build the chat completions payload, streamed or not
+fn chat_request(request: &StreamingRequest, stream: bool) -> ChatCompletionRequest {
+    ChatCompletionRequest {
+        messages: request
+            .messages
+            .iter()
+            .map(|m| ChatMessage {
+                role: match m.role {
+                    MessageRole::System => "system".to_string(),
+                    MessageRole::User => "user".to_string(),
+                    MessageRole::Assistant => "assistant".to_string(),
+                },
+                content: message_content(m),
+            })
+            .collect(),
+        stream,
+        stream_options: stream.then_some(StreamOptions {
+            include_usage: true,
+        }),
+        max_tokens: request.max_tokens,
+        temperature: request.temperature,
+        top_p: request.top_p,
+        stop: request.stop.clone(),
+        tools: chat_tools(&request.tools),
+        tool_choice: request.tool_choice.as_ref().map(tool_choice_value),
+    }
+}
+
+/// Parse an Azure OpenAI error response
+///
+/// Prompts rejected by the content filter come back as HTTP 400 with
+/// `error.code = "content_filter"` and the per-category results under
+/// `error.innererror.content_filter_result`.
+async fn parse_azure_error(response: reqwest::Response) -> ProviderError {
+    let status_code = response.status().as_u16();
+
+    let body = match response.text().await {
+        Ok(text) => text,
+        Err(e) => {
+            return ProviderError::api_error(
+                status_code,
+                format!("Failed to read error response: {}", e),
+            );
+        }
+    };
+
+    map_azure_error(status_code, body)
+}
+
+/// Map an Azure error body to a provider error
+fn map_azure_error(status_code: u16, body: String) -> ProviderError {
+    if let Ok(response) = serde_json::from_str::<AzureErrorResponse>(&body) {
+        let error = response.error;
+        match error.code.as_deref() {
+            Some("content_filter") => {
+                let categories = error
+                    .innererror
+                    .and_then(|e| e.content_filter_result)
+                    .map(|r| filtered_categories(&r))
+                    .unwrap_or_default();
+                return ProviderError::ContentFilterError(if categories.is_empty() {
+                    error.message
+                } else {
+                    format!("{} ({})", error.message, categories)
+                });
+            }
+            Some("context_length_exceeded") => {
+                return ProviderError::ContextLengthExceeded(error.message);
+            }
+            Some("DeploymentNotFound") => return ProviderError::InvalidModel(error.message),
+            _ => {}
+        }
+    }
+
+    error_from_body(status_code, body)
+}
+
+/// Describe the categories a content filter result flagged, e.g. `hate: medium`
+fn filtered_categories(results: &HashMap<String, FilterResult>) -> String {
+    let mut categories: Vec<String> = results
+        .iter()
+        .filter(|(_, r)| r.filtered)
+        .map(|(category, r)| match r.severity {
+            Some(ref severity) => format!("{}: {}", category, severity),
+            None => category.clone(),
+        })
+        .collect();
+    categories.sort();
+    categories.join(", ")
+}
+
+// Azure OpenAI API request/response types
+
+#[derive(Debug, Serialize)]
+struct ChatCompletionRequest {
+    messages: Vec<ChatMessage>,
+    stream: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stream_options: Option<StreamOptions>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stop: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tools: Vec<ChatTool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_choice: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Serialize)]
+struct StreamOptions {
+    include_usage: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct ChatMessage {
+    role: String,
+    content: serde_json::Value,
+}
+
+/// Streaming chunk or non-streaming completion; Azure omits or blanks most
+/// fields on filter-only chunks
+#[derive(Debug, Deserialize)]
+struct AzureChatCompletionChunk {
+    #[serde(default)]
+    choices: Vec<AzureStreamChoice>,
+    #[serde(default)]
+    prompt_filter_results: Vec<PromptFilterResult>,
+    /// Reported on non-streaming completions and the final streamed chunk
+    #[serde(default)]
+    usage: Option<AzureUsage>,
+}
+
+impl AzureChatCompletionChunk {
+    /// Error for a prompt or completion blocked by the content filter
+    fn filtered(&self) -> Option<ProviderError> {
+        for prompt in &self.prompt_filter_results {
+            let categories = filtered_categories(&prompt.content_filter_results);
+            if !categories.is_empty() {
+                return Some(ProviderError::ContentFilterError(format!(
+                    "Prompt filtered ({})",
+                    categories
+                )));
+            }
+        }
+
+        let choice = self.choices.first()?;
+        let categories = filtered_categories(&choice.content_filter_results);
+        if choice.finish_reason.as_deref() == Some("content_filter") || !categories.is_empty() {
+            return Some(ProviderError::ContentFilterError(if categories.is_empty() {
+                "Completion filtered".to_string()
+            } else {
+                format!("Completion filtered ({})", categories)
+            }));
+        }
+
+        None
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct AzureStreamChoice {
+    #[serde(default)]
+    delta: Option<Delta>,
+    /// Complete message of a non-streaming completion
+    #[serde(default)]
+    message: Option<CompletionMessage>,
+    #[serde(default)]
+    finish_reason: Option<String>,
+    #[serde(default)]
+    content_filter_results: HashMap<String, FilterResult>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CompletionMessage {
+    #[serde(default)]
+    content: Option<String>,
+    /// Reasoning text from DeepSeek-style models
+    #[serde(default)]
+    reasoning_content: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AzureUsage {
+    prompt_tokens: u64,
+    completion_tokens: u64,
+    #[serde(default)]
+    completion_tokens_details: Option<CompletionTokensDetails>,
+    #[serde(default)]
+    prompt_tokens_details: Option<PromptTokensDetails>,
+}
+
+impl AzureUsage {
+    /// Usage counts, with reasoning tokens split out of the completion tokens
+    fn token_usage(&self) -> TokenUsage {
+        let reasoning_tokens = self.reasoning_tokens();
+        TokenUsage {
+            input_tokens: Some(self.prompt_tokens),
+            // completion_tokens includes the reasoning tokens
+            output_tokens: Some(
+                self.completion_tokens.saturating_sub(reasoning_tokens.unwrap_or(0)),
+            ),
+            thinking_tokens: reasoning_tokens,
+            cache_creation_tokens: None,
+            // Caching is automatic; cached_tokens is part of prompt_tokens
+            cache_read_tokens: self.cached_tokens(),
+        }
+    }
+
+    /// Reasoning tokens reported for o-series deployments
+    fn reasoning_tokens(&self) -> Option<u64> {
+        self.completion_tokens_details
+            .as_ref()
+            .and_then(|d| d.reasoning_tokens)
+    }
+
+    /// Prompt tokens served from the prompt cache
+    fn cached_tokens(&self) -> Option<u64> {
+        self.prompt_tokens_details
+            .as_ref()
+            .and_then(|d| d.cached_tokens)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct PromptTokensDetails {
+    #[serde(default)]
+    cached_tokens: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CompletionTokensDetails {
+    #[serde(default)]
+    reasoning_tokens: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PromptFilterResult {
+    #[serde(default)]
+    content_filter_results: HashMap<String, FilterResult>,
+}
+
+/// Result for one filter category (`hate`, `sexual`, `violence`, `self_harm`,
+/// `jailbreak`, ...)
+#[derive(Debug, Deserialize)]
+struct FilterResult {
+    #[serde(default)]
+    filtered: bool,
+    #[serde(default)]
+    severity: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AzureErrorResponse {
+    error: AzureError,
+}
+
+#[derive(Debug, Deserialize)]
+struct AzureError {
+    #[serde(default)]
+    code: Option<String>,
+    #[serde(default)]
+    message: String,
+    #[serde(default)]
+    innererror: Option<AzureInnerError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AzureInnerError {
+    #[serde(default)]
+    content_filter_result: Option<HashMap<String, FilterResult>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use llm_latency_lens_core::TokenKind;
+    use wiremock::matchers::{body_partial_json, header, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn sse_body(chunks: &[serde_json::Value]) -> String {
+        let mut body: String = chunks
+            .iter()
+            .map(|c| format!("data: {}\n\n", c))
+            .collect();
+        body.push_str("data: [DONE]\n\n");
+        body
+    }
+
+    fn safe_results() -> serde_json::Value {
+        serde_json::json!({
+            "hate": { "filtered": false, "severity": "safe" },
+            "violence": { "filtered": false, "severity": "safe" }
+        })
+    }
+
+    fn test_request() -> StreamingRequest {
+        StreamingRequest::builder()
+            .model("chat-prod")
+            .message(MessageRole::User, "Hello")
+            .build()
+    }
+
+    fn mock_provider(server: &MockServer) -> AzureOpenAIProvider {
+        AzureOpenAIProvider::builder()
+            .endpoint(server.uri())
+            .api_key("azure-key")
+            .deployment("chat-prod", "gpt-4o-mini")
+            .build()
+    }
+
+    #[test]
+    fn test_provider_name() {
+        let provider = AzureOpenAIProvider::new("https://res.openai.azure.com", "key");
+        assert_eq!(provider.name(), "azure-openai");
+    }
+
+    #[test]
+    fn test_builder() {
+        let provider = AzureOpenAIProvider::builder()
+            .endpoint("https://res.openai.azure.com/")
+            .bearer_token("entra-token")
+            .api_version("2024-06-01")
+            .deployment("chat-prod", "gpt-4o")
+            .max_retries(5)
+            .build();
+
+        assert_eq!(provider.endpoint, "https://res.openai.azure.com");
+        assert_eq!(provider.api_version, "2024-06-01");
+        assert_eq!(provider.max_retries, 5);
+        assert_eq!(
+            provider.chat_completions_url("chat-prod"),
+            "https://res.openai.azure.com/openai/deployments/chat-prod/chat/completions?api-version=2024-06-01"
+        );
+        assert_eq!(
+            provider.build_headers().get(AUTHORIZATION).unwrap(),
+            "Bearer entra-token"
+        );
+    }
+
+    #[test]
+    fn test_build_headers() {
+        let provider = AzureOpenAIProvider::new("https://res.openai.azure.com", "azure-key");
+        let headers = provider.build_headers();
+
+        assert_eq!(headers.get("api-key").unwrap(), "azure-key");
+        assert!(headers.get(AUTHORIZATION).is_none());
+    }
+
+    #[test]
+    fn test_calculate_cost_uses_deployment_model() {
+        let provider = AzureOpenAIProvider::builder()
+            .endpoint("https://res.openai.azure.com")
+            .api_key("key")
+            .deployment("chat-prod", "gpt-4o")
+            .build();
+
+        // chat-prod serves gpt-4o: $2.50/1M input, $10.00/1M output
+        let cost = provider.calculate_cost("chat-prod", 1000, 1000).unwrap();
+        assert!((cost - 0.0125).abs() < 0.0001);
+
+        // Unmapped deployments named after a model fall back to that model
+        assert!(provider.calculate_cost("gpt-4o-mini", 1000, 1000).is_some());
+        assert!(provider.calculate_cost("custom-deployment", 1000, 1000).is_none());
+    }
+
+    #[test]
+    fn test_validate_model() {
+        let provider = AzureOpenAIProvider::new("https://res.openai.azure.com", "key");
+        assert!(provider.validate_model("any-deployment").is_ok());
+
+        let provider = AzureOpenAIProvider::builder()
+            .endpoint("https://res.openai.azure.com")
+            .api_key("key")
+            .deployment("chat-prod", "gpt-4o")
+            .build();
+        assert!(provider.validate_model("chat-prod").is_ok());
+        assert!(provider.validate_model("other").is_err());
+    }
+
+    #[test]
+    fn test_map_azure_error() {
+        let body = serde_json::json!({
+            "error": {
+                "code": "content_filter",
+                "message": "The response was filtered due to the prompt triggering Azure OpenAI's content management policy.",
+                "innererror": {
+                    "code": "ResponsibleAIPolicyViolation",
+                    "content_filter_result": {
+                        "hate": { "filtered": true, "severity": "high" },
+                        "sexual": { "filtered": false, "severity": "safe" }
+                    }
+                }
+            }
+        })
+        .to_string();
+
+        match map_azure_error(400, body) {
+            ProviderError::ContentFilterError(message) => assert!(message.contains("hate: high")),
+            e => panic!("unexpected error: {:?}", e),
+        }
+
+        let body = r#"{"error": {"code": "DeploymentNotFound", "message": "The API deployment for this resource does not exist."}}"#;
+        assert!(matches!(
+            map_azure_error(404, body.to_string()),
+            ProviderError::InvalidModel(_)
+        ));
+
+        let body = r#"{"error": {"code": "429", "message": "Requests to the ChatCompletions_Create Operation have exceeded call rate limit."}}"#;
+        assert!(matches!(
+            map_azure_error(429, body.to_string()),
+            ProviderError::RateLimitError { .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_stream() {
+        let server = MockServer::start().await;
+        let body = sse_body(&[
+            serde_json::json!({
+                "id": "", "object": "", "created": 0, "model": "", "choices": [],
+                "prompt_filter_results": [{ "prompt_index": 0, "content_filter_results": safe_results() }]
+            }),
+            serde_json::json!({
+                "id": "chatcmpl-1", "object": "chat.completion.chunk", "created": 1, "model": "gpt-4o-mini",
+                "choices": [{ "index": 0, "delta": { "role": "assistant", "content": "" }, "content_filter_results": {} }]
+            }),
+            serde_json::json!({
+                "id": "chatcmpl-1", "object": "chat.completion.chunk", "created": 1, "model": "gpt-4o-mini",
+                "choices": [{ "index": 0, "delta": { "content": "Hi" }, "content_filter_results": safe_results() }]
+            }),
+            serde_json::json!({
+                "id": "chatcmpl-1", "object": "chat.completion.chunk", "created": 1, "model": "gpt-4o-mini",
+                "choices": [{ "index": 0, "delta": { "content": " there" }, "content_filter_results": safe_results() }]
+            }),
+            serde_json::json!({
+                "id": "chatcmpl-1", "object": "chat.completion.chunk", "created": 1, "model": "gpt-4o-mini",
+                "choices": [{ "index": 0, "delta": {}, "finish_reason": "stop", "content_filter_results": {} }]
+            }),
+            serde_json::json!({
+                "id": "chatcmpl-1", "object": "chat.completion.chunk", "created": 1, "model": "gpt-4o-mini",
+                "choices": [],
+                "usage": {
+                    "prompt_tokens": 1000, "completion_tokens": 1000, "total_tokens": 2000,
+                    "prompt_tokens_details": { "cached_tokens": 768 }
+                }
+            }),
+        ]);
+
+        Mock::given(method("POST"))
+            .and(path("/openai/deployments/chat-prod/chat/completions"))
+            .and(query_param("api-version", DEFAULT_API_VERSION))
+            .and(header("api-key", "azure-key"))
+            .and(body_partial_json(serde_json::json!({
+                "stream": true,
+                "stream_options": { "include_usage": true }
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(body, "text/event-stream"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        let result = provider
+            .complete(test_request(), &TimingEngine::new())
+            .await
+            .unwrap();
+
+        assert_eq!(result.content, "Hi there");
+        assert_eq!(result.token_events.len(), 2);
+        assert_eq!(result.metadata.model, "gpt-4o-mini");
+        assert_eq!(result.metadata.input_tokens, Some(1000));
+        assert_eq!(result.metadata.output_tokens, Some(1000));
+        assert_eq!(result.metadata.cache_read_tokens, Some(768));
+        assert_eq!(result.metadata.cache_hit(), Some(true));
+        // Priced as gpt-4o-mini: $0.15/1M input, $0.075/1M cached input, $0.60/1M output
+        let cost = result.metadata.estimated_cost.unwrap();
+        assert!((cost - 0.0006924).abs() < 0.000001);
+    }
+
+    #[tokio::test]
+    async fn test_stream_reasoning_content_deltas() {
+        let server = MockServer::start().await;
+        let body = sse_body(&[
+            serde_json::json!({
+                "choices": [{ "index": 0, "delta": { "role": "assistant", "content": null, "reasoning_content": "Let me" } }]
+            }),
+            serde_json::json!({
+                "choices": [{ "index": 0, "delta": { "content": null, "reasoning_content": " think" } }]
+            }),
+            serde_json::json!({
+                "choices": [{ "index": 0, "delta": { "content": "Done" }, "finish_reason": "stop", "content_filter_results": safe_results() }]
+            }),
+            serde_json::json!({
+                "choices": [],
+                "usage": {
+                    "prompt_tokens": 10, "completion_tokens": 30, "total_tokens": 40,
+                    "completion_tokens_details": { "reasoning_tokens": 20 }
+                }
+            }),
+        ]);
+
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(body, "text/event-stream"))
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        let result = provider
+            .complete(test_request(), &TimingEngine::new())
+            .await
+            .unwrap();
+
+        assert_eq!(result.thinking, "Let me think");
+        assert_eq!(result.content, "Done");
+        assert_eq!(result.token_events[0].kind, TokenKind::Thinking);
+        assert_eq!(result.token_events[2].kind, TokenKind::Content);
+        assert_eq!(result.reasoning_time(), result.time_to_first_visible_token());
+        assert_eq!(result.metadata.thinking_tokens, Some(20));
+        assert_eq!(result.metadata.output_tokens, Some(10));
+    }
+
+    #[tokio::test]
+    async fn test_stream_tool_calls() {
+        let server = MockServer::start().await;
+        let body = sse_body(&[
+            serde_json::json!({
+                "choices": [{ "index": 0, "delta": { "role": "assistant", "content": null, "tool_calls": [
+                    { "index": 0, "id": "call_1", "type": "function", "function": { "name": "get_weather", "arguments": "" } }
+                ] } }]
+            }),
+            serde_json::json!({
+                "choices": [{ "index": 0, "delta": { "tool_calls": [
+                    { "index": 0, "function": { "arguments": "{\"city\":\"Paris\"}" } }
+                ] }, "content_filter_results": safe_results() }]
+            }),
+            serde_json::json!({
+                "choices": [{ "index": 0, "delta": {}, "finish_reason": "tool_calls", "content_filter_results": {} }]
+            }),
+        ]);
+
+        Mock::given(method("POST"))
+            .and(body_partial_json(serde_json::json!({
+                "tools": [{ "type": "function", "function": { "name": "get_weather" } }],
+                "tool_choice": "required"
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(body, "text/event-stream"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        let request = StreamingRequest::builder()
+            .model("chat-prod")
+            .message(MessageRole::User, "Weather in Paris?")
+            .tool(crate::traits::Tool::new(
+                "get_weather",
+                serde_json::json!({"type": "object", "properties": {"city": {"type": "string"}}}),
+            ))
+            .tool_choice(crate::traits::ToolChoice::Required)
+            .build();
+
+        let result = provider.complete(request, &TimingEngine::new()).await.unwrap();
+
+        assert_eq!(result.content, "");
+        assert_eq!(result.token_events.len(), 2);
+        assert!(result.token_events.iter().all(|e| e.kind == TokenKind::ToolCall));
+        assert_eq!(result.time_to_first_tool_call(), result.ttft());
+        assert!(result.tool_call_duration().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_complete_non_streaming() {
+        let server = MockServer::start().await;
+        let body = serde_json::json!({
+            "id": "chatcmpl-2", "object": "chat.completion", "created": 1, "model": "gpt-4o-mini",
+            "prompt_filter_results": [{ "prompt_index": 0, "content_filter_results": safe_results() }],
+            "choices": [{
+                "index": 0,
+                "message": { "role": "assistant", "content": "Hi there", "reasoning_content": "Greet back" },
+                "finish_reason": "stop",
+                "content_filter_results": safe_results()
+            }],
+            "usage": {
+                "prompt_tokens": 1000, "completion_tokens": 1000, "total_tokens": 2000,
+                "completion_tokens_details": { "reasoning_tokens": 200 }
+            }
+        });
+
+        Mock::given(method("POST"))
+            .and(path("/openai/deployments/chat-prod/chat/completions"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(body))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        let result = provider
+            .complete_non_streaming(test_request(), &TimingEngine::new())
+            .await
+            .unwrap();
+
+        assert_eq!(result.content, "Hi there");
+        assert_eq!(result.thinking, "Greet back");
+        assert!(result.token_events.is_empty());
+        assert_eq!(result.metadata.input_tokens, Some(1000));
+        assert_eq!(result.metadata.output_tokens, Some(800));
+        assert_eq!(result.metadata.thinking_tokens, Some(200));
+        // Priced as gpt-4o-mini: $0.15/1M input, $0.60/1M output (reasoning
+        // billed as output)
+        let cost = result.metadata.estimated_cost.unwrap();
+        assert!((cost - 0.00075).abs() < 0.000001);
+    }
+
+    #[tokio::test]
+    async fn test_stream_completion_filtered() {
+        let server = MockServer::start().await;
+        let body = sse_body(&[
+            serde_json::json!({
+                "choices": [{ "index": 0, "delta": { "content": "Some" }, "content_filter_results": safe_results() }]
+            }),
+            serde_json::json!({
+                "choices": [{
+                    "index": 0, "delta": {}, "finish_reason": "content_filter",
+                    "content_filter_results": { "violence": { "filtered": true, "severity": "medium" } }
+                }]
+            }),
+        ]);
+
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(body, "text/event-stream"))
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        let err = provider
+            .complete(test_request(), &TimingEngine::new())
+            .await
+            .unwrap_err();
+
+        match err {
+            ProviderError::ContentFilterError(message) => {
+                assert!(message.contains("violence: medium"))
+            }
+            e => panic!("unexpected error: {:?}", e),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_stream_prompt_filtered() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(400).set_body_json(serde_json::json!({
+                "error": {
+                    "code": "content_filter",
+                    "message": "The prompt was filtered.",
+                    "innererror": {
+                        "code": "ResponsibleAIPolicyViolation",
+                        "content_filter_result": { "jailbreak": { "filtered": true, "detected": true } }
+                    }
+                }
+            })))
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        let result = provider.stream(test_request(), &TimingEngine::new()).await;
+
+        assert!(matches!(result, Err(ProviderError::ContentFilterError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_health_check() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/openai/models"))
+            .and(header("api-key", "azure-key"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(r#"{"data": []}"#))
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        assert!(provider.health_check().await.is_ok());
+    }
+}
@@ -0,0 +1,192 @@
+//! AWS credential loading
+//!
+//! Credentials are resolved the same way the AWS CLI does for the common cases:
+//! environment variables first, then the shared credentials file
+//! (`~/.aws/credentials`, or `AWS_SHARED_CREDENTIALS_FILE`) using the profile
+//! named by `AWS_PROFILE` (default: `default`).
+
+use crate::error::{ProviderError, Result};
+use std::path::PathBuf;
+
+/// AWS access credentials
+#[derive(Clone, PartialEq, Eq)]
+pub struct AwsCredentials {
+    /// Access key ID
+    pub access_key_id: String,
+    /// Secret access key
+    pub secret_access_key: String,
+    /// Session token for temporary credentials
+    pub session_token: Option<String>,
+}
+
+impl std::fmt::Debug for AwsCredentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AwsCredentials")
+            .field("access_key_id", &self.access_key_id)
+            .field("secret_access_key", &"** redacted **")
+            .field("session_token", &self.session_token.as_ref().map(|_| "** redacted **"))
+            .finish()
+    }
+}
+
+impl AwsCredentials {
+    /// Create credentials from an access key pair
+    pub fn new(access_key_id: impl Into<String>, secret_access_key: impl Into<String>) -> Self {
+        Self {
+            access_key_id: access_key_id.into(),
+            secret_access_key: secret_access_key.into(),
+            session_token: None,
+        }
+    }
+
+    /// Attach a session token
+    pub fn with_session_token(mut self, token: impl Into<String>) -> Self {
+        self.session_token = Some(token.into());
+        self
+    }
+
+    /// Load credentials from `AWS_ACCESS_KEY_ID`, `AWS_SECRET_ACCESS_KEY` and
+    /// `AWS_SESSION_TOKEN`
+    pub fn from_env() -> Option<Self> {
+        let access_key_id = non_empty_env("AWS_ACCESS_KEY_ID")?;
+        let secret_access_key = non_empty_env("AWS_SECRET_ACCESS_KEY")?;
+
+        Some(Self {
+            access_key_id,
+            secret_access_key,
+            session_token: non_empty_env("AWS_SESSION_TOKEN"),
+        })
+    }
+
+    /// Load credentials for a profile from the shared credentials file
+    pub fn from_profile(profile: &str) -> Result<Option<Self>> {
+        let path = match credentials_file() {
+            Some(path) => path,
+            None => return Ok(None),
+        };
+
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let contents = std::fs::read_to_string(&path).map_err(|e| {
+            ProviderError::ConfigError(format!(
+                "Failed to read AWS credentials file {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+
+        Ok(parse_profile(&contents, profile))
+    }
+
+    /// Resolve credentials from the environment, then the shared credentials file
+    pub fn load() -> Result<Self> {
+        if let Some(credentials) = Self::from_env() {
+            return Ok(credentials);
+        }
+
+        let profile = non_empty_env("AWS_PROFILE").unwrap_or_else(|| "default".to_string());
+        Self::from_profile(&profile)?.ok_or_else(|| {
+            ProviderError::ConfigError(format!(
+                "No AWS credentials found: set AWS_ACCESS_KEY_ID/AWS_SECRET_ACCESS_KEY \
+                 or configure profile '{}' in the shared credentials file",
+                profile
+            ))
+        })
+    }
+}
+
+/// Read an environment variable, treating empty values as unset
+pub(crate) fn non_empty_env(name: &str) -> Option<String> {
+    std::env::var(name).ok().filter(|v| !v.is_empty())
+}
+
+/// Location of the shared credentials file
+fn credentials_file() -> Option<PathBuf> {
+    non_empty_env("AWS_SHARED_CREDENTIALS_FILE")
+        .map(PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|home| home.join(".aws").join("credentials")))
+}
+
+/// Extract a profile's credentials from INI-formatted file contents
+fn parse_profile(contents: &str, profile: &str) -> Option<AwsCredentials> {
+    let mut in_profile = false;
+    let mut access_key_id = None;
+    let mut secret_access_key = None;
+    let mut session_token = None;
+
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+
+        if let Some(section) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            in_profile = section.trim() == profile;
+            continue;
+        }
+
+        if !in_profile {
+            continue;
+        }
+
+        if let Some((key, value)) = line.split_once('=') {
+            let value = value.trim().to_string();
+            match key.trim() {
+                "aws_access_key_id" => access_key_id = Some(value),
+                "aws_secret_access_key" => secret_access_key = Some(value),
+                "aws_session_token" => session_token = Some(value),
+                _ => {}
+            }
+        }
+    }
+
+    Some(AwsCredentials {
+        access_key_id: access_key_id?,
+        secret_access_key: secret_access_key?,
+        session_token,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CREDENTIALS: &str = r#"
+[default]
+aws_access_key_id = AKIDDEFAULT
+aws_secret_access_key = default-secret
+
+# Temporary credentials
+[bench]
+aws_access_key_id=AKIDBENCH
+aws_secret_access_key=bench-secret
+aws_session_token = bench-token
+region = us-west-2
+"#;
+
+    #[test]
+    fn test_parse_profile() {
+        let default = parse_profile(CREDENTIALS, "default").unwrap();
+        assert_eq!(default, AwsCredentials::new("AKIDDEFAULT", "default-secret"));
+
+        let bench = parse_profile(CREDENTIALS, "bench").unwrap();
+        assert_eq!(
+            bench,
+            AwsCredentials::new("AKIDBENCH", "bench-secret").with_session_token("bench-token")
+        );
+
+        assert!(parse_profile(CREDENTIALS, "missing").is_none());
+    }
+
+    #[test]
+    fn test_debug_redacts_secrets() {
+        let credentials = AwsCredentials::new("AKID", "super-secret").with_session_token("token");
+        let debug = format!("{:?}", credentials);
+
+        assert!(debug.contains("AKID"));
+        assert!(!debug.contains("super-secret"));
+        assert!(!debug.contains("\"token\""));
+    }
+}
@@ -0,0 +1,364 @@
+//! AWS event stream codec
+//!
+//! Bedrock streaming APIs return `application/vnd.amazon.eventstream` bodies,
+//! a binary framing where each message is laid out as:
+//!
+//! ```text
+//! [total length: u32][headers length: u32][prelude crc: u32][headers][payload][message crc: u32]
+//! ```
+//!
+//! Both CRCs are CRC32 (IEEE). The decoder is incremental: network reads are
+//! pushed as they arrive and complete messages are pulled out one at a time,
+//! so frames split across reads (or several frames in one read) are handled.
+
+use crate::error::{ProviderError, Result};
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+
+/// Size of the prelude (total length, headers length, prelude CRC)
+const PRELUDE_LEN: usize = 12;
+
+/// Size of the trailing message CRC
+const CRC_LEN: usize = 4;
+
+/// Largest message the service will send (16 MiB)
+const MAX_MESSAGE_LEN: usize = 16 * 1024 * 1024;
+
+/// A typed event stream header value
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HeaderValue {
+    /// Boolean (types 0 and 1)
+    Bool(bool),
+    /// Signed byte
+    Byte(i8),
+    /// 16-bit integer
+    Int16(i16),
+    /// 32-bit integer
+    Int32(i32),
+    /// 64-bit integer
+    Int64(i64),
+    /// Length-prefixed byte array
+    ByteArray(Vec<u8>),
+    /// Length-prefixed UTF-8 string
+    String(String),
+    /// Milliseconds since the Unix epoch
+    Timestamp(i64),
+    /// 16-byte UUID
+    Uuid([u8; 16]),
+}
+
+/// A single event stream message
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Message {
+    /// Message headers, in wire order
+    pub headers: Vec<(String, HeaderValue)>,
+    /// Raw payload bytes
+    pub payload: Bytes,
+}
+
+impl Message {
+    /// Create a message with the given payload and no headers
+    pub fn new(payload: impl Into<Bytes>) -> Self {
+        Self {
+            headers: Vec::new(),
+            payload: payload.into(),
+        }
+    }
+
+    /// Add a header
+    pub fn with_header(mut self, name: impl Into<String>, value: HeaderValue) -> Self {
+        self.headers.push((name.into(), value));
+        self
+    }
+
+    /// Get a header value by name
+    pub fn header(&self, name: &str) -> Option<&HeaderValue> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v)
+    }
+
+    /// Get a string header value by name
+    pub fn header_str(&self, name: &str) -> Option<&str> {
+        match self.header(name) {
+            Some(HeaderValue::String(s)) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Encode the message into its wire format
+    pub fn encode(&self) -> Vec<u8> {
+        let mut headers = BytesMut::new();
+        for (name, value) in &self.headers {
+            headers.put_u8(name.len() as u8);
+            headers.put_slice(name.as_bytes());
+            match value {
+                HeaderValue::Bool(true) => headers.put_u8(0),
+                HeaderValue::Bool(false) => headers.put_u8(1),
+                HeaderValue::Byte(v) => {
+                    headers.put_u8(2);
+                    headers.put_i8(*v);
+                }
+                HeaderValue::Int16(v) => {
+                    headers.put_u8(3);
+                    headers.put_i16(*v);
+                }
+                HeaderValue::Int32(v) => {
+                    headers.put_u8(4);
+                    headers.put_i32(*v);
+                }
+                HeaderValue::Int64(v) => {
+                    headers.put_u8(5);
+                    headers.put_i64(*v);
+                }
+                HeaderValue::ByteArray(v) => {
+                    headers.put_u8(6);
+                    headers.put_u16(v.len() as u16);
+                    headers.put_slice(v);
+                }
+                HeaderValue::String(v) => {
+                    headers.put_u8(7);
+                    headers.put_u16(v.len() as u16);
+                    headers.put_slice(v.as_bytes());
+                }
+                HeaderValue::Timestamp(v) => {
+                    headers.put_u8(8);
+                    headers.put_i64(*v);
+                }
+                HeaderValue::Uuid(v) => {
+                    headers.put_u8(9);
+                    headers.put_slice(v);
+                }
+            }
+        }
+
+        let total_len = PRELUDE_LEN + headers.len() + self.payload.len() + CRC_LEN;
+        let mut out = Vec::with_capacity(total_len);
+        out.put_u32(total_len as u32);
+        out.put_u32(headers.len() as u32);
+        let prelude_crc = crc32fast::hash(&out);
+        out.put_u32(prelude_crc);
+        out.put_slice(&headers);
+        out.put_slice(&self.payload);
+        let message_crc = crc32fast::hash(&out);
+        out.put_u32(message_crc);
+        out
+    }
+}
+
+/// Incremental event stream decoder
+#[derive(Debug, Default)]
+pub struct EventStreamDecoder {
+    buffer: BytesMut,
+}
+
+impl EventStreamDecoder {
+    /// Create an empty decoder
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append bytes read from the network
+    pub fn push(&mut self, data: &[u8]) {
+        self.buffer.extend_from_slice(data);
+    }
+
+    /// Whether no partial message is buffered
+    pub fn is_empty(&self) -> bool {
+        self.buffer.is_empty()
+    }
+
+    /// Decode the next complete message, if one is buffered
+    pub fn decode(&mut self) -> Result<Option<Message>> {
+        if self.buffer.len() < PRELUDE_LEN {
+            return Ok(None);
+        }
+
+        let mut prelude = &self.buffer[..PRELUDE_LEN];
+        let total_len = prelude.get_u32() as usize;
+        let headers_len = prelude.get_u32() as usize;
+        let prelude_crc = prelude.get_u32();
+
+        if crc32fast::hash(&self.buffer[..8]) != prelude_crc {
+            return Err(frame_error("prelude checksum mismatch"));
+        }
+        if total_len > MAX_MESSAGE_LEN {
+            return Err(frame_error(format!("message length {} exceeds limit", total_len)));
+        }
+        if total_len < PRELUDE_LEN + headers_len + CRC_LEN {
+            return Err(frame_error(format!(
+                "message length {} too short for {} header bytes",
+                total_len, headers_len
+            )));
+        }
+        if self.buffer.len() < total_len {
+            return Ok(None);
+        }
+
+        let frame = self.buffer.split_to(total_len).freeze();
+        let message_crc = (&frame[total_len - CRC_LEN..]).get_u32();
+        if crc32fast::hash(&frame[..total_len - CRC_LEN]) != message_crc {
+            return Err(frame_error("message checksum mismatch"));
+        }
+
+        let headers = decode_headers(frame.slice(PRELUDE_LEN..PRELUDE_LEN + headers_len))?;
+        let payload = frame.slice(PRELUDE_LEN + headers_len..total_len - CRC_LEN);
+
+        Ok(Some(Message { headers, payload }))
+    }
+}
+
+/// Decode the header block of a message
+fn decode_headers(mut buf: Bytes) -> Result<Vec<(String, HeaderValue)>> {
+    let mut headers = Vec::new();
+
+    while buf.has_remaining() {
+        let name_len = buf.get_u8() as usize;
+        ensure_remaining(&buf, name_len + 1)?;
+        let name = String::from_utf8(buf.split_to(name_len).to_vec())
+            .map_err(|_| frame_error("header name is not valid UTF-8"))?;
+
+        let value = match buf.get_u8() {
+            0 => HeaderValue::Bool(true),
+            1 => HeaderValue::Bool(false),
+            2 => {
+                ensure_remaining(&buf, 1)?;
+                HeaderValue::Byte(buf.get_i8())
+            }
+            3 => {
+                ensure_remaining(&buf, 2)?;
+                HeaderValue::Int16(buf.get_i16())
+            }
+            4 => {
+                ensure_remaining(&buf, 4)?;
+                HeaderValue::Int32(buf.get_i32())
+            }
+            5 => {
+                ensure_remaining(&buf, 8)?;
+                HeaderValue::Int64(buf.get_i64())
+            }
+            6 => {
+                ensure_remaining(&buf, 2)?;
+                let len = buf.get_u16() as usize;
+                ensure_remaining(&buf, len)?;
+                HeaderValue::ByteArray(buf.split_to(len).to_vec())
+            }
+            7 => {
+                ensure_remaining(&buf, 2)?;
+                let len = buf.get_u16() as usize;
+                ensure_remaining(&buf, len)?;
+                HeaderValue::String(
+                    String::from_utf8(buf.split_to(len).to_vec())
+                        .map_err(|_| frame_error("header value is not valid UTF-8"))?,
+                )
+            }
+            8 => {
+                ensure_remaining(&buf, 8)?;
+                HeaderValue::Timestamp(buf.get_i64())
+            }
+            9 => {
+                ensure_remaining(&buf, 16)?;
+                let mut uuid = [0u8; 16];
+                buf.copy_to_slice(&mut uuid);
+                HeaderValue::Uuid(uuid)
+            }
+            other => {
+                return Err(frame_error(format!("unknown header value type {}", other)));
+            }
+        };
+
+        headers.push((name, value));
+    }
+
+    Ok(headers)
+}
+
+fn ensure_remaining(buf: &Bytes, len: usize) -> Result<()> {
+    if buf.remaining() < len {
+        Err(frame_error("truncated header"))
+    } else {
+        Ok(())
+    }
+}
+
+fn frame_error(message: impl std::fmt::Display) -> ProviderError {
+    ProviderError::streaming(format!("Invalid event stream frame: {}", message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_message() -> Message {
+        Message::new(r#"{"delta":{"text":"Hi"}}"#)
+            .with_header(":event-type", HeaderValue::String("contentBlockDelta".to_string()))
+            .with_header(":content-type", HeaderValue::String("application/json".to_string()))
+            .with_header(":message-type", HeaderValue::String("event".to_string()))
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let message = sample_message()
+            .with_header("flag", HeaderValue::Bool(false))
+            .with_header("count", HeaderValue::Int32(-7))
+            .with_header("ts", HeaderValue::Timestamp(1_700_000_000_000))
+            .with_header("id", HeaderValue::Uuid([7u8; 16]));
+
+        let mut decoder = EventStreamDecoder::new();
+        decoder.push(&message.encode());
+
+        let decoded = decoder.decode().unwrap().unwrap();
+        assert_eq!(decoded, message);
+        assert_eq!(decoded.header_str(":event-type"), Some("contentBlockDelta"));
+        assert!(decoder.decode().unwrap().is_none());
+        assert!(decoder.is_empty());
+    }
+
+    #[test]
+    fn test_empty_message_layout() {
+        // A message with no headers and no payload is just prelude + CRC
+        let encoded = Message::new(Bytes::new()).encode();
+
+        assert_eq!(encoded.len(), 16);
+        assert_eq!(&encoded[..8], &[0, 0, 0, 16, 0, 0, 0, 0]);
+        assert_eq!(&encoded[8..12], &crc32fast::hash(&encoded[..8]).to_be_bytes());
+    }
+
+    #[test]
+    fn test_split_and_batched_reads() {
+        let first = sample_message();
+        let second = Message::new("second");
+        let mut wire = first.encode();
+        wire.extend(second.encode());
+
+        let mut decoder = EventStreamDecoder::new();
+        let mut decoded = Vec::new();
+        for chunk in wire.chunks(5) {
+            decoder.push(chunk);
+            while let Some(message) = decoder.decode().unwrap() {
+                decoded.push(message);
+            }
+        }
+
+        assert_eq!(decoded, vec![first, second]);
+        assert!(decoder.is_empty());
+    }
+
+    #[test]
+    fn test_checksum_mismatch() {
+        let mut wire = sample_message().encode();
+        let last = wire.len() - 6;
+        wire[last] ^= 0xff;
+
+        let mut decoder = EventStreamDecoder::new();
+        decoder.push(&wire);
+        assert!(decoder.decode().is_err());
+
+        let mut wire = sample_message().encode();
+        wire[9] ^= 0xff;
+
+        let mut decoder = EventStreamDecoder::new();
+        decoder.push(&wire);
+        assert!(decoder.decode().is_err());
+    }
+}
@@ -0,0 +1,1592 @@
+//! AWS Bedrock provider implementation
+//!
+//! This module provides an adapter for the Amazon Bedrock runtime API with
+//! support for:
+//! - SigV4 request signing from environment or shared-profile credentials
+//!   (or Bedrock API keys sent as bearer tokens)
+//! - `ConverseStream` for any Bedrock text model, and
+//!   `InvokeModelWithResponseStream` for Anthropic models, and their
+//!   non-streaming counterparts `Converse` and `InvokeModel`
+//! - Decoding of the binary `application/vnd.amazon.eventstream` framing
+//! - Token usage reporting and cost calculation for common Bedrock models
+//!
+//! Cross-region inference profile IDs (e.g. `us.anthropic.claude-3-5-haiku-20241022-v1:0`)
+//! are accepted wherever the underlying model ID is.
+
+pub mod credentials;
+pub mod event_stream;
+pub mod sigv4;
+
+pub use credentials::AwsCredentials;
+
+use crate::error::{ProviderError, Result};
+use crate::headers::ServerSignals;
+use crate::models::{self, ModelCache};
+use crate::network::NetworkProbe;
+use crate::retry::{self, RetryPolicy};
+use crate::traits::{
+    known_models, CompletionResult, MessageRole, Provider, ResponseMetadata, SharedUsage,
+    StreamingRequest, StreamingResponse, TokenUsage,
+};
+use async_trait::async_trait;
+use base64::Engine;
+use credentials::non_empty_env;
+use event_stream::{EventStreamDecoder, Message};
+use futures::stream::BoxStream;
+use futures::StreamExt;
+use llm_latency_lens_core::{Clock, RequestId, TimingEngine, Timestamp, TokenEvent, TokenKind};
+use reqwest::header::{HeaderMap, HeaderValue, ACCEPT, AUTHORIZATION, CONTENT_TYPE};
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use sigv4::{uri_encode, SigV4Signer};
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// Region used when none is configured
+const DEFAULT_REGION: &str = "us-east-1";
+
+/// SigV4 signing name shared by the Bedrock control plane and runtime
+const SIGNING_SERVICE: &str = "bedrock";
+
+/// Anthropic API version expected by Bedrock's native Anthropic payloads
+const ANTHROPIC_BEDROCK_VERSION: &str = "bedrock-2023-05-31";
+
+/// Bedrock streaming API to call
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BedrockApi {
+    /// `ConverseStream`: unified message format for all text models
+    #[default]
+    Converse,
+    /// `InvokeModelWithResponseStream`: model-native payloads (Anthropic models only)
+    InvokeModel,
+}
+
+/// How requests are authenticated
+enum BedrockAuth {
+    /// SigV4-signed requests
+    SigV4(SigV4Signer),
+    /// Bedrock API key sent as a bearer token
+    Bearer(String),
+}
+
+/// AWS Bedrock provider adapter
+pub struct BedrockProvider {
+    /// HTTP client
+    client: reqwest::Client,
+    /// Request authentication
+    auth: BedrockAuth,
+    /// AWS region
+    region: String,
+    /// Runtime endpoint (`bedrock-runtime`)
+    base_url: String,
+    /// Control plane endpoint (`bedrock`), used for health checks and model listing
+    control_url: String,
+    /// Streaming API to call
+    api: BedrockApi,
+    /// Retries of the initial request
+    max_retries: u32,
+    /// Foundation models discovered through ListFoundationModels
+    models: ModelCache,
+}
+
+impl BedrockProvider {
+    /// Create a new Bedrock provider with explicit credentials
+    ///
+    /// # Arguments
+    ///
+    /// * `credentials` - AWS access key pair (and optional session token)
+    /// * `region` - AWS region hosting the models
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use llm_latency_lens_providers::bedrock::{AwsCredentials, BedrockProvider};
+    ///
+    /// let credentials = AwsCredentials::new("AKIA...", "secret");
+    /// let provider = BedrockProvider::new(credentials, "us-east-1");
+    /// ```
+    pub fn new(credentials: AwsCredentials, region: impl Into<String>) -> Self {
+        Self::builder()
+            .credentials(credentials)
+            .region(region)
+            .build()
+            .expect("explicit credentials do not require discovery")
+    }
+
+    /// Create a provider using credentials and region from the environment
+    ///
+    /// Uses `AWS_BEARER_TOKEN_BEDROCK` if set, otherwise SigV4 credentials from
+    /// `AWS_ACCESS_KEY_ID`/`AWS_SECRET_ACCESS_KEY` or the shared credentials file.
+    pub fn from_env() -> Result<Self> {
+        Self::builder().build()
+    }
+
+    /// Create a provider with custom configuration
+    pub fn builder() -> BedrockProviderBuilder {
+        BedrockProviderBuilder::default()
+    }
+
+    /// Get the configured region
+    pub fn region(&self) -> &str {
+        &self.region
+    }
+
+    /// Build HTTP client with optimized settings
+    fn build_client(timeout: Duration) -> reqwest::Client {
+        crate::network::instrument(reqwest::Client::builder())
+            .timeout(timeout)
+            .tcp_keepalive(Duration::from_secs(60))
+            .pool_idle_timeout(Duration::from_secs(90))
+            .build()
+            .expect("Failed to build HTTP client")
+    }
+
+    /// Add authentication headers to a request
+    fn authorize(&self, method: &str, url: &Url, headers: &mut HeaderMap, body: &[u8]) {
+        match self.auth {
+            BedrockAuth::SigV4(ref signer) => signer.sign(method, url, headers, body),
+            BedrockAuth::Bearer(ref token) => {
+                headers.insert(
+                    AUTHORIZATION,
+                    HeaderValue::from_str(&format!("Bearer {}", token))
+                        .expect("Invalid API key format"),
+                );
+            }
+        }
+    }
+
+    /// Build the runtime URL for a model operation
+    fn model_url(&self, model: &str, operation: &str) -> Result<Url> {
+        let url = format!("{}/model/{}/{}", self.base_url, uri_encode(model), operation);
+        Url::parse(&url)
+            .map_err(|e| ProviderError::ConfigError(format!("Invalid Bedrock URL {}: {}", url, e)))
+    }
+
+    /// Build a `ConverseStream` payload
+    fn converse_payload(request: &StreamingRequest) -> ConverseRequest {
+        let system = request
+            .messages
+            .iter()
+            .filter(|m| m.role == MessageRole::System)
+            .map(|m| ConverseContent {
+                text: m.text(),
+            })
+            .collect();
+
+        let messages = request
+            .messages
+            .iter()
+            .filter(|m| m.role != MessageRole::System)
+            .map(|m| ConverseMessage {
+                role: match m.role {
+                    MessageRole::Assistant => "assistant".to_string(),
+                    _ => "user".to_string(),
+                },
+                content: vec![ConverseContent {
+                    text: m.text(),
+                }],
+            })
+            .collect();
+
+        ConverseRequest {
+            messages,
+            system,
+            inference_config: InferenceConfig {
+                max_tokens: request.max_tokens,
+                temperature: request.temperature,
+                top_p: request.top_p,
+                stop_sequences: request.stop.clone(),
+            },
+        }
+    }
+
+    /// Build a native Anthropic payload for `InvokeModelWithResponseStream`
+    fn invoke_payload(request: &StreamingRequest) -> Result<AnthropicInvokeRequest> {
+        if !base_model_id(&request.model).starts_with("anthropic.") {
+            return Err(ProviderError::ConfigError(format!(
+                "The InvokeModel API is only supported for Anthropic models; use the Converse API for {}",
+                request.model
+            )));
+        }
+
+        let system = request
+            .messages
+            .iter()
+            .filter(|m| m.role == MessageRole::System)
+            .map(|m| m.text())
+            .collect::<Vec<_>>()
+            .join("\n\n");
+
+        Ok(AnthropicInvokeRequest {
+            anthropic_version: ANTHROPIC_BEDROCK_VERSION.to_string(),
+            max_tokens: request.max_tokens.unwrap_or(4096),
+            messages: request
+                .messages
+                .iter()
+                .filter(|m| m.role != MessageRole::System)
+                .map(|m| AnthropicMessage {
+                    role: match m.role {
+                        MessageRole::Assistant => "assistant".to_string(),
+                        _ => "user".to_string(),
+                    },
+                    content: m.text(),
+                })
+                .collect(),
+            system: if system.is_empty() { None } else { Some(system) },
+            temperature: request.temperature,
+            top_p: request.top_p,
+            stop_sequences: request.stop.clone(),
+        })
+    }
+
+    /// Select the operation and serialize its payload
+    fn payload(&self, request: &StreamingRequest, stream: bool) -> Result<(&'static str, Vec<u8>)> {
+        let (operation, body) = match (self.api, stream) {
+            (BedrockApi::Converse, true) => (
+                "converse-stream",
+                serde_json::to_vec(&Self::converse_payload(request)),
+            ),
+            (BedrockApi::Converse, false) => (
+                "converse",
+                serde_json::to_vec(&Self::converse_payload(request)),
+            ),
+            (BedrockApi::InvokeModel, true) => (
+                "invoke-with-response-stream",
+                serde_json::to_vec(&Self::invoke_payload(request)?),
+            ),
+            (BedrockApi::InvokeModel, false) => (
+                "invoke",
+                serde_json::to_vec(&Self::invoke_payload(request)?),
+            ),
+        };
+        Ok((operation, body.map_err(ProviderError::from_json_error)?))
+    }
+}
+
+/// Builder for Bedrock provider
+#[derive(Default)]
+pub struct BedrockProviderBuilder {
+    credentials: Option<AwsCredentials>,
+    bearer_token: Option<String>,
+    region: Option<String>,
+    base_url: Option<String>,
+    api: Option<BedrockApi>,
+    max_retries: Option<u32>,
+    timeout: Option<Duration>,
+}
+
+impl BedrockProviderBuilder {
+    /// Set explicit SigV4 credentials
+    pub fn credentials(mut self, credentials: AwsCredentials) -> Self {
+        self.credentials = Some(credentials);
+        self
+    }
+
+    /// Authenticate with a Bedrock API key instead of SigV4
+    pub fn bearer_token(mut self, token: impl Into<String>) -> Self {
+        self.bearer_token = Some(token.into());
+        self
+    }
+
+    /// Set the AWS region
+    pub fn region(mut self, region: impl Into<String>) -> Self {
+        self.region = Some(region.into());
+        self
+    }
+
+    /// Set the base URL (for VPC endpoints or a local stand-in server)
+    ///
+    /// The same URL is used for runtime calls and health checks.
+    pub fn base_url(mut self, url: impl Into<String>) -> Self {
+        self.base_url = Some(url.into());
+        self
+    }
+
+    /// Select the streaming API
+    pub fn api(mut self, api: BedrockApi) -> Self {
+        self.api = Some(api);
+        self
+    }
+
+    /// Set how many times a failed initial request is retried (3 by default)
+    pub fn max_retries(mut self, retries: u32) -> Self {
+        self.max_retries = Some(retries);
+        self
+    }
+
+    /// Set the HTTP request timeout (120 seconds by default)
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Build the provider
+    ///
+    /// The region defaults to `AWS_REGION`, then `AWS_DEFAULT_REGION`, then
+    /// `us-east-1`. Without explicit credentials or a bearer token, credentials
+    /// are discovered from the environment, which fails if none are found.
+    pub fn build(self) -> Result<BedrockProvider> {
+        let region = self
+            .region
+            .or_else(|| non_empty_env("AWS_REGION"))
+            .or_else(|| non_empty_env("AWS_DEFAULT_REGION"))
+            .unwrap_or_else(|| DEFAULT_REGION.to_string());
+
+        let auth = match (self.bearer_token, self.credentials) {
+            (Some(token), _) => BedrockAuth::Bearer(token),
+            (None, Some(credentials)) => {
+                BedrockAuth::SigV4(SigV4Signer::new(credentials, &region, SIGNING_SERVICE))
+            }
+            (None, None) => match non_empty_env("AWS_BEARER_TOKEN_BEDROCK") {
+                Some(token) => BedrockAuth::Bearer(token),
+                None => BedrockAuth::SigV4(SigV4Signer::new(
+                    AwsCredentials::load()?,
+                    &region,
+                    SIGNING_SERVICE,
+                )),
+            },
+        };
+
+        let (base_url, control_url) = match self.base_url {
+            Some(url) => {
+                let url = url.trim_end_matches('/').to_string();
+                (url.clone(), url)
+            }
+            None => (
+                format!("https://bedrock-runtime.{}.amazonaws.com", region),
+                format!("https://bedrock.{}.amazonaws.com", region),
+            ),
+        };
+
+        Ok(BedrockProvider {
+            client: BedrockProvider::build_client(
+                self.timeout.unwrap_or(crate::network::DEFAULT_TIMEOUT),
+            ),
+            auth,
+            region,
+            base_url,
+            control_url,
+            api: self.api.unwrap_or_default(),
+            max_retries: self.max_retries.unwrap_or(3),
+            models: ModelCache::default(),
+        })
+    }
+}
+
+#[async_trait]
+impl Provider for BedrockProvider {
+    fn name(&self) -> &'static str {
+        "aws-bedrock"
+    }
+
+    fn provider_kind(&self) -> llm_latency_lens_core::Provider {
+        llm_latency_lens_core::Provider::AwsBedrock
+    }
+
+    async fn health_check(&self) -> Result<()> {
+        // ListFoundationModels validates credentials, region and signing
+        let url = Url::parse(&format!(
+            "{}/foundation-models?byOutputModality=TEXT",
+            self.control_url
+        ))
+        .map_err(|e| ProviderError::ConfigError(format!("Invalid Bedrock URL: {}", e)))?;
+
+        let mut headers = HeaderMap::new();
+        self.authorize("GET", &url, &mut headers, b"");
+
+        let response = self
+            .client
+            .get(url)
+            .headers(headers)
+            .send()
+            .await
+            .map_err(ProviderError::from_reqwest)?;
+
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(parse_bedrock_error(response).await)
+        }
+    }
+
+    async fn stream(
+        &self,
+        request: StreamingRequest,
+        timing_engine: &TimingEngine,
+    ) -> Result<StreamingResponse> {
+        // Validate model
+        self.validate_model(&request.model)?;
+
+        // Start timing measurement
+        let mut timing = timing_engine.start();
+        let network = NetworkProbe::new(timing_engine.clock());
+        timing.checkpoint("request_start");
+
+        // Build request payload
+        let (operation, body) = self.payload(&request, true)?;
+
+        timing.checkpoint("payload_built");
+
+        let url = self.model_url(&request.model, operation)?;
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        headers.insert(
+            ACCEPT,
+            HeaderValue::from_static("application/vnd.amazon.eventstream"),
+        );
+        self.authorize("POST", &url, &mut headers, &body);
+
+        timing.checkpoint("headers_built");
+
+        let response = retry::send(
+            RetryPolicy::new(self.max_retries),
+            &network,
+            self.client.post(url).headers(headers).body(body),
+            parse_bedrock_error,
+        )
+        .await?;
+
+        timing.checkpoint("response_received");
+
+        let response_headers =
+            crate::headers::capture(response.headers(), &request.redact_headers);
+        let server = ServerSignals::from_headers(response.headers());
+
+        // Create token stream
+        let usage = SharedUsage::new();
+        let state = DecodeState {
+            body: response.bytes_stream().boxed(),
+            decoder: EventStreamDecoder::new(),
+            handler: EventHandler {
+                api: self.api,
+                request_id: request.request_id,
+                clock: timing_engine.clock().clone(),
+                request_start: timing.start_time(),
+                sequence: 0,
+                last_token_time: None,
+                usage: usage.clone(),
+            },
+            pending: VecDeque::new(),
+            network: network.clone(),
+            done: false,
+        };
+
+        let token_stream = futures::stream::unfold(state, |mut state| async move {
+            loop {
+                if let Some(item) = state.pending.pop_front() {
+                    return Some((item, state));
+                }
+                if state.done {
+                    return None;
+                }
+                state.read_next().await;
+            }
+        })
+        .boxed();
+
+        timing.checkpoint("stream_initialized");
+
+        Ok(StreamingResponse {
+            request_id: request.request_id,
+            token_stream: Box::pin(token_stream),
+            metadata: ResponseMetadata {
+                model: request.model,
+                input_tokens: None, // Reported at the end of the stream
+                output_tokens: None,
+                thinking_tokens: None,
+                cache_creation_tokens: None,
+                cache_read_tokens: None,
+                estimated_cost: None,
+                headers: response_headers,
+                server,
+            },
+            usage,
+            network,
+        })
+    }
+
+    async fn complete_non_streaming(
+        &self,
+        request: StreamingRequest,
+        timing_engine: &TimingEngine,
+    ) -> Result<CompletionResult> {
+        self.validate_model(&request.model)?;
+
+        let timing = timing_engine.start();
+        let network = NetworkProbe::new(timing_engine.clock());
+
+        let (operation, body) = self.payload(&request, false)?;
+        let url = self.model_url(&request.model, operation)?;
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        headers.insert(ACCEPT, HeaderValue::from_static("application/json"));
+        self.authorize("POST", &url, &mut headers, &body);
+
+        let response = retry::send(
+            RetryPolicy::new(self.max_retries),
+            &network,
+            self.client.post(url).headers(headers).body(body),
+            parse_bedrock_error,
+        )
+        .await?;
+
+        let response_headers =
+            crate::headers::capture(response.headers(), &request.redact_headers);
+        let server = ServerSignals::from_headers(response.headers());
+
+        let mut usage = TokenUsage::default();
+        let (content, body) = match self.api {
+            BedrockApi::Converse => {
+                let (response, body): (ConverseResponse, _) =
+                    crate::body::read_json(response, timing_engine.clock(), timing.start_time())
+                        .await?;
+                if let "content_filtered" | "guardrail_intervened" = response.stop_reason.as_str() {
+                    return Err(ProviderError::ContentFilterError(format!(
+                        "Response stopped: {}",
+                        response.stop_reason
+                    )));
+                }
+                if let Some(reported) = response.usage {
+                    usage.input_tokens = Some(reported.input_tokens);
+                    usage.output_tokens = Some(reported.output_tokens);
+                }
+                let content = response
+                    .output
+                    .message
+                    .content
+                    .into_iter()
+                    .filter_map(|block| block.text)
+                    .collect::<String>();
+                (content, body)
+            }
+            BedrockApi::InvokeModel => {
+                let (response, body): (AnthropicInvokeResponse, _) =
+                    crate::body::read_json(response, timing_engine.clock(), timing.start_time())
+                        .await?;
+                if let Some(reported) = response.usage {
+                    usage.input_tokens = reported.input_tokens;
+                    usage.output_tokens = reported.output_tokens;
+                }
+                let content = response
+                    .content
+                    .into_iter()
+                    .filter(|block| block.block_type == "text")
+                    .filter_map(|block| block.text)
+                    .collect::<String>();
+                (content, body)
+            }
+        };
+
+        let mut metadata = ResponseMetadata {
+            model: request.model.clone(),
+            input_tokens: None,
+            output_tokens: None,
+            thinking_tokens: None,
+            cache_creation_tokens: None,
+            cache_read_tokens: None,
+            estimated_cost: None,
+            headers: response_headers,
+            server,
+        };
+        metadata.apply_usage(usage);
+        metadata.estimate_cost(|usage| self.usage_cost(&request.model, usage));
+
+        Ok(CompletionResult::from_body(
+            request.request_id,
+            content,
+            String::new(),
+            metadata,
+            &network,
+            body,
+        ))
+    }
+
+    fn supported_models(&self) -> Vec<String> {
+        vec![
+            // Anthropic Claude
+            "anthropic.claude-3-5-sonnet-20241022-v2:0".to_string(),
+            "anthropic.claude-3-5-sonnet-20240620-v1:0".to_string(),
+            "anthropic.claude-3-5-haiku-20241022-v1:0".to_string(),
+            "anthropic.claude-3-opus-20240229-v1:0".to_string(),
+            "anthropic.claude-3-sonnet-20240229-v1:0".to_string(),
+            "anthropic.claude-3-haiku-20240307-v1:0".to_string(),
+            // Meta Llama
+            "meta.llama3-1-8b-instruct-v1:0".to_string(),
+            "meta.llama3-1-70b-instruct-v1:0".to_string(),
+            "meta.llama3-1-405b-instruct-v1:0".to_string(),
+            // Amazon Nova
+            "amazon.nova-micro-v1:0".to_string(),
+            "amazon.nova-lite-v1:0".to_string(),
+            "amazon.nova-pro-v1:0".to_string(),
+            // Mistral
+            "mistral.mistral-large-2407-v1:0".to_string(),
+        ]
+    }
+
+    async fn list_models(&self) -> Result<Vec<String>> {
+        let url = Url::parse(&format!(
+            "{}/foundation-models?byOutputModality=TEXT",
+            self.control_url
+        ))
+        .map_err(|e| ProviderError::ConfigError(format!("Invalid Bedrock URL: {}", e)))?;
+
+        let mut headers = HeaderMap::new();
+        self.authorize("GET", &url, &mut headers, b"");
+
+        let response = self
+            .client
+            .get(url)
+            .headers(headers)
+            .send()
+            .await
+            .map_err(ProviderError::from_reqwest)?;
+
+        if !response.status().is_success() {
+            return Err(parse_bedrock_error(response).await);
+        }
+        let list: FoundationModelList = models::read_list(response).await?;
+        let mut ids: Vec<String> = list
+            .model_summaries
+            .into_iter()
+            .map(|m| m.model_id)
+            .collect();
+        ids.sort();
+        Ok(ids)
+    }
+
+    fn model_cache(&self) -> Option<&ModelCache> {
+        Some(&self.models)
+    }
+
+    fn validate_model(&self, model: &str) -> Result<()> {
+        let base = base_model_id(model);
+        let supported = self.supported_models();
+        let discovered = self.models.get().unwrap_or_default();
+        if supported.iter().chain(&discovered).any(|m| m == base) {
+            Ok(())
+        } else {
+            Err(ProviderError::InvalidModel(format!(
+                "Model '{}' is not supported by {}. Supported models: {}",
+                model,
+                self.name(),
+                known_models(supported, discovered).join(", ")
+            )))
+        }
+    }
+}
+
+/// Strip a cross-region inference profile prefix (`us.`, `eu.`, `apac.`, ...)
+pub(crate) fn base_model_id(model: &str) -> &str {
+    ["us.", "eu.", "apac.", "us-gov."]
+        .iter()
+        .find_map(|prefix| model.strip_prefix(prefix))
+        .unwrap_or(model)
+}
+
+/// State carried between reads of the response body
+struct DecodeState {
+    body: BoxStream<'static, reqwest::Result<bytes::Bytes>>,
+    decoder: EventStreamDecoder,
+    handler: EventHandler,
+    pending: VecDeque<Result<TokenEvent>>,
+    network: NetworkProbe,
+    done: bool,
+}
+
+impl DecodeState {
+    /// Read one chunk from the network and queue the events it completes
+    async fn read_next(&mut self) {
+        match self.body.next().await {
+            Some(Ok(chunk)) => {
+                let received_at = self.handler.clock.now();
+                let mut messages = 0;
+                self.decoder.push(&chunk);
+                loop {
+                    match self.decoder.decode() {
+                        Ok(Some(message)) => {
+                            self.network.first_event_at(received_at);
+                            messages += 1;
+                            if let Some(item) = self.handler.handle(message, received_at) {
+                                self.pending.push_back(item);
+                            }
+                        }
+                        Ok(None) => break,
+                        Err(e) => {
+                            self.pending.push_back(Err(e));
+                            self.done = true;
+                            break;
+                        }
+                    }
+                }
+                self.network.record_read(messages);
+            }
+            Some(Err(e)) => {
+                tracing::error!("Event stream read error: {}", e);
+                self.pending.push_back(Err(ProviderError::streaming(format!(
+                    "Failed to read event stream: {}",
+                    e
+                ))));
+                self.done = true;
+            }
+            None => {
+                if !self.decoder.is_empty() {
+                    self.pending.push_back(Err(ProviderError::streaming(
+                        "Event stream ended in the middle of a message",
+                    )));
+                }
+                tracing::debug!("Event stream completed");
+                self.done = true;
+            }
+        }
+    }
+}
+
+/// Turns decoded event stream messages into token events
+struct EventHandler {
+    api: BedrockApi,
+    request_id: RequestId,
+    clock: Clock,
+    request_start: Timestamp,
+    sequence: u64,
+    last_token_time: Option<Timestamp>,
+    usage: SharedUsage,
+}
+
+impl EventHandler {
+    /// Handle a message completed by the read that arrived at `received_at`
+    fn handle(
+        &mut self,
+        message: Message,
+        received_at: Timestamp,
+    ) -> Option<Result<TokenEvent>> {
+        match message.header_str(":message-type").unwrap_or("event") {
+            "exception" => {
+                let kind = message
+                    .header_str(":exception-type")
+                    .unwrap_or("UnknownException");
+                tracing::error!("Exception event received: {}", kind);
+                return Some(Err(map_bedrock_error(
+                    kind,
+                    payload_message(&message.payload),
+                    None,
+                )));
+            }
+            "error" => {
+                let code = message.header_str(":error-code").unwrap_or("UnknownError");
+                let text = message.header_str(":error-message").unwrap_or_default();
+                tracing::error!("Error event received: {}", code);
+                return Some(Err(map_bedrock_error(code, text.to_string(), None)));
+            }
+            _ => {}
+        }
+
+        let event_type = message.header_str(":event-type")?;
+        let result = match self.api {
+            BedrockApi::Converse => self.converse_event(event_type, &message.payload),
+            BedrockApi::InvokeModel => self.invoke_event(event_type, &message.payload),
+        };
+
+        match result {
+            Ok(Some(content)) => Some(Ok(self.token(content, received_at))),
+            Ok(None) => None,
+            Err(e) => Some(Err(e)),
+        }
+    }
+
+    /// Handle a `ConverseStream` event, returning any generated text
+    fn converse_event(&self, event_type: &str, payload: &[u8]) -> Result<Option<String>> {
+        match event_type {
+            "contentBlockDelta" => {
+                let event: ContentBlockDeltaEvent =
+                    serde_json::from_slice(payload).map_err(ProviderError::from_json_error)?;
+                Ok(event.delta.text.filter(|t| !t.is_empty()))
+            }
+            "messageStop" => {
+                let event: MessageStopEvent =
+                    serde_json::from_slice(payload).map_err(ProviderError::from_json_error)?;
+                match event.stop_reason.as_str() {
+                    "content_filtered" | "guardrail_intervened" => Err(
+                        ProviderError::ContentFilterError(format!(
+                            "Response stopped: {}",
+                            event.stop_reason
+                        )),
+                    ),
+                    _ => Ok(None),
+                }
+            }
+            "metadata" => {
+                let event: MetadataEvent =
+                    serde_json::from_slice(payload).map_err(ProviderError::from_json_error)?;
+                if let Some(usage) = event.usage {
+                    self.usage.update(|u| {
+                        u.input_tokens = Some(usage.input_tokens);
+                        u.output_tokens = Some(usage.output_tokens);
+                    });
+                }
+                Ok(None)
+            }
+            // messageStart, contentBlockStart, contentBlockStop
+            _ => Ok(None),
+        }
+    }
+
+    /// Handle an `InvokeModelWithResponseStream` chunk carrying an Anthropic event
+    fn invoke_event(&self, event_type: &str, payload: &[u8]) -> Result<Option<String>> {
+        if event_type != "chunk" {
+            return Ok(None);
+        }
+
+        let chunk: PayloadChunk =
+            serde_json::from_slice(payload).map_err(ProviderError::from_json_error)?;
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(chunk.bytes)
+            .map_err(|e| ProviderError::streaming(format!("Invalid chunk encoding: {}", e)))?;
+        let event: AnthropicStreamEvent =
+            serde_json::from_slice(&bytes).map_err(ProviderError::from_json_error)?;
+
+        if let Some(metrics) = event.invocation_metrics {
+            self.usage.update(|u| {
+                u.input_tokens = Some(metrics.input_token_count);
+                u.output_tokens = Some(metrics.output_token_count);
+            });
+        }
+
+        match event.event_type.as_str() {
+            "message_start" => {
+                if let Some(usage) = event.message.and_then(|m| m.usage) {
+                    self.usage
+                        .update(|u| u.input_tokens = usage.input_tokens.or(u.input_tokens));
+                }
+                Ok(None)
+            }
+            "content_block_delta" => Ok(event
+                .delta
+                .filter(|d| d.delta_type.as_deref() == Some("text_delta"))
+                .and_then(|d| d.text)),
+            "message_delta" => {
+                if let Some(usage) = event.usage {
+                    self.usage
+                        .update(|u| u.output_tokens = usage.output_tokens.or(u.output_tokens));
+                }
+                Ok(None)
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Record timing for a piece of generated text
+    fn token(&mut self, content: String, now: Timestamp) -> TokenEvent {
+        let time_since_start = now.duration_since(self.request_start);
+        let inter_token_latency = self.last_token_time.map(|t| now.duration_since(t));
+        self.last_token_time = Some(now);
+
+        let event = TokenEvent {
+            request_id: self.request_id,
+            sequence: self.sequence,
+            kind: TokenKind::Content,
+            content: Some(content),
+            timestamp_nanos: now.as_nanos(),
+            time_since_start,
+            inter_token_latency,
+        };
+
+        self.sequence += 1;
+        event
+    }
+}
+
+/// Parse a Bedrock error response
+///
+/// The error type comes from the `x-amzn-ErrorType` header (for example
+/// `ThrottlingException:http://internal.amazon.com/coral/...`) and the message
+/// from the JSON body.
+async fn parse_bedrock_error(response: reqwest::Response) -> ProviderError {
+    let status_code = response.status().as_u16();
+    let error_type = response
+        .headers()
+        .get("x-amzn-errortype")
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.to_string());
+
+    let body = match response.text().await {
+        Ok(text) => text,
+        Err(e) => {
+            return ProviderError::api_error(
+                status_code,
+                format!("Failed to read error response: {}", e),
+            );
+        }
+    };
+
+    let message = payload_message(body.as_bytes());
+    match error_type {
+        Some(kind) => map_bedrock_error(&kind, message, Some(status_code)),
+        None => match status_code {
+            403 => ProviderError::AuthenticationError(message),
+            429 => ProviderError::rate_limit(message, None),
+            _ => ProviderError::api_error_with_body(status_code, message, body),
+        },
+    }
+}
+
+/// Extract the `message` field from an error payload
+fn payload_message(payload: &[u8]) -> String {
+    serde_json::from_slice::<serde_json::Value>(payload)
+        .ok()
+        .and_then(|v| {
+            v.get("message")
+                .or_else(|| v.get("Message"))
+                .and_then(|m| m.as_str())
+                .map(|m| m.to_string())
+        })
+        .unwrap_or_else(|| String::from_utf8_lossy(payload).into_owned())
+}
+
+/// Map a Bedrock exception type to a provider error
+///
+/// Exception names arrive capitalized in HTTP headers and camel-cased in
+/// event stream headers, so they are compared case-insensitively.
+fn map_bedrock_error(kind: &str, message: String, status_code: Option<u16>) -> ProviderError {
+    let kind = kind.split(':').next().unwrap_or(kind).to_ascii_lowercase();
+
+    match kind.as_str() {
+        "accessdeniedexception" | "unrecognizedclientexception" | "invalidsignatureexception"
+        | "expiredtokenexception" => ProviderError::AuthenticationError(message),
+        "throttlingexception" | "toomanyrequestsexception" => {
+            ProviderError::rate_limit(message, None)
+        }
+        "resourcenotfoundexception" => ProviderError::InvalidModel(message),
+        "serviceunavailableexception" | "modelnotreadyexception" => {
+            ProviderError::ServiceUnavailable(message)
+        }
+        "modelstreamerrorexception" => ProviderError::streaming(message),
+        "internalserverexception" => ProviderError::api_error(status_code.unwrap_or(500), message),
+        "modeltimeoutexception" => ProviderError::api_error(status_code.unwrap_or(408), message),
+        "validationexception" if message.contains("too long") => {
+            ProviderError::ContextLengthExceeded(message)
+        }
+        _ => ProviderError::api_error(
+            status_code.unwrap_or(400),
+            format!("{}: {}", kind, message),
+        ),
+    }
+}
+
+// Bedrock API request/response types
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ConverseRequest {
+    messages: Vec<ConverseMessage>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    system: Vec<ConverseContent>,
+    inference_config: InferenceConfig,
+}
+
+#[derive(Debug, Serialize)]
+struct ConverseMessage {
+    role: String,
+    content: Vec<ConverseContent>,
+}
+
+#[derive(Debug, Serialize)]
+struct ConverseContent {
+    text: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct InferenceConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stop_sequences: Option<Vec<String>>,
+}
+
+#[derive(Debug, Serialize)]
+struct AnthropicInvokeRequest {
+    anthropic_version: String,
+    max_tokens: u32,
+    messages: Vec<AnthropicMessage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    system: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stop_sequences: Option<Vec<String>>,
+}
+
+#[derive(Debug, Serialize)]
+struct AnthropicMessage {
+    role: String,
+    content: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ContentBlockDeltaEvent {
+    delta: ConverseDelta,
+}
+
+#[derive(Debug, Deserialize)]
+struct ConverseDelta {
+    #[serde(default)]
+    text: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MessageStopEvent {
+    stop_reason: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct MetadataEvent {
+    #[serde(default)]
+    usage: Option<ConverseUsage>,
+}
+
+/// ListFoundationModels response
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FoundationModelList {
+    #[serde(default)]
+    model_summaries: Vec<FoundationModelSummary>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FoundationModelSummary {
+    model_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ConverseUsage {
+    input_tokens: u64,
+    output_tokens: u64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ConverseResponse {
+    output: ConverseOutput,
+    stop_reason: String,
+    #[serde(default)]
+    usage: Option<ConverseUsage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ConverseOutput {
+    message: ConverseOutputMessage,
+}
+
+#[derive(Debug, Deserialize)]
+struct ConverseOutputMessage {
+    #[serde(default)]
+    content: Vec<ConverseDelta>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AnthropicInvokeResponse {
+    #[serde(default)]
+    content: Vec<AnthropicContentBlock>,
+    #[serde(default)]
+    usage: Option<AnthropicUsage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AnthropicContentBlock {
+    #[serde(rename = "type")]
+    block_type: String,
+    #[serde(default)]
+    text: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PayloadChunk {
+    bytes: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct AnthropicStreamEvent {
+    #[serde(rename = "type")]
+    event_type: String,
+    #[serde(default)]
+    message: Option<AnthropicMessageStart>,
+    #[serde(default)]
+    delta: Option<AnthropicDelta>,
+    #[serde(default)]
+    usage: Option<AnthropicUsage>,
+    #[serde(default, rename = "amazon-bedrock-invocationMetrics")]
+    invocation_metrics: Option<InvocationMetrics>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AnthropicMessageStart {
+    #[serde(default)]
+    usage: Option<AnthropicUsage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AnthropicDelta {
+    #[serde(default, rename = "type")]
+    delta_type: Option<String>,
+    #[serde(default)]
+    text: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AnthropicUsage {
+    #[serde(default)]
+    input_tokens: Option<u64>,
+    #[serde(default)]
+    output_tokens: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct InvocationMetrics {
+    input_token_count: u64,
+    output_token_count: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use event_stream::HeaderValue as EventHeader;
+    use wiremock::matchers::{header, header_exists, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const MODEL: &str = "anthropic.claude-3-5-haiku-20241022-v1:0";
+
+    fn event(event_type: &str, payload: serde_json::Value) -> Vec<u8> {
+        Message::new(payload.to_string())
+            .with_header(":event-type", EventHeader::String(event_type.to_string()))
+            .with_header(":content-type", EventHeader::String("application/json".to_string()))
+            .with_header(":message-type", EventHeader::String("event".to_string()))
+            .encode()
+    }
+
+    fn exception(kind: &str, message: &str) -> Vec<u8> {
+        Message::new(serde_json::json!({ "message": message }).to_string())
+            .with_header(":exception-type", EventHeader::String(kind.to_string()))
+            .with_header(":content-type", EventHeader::String("application/json".to_string()))
+            .with_header(":message-type", EventHeader::String("exception".to_string()))
+            .encode()
+    }
+
+    /// Recorded `ConverseStream` response, re-framed for replay
+    fn converse_frames() -> Vec<u8> {
+        [
+            event("messageStart", serde_json::json!({ "role": "assistant" })),
+            event(
+                "contentBlockDelta",
+                serde_json::json!({ "contentBlockIndex": 0, "delta": { "text": "Hello" } }),
+            ),
+            event(
+                "contentBlockDelta",
+                serde_json::json!({ "contentBlockIndex": 0, "delta": { "text": " there" } }),
+            ),
+            event("contentBlockStop", serde_json::json!({ "contentBlockIndex": 0 })),
+            event("messageStop", serde_json::json!({ "stopReason": "end_turn" })),
+            event(
+                "metadata",
+                serde_json::json!({
+                    "usage": { "inputTokens": 9, "outputTokens": 2, "totalTokens": 11 },
+                    "metrics": { "latencyMs": 312 }
+                }),
+            ),
+        ]
+        .concat()
+    }
+
+    fn test_request(model: &str) -> StreamingRequest {
+        StreamingRequest::builder()
+            .model(model)
+            .message(MessageRole::System, "Be brief")
+            .message(MessageRole::User, "Say hello")
+            .max_tokens(32)
+            .build()
+    }
+
+    fn mock_provider(server: &MockServer) -> BedrockProvider {
+        BedrockProvider::builder()
+            .credentials(AwsCredentials::new("AKIDEXAMPLE", "secret"))
+            .region("us-west-2")
+            .base_url(server.uri())
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_provider_name() {
+        let provider = BedrockProvider::new(AwsCredentials::new("AKID", "secret"), "us-east-1");
+        assert_eq!(provider.name(), "aws-bedrock");
+    }
+
+    #[test]
+    fn test_builder() {
+        let provider = BedrockProvider::builder()
+            .bearer_token("bedrock-api-key")
+            .region("eu-central-1")
+            .api(BedrockApi::InvokeModel)
+            .max_retries(5)
+            .build()
+            .unwrap();
+
+        assert_eq!(provider.region(), "eu-central-1");
+        assert_eq!(
+            provider.base_url,
+            "https://bedrock-runtime.eu-central-1.amazonaws.com"
+        );
+        assert_eq!(provider.control_url, "https://bedrock.eu-central-1.amazonaws.com");
+        assert_eq!(provider.api, BedrockApi::InvokeModel);
+        assert_eq!(provider.max_retries, 5);
+        assert!(matches!(provider.auth, BedrockAuth::Bearer(_)));
+    }
+
+    #[test]
+    fn test_model_url_encodes_model_id() {
+        let provider = BedrockProvider::new(AwsCredentials::new("AKID", "secret"), "us-east-1");
+        let url = provider.model_url(MODEL, "converse-stream").unwrap();
+
+        assert_eq!(
+            url.as_str(),
+            "https://bedrock-runtime.us-east-1.amazonaws.com/model/anthropic.claude-3-5-haiku-20241022-v1%3A0/converse-stream"
+        );
+    }
+
+    #[test]
+    fn test_validate_model() {
+        let provider = BedrockProvider::new(AwsCredentials::new("AKID", "secret"), "us-east-1");
+
+        assert!(provider.validate_model(MODEL).is_ok());
+        assert!(provider
+            .validate_model("us.anthropic.claude-3-5-haiku-20241022-v1:0")
+            .is_ok());
+        assert!(provider.validate_model("invalid-model").is_err());
+    }
+
+    #[test]
+    fn test_calculate_cost() {
+        let provider = BedrockProvider::new(AwsCredentials::new("AKID", "secret"), "us-east-1");
+
+        // Claude 3.5 Haiku: $0.80/1M input, $4.00/1M output
+        let cost = provider
+            .calculate_cost("us.anthropic.claude-3-5-haiku-20241022-v1:0", 1000, 1000)
+            .unwrap();
+        // Input: 0.001 * 0.80 = 0.0008
+        // Output: 0.001 * 4.0 = 0.004
+        assert!((cost - 0.0048).abs() < 0.0001);
+
+        assert!(provider.calculate_cost("unknown-model", 1000, 1000).is_none());
+    }
+
+    #[test]
+    fn test_invoke_payload_requires_anthropic() {
+        assert!(BedrockProvider::invoke_payload(&test_request(MODEL)).is_ok());
+        assert!(matches!(
+            BedrockProvider::invoke_payload(&test_request("amazon.nova-lite-v1:0")),
+            Err(ProviderError::ConfigError(_))
+        ));
+    }
+
+    #[test]
+    fn test_map_bedrock_error() {
+        assert!(matches!(
+            map_bedrock_error(
+                "ThrottlingException:http://internal.amazon.com/coral/com.amazon.bedrock/",
+                "Too many requests".to_string(),
+                Some(429)
+            ),
+            ProviderError::RateLimitError { .. }
+        ));
+        assert!(matches!(
+            map_bedrock_error("accessDeniedException", "denied".to_string(), None),
+            ProviderError::AuthenticationError(_)
+        ));
+        assert!(map_bedrock_error("internalServerException", "oops".to_string(), None)
+            .is_retryable());
+        assert!(matches!(
+            map_bedrock_error("validationException", "bad".to_string(), Some(400)),
+            ProviderError::ApiError { status_code: 400, .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_converse_stream() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path(
+                "/model/anthropic.claude-3-5-haiku-20241022-v1%3A0/converse-stream",
+            ))
+            .and(header_exists("x-amz-date"))
+            .and(header("content-type", "application/json"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_raw(converse_frames(), "application/vnd.amazon.eventstream"),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        let result = provider
+            .complete(test_request(MODEL), &TimingEngine::new())
+            .await
+            .unwrap();
+
+        assert_eq!(result.content, "Hello there");
+        assert_eq!(result.token_events.len(), 2);
+        assert!(result.token_events[1].inter_token_latency.is_some());
+        assert_eq!(result.metadata.input_tokens, Some(9));
+        assert_eq!(result.metadata.output_tokens, Some(2));
+
+        let requests = server.received_requests().await.unwrap();
+        let authorization = requests[0].headers.get("authorization").unwrap();
+        assert!(authorization
+            .to_str()
+            .unwrap()
+            .starts_with("AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/"));
+        let body: serde_json::Value = serde_json::from_slice(&requests[0].body).unwrap();
+        assert_eq!(body["system"][0]["text"], "Be brief");
+        assert_eq!(body["inferenceConfig"]["maxTokens"], 32);
+    }
+
+    #[tokio::test]
+    async fn test_converse_non_streaming() {
+        let server = MockServer::start().await;
+        let body = serde_json::json!({
+            "output": { "message": { "role": "assistant", "content": [{ "text": "Hello there" }] } },
+            "stopReason": "end_turn",
+            "usage": { "inputTokens": 9, "outputTokens": 2, "totalTokens": 11 },
+            "metrics": { "latencyMs": 310 }
+        });
+
+        Mock::given(method("POST"))
+            .and(path("/model/anthropic.claude-3-5-haiku-20241022-v1%3A0/converse"))
+            .and(header_exists("x-amz-date"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(body))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        let result = provider
+            .complete_non_streaming(test_request(MODEL), &TimingEngine::new())
+            .await
+            .unwrap();
+
+        assert_eq!(result.content, "Hello there");
+        assert!(result.token_events.is_empty());
+        assert!(!result.is_streamed());
+        assert_eq!(result.metadata.input_tokens, Some(9));
+        assert_eq!(result.metadata.output_tokens, Some(2));
+        assert!(result.metadata.estimated_cost.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_invoke_model_stream() {
+        let server = MockServer::start().await;
+        let chunk = |payload: serde_json::Value| {
+            let bytes = base64::engine::general_purpose::STANDARD.encode(payload.to_string());
+            event("chunk", serde_json::json!({ "bytes": bytes }))
+        };
+        let frames = [
+            chunk(serde_json::json!({
+                "type": "message_start",
+                "message": { "usage": { "input_tokens": 12, "output_tokens": 1 } }
+            })),
+            chunk(serde_json::json!({
+                "type": "content_block_delta", "index": 0,
+                "delta": { "type": "text_delta", "text": "Hi" }
+            })),
+            chunk(serde_json::json!({
+                "type": "message_delta",
+                "delta": { "stop_reason": "end_turn" },
+                "usage": { "output_tokens": 3 }
+            })),
+            chunk(serde_json::json!({
+                "type": "message_stop",
+                "amazon-bedrock-invocationMetrics": {
+                    "inputTokenCount": 12, "outputTokenCount": 3,
+                    "invocationLatency": 420, "firstByteLatency": 210
+                }
+            })),
+        ]
+        .concat();
+
+        Mock::given(method("POST"))
+            .and(path(
+                "/model/anthropic.claude-3-5-haiku-20241022-v1%3A0/invoke-with-response-stream",
+            ))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_raw(frames, "application/vnd.amazon.eventstream"),
+            )
+            .mount(&server)
+            .await;
+
+        let provider = BedrockProvider::builder()
+            .bearer_token("bedrock-api-key")
+            .base_url(server.uri())
+            .api(BedrockApi::InvokeModel)
+            .build()
+            .unwrap();
+        let result = provider
+            .complete(test_request(MODEL), &TimingEngine::new())
+            .await
+            .unwrap();
+
+        assert_eq!(result.content, "Hi");
+        assert_eq!(result.metadata.input_tokens, Some(12));
+        assert_eq!(result.metadata.output_tokens, Some(3));
+
+        let requests = server.received_requests().await.unwrap();
+        assert_eq!(
+            requests[0].headers.get("authorization").unwrap(),
+            "Bearer bedrock-api-key"
+        );
+        let body: serde_json::Value = serde_json::from_slice(&requests[0].body).unwrap();
+        assert_eq!(body["anthropic_version"], ANTHROPIC_BEDROCK_VERSION);
+    }
+
+    #[tokio::test]
+    async fn test_stream_exception_event() {
+        let server = MockServer::start().await;
+        let frames = [
+            event(
+                "contentBlockDelta",
+                serde_json::json!({ "contentBlockIndex": 0, "delta": { "text": "Hel" } }),
+            ),
+            exception("throttlingException", "Too many tokens, please wait"),
+        ]
+        .concat();
+
+        Mock::given(method("POST"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_raw(frames, "application/vnd.amazon.eventstream"),
+            )
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        let err = provider
+            .complete(test_request(MODEL), &TimingEngine::new())
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, ProviderError::RateLimitError { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_stream_truncated_frame() {
+        let server = MockServer::start().await;
+        let mut frames = converse_frames();
+        frames.truncate(frames.len() - 10);
+
+        Mock::given(method("POST"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_raw(frames, "application/vnd.amazon.eventstream"),
+            )
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        let err = provider
+            .complete(test_request(MODEL), &TimingEngine::new())
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, ProviderError::StreamingError(_)));
+    }
+
+    #[tokio::test]
+    async fn test_stream_error_status() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .respond_with(
+                ResponseTemplate::new(403)
+                    .insert_header(
+                        "x-amzn-ErrorType",
+                        "AccessDeniedException:http://internal.amazon.com/coral/com.amazon.bedrock/",
+                    )
+                    .set_body_string(r#"{"message":"You don't have access to the model"}"#),
+            )
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        let result = provider.stream(test_request(MODEL), &TimingEngine::new()).await;
+
+        match result {
+            Err(ProviderError::AuthenticationError(message)) => {
+                assert_eq!(message, "You don't have access to the model");
+            }
+            Err(e) => panic!("unexpected error: {:?}", e),
+            Ok(_) => panic!("expected an error"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_health_check() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/foundation-models"))
+            .and(header_exists("authorization"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_string(r#"{"modelSummaries": []}"#),
+            )
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        assert!(provider.health_check().await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_discover_models_accepts_new_models() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/foundation-models"))
+            .and(header_exists("authorization"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"{"modelSummaries": [
+                    {"modelId": "amazon.nova-premier-v1:0", "outputModalities": ["TEXT"]},
+                    {"modelId": "anthropic.claude-3-haiku-20240307-v1:0"}
+                ]}"#,
+            ))
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        assert!(provider
+            .validate_model("us.amazon.nova-premier-v1:0")
+            .is_err());
+
+        let models = provider.discover_models().await.unwrap();
+        assert_eq!(
+            models,
+            vec![
+                "amazon.nova-premier-v1:0",
+                "anthropic.claude-3-haiku-20240307-v1:0"
+            ]
+        );
+        assert!(provider
+            .validate_model("us.amazon.nova-premier-v1:0")
+            .is_ok());
+        assert!(provider.validate_model("amazon.nova-lite-v1:0").is_ok());
+    }
+}
@@ -0,0 +1,90 @@
+//! Non-streaming (`stream: false`) response bodies
+//!
+//! A non-streaming response arrives as a single JSON body. Adapters read it to
+//! the end and parse it in two separate steps so the download and the parse
+//! can be timed on their own. Nothing in the response is usable until the
+//! body is parsed, so that point stands in for the time to first token.
+
+use crate::error::{ProviderError, Result};
+use llm_latency_lens_core::{Clock, Timestamp};
+use serde::de::DeserializeOwned;
+use std::time::Duration;
+
+/// Size and timing of a non-streaming response body
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BodyStats {
+    /// Body size in bytes
+    pub size_bytes: u64,
+    /// Time from the response headers to the last body byte
+    pub download_time: Duration,
+    /// Time spent parsing the body
+    pub parse_time: Duration,
+    /// Time from the request start until the body was parsed
+    pub time_to_parsed: Duration,
+}
+
+/// Read a complete response body and parse it as JSON
+///
+/// Call right after the response headers arrive; `request_start` is the
+/// start of the request's timing measurement.
+pub async fn read_json<T: DeserializeOwned>(
+    response: reqwest::Response,
+    clock: &Clock,
+    request_start: Timestamp,
+) -> Result<(T, BodyStats)> {
+    let headers_at = clock.now();
+    let bytes = response.bytes().await.map_err(ProviderError::from_reqwest)?;
+    let downloaded_at = clock.now();
+
+    let body = serde_json::from_slice(&bytes).map_err(ProviderError::from_json_error)?;
+    let parsed_at = clock.now();
+
+    let stats = BodyStats {
+        size_bytes: bytes.len() as u64,
+        download_time: downloaded_at.duration_since(headers_at),
+        parse_time: parsed_at.duration_since(downloaded_at),
+        time_to_parsed: parsed_at.duration_since(request_start),
+    };
+    Ok((body, stats))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::method;
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn test_read_json() {
+        let server = MockServer::start().await;
+        let body = r#"{"text":"hello"}"#;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(body))
+            .mount(&server)
+            .await;
+
+        let clock = Clock::new();
+        let start = clock.now();
+        let response = reqwest::get(server.uri()).await.unwrap();
+        let (value, stats): (serde_json::Value, _) =
+            read_json(response, &clock, start).await.unwrap();
+
+        assert_eq!(value["text"], "hello");
+        assert_eq!(stats.size_bytes, body.len() as u64);
+        assert!(stats.time_to_parsed >= stats.download_time + stats.parse_time);
+    }
+
+    #[tokio::test]
+    async fn test_read_json_invalid() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("not json"))
+            .mount(&server)
+            .await;
+
+        let clock = Clock::new();
+        let response = reqwest::get(server.uri()).await.unwrap();
+        let result = read_json::<serde_json::Value>(response, &clock, clock.now()).await;
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,246 @@
+//! Embeddings requests
+//!
+//! Embedding calls return one vector per input in a single JSON body, so they
+//! are timed like non-streaming completions: the result records the network
+//! phases and the body size, download and parse time (see [`BodyStats`]).
+//!
+//! Providers with an embeddings endpoint implement [`EmbeddingProvider`] on
+//! top of [`Provider`].
+
+use crate::body::BodyStats;
+use crate::error::{ProviderError, Result};
+use crate::network::NetworkTimings;
+use crate::retry::RetryStats;
+use crate::traits::{Provider, ResponseMetadata};
+use async_trait::async_trait;
+use llm_latency_lens_core::{RequestId, SessionId, TimingEngine};
+use std::time::Duration;
+
+/// Configuration for an embeddings request
+#[derive(Debug, Clone)]
+pub struct EmbeddingRequest {
+    /// Unique identifier for this request
+    pub request_id: RequestId,
+    /// Session this request belongs to
+    pub session_id: SessionId,
+    /// Embedding model
+    pub model: String,
+    /// Texts to embed, sent as one batch
+    pub inputs: Vec<String>,
+    /// Requested vector size, for models that can shorten their output
+    pub dimensions: Option<u32>,
+    /// Response headers to redact in addition to
+    /// [`DEFAULT_REDACTED_HEADERS`](crate::headers::DEFAULT_REDACTED_HEADERS)
+    pub redact_headers: Vec<String>,
+}
+
+impl EmbeddingRequest {
+    /// Create a new request builder
+    pub fn builder() -> EmbeddingRequestBuilder {
+        EmbeddingRequestBuilder::default()
+    }
+}
+
+/// Builder for embeddings requests
+#[derive(Default)]
+pub struct EmbeddingRequestBuilder {
+    request_id: Option<RequestId>,
+    session_id: Option<SessionId>,
+    model: Option<String>,
+    inputs: Vec<String>,
+    dimensions: Option<u32>,
+    redact_headers: Vec<String>,
+}
+
+impl EmbeddingRequestBuilder {
+    /// Set the request ID
+    pub fn request_id(mut self, id: RequestId) -> Self {
+        self.request_id = Some(id);
+        self
+    }
+
+    /// Set the session ID
+    pub fn session_id(mut self, id: SessionId) -> Self {
+        self.session_id = Some(id);
+        self
+    }
+
+    /// Set the model
+    pub fn model(mut self, model: impl Into<String>) -> Self {
+        self.model = Some(model.into());
+        self
+    }
+
+    /// Add an input text
+    pub fn input(mut self, input: impl Into<String>) -> Self {
+        self.inputs.push(input.into());
+        self
+    }
+
+    /// Add several input texts
+    pub fn inputs(mut self, inputs: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.inputs.extend(inputs.into_iter().map(Into::into));
+        self
+    }
+
+    /// Set the requested vector size
+    pub fn dimensions(mut self, dimensions: u32) -> Self {
+        self.dimensions = Some(dimensions);
+        self
+    }
+
+    /// Redact a response header in the recorded metadata
+    pub fn redact_header(mut self, name: impl Into<String>) -> Self {
+        self.redact_headers.push(name.into());
+        self
+    }
+
+    /// Build the request
+    pub fn build(self) -> EmbeddingRequest {
+        EmbeddingRequest {
+            request_id: self.request_id.unwrap_or_default(),
+            session_id: self.session_id.unwrap_or_default(),
+            model: self.model.expect("model is required"),
+            inputs: self.inputs,
+            dimensions: self.dimensions,
+            redact_headers: self.redact_headers,
+        }
+    }
+}
+
+/// Result of an embeddings request
+#[derive(Debug, Clone)]
+pub struct EmbeddingResult {
+    /// Request ID
+    pub request_id: RequestId,
+    /// Number of inputs sent
+    pub input_count: usize,
+    /// Returned vectors, in input order
+    pub embeddings: Vec<Vec<f32>>,
+    /// Response metadata (input tokens, cost, headers, server signals)
+    pub metadata: ResponseMetadata,
+    /// Network-phase timings (DNS, TCP connect, TLS, first byte)
+    pub network: NetworkTimings,
+    /// Retries of the request and the backoff between them
+    pub retries: RetryStats,
+    /// Body size, download and parse time
+    pub body: BodyStats,
+}
+
+impl EmbeddingResult {
+    /// Number of vectors returned
+    pub fn vector_count(&self) -> usize {
+        self.embeddings.len()
+    }
+
+    /// Size of the returned vectors (None when no vector was returned)
+    pub fn dimensions(&self) -> Option<usize> {
+        self.embeddings.first().map(Vec::len)
+    }
+
+    /// Time from the request start until the response was parsed
+    pub fn latency(&self) -> Duration {
+        self.body.time_to_parsed
+    }
+
+    /// Vectors returned per second of latency
+    pub fn vectors_per_second(&self) -> Option<f64> {
+        let secs = self.latency().as_secs_f64();
+        (secs > 0.0).then(|| self.vector_count() as f64 / secs)
+    }
+}
+
+/// Providers with an embeddings endpoint
+#[async_trait]
+pub trait EmbeddingProvider: Provider {
+    /// Embed a batch of inputs
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The request configuration
+    /// * `timing_engine` - Timing engine for high-precision measurements
+    ///
+    /// # Returns
+    ///
+    /// An `EmbeddingResult` with the vectors and timing data
+    async fn embed(
+        &self,
+        request: EmbeddingRequest,
+        timing_engine: &TimingEngine,
+    ) -> Result<EmbeddingResult>;
+
+    /// Calculate the cost of an embeddings request
+    ///
+    /// Returns None if pricing is not available for the model.
+    fn calculate_embedding_cost(&self, model: &str, input_tokens: u64) -> Option<f64>;
+
+    /// Get the embedding models known to this provider
+    ///
+    /// An empty list accepts any model.
+    fn supported_embedding_models(&self) -> Vec<String>;
+
+    /// Validate an embedding model name
+    fn validate_embedding_model(&self, model: &str) -> Result<()> {
+        let supported = self.supported_embedding_models();
+        if supported.is_empty() || supported.contains(&model.to_string()) {
+            Ok(())
+        } else {
+            Err(ProviderError::InvalidModel(format!(
+                "Embedding model '{}' is not supported by {}. Supported models: {}",
+                model,
+                self.name(),
+                supported.join(", ")
+            )))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::headers::ServerSignals;
+
+    #[test]
+    fn test_request_builder() {
+        let request = EmbeddingRequest::builder()
+            .model("text-embedding-3-small")
+            .input("first")
+            .inputs(["second", "third"])
+            .dimensions(256)
+            .build();
+
+        assert_eq!(request.model, "text-embedding-3-small");
+        assert_eq!(request.inputs, vec!["first", "second", "third"]);
+        assert_eq!(request.dimensions, Some(256));
+    }
+
+    #[test]
+    fn test_result_rates() {
+        let result = EmbeddingResult {
+            request_id: RequestId::new(),
+            input_count: 4,
+            embeddings: vec![vec![0.0; 8]; 4],
+            metadata: ResponseMetadata {
+                model: "text-embedding-3-small".to_string(),
+                input_tokens: Some(40),
+                output_tokens: None,
+                thinking_tokens: None,
+                cache_creation_tokens: None,
+                cache_read_tokens: None,
+                estimated_cost: None,
+                headers: Vec::new(),
+                server: ServerSignals::default(),
+            },
+            network: NetworkTimings::default(),
+            retries: RetryStats::default(),
+            body: BodyStats {
+                time_to_parsed: Duration::from_millis(200),
+                ..BodyStats::default()
+            },
+        };
+
+        assert_eq!(result.vector_count(), 4);
+        assert_eq!(result.dimensions(), Some(8));
+        assert_eq!(result.vectors_per_second(), Some(20.0));
+    }
+}
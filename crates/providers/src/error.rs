@@ -225,6 +225,14 @@ pub async fn parse_api_error(response: reqwest::Response) -> ProviderError {
         }
     };
 
+    error_from_body(status_code, body)
+}
+
+/// Build a provider error from an error status code and response body
+///
+/// Used by [`parse_api_error`], and by adapters that inspect the body for
+/// provider-specific errors before falling back to the common mapping.
+pub fn error_from_body(status_code: u16, body: String) -> ProviderError {
     // Handle specific status codes
     match status_code {
         401 => ProviderError::AuthenticationError(
@@ -243,7 +251,12 @@ pub async fn parse_api_error(response: reqwest::Response) -> ProviderError {
         503 => ProviderError::ServiceUnavailable(
             extract_error_message(&body).unwrap_or_else(|| "Service unavailable".to_string()),
         ),
-        _ => ProviderError::api_error_with_body(status_code, status.to_string(), body),
+        _ => {
+            let status = reqwest::StatusCode::from_u16(status_code)
+                .map(|s| s.to_string())
+                .unwrap_or_else(|_| status_code.to_string());
+            ProviderError::api_error_with_body(status_code, status, body)
+        }
     }
 }
 
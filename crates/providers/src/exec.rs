@@ -0,0 +1,619 @@
+//! External process provider
+//!
+//! Runs a user-supplied command for each request, so backends without a
+//! built-in adapter (internal gateways, custom SDKs) can be benchmarked
+//! without changing this crate. The protocol:
+//!
+//! - The request is written to the command's stdin as one line of JSON
+//!   ([`ExecRequest`]), then stdin is closed.
+//! - The command writes newline-delimited JSON events to stdout:
+//!
+//! ```text
+//! {"type":"token","text":"Hel"}
+//! {"type":"token","text":"thinking...","kind":"thinking"}
+//! {"type":"usage","input_tokens":12,"output_tokens":40}
+//! {"type":"error","message":"upstream overloaded","status":503}
+//! ```
+//!
+//!   `kind` is `content` (default), `thinking` or `tool_call`. Usage fields
+//!   are optional and merged across events. An `error` event fails the
+//!   request; with a `status` it is reported as an API error with that
+//!   status, so 429 and 5xx are retryable. Unknown event types are ignored.
+//! - The stream ends when stdout closes. A non-zero exit status fails the
+//!   request with the command's stderr.
+//!
+//! Events are timestamped at the arrival of the stdout read that completed
+//! them, like SSE events. The first stdout read stands in for the first byte;
+//! the process start is included in every timing.
+//!
+//! The API key, when set, is passed to the command in `LLM_LENS_API_KEY`.
+
+use crate::error::{ProviderError, Result};
+use crate::headers::ServerSignals;
+use crate::network::NetworkProbe;
+use crate::traits::{
+    CompletionResult, Message, Provider, ResponseMetadata, SharedUsage, StreamingRequest,
+    StreamingResponse, Tool, ToolChoice,
+};
+use async_trait::async_trait;
+use futures::StreamExt;
+use llm_latency_lens_core::{Clock, RequestId, TimingEngine, Timestamp, TokenEvent, TokenKind};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::process::Stdio;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::process::{Child, ChildStdout, Command};
+use tokio::task::JoinHandle;
+
+/// Environment variable carrying the API key to the command
+pub const API_KEY_ENV: &str = "LLM_LENS_API_KEY";
+
+/// Size of each stdout read
+const READ_BUFFER_SIZE: usize = 8 * 1024;
+
+/// External process provider adapter
+pub struct ExecProvider {
+    /// Program to run
+    program: String,
+    /// Program arguments
+    args: Vec<String>,
+    /// Extra environment variables for the command
+    env: Vec<(String, String)>,
+}
+
+impl ExecProvider {
+    /// Create a provider from a command line
+    ///
+    /// The command line is split on whitespace; quoting is not supported.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use llm_latency_lens_providers::exec::ExecProvider;
+    ///
+    /// # fn example() -> Result<(), llm_latency_lens_providers::ProviderError> {
+    /// let provider = ExecProvider::from_command_line("python3 gateway_client.py")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_command_line(command: &str) -> Result<Self> {
+        Self::builder().command_line(command).build()
+    }
+
+    /// Create a provider with custom configuration
+    pub fn builder() -> ExecProviderBuilder {
+        ExecProviderBuilder::default()
+    }
+
+    /// Start the command with piped stdio
+    fn spawn(&self) -> Result<Child> {
+        Command::new(&self.program)
+            .args(&self.args)
+            .envs(self.env.iter().map(|(k, v)| (k, v)))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| {
+                ProviderError::ConfigError(format!("Failed to start '{}': {}", self.program, e))
+            })
+    }
+}
+
+/// Builder for the exec provider
+#[derive(Default)]
+pub struct ExecProviderBuilder {
+    program: Option<String>,
+    args: Vec<String>,
+    env: Vec<(String, String)>,
+}
+
+impl ExecProviderBuilder {
+    /// Set the program to run
+    pub fn program(mut self, program: impl Into<String>) -> Self {
+        self.program = Some(program.into());
+        self
+    }
+
+    /// Set the program and its arguments from a whitespace-separated
+    /// command line
+    pub fn command_line(mut self, command: &str) -> Self {
+        let mut parts = command.split_whitespace();
+        self.program = parts.next().map(str::to_string);
+        self.args = parts.map(str::to_string).collect();
+        self
+    }
+
+    /// Add a program argument
+    pub fn arg(mut self, arg: impl Into<String>) -> Self {
+        self.args.push(arg.into());
+        self
+    }
+
+    /// Add several program arguments
+    pub fn args(mut self, args: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.args.extend(args.into_iter().map(Into::into));
+        self
+    }
+
+    /// Set an environment variable for the command
+    pub fn env(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.env.push((name.into(), value.into()));
+        self
+    }
+
+    /// Build the provider
+    ///
+    /// Fails when no program is set.
+    pub fn build(self) -> Result<ExecProvider> {
+        let program = self
+            .program
+            .filter(|p| !p.is_empty())
+            .ok_or_else(|| ProviderError::ConfigError("Exec provider needs a command".to_string()))?;
+        Ok(ExecProvider {
+            program,
+            args: self.args,
+            env: self.env,
+        })
+    }
+}
+
+#[async_trait]
+impl Provider for ExecProvider {
+    fn name(&self) -> &'static str {
+        "exec"
+    }
+
+    fn provider_kind(&self) -> llm_latency_lens_core::Provider {
+        llm_latency_lens_core::Provider::Exec
+    }
+
+    async fn health_check(&self) -> Result<()> {
+        // The command can only be checked by starting it
+        let mut child = self.spawn()?;
+        let _ = child.kill().await;
+        Ok(())
+    }
+
+    async fn stream(
+        &self,
+        request: StreamingRequest,
+        timing_engine: &TimingEngine,
+    ) -> Result<StreamingResponse> {
+        // Validate model
+        self.validate_model(&request.model)?;
+
+        // Start timing measurement
+        let mut timing = timing_engine.start();
+        let network = NetworkProbe::new(timing_engine.clock());
+        timing.checkpoint("request_start");
+
+        let mut child = self.spawn()?;
+        timing.checkpoint("process_started");
+
+        // Send the request and close stdin
+        let mut payload =
+            serde_json::to_vec(&ExecRequest::new(&request)).map_err(ProviderError::from_json_error)?;
+        payload.push(b'\n');
+        let mut stdin = child.stdin.take().expect("stdin is piped");
+        stdin
+            .write_all(&payload)
+            .await
+            .map_err(|e| ProviderError::streaming(format!("Failed to send the request: {}", e)))?;
+        drop(stdin);
+
+        timing.checkpoint("request_sent");
+
+        let stdout = child.stdout.take().expect("stdout is piped");
+        let mut stderr = child.stderr.take().expect("stderr is piped");
+        let stderr = tokio::spawn(async move {
+            let mut output = Vec::new();
+            let _ = stderr.read_to_end(&mut output).await;
+            String::from_utf8_lossy(&output).into_owned()
+        });
+
+        let usage = SharedUsage::new();
+        let state = ExecState {
+            program: self.program.clone(),
+            child,
+            stdout,
+            stderr: Some(stderr),
+            pending: Vec::new(),
+            lines: VecDeque::new(),
+            clock: timing_engine.clock().clone(),
+            network: network.clone(),
+            usage: usage.clone(),
+            request_id: request.request_id,
+            request_start: timing.start_time(),
+            sequence: 0,
+            last_token_time: None,
+            finished: false,
+        };
+        let token_stream = futures::stream::unfold(state, next_token).boxed();
+
+        Ok(StreamingResponse {
+            request_id: request.request_id,
+            token_stream: Box::pin(token_stream),
+            metadata: ResponseMetadata {
+                model: request.model,
+                input_tokens: None, // Reported in usage events
+                output_tokens: None,
+                thinking_tokens: None,
+                cache_creation_tokens: None,
+                cache_read_tokens: None,
+                estimated_cost: None,
+                headers: Vec::new(),
+                server: ServerSignals::default(),
+            },
+            usage,
+            network,
+        })
+    }
+
+    async fn complete_non_streaming(
+        &self,
+        _request: StreamingRequest,
+        _timing_engine: &TimingEngine,
+    ) -> Result<CompletionResult> {
+        Err(ProviderError::ConfigError(
+            "The exec provider has no non-streaming mode".to_string(),
+        ))
+    }
+
+    fn supported_models(&self) -> Vec<String> {
+        // Any model name is passed through to the command
+        Vec::new()
+    }
+}
+
+/// State of a running command's output
+struct ExecState {
+    program: String,
+    child: Child,
+    stdout: ChildStdout,
+    stderr: Option<JoinHandle<String>>,
+    /// Bytes of an incomplete line
+    pending: Vec<u8>,
+    /// Complete lines with the arrival time of the read that completed them
+    lines: VecDeque<(Timestamp, Vec<u8>)>,
+    clock: Clock,
+    network: NetworkProbe,
+    usage: SharedUsage,
+    request_id: RequestId,
+    request_start: Timestamp,
+    sequence: u64,
+    last_token_time: Option<Timestamp>,
+    finished: bool,
+}
+
+impl ExecState {
+    /// Read stdout until at least one complete line is queued
+    ///
+    /// Returns false at the end of stdout.
+    async fn fill(&mut self) -> std::io::Result<bool> {
+        let mut buf = [0u8; READ_BUFFER_SIZE];
+        while self.lines.is_empty() {
+            let n = self.stdout.read(&mut buf).await?;
+            let now = self.clock.now();
+            if n == 0 {
+                // A last line without a trailing newline
+                if !self.pending.is_empty() {
+                    self.lines.push_back((now, std::mem::take(&mut self.pending)));
+                    self.network.first_event_at(now);
+                    self.network.record_read(1);
+                    return Ok(true);
+                }
+                return Ok(false);
+            }
+            self.network.first_byte();
+
+            self.pending.extend_from_slice(&buf[..n]);
+            let mut completed = 0;
+            while let Some(end) = self.pending.iter().position(|b| *b == b'\n') {
+                let line: Vec<u8> = self.pending.drain(..=end).collect();
+                self.lines.push_back((now, line));
+                completed += 1;
+            }
+            if completed > 0 {
+                self.network.first_event_at(now);
+            }
+            self.network.record_read(completed);
+        }
+        Ok(true)
+    }
+
+    /// Wait for the command to exit, failing on a non-zero status
+    async fn finish(&mut self) -> Result<()> {
+        let status = self
+            .child
+            .wait()
+            .await
+            .map_err(|e| ProviderError::streaming(format!("Failed to wait for '{}': {}", self.program, e)))?;
+        if status.success() {
+            return Ok(());
+        }
+        let stderr = match self.stderr.take() {
+            Some(task) => task.await.unwrap_or_default(),
+            None => String::new(),
+        };
+        Err(ProviderError::streaming(format!(
+            "'{}' exited with {}: {}",
+            self.program,
+            status,
+            stderr.trim()
+        )))
+    }
+}
+
+/// Read events until the next token, the end of the output or an error
+async fn next_token(mut state: ExecState) -> Option<(Result<TokenEvent>, ExecState)> {
+    if state.finished {
+        return None;
+    }
+
+    loop {
+        match state.fill().await {
+            Ok(true) => {}
+            Ok(false) => {
+                state.finished = true;
+                return match state.finish().await {
+                    Ok(()) => None,
+                    Err(e) => Some((Err(e), state)),
+                };
+            }
+            Err(e) => {
+                state.finished = true;
+                let error = ProviderError::streaming(format!("Failed to read output: {}", e));
+                return Some((Err(error), state));
+            }
+        }
+
+        let (now, line) = state.lines.pop_front().expect("fill queued a line");
+        let line = String::from_utf8_lossy(&line);
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let event = match serde_json::from_str::<ExecEvent>(line.trim()) {
+            Ok(event) => event,
+            Err(e) => {
+                state.finished = true;
+                return Some((Err(ProviderError::from_json_error(e)), state));
+            }
+        };
+        let (kind, content) = match event {
+            ExecEvent::Token { text, kind } => (kind, text),
+            ExecEvent::Usage(reported) => {
+                state.usage.update(|u| {
+                    u.input_tokens = reported.input_tokens.or(u.input_tokens);
+                    u.output_tokens = reported.output_tokens.or(u.output_tokens);
+                    u.thinking_tokens = reported.thinking_tokens.or(u.thinking_tokens);
+                    u.cache_creation_tokens =
+                        reported.cache_creation_tokens.or(u.cache_creation_tokens);
+                    u.cache_read_tokens = reported.cache_read_tokens.or(u.cache_read_tokens);
+                });
+                continue;
+            }
+            ExecEvent::Error { message, status } => {
+                state.finished = true;
+                let error = match status {
+                    Some(status) => ProviderError::api_error(status, message),
+                    None => ProviderError::streaming(message),
+                };
+                return Some((Err(error), state));
+            }
+            ExecEvent::Other => continue,
+        };
+
+        let time_since_start = now.duration_since(state.request_start);
+        let inter_token_latency = state.last_token_time.map(|t| now.duration_since(t));
+        state.last_token_time = Some(now);
+
+        let event = TokenEvent {
+            request_id: state.request_id,
+            sequence: state.sequence,
+            kind,
+            content: Some(content),
+            timestamp_nanos: now.as_nanos(),
+            time_since_start,
+            inter_token_latency,
+        };
+        state.sequence += 1;
+
+        return Some((Ok(event), state));
+    }
+}
+
+/// Request written to the command's stdin
+#[derive(Debug, Serialize)]
+pub struct ExecRequest<'a> {
+    /// Request ID
+    pub request_id: RequestId,
+    /// Model name, passed through unchanged
+    pub model: &'a str,
+    /// Conversation messages
+    pub messages: &'a [Message],
+    /// Maximum tokens to generate
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u32>,
+    /// Sampling temperature
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+    /// Top-p sampling parameter
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f32>,
+    /// Stop sequences
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stop: Option<&'a [String]>,
+    /// Token budget for extended thinking
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thinking_budget: Option<u32>,
+    /// Tools the model may call
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    pub tools: &'a [Tool],
+    /// `auto`, `none`, `required` or a tool name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_choice: Option<String>,
+}
+
+impl<'a> ExecRequest<'a> {
+    /// Wire form of a streaming request
+    pub fn new(request: &'a StreamingRequest) -> Self {
+        Self {
+            request_id: request.request_id,
+            model: &request.model,
+            messages: &request.messages,
+            max_tokens: request.max_tokens,
+            temperature: request.temperature,
+            top_p: request.top_p,
+            stop: request.stop.as_deref(),
+            thinking_budget: request.thinking_budget,
+            tools: &request.tools,
+            tool_choice: request.tool_choice.as_ref().map(|choice| match choice {
+                ToolChoice::Auto => "auto".to_string(),
+                ToolChoice::None => "none".to_string(),
+                ToolChoice::Required => "required".to_string(),
+                ToolChoice::Tool(name) => name.clone(),
+            }),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ExecEvent {
+    Token {
+        text: String,
+        #[serde(default)]
+        kind: TokenKind,
+    },
+    Usage(ReportedUsage),
+    Error {
+        message: String,
+        #[serde(default)]
+        status: Option<u16>,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReportedUsage {
+    #[serde(default)]
+    input_tokens: Option<u64>,
+    #[serde(default)]
+    output_tokens: Option<u64>,
+    #[serde(default)]
+    thinking_tokens: Option<u64>,
+    #[serde(default)]
+    cache_creation_tokens: Option<u64>,
+    #[serde(default)]
+    cache_read_tokens: Option<u64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::MessageRole;
+    use std::time::Duration;
+
+    /// Provider running `script` with `sh -c`; `$0` is `arg`
+    fn sh(script: &str, arg: &str) -> ExecProvider {
+        ExecProvider::builder()
+            .program("sh")
+            .args(["-c", script, arg])
+            .build()
+            .unwrap()
+    }
+
+    fn request() -> StreamingRequest {
+        StreamingRequest::builder()
+            .model("internal-gateway")
+            .message(MessageRole::User, "Hi")
+            .max_tokens(16)
+            .build()
+    }
+
+    #[tokio::test]
+    async fn test_stream_events() {
+        let request_file = std::env::temp_dir().join(format!("exec-request-{}.json", std::process::id()));
+        let provider = sh(
+            r#"cat > "$0"
+printf '{"type":"token","text":"Hmm","kind":"thinking"}\n{"type":"token","text":"4"}\n'
+sleep 0.1
+printf '{"type":"heartbeat"}\n{"type":"token","text":"2"}\n'
+printf '{"type":"usage","input_tokens":12}\n{"type":"usage","output_tokens":2}'"#,
+            request_file.to_str().unwrap(),
+        );
+
+        let result = provider.complete(request(), &TimingEngine::new()).await.unwrap();
+
+        assert_eq!(result.thinking, "Hmm");
+        assert_eq!(result.content, "42");
+        assert_eq!(result.token_events.len(), 3);
+        assert_eq!(result.token_events[0].kind, TokenKind::Thinking);
+        // Events are timed at the read that delivered them
+        assert!(result.token_events[2].inter_token_latency.unwrap() >= Duration::from_millis(100));
+        assert_eq!(result.metadata.input_tokens, Some(12));
+        assert_eq!(result.metadata.output_tokens, Some(2));
+        assert!(result.network.time_to_first_byte.is_some());
+        assert!(result.reads.max_events_per_read >= 2);
+
+        let sent: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&request_file).unwrap()).unwrap();
+        std::fs::remove_file(&request_file).unwrap();
+        assert_eq!(sent["model"], "internal-gateway");
+        assert_eq!(sent["max_tokens"], 16);
+        assert_eq!(sent["messages"][0]["role"], "user");
+    }
+
+    #[tokio::test]
+    async fn test_stream_error_event() {
+        let provider = sh(
+            r#"cat > /dev/null; printf '{"type":"error","message":"overloaded","status":429}\n'"#,
+            "exec",
+        );
+
+        let result = provider.complete(request(), &TimingEngine::new()).await;
+
+        let error = result.unwrap_err();
+        assert!(matches!(error, ProviderError::ApiError { status_code: 429, .. }));
+        assert!(error.is_retryable());
+    }
+
+    #[tokio::test]
+    async fn test_stream_exit_status() {
+        let provider = sh("cat > /dev/null; echo 'gateway unreachable' >&2; exit 3", "exec");
+
+        let result = provider.complete(request(), &TimingEngine::new()).await;
+
+        assert!(
+            matches!(result, Err(ProviderError::StreamingError(m)) if m.contains("gateway unreachable"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_missing_program() {
+        let provider = ExecProvider::from_command_line("/nonexistent/llm-gateway --fast").unwrap();
+        let result = provider.stream(request(), &TimingEngine::new()).await;
+        assert!(matches!(result, Err(ProviderError::ConfigError(_))));
+    }
+
+    #[test]
+    fn test_request_tool_choice() {
+        let request = StreamingRequest::builder()
+            .model("internal-gateway")
+            .message(MessageRole::User, "Weather?")
+            .tool(Tool::new("get_weather", serde_json::json!({"type": "object"})))
+            .tool_choice(ToolChoice::Tool("get_weather".to_string()))
+            .build();
+
+        let value = serde_json::to_value(ExecRequest::new(&request)).unwrap();
+
+        assert_eq!(value["tool_choice"], "get_weather");
+        assert_eq!(value["tools"][0]["name"], "get_weather");
+        assert!(value.get("temperature").is_none());
+        assert!(ExecProvider::builder().build().is_err());
+    }
+}
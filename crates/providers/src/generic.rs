@@ -0,0 +1,968 @@
+//! Generic OpenAI-compatible provider implementation
+//!
+//! This module provides an adapter for self-hosted and third-party servers that
+//! expose the OpenAI Chat Completions API (vLLM, TGI, llama.cpp, Ollama, LiteLLM)
+//! with support for:
+//! - Arbitrary base URLs and model names
+//! - Optional bearer authentication and custom headers
+//! - Tolerant SSE parsing for server dialect differences (missing `id`/`model`,
+//!   `usage` in the final chunk, `reasoning_content` deltas, in-stream errors)
+//! - Embeddings (`/embeddings`) for servers that serve embedding models
+
+use crate::embeddings::{EmbeddingProvider, EmbeddingRequest, EmbeddingResult};
+use crate::error::{error_from_body, parse_api_error, ProviderError, Result};
+use crate::headers::ServerSignals;
+use crate::models::{self, ModelList};
+use crate::network::NetworkProbe;
+use crate::openai::message_content;
+use crate::retry::{self, RetryPolicy};
+use crate::traits::{
+    CompletionResult, MessageRole, Provider, ResponseMetadata, SharedUsage, StreamingRequest,
+    StreamingResponse, TokenUsage,
+};
+use async_trait::async_trait;
+use futures::StreamExt;
+use llm_latency_lens_core::{TimingEngine, Timestamp, TokenEvent, TokenKind};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, AUTHORIZATION, CONTENT_TYPE};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Generic OpenAI-compatible provider adapter
+///
+/// Any model name is accepted; the server decides whether it exists.
+pub struct GenericOpenAIProvider {
+    /// HTTP client
+    client: reqwest::Client,
+    /// Base URL including the API prefix (e.g. `http://localhost:8000/v1`)
+    base_url: String,
+    /// Bearer token (optional, most self-hosted servers run without auth)
+    api_key: Option<String>,
+    /// Additional headers sent with every request
+    headers: Vec<(String, String)>,
+    /// Request `stream_options.include_usage`
+    include_usage: bool,
+    /// Retries of the initial request
+    max_retries: u32,
+}
+
+impl GenericOpenAIProvider {
+    /// Create a new provider for an OpenAI-compatible server
+    ///
+    /// # Arguments
+    ///
+    /// * `base_url` - Base URL including the API prefix
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use llm_latency_lens_providers::generic::GenericOpenAIProvider;
+    ///
+    /// let provider = GenericOpenAIProvider::new("http://localhost:8000/v1");
+    /// ```
+    pub fn new(base_url: impl Into<String>) -> Result<Self> {
+        Self::builder().base_url(base_url).build()
+    }
+
+    /// Create a provider with custom configuration
+    pub fn builder() -> GenericOpenAIProviderBuilder {
+        GenericOpenAIProviderBuilder::default()
+    }
+
+    /// Build HTTP client with optimized settings
+    fn build_client(timeout: Duration) -> reqwest::Client {
+        crate::network::instrument(reqwest::Client::builder())
+            .timeout(timeout)
+            .tcp_keepalive(Duration::from_secs(60))
+            .pool_idle_timeout(Duration::from_secs(90))
+            .build()
+            .expect("Failed to build HTTP client")
+    }
+
+    /// Build headers for API request
+    fn build_headers(&self) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+
+        if let Some(ref key) = self.api_key {
+            headers.insert(
+                AUTHORIZATION,
+                HeaderValue::from_str(&format!("Bearer {}", key))
+                    .expect("Invalid API key format"),
+            );
+        }
+
+        // Header names and values are validated by the builder
+        for (name, value) in &self.headers {
+            headers.insert(
+                HeaderName::from_bytes(name.as_bytes()).expect("Invalid header name"),
+                HeaderValue::from_str(value).expect("Invalid header value"),
+            );
+        }
+
+        headers
+    }
+
+    /// Build the chat completions payload, streamed or not
+    fn chat_request(&self, request: &StreamingRequest, stream: bool) -> ChatCompletionRequest {
+        ChatCompletionRequest {
+            model: request.model.clone(),
+            messages: request
+                .messages
+                .iter()
+                .map(|m| ChatMessage {
+                    role: match m.role {
+                        MessageRole::System => "system".to_string(),
+                        MessageRole::User => "user".to_string(),
+                        MessageRole::Assistant => "assistant".to_string(),
+                    },
+                    content: message_content(m),
+                })
+                .collect(),
+            stream,
+            stream_options: (stream && self.include_usage).then_some(StreamOptions {
+                include_usage: true,
+            }),
+            max_tokens: request.max_tokens,
+            temperature: request.temperature,
+            top_p: request.top_p,
+            stop: request.stop.clone(),
+        }
+    }
+}
+
+/// Builder for generic OpenAI-compatible provider
+#[derive(Default)]
+pub struct GenericOpenAIProviderBuilder {
+    base_url: Option<String>,
+    api_key: Option<String>,
+    headers: Vec<(String, String)>,
+    include_usage: bool,
+    max_retries: Option<u32>,
+    timeout: Option<Duration>,
+}
+
+impl GenericOpenAIProviderBuilder {
+    /// Set the base URL (e.g. `http://localhost:11434/v1` for Ollama)
+    pub fn base_url(mut self, url: impl Into<String>) -> Self {
+        self.base_url = Some(url.into());
+        self
+    }
+
+    /// Set a bearer token; empty keys disable authentication
+    pub fn api_key(mut self, key: impl Into<String>) -> Self {
+        let key = key.into();
+        self.api_key = if key.is_empty() { None } else { Some(key) };
+        self
+    }
+
+    /// Add a header sent with every request
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Ask the server to report usage in the final chunk
+    ///
+    /// Sends `stream_options.include_usage`, which older servers may reject.
+    pub fn include_usage(mut self, include: bool) -> Self {
+        self.include_usage = include;
+        self
+    }
+
+    /// Set how many times a failed initial request is retried (3 by default)
+    pub fn max_retries(mut self, retries: u32) -> Self {
+        self.max_retries = Some(retries);
+        self
+    }
+
+    /// Set the HTTP request timeout (120 seconds by default)
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Build the provider
+    pub fn build(self) -> Result<GenericOpenAIProvider> {
+        let base_url = self
+            .base_url
+            .filter(|url| !url.is_empty())
+            .ok_or_else(|| {
+                ProviderError::ConfigError(
+                    "Base URL is required for OpenAI-compatible providers".to_string(),
+                )
+            })?;
+
+        for (name, value) in &self.headers {
+            HeaderName::from_bytes(name.as_bytes()).map_err(|_| {
+                ProviderError::ConfigError(format!("Invalid header name: {}", name))
+            })?;
+            HeaderValue::from_str(value).map_err(|_| {
+                ProviderError::ConfigError(format!("Invalid value for header {}", name))
+            })?;
+        }
+
+        Ok(GenericOpenAIProvider {
+            client: GenericOpenAIProvider::build_client(
+                self.timeout.unwrap_or(crate::network::DEFAULT_TIMEOUT),
+            ),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            api_key: self.api_key,
+            headers: self.headers,
+            include_usage: self.include_usage,
+            max_retries: self.max_retries.unwrap_or(3),
+        })
+    }
+}
+
+#[async_trait]
+impl Provider for GenericOpenAIProvider {
+    fn name(&self) -> &'static str {
+        "generic"
+    }
+
+    fn provider_kind(&self) -> llm_latency_lens_core::Provider {
+        llm_latency_lens_core::Provider::Generic
+    }
+
+    async fn health_check(&self) -> Result<()> {
+        let url = format!("{}/models", self.base_url);
+        let response = self
+            .client
+            .get(&url)
+            .headers(self.build_headers())
+            .send()
+            .await
+            .map_err(ProviderError::from_reqwest)?;
+
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(parse_api_error(response).await)
+        }
+    }
+
+    async fn stream(
+        &self,
+        request: StreamingRequest,
+        timing_engine: &TimingEngine,
+    ) -> Result<StreamingResponse> {
+        // Start timing measurement
+        let mut timing = timing_engine.start();
+        let network = NetworkProbe::new(timing_engine.clock());
+        timing.checkpoint("request_start");
+
+        // Build request payload
+        let payload = self.chat_request(&request, true);
+
+        timing.checkpoint("payload_built");
+
+        let url = format!("{}/chat/completions", self.base_url);
+        let headers = self.build_headers();
+
+        timing.checkpoint("headers_built");
+
+        // Build the SSE request
+        let request_id = request.request_id;
+        let req_builder = self
+            .client
+            .post(&url)
+            .headers(headers)
+            .json(&payload);
+
+        timing.checkpoint("http_request_built");
+
+        let response = retry::send(
+            RetryPolicy::new(self.max_retries),
+            &network,
+            req_builder,
+            parse_api_error,
+        )
+        .await?;
+
+        timing.checkpoint("response_received");
+
+        // Servers that reject the request may still answer with JSON
+        if !is_event_stream(&response) {
+            let status_code = response.status().as_u16();
+            let body = response.text().await.unwrap_or_default();
+            return Err(error_from_body(status_code, body));
+        }
+
+        let response_headers =
+            crate::headers::capture(response.headers(), &request.redact_headers);
+        let server = ServerSignals::from_headers(response.headers());
+        let event_source = crate::sse::events(
+            response.bytes_stream(),
+            timing_engine.clock().clone(),
+            network.clone(),
+        );
+
+        timing.checkpoint("event_source_created");
+
+        // Create token stream
+        let request_start = timing.start_time();
+        let usage = SharedUsage::new();
+        let stream_usage = usage.clone();
+        let mut sequence = 0u64;
+        let mut last_token_time: Option<Timestamp> = None;
+
+        let token_stream = event_source
+            .map(move |event_result| {
+                match event_result {
+                    Ok(message) => {
+                        let data = message.data.trim();
+                        if data == "[DONE]" {
+                            tracing::debug!("SSE stream completed");
+                            return None;
+                        }
+                        if data.is_empty() {
+                            return None;
+                        }
+
+                        // Parse SSE chunk
+                        let chunk: CompatChunk = match serde_json::from_str(data) {
+                            Ok(c) => c,
+                            Err(e) => {
+                                tracing::error!("Failed to parse SSE chunk: {}", e);
+                                return Some(Err(ProviderError::sse_parse(format!(
+                                    "Invalid JSON in SSE event: {}",
+                                    e
+                                ))));
+                            }
+                        };
+
+                        if let Some(error) = chunk.error {
+                            tracing::error!("Error chunk received: {}", data);
+                            return Some(Err(error.into_provider_error()));
+                        }
+
+                        // vLLM, llama.cpp and Ollama report usage in the final chunk
+                        if let Some(ref reported) = chunk.usage {
+                            let reported = reported.token_usage();
+                            stream_usage.update(|u| {
+                                u.input_tokens = reported.input_tokens.or(u.input_tokens);
+                                u.output_tokens = reported.output_tokens.or(u.output_tokens);
+                                u.thinking_tokens = reported.thinking_tokens.or(u.thinking_tokens);
+                                u.cache_read_tokens =
+                                    reported.cache_read_tokens.or(u.cache_read_tokens);
+                            });
+                        }
+
+                        // Skip chunks without text (role, usage-only)
+                        let (kind, content) = chunk
+                            .choices
+                            .first()
+                            .and_then(|c| c.delta.as_ref())
+                            .and_then(CompatDelta::text)?;
+
+                        // Record timing at the arrival of the read carrying the event
+                        let now = message.received_at;
+                        let time_since_start = now.duration_since(request_start);
+                        let inter_token_latency = last_token_time.map(|t| now.duration_since(t));
+                        last_token_time = Some(now);
+
+                        let event = TokenEvent {
+                            request_id,
+                            sequence,
+                            kind,
+                            content: Some(content),
+                            timestamp_nanos: now.as_nanos(),
+                            time_since_start,
+                            inter_token_latency,
+                        };
+
+                        sequence += 1;
+
+                        Some(Ok(event))
+                    }
+                    Err(e) => Some(Err(e)),
+                }
+            })
+            .filter_map(|x| async move { x })
+            .boxed();
+
+        timing.checkpoint("stream_initialized");
+
+        Ok(StreamingResponse {
+            request_id: request.request_id,
+            token_stream: Box::pin(token_stream),
+            metadata: ResponseMetadata {
+                model: request.model,
+                input_tokens: None, // Reported in the final chunk, if at all
+                output_tokens: None,
+                thinking_tokens: None,
+                cache_creation_tokens: None,
+                cache_read_tokens: None,
+                estimated_cost: None,
+                headers: response_headers,
+                server,
+            },
+            usage,
+            network,
+        })
+    }
+
+    async fn complete_non_streaming(
+        &self,
+        request: StreamingRequest,
+        timing_engine: &TimingEngine,
+    ) -> Result<CompletionResult> {
+        let timing = timing_engine.start();
+        let network = NetworkProbe::new(timing_engine.clock());
+
+        let payload = self.chat_request(&request, false);
+        let url = format!("{}/chat/completions", self.base_url);
+        let req_builder = self
+            .client
+            .post(&url)
+            .headers(self.build_headers())
+            .json(&payload);
+
+        let response = retry::send(
+            RetryPolicy::new(self.max_retries),
+            &network,
+            req_builder,
+            parse_api_error,
+        )
+        .await?;
+
+        let response_headers =
+            crate::headers::capture(response.headers(), &request.redact_headers);
+        let server = ServerSignals::from_headers(response.headers());
+        let (completion, body): (CompatChunk, _) =
+            crate::body::read_json(response, timing_engine.clock(), timing.start_time()).await?;
+
+        if let Some(error) = completion.error {
+            return Err(error.into_provider_error());
+        }
+
+        let (content, thinking) = completion
+            .choices
+            .into_iter()
+            .next()
+            .and_then(|c| c.message)
+            .map(|m| {
+                (
+                    m.content.unwrap_or_default(),
+                    m.reasoning_content.unwrap_or_default(),
+                )
+            })
+            .unwrap_or_default();
+
+        let mut metadata = ResponseMetadata {
+            model: request.model.clone(),
+            input_tokens: None,
+            output_tokens: None,
+            thinking_tokens: None,
+            cache_creation_tokens: None,
+            cache_read_tokens: None,
+            estimated_cost: None,
+            headers: response_headers,
+            server,
+        };
+        if let Some(ref usage) = completion.usage {
+            metadata.apply_usage(usage.token_usage());
+        }
+        metadata.estimate_cost(|usage| self.usage_cost(&request.model, usage));
+
+        Ok(CompletionResult::from_body(
+            request.request_id,
+            content,
+            thinking,
+            metadata,
+            &network,
+            body,
+        ))
+    }
+
+    fn supported_models(&self) -> Vec<String> {
+        // Any model served by the endpoint is accepted
+        Vec::new()
+    }
+
+    async fn list_models(&self) -> Result<Vec<String>> {
+        let url = format!("{}/models", self.base_url);
+        let response = self
+            .client
+            .get(&url)
+            .headers(self.build_headers())
+            .send()
+            .await
+            .map_err(ProviderError::from_reqwest)?;
+
+        if !response.status().is_success() {
+            return Err(parse_api_error(response).await);
+        }
+        let list: ModelList = models::read_list(response).await?;
+        Ok(list.ids())
+    }
+}
+
+#[async_trait]
+impl EmbeddingProvider for GenericOpenAIProvider {
+    async fn embed(
+        &self,
+        request: EmbeddingRequest,
+        timing_engine: &TimingEngine,
+    ) -> Result<EmbeddingResult> {
+        let url = format!("{}/embeddings", self.base_url);
+        crate::openai::embed(
+            &self.client,
+            &url,
+            self.build_headers(),
+            request,
+            timing_engine,
+            RetryPolicy::new(self.max_retries),
+            |_| None,
+        )
+        .await
+    }
+
+    fn calculate_embedding_cost(&self, _model: &str, _input_tokens: u64) -> Option<f64> {
+        // Self-hosted deployments have no per-token list price
+        None
+    }
+
+    fn supported_embedding_models(&self) -> Vec<String> {
+        // Any model served by the endpoint is accepted
+        Vec::new()
+    }
+}
+
+// OpenAI-compatible request/response types
+
+#[derive(Debug, Serialize)]
+struct ChatCompletionRequest {
+    model: String,
+    messages: Vec<ChatMessage>,
+    stream: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stream_options: Option<StreamOptions>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stop: Option<Vec<String>>,
+}
+
+#[derive(Debug, Serialize)]
+struct StreamOptions {
+    include_usage: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct ChatMessage {
+    role: String,
+    content: serde_json::Value,
+}
+
+/// Streaming chunk or non-streaming completion; every field is optional since
+/// servers disagree on which ones they send
+#[derive(Debug, Deserialize)]
+struct CompatChunk {
+    #[serde(default)]
+    choices: Vec<CompatChoice>,
+    #[serde(default)]
+    usage: Option<CompatUsage>,
+    #[serde(default)]
+    error: Option<CompatError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CompatChoice {
+    #[serde(default)]
+    delta: Option<CompatDelta>,
+    /// Complete message of a non-streaming completion
+    #[serde(default)]
+    message: Option<CompatDelta>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CompatDelta {
+    #[serde(default)]
+    content: Option<String>,
+    /// Reasoning text from DeepSeek-style reasoning parsers (vLLM, SGLang)
+    #[serde(default)]
+    reasoning_content: Option<String>,
+}
+
+impl CompatDelta {
+    /// Visible or reasoning text carried by this delta, if any
+    fn text(&self) -> Option<(TokenKind, String)> {
+        let non_empty = |text: &Option<String>| text.clone().filter(|t| !t.is_empty());
+        non_empty(&self.content)
+            .map(|t| (TokenKind::Content, t))
+            .or_else(|| non_empty(&self.reasoning_content).map(|t| (TokenKind::Thinking, t)))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct CompatUsage {
+    #[serde(default)]
+    prompt_tokens: Option<u64>,
+    #[serde(default)]
+    completion_tokens: Option<u64>,
+    #[serde(default)]
+    completion_tokens_details: Option<CompatTokenDetails>,
+    #[serde(default)]
+    prompt_tokens_details: Option<CompatTokenDetails>,
+}
+
+impl CompatUsage {
+    /// Usage counts, with reasoning tokens split out of the completion tokens
+    fn token_usage(&self) -> TokenUsage {
+        let reasoning_tokens = self.reasoning_tokens();
+        TokenUsage {
+            input_tokens: self.prompt_tokens,
+            // completion_tokens includes any reasoning tokens
+            output_tokens: self
+                .completion_tokens
+                .map(|c| c.saturating_sub(reasoning_tokens.unwrap_or(0))),
+            thinking_tokens: reasoning_tokens,
+            cache_creation_tokens: None,
+            cache_read_tokens: self.cached_tokens(),
+        }
+    }
+
+    fn reasoning_tokens(&self) -> Option<u64> {
+        self.completion_tokens_details
+            .as_ref()
+            .and_then(|d| d.reasoning_tokens)
+    }
+
+    /// Prefix-cache hits (vLLM reports these when prefix caching is enabled)
+    fn cached_tokens(&self) -> Option<u64> {
+        self.prompt_tokens_details
+            .as_ref()
+            .and_then(|d| d.cached_tokens)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct CompatTokenDetails {
+    #[serde(default)]
+    reasoning_tokens: Option<u64>,
+    #[serde(default)]
+    cached_tokens: Option<u64>,
+}
+
+/// In-stream error, either `{"error": "message"}` (TGI) or
+/// `{"error": {"message": ..., "code": ...}}` (vLLM, llama.cpp)
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum CompatError {
+    Message(String),
+    Object {
+        #[serde(default)]
+        message: String,
+        #[serde(default)]
+        code: Option<serde_json::Value>,
+    },
+}
+
+impl CompatError {
+    fn into_provider_error(self) -> ProviderError {
+        match self {
+            CompatError::Message(message) => ProviderError::streaming(message),
+            CompatError::Object { message, code } => {
+                match code.as_ref().and_then(|c| c.as_u64()) {
+                    Some(status) => error_from_body(
+                        status as u16,
+                        serde_json::json!({ "error": { "message": message } }).to_string(),
+                    ),
+                    None => ProviderError::streaming(message),
+                }
+            }
+        }
+    }
+}
+
+/// Whether a response declares an SSE body
+fn is_event_stream(response: &reqwest::Response) -> bool {
+    response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.starts_with("text/event-stream"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{body_partial_json, header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn sse_body(chunks: &[&str], done: bool) -> String {
+        let mut body: String = chunks.iter().map(|c| format!("data: {}\n\n", c)).collect();
+        if done {
+            body.push_str("data: [DONE]\n\n");
+        }
+        body
+    }
+
+    fn test_request() -> StreamingRequest {
+        StreamingRequest::builder()
+            .model("meta-llama/Llama-3.1-8B-Instruct")
+            .message(MessageRole::User, "Hello")
+            .build()
+    }
+
+    fn mock_provider(server: &MockServer) -> GenericOpenAIProvider {
+        GenericOpenAIProvider::builder()
+            .base_url(format!("{}/v1", server.uri()))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_provider_name() {
+        let provider = GenericOpenAIProvider::new("http://localhost:8000/v1").unwrap();
+        assert_eq!(provider.name(), "generic");
+    }
+
+    #[test]
+    fn test_builder() {
+        let provider = GenericOpenAIProvider::builder()
+            .base_url("http://localhost:8000/v1/")
+            .api_key("token")
+            .header("X-Tenant", "bench")
+            .include_usage(true)
+            .max_retries(5)
+            .build()
+            .unwrap();
+
+        assert_eq!(provider.base_url, "http://localhost:8000/v1");
+        assert_eq!(provider.api_key, Some("token".to_string()));
+        assert!(provider.include_usage);
+        assert_eq!(provider.max_retries, 5);
+
+        let headers = provider.build_headers();
+        assert_eq!(headers.get(AUTHORIZATION).unwrap(), "Bearer token");
+        assert_eq!(headers.get("X-Tenant").unwrap(), "bench");
+    }
+
+    #[test]
+    fn test_builder_errors() {
+        assert!(GenericOpenAIProvider::builder().build().is_err());
+        assert!(GenericOpenAIProvider::builder()
+            .base_url("http://localhost:8000/v1")
+            .header("bad header", "value")
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn test_no_auth_by_default() {
+        let provider = GenericOpenAIProvider::builder()
+            .base_url("http://localhost:8000/v1")
+            .api_key("")
+            .build()
+            .unwrap();
+
+        assert!(provider.build_headers().get(AUTHORIZATION).is_none());
+    }
+
+    #[test]
+    fn test_validate_model_accepts_any() {
+        let provider = GenericOpenAIProvider::new("http://localhost:8000/v1").unwrap();
+
+        assert!(provider.validate_model("llama3.1:8b").is_ok());
+        assert!(provider.validate_model("Qwen/Qwen2.5-7B-Instruct").is_ok());
+        assert!(provider.calculate_cost("llama3.1:8b", 1000, 1000).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_stream_tolerates_dialects() {
+        let server = MockServer::start().await;
+        // No id/object/created, reasoning_content deltas, usage in the final
+        // chunk with empty choices, and no [DONE] terminator
+        let body = sse_body(
+            &[
+                r#"{"choices": [{"index": 0, "delta": {"role": "assistant"}}]}"#,
+                r#"{"choices": [{"index": 0, "delta": {"reasoning_content": "Thinking"}}]}"#,
+                r#"{"choices": [{"index": 0, "delta": {"content": "Hi"}}]}"#,
+                r#"{"choices": [{"index": 0, "delta": {"content": " there"}, "finish_reason": "stop"}]}"#,
+                r#"{"choices": [], "usage": {"prompt_tokens": 9, "completion_tokens": 2, "total_tokens": 11}}"#,
+            ],
+            false,
+        );
+
+        Mock::given(method("POST"))
+            .and(path("/v1/chat/completions"))
+            .and(body_partial_json(serde_json::json!({
+                "model": "meta-llama/Llama-3.1-8B-Instruct",
+                "stream": true
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(body, "text/event-stream"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        let result = provider
+            .complete(test_request(), &TimingEngine::new())
+            .await
+            .unwrap();
+
+        assert_eq!(result.content, "Hi there");
+        assert_eq!(result.thinking, "Thinking");
+        assert_eq!(result.token_events.len(), 3);
+        assert_eq!(result.token_events[0].kind, TokenKind::Thinking);
+        assert!(result.reasoning_time().is_some());
+        assert_eq!(result.metadata.input_tokens, Some(9));
+        assert_eq!(result.metadata.output_tokens, Some(2));
+    }
+
+    #[tokio::test]
+    async fn test_complete_non_streaming() {
+        let server = MockServer::start().await;
+        let body = r#"{"choices": [{"index": 0, "message": {"role": "assistant", "content": "Hi there", "reasoning_content": "Thinking"}}], "usage": {"prompt_tokens": 9, "completion_tokens": 2}}"#;
+
+        Mock::given(method("POST"))
+            .and(path("/v1/chat/completions"))
+            .and(body_partial_json(serde_json::json!({"stream": false})))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(body, "application/json"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        let result = provider
+            .complete_non_streaming(test_request(), &TimingEngine::new())
+            .await
+            .unwrap();
+
+        assert_eq!(result.content, "Hi there");
+        assert_eq!(result.thinking, "Thinking");
+        assert!(result.token_events.is_empty());
+        assert_eq!(result.metadata.input_tokens, Some(9));
+        assert_eq!(result.metadata.output_tokens, Some(2));
+        assert_eq!(result.body.unwrap().size_bytes, body.len() as u64);
+
+        // stream_options is only valid on streamed requests
+        let requests = server.received_requests().await.unwrap();
+        let sent: serde_json::Value = serde_json::from_slice(&requests[0].body).unwrap();
+        assert!(sent.get("stream_options").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_embed() {
+        let server = MockServer::start().await;
+        let body = r#"{"data": [{"index": 0, "embedding": [0.5, -0.5, 0.25]}], "usage": {"prompt_tokens": 3, "total_tokens": 3}}"#;
+
+        Mock::given(method("POST"))
+            .and(path("/v1/embeddings"))
+            .and(body_partial_json(serde_json::json!({"model": "bge-m3", "input": ["hello"]})))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(body, "application/json"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        let request = EmbeddingRequest::builder().model("bge-m3").input("hello").build();
+        let result = provider.embed(request, &TimingEngine::new()).await.unwrap();
+
+        assert_eq!(result.vector_count(), 1);
+        assert_eq!(result.dimensions(), Some(3));
+        assert_eq!(result.metadata.input_tokens, Some(3));
+        assert_eq!(result.metadata.estimated_cost, None);
+    }
+
+    #[tokio::test]
+    async fn test_stream_sends_auth_headers_and_usage_option() {
+        let server = MockServer::start().await;
+        let body = sse_body(
+            &[r#"{"id": "cmpl-1", "object": "chat.completion.chunk", "created": 1, "model": "m", "choices": [{"index": 0, "delta": {"content": "ok"}}]}"#],
+            true,
+        );
+
+        Mock::given(method("POST"))
+            .and(path("/v1/chat/completions"))
+            .and(header("authorization", "Bearer token"))
+            .and(header("x-tenant", "bench"))
+            .and(body_partial_json(serde_json::json!({
+                "stream_options": {"include_usage": true}
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(body, "text/event-stream"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = GenericOpenAIProvider::builder()
+            .base_url(format!("{}/v1", server.uri()))
+            .api_key("token")
+            .header("X-Tenant", "bench")
+            .include_usage(true)
+            .build()
+            .unwrap();
+
+        let result = provider
+            .complete(test_request(), &TimingEngine::new())
+            .await
+            .unwrap();
+
+        assert_eq!(result.content, "ok");
+    }
+
+    #[tokio::test]
+    async fn test_stream_in_band_error() {
+        let server = MockServer::start().await;
+        let body = sse_body(
+            &[
+                r#"{"choices": [{"index": 0, "delta": {"content": "Par"}}]}"#,
+                r#"{"error": {"message": "KV cache exhausted", "code": 503}}"#,
+            ],
+            false,
+        );
+
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(body, "text/event-stream"))
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        let err = provider
+            .complete(test_request(), &TimingEngine::new())
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, ProviderError::ServiceUnavailable(_)));
+    }
+
+    #[tokio::test]
+    async fn test_stream_maps_error_status() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(404).set_body_json(serde_json::json!({
+                "object": "error",
+                "message": "The model `missing` does not exist.",
+                "type": "NotFoundError",
+                "code": 404
+            })))
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        let result = provider.stream(test_request(), &TimingEngine::new()).await;
+
+        assert!(matches!(result, Err(ProviderError::ApiError { status_code: 404, .. })));
+    }
+
+    #[tokio::test]
+    async fn test_health_check() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/v1/models"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(r#"{"data": []}"#))
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        assert!(provider.health_check().await.is_ok());
+    }
+}
@@ -1,27 +1,44 @@
-//! Google (Gemini) provider stub
+//! Google (Gemini) provider implementation
 //!
-//! This module provides a stub implementation for Google's Gemini API.
-//! Full implementation will be added in a future release.
+//! This module provides an adapter for Google's Gemini API using the
+//! `streamGenerateContent` endpoint with support for:
+//! - Server-Sent Events (SSE) streaming (`alt=sse`)
+//! - Token usage reporting from `usageMetadata`
+//! - Mapping of Gemini error bodies (`google.rpc.Status`) to provider errors
+//! - Cost calculation for Gemini models
+//! - Embeddings via `embedContent` (one input) and `batchEmbedContents`
 
+use crate::embeddings::{EmbeddingProvider, EmbeddingRequest, EmbeddingResult};
 use crate::error::{ProviderError, Result};
-use crate::traits::{Provider, StreamingRequest, StreamingResponse};
+use crate::headers::ServerSignals;
+use crate::models::{self, ModelCache};
+use crate::network::NetworkProbe;
+use crate::retry::{self, RetryPolicy};
+use crate::traits::{
+    media_type_for_path, CompletionResult, ContentPart, MessageRole, Provider, ResponseMetadata,
+    SharedUsage, StreamingRequest, StreamingResponse, TokenUsage,
+};
 use async_trait::async_trait;
-use llm_latency_lens_core::TimingEngine;
+use futures::StreamExt;
+use llm_latency_lens_core::{TimingEngine, Timestamp, TokenEvent, TokenKind};
+use reqwest::header::{HeaderMap, HeaderValue, CONTENT_TYPE};
+use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
-/// Google Gemini provider adapter (stub)
+/// Default Gemini API endpoint
+const DEFAULT_BASE_URL: &str = "https://generativelanguage.googleapis.com/v1beta";
+
+/// Google Gemini provider adapter
 pub struct GoogleProvider {
     /// HTTP client
-    #[allow(dead_code)]
     client: reqwest::Client,
     /// API key
-    #[allow(dead_code)]
     api_key: String,
     /// Base URL
-    #[allow(dead_code)]
     base_url: String,
-    /// Maximum retry attempts
-    #[allow(dead_code)]
+    /// Models discovered from `/models`
+    models: ModelCache,
+    /// Retries of the initial request
     max_retries: u32,
 }
 
@@ -41,9 +58,10 @@ impl GoogleProvider {
     /// ```
     pub fn new(api_key: impl Into<String>) -> Self {
         Self {
-            client: Self::build_client(),
+            client: Self::build_client(crate::network::DEFAULT_TIMEOUT),
             api_key: api_key.into(),
-            base_url: "https://generativelanguage.googleapis.com/v1".to_string(),
+            base_url: DEFAULT_BASE_URL.to_string(),
+            models: ModelCache::default(),
             max_retries: 3,
         }
     }
@@ -54,14 +72,70 @@ impl GoogleProvider {
     }
 
     /// Build HTTP client with optimized settings
-    fn build_client() -> reqwest::Client {
-        reqwest::Client::builder()
-            .timeout(Duration::from_secs(120))
+    fn build_client(timeout: Duration) -> reqwest::Client {
+        crate::network::instrument(reqwest::Client::builder())
+            .timeout(timeout)
             .tcp_keepalive(Duration::from_secs(60))
             .pool_idle_timeout(Duration::from_secs(90))
             .build()
             .expect("Failed to build HTTP client")
     }
+
+    /// Build headers for API request
+    fn build_headers(&self) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        headers.insert(
+            "x-goog-api-key",
+            HeaderValue::from_str(&self.api_key).expect("Invalid API key format"),
+        );
+        headers
+    }
+
+    /// Build the Gemini request payload
+    fn build_payload(request: &StreamingRequest) -> GenerateContentRequest {
+        // Gemini takes system prompts separately from the conversation
+        let system_parts: Vec<Part> = request
+            .messages
+            .iter()
+            .filter(|m| m.role == MessageRole::System)
+            .map(|m| Part::text(m.text()))
+            .collect();
+
+        let contents = request
+            .messages
+            .iter()
+            .filter(|m| m.role != MessageRole::System)
+            .map(|m| Content {
+                role: Some(
+                    match m.role {
+                        MessageRole::Assistant => "model",
+                        _ => "user",
+                    }
+                    .to_string(),
+                ),
+                parts: m.content.iter().map(Part::from_content).collect(),
+            })
+            .collect();
+
+        GenerateContentRequest {
+            contents,
+            system_instruction: if system_parts.is_empty() {
+                None
+            } else {
+                Some(Content {
+                    role: None,
+                    parts: system_parts,
+                })
+            },
+            generation_config: GenerationConfig {
+                max_output_tokens: request.max_tokens,
+                temperature: request.temperature,
+                top_p: request.top_p,
+                stop_sequences: request.stop.clone(),
+            },
+        }
+    }
 }
 
 /// Builder for Google provider
@@ -70,6 +144,7 @@ pub struct GoogleProviderBuilder {
     api_key: Option<String>,
     base_url: Option<String>,
     max_retries: Option<u32>,
+    timeout: Option<Duration>,
 }
 
 impl GoogleProviderBuilder {
@@ -85,20 +160,29 @@ impl GoogleProviderBuilder {
         self
     }
 
-    /// Set maximum retry attempts
+    /// Set how many times a failed initial request is retried (3 by default)
     pub fn max_retries(mut self, retries: u32) -> Self {
         self.max_retries = Some(retries);
         self
     }
 
+    /// Set the HTTP request timeout (120 seconds by default)
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
     /// Build the provider
     pub fn build(self) -> GoogleProvider {
         GoogleProvider {
-            client: GoogleProvider::build_client(),
+            client: GoogleProvider::build_client(
+                self.timeout.unwrap_or(crate::network::DEFAULT_TIMEOUT),
+            ),
             api_key: self.api_key.expect("API key is required"),
-            base_url: self.base_url.unwrap_or_else(|| {
-                "https://generativelanguage.googleapis.com/v1".to_string()
-            }),
+            base_url: self
+                .base_url
+                .unwrap_or_else(|| DEFAULT_BASE_URL.to_string()),
+            models: ModelCache::default(),
             max_retries: self.max_retries.unwrap_or(3),
         }
     }
@@ -110,46 +194,242 @@ impl Provider for GoogleProvider {
         "google"
     }
 
+    fn provider_kind(&self) -> llm_latency_lens_core::Provider {
+        llm_latency_lens_core::Provider::Google
+    }
+
     async fn health_check(&self) -> Result<()> {
-        // Stub: Always return success for now
-        tracing::warn!("Google provider is a stub - health check not implemented");
-        Ok(())
+        // Listing models is the cheapest call that validates the API key
+        let url = format!("{}/models", self.base_url);
+        let response = self
+            .client
+            .get(&url)
+            .headers(self.build_headers())
+            .send()
+            .await
+            .map_err(ProviderError::from_reqwest)?;
+
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(parse_google_error(response).await)
+        }
     }
 
     async fn stream(
         &self,
-        _request: StreamingRequest,
-        _timing_engine: &TimingEngine,
+        request: StreamingRequest,
+        timing_engine: &TimingEngine,
     ) -> Result<StreamingResponse> {
-        // Stub: Return error indicating not implemented
-        Err(ProviderError::Other(
-            "Google provider is not yet implemented. Coming soon!".to_string(),
-        ))
+        // Validate model
+        self.validate_model(&request.model)?;
+
+        // Start timing measurement
+        let mut timing = timing_engine.start();
+        let network = NetworkProbe::new(timing_engine.clock());
+        timing.checkpoint("request_start");
+
+        // Build request payload
+        let payload = Self::build_payload(&request);
+
+        timing.checkpoint("payload_built");
+
+        let url = format!(
+            "{}/models/{}:streamGenerateContent?alt=sse",
+            self.base_url, request.model
+        );
+        let headers = self.build_headers();
+
+        timing.checkpoint("headers_built");
+
+        // Build the SSE request
+        let request_id = request.request_id;
+        let req_builder = self
+            .client
+            .post(&url)
+            .headers(headers)
+            .json(&payload);
+
+        timing.checkpoint("http_request_built");
+
+        let response = retry::send(
+            RetryPolicy::new(self.max_retries),
+            &network,
+            req_builder,
+            parse_google_error,
+        )
+        .await?;
+
+        timing.checkpoint("response_received");
+
+        let response_headers =
+            crate::headers::capture(response.headers(), &request.redact_headers);
+        let server = ServerSignals::from_headers(response.headers());
+        let event_source = crate::sse::events(
+            response.bytes_stream(),
+            timing_engine.clock().clone(),
+            network.clone(),
+        );
+
+        timing.checkpoint("event_source_created");
+
+        // Create token stream
+        let request_start = timing.start_time();
+        let usage = SharedUsage::new();
+        let stream_usage = usage.clone();
+        let mut sequence = 0u64;
+        let mut last_token_time: Option<Timestamp> = None;
+
+        let token_stream = event_source
+            .map(move |event_result| {
+                match event_result {
+                    Ok(message) => {
+                        // Parse SSE chunk
+                        let chunk: GenerateContentChunk =
+                            match serde_json::from_str(&message.data) {
+                                Ok(c) => c,
+                                Err(e) => {
+                                    tracing::error!("Failed to parse SSE chunk: {}", e);
+                                    return Some(Err(ProviderError::sse_parse(format!(
+                                        "Invalid JSON in SSE event: {}",
+                                        e
+                                    ))));
+                                }
+                            };
+
+                        if let Some(error) = chunk.error {
+                            tracing::error!("Error chunk received: {}", message.data);
+                            let code = error.code;
+                            return Some(Err(error.into_provider_error(code)));
+                        }
+
+                        // Usage is cumulative, the last chunk carries the final counts
+                        if let Some(ref meta) = chunk.usage_metadata {
+                            stream_usage.update(|u| {
+                                u.input_tokens = meta.prompt_token_count.or(u.input_tokens);
+                                u.output_tokens = meta.candidates_token_count.or(u.output_tokens);
+                                u.thinking_tokens =
+                                    meta.thoughts_token_count.or(u.thinking_tokens);
+                                u.cache_read_tokens =
+                                    meta.cached_content_token_count.or(u.cache_read_tokens);
+                            });
+                        }
+
+                        if let Some(err) = chunk.blocked() {
+                            return Some(Err(err));
+                        }
+
+                        // Skip chunks without visible text (usage-only, thoughts)
+                        let content = chunk.text()?;
+
+                        // Record timing at the arrival of the read carrying the event
+                        let now = message.received_at;
+                        let time_since_start = now.duration_since(request_start);
+                        let inter_token_latency = last_token_time.map(|t| now.duration_since(t));
+                        last_token_time = Some(now);
+
+                        let event = TokenEvent {
+                            request_id,
+                            sequence,
+                            kind: TokenKind::Content,
+                            content: Some(content),
+                            timestamp_nanos: now.as_nanos(),
+                            time_since_start,
+                            inter_token_latency,
+                        };
+
+                        sequence += 1;
+
+                        Some(Ok(event))
+                    }
+                    Err(e) => Some(Err(e)),
+                }
+            })
+            .filter_map(|x| async move { x })
+            .boxed();
+
+        timing.checkpoint("stream_initialized");
+
+        Ok(StreamingResponse {
+            request_id: request.request_id,
+            token_stream: Box::pin(token_stream),
+            metadata: ResponseMetadata {
+                model: request.model,
+                input_tokens: None, // Reported in usageMetadata
+                output_tokens: None,
+                thinking_tokens: None,
+                cache_creation_tokens: None,
+                cache_read_tokens: None,
+                estimated_cost: None,
+                headers: response_headers,
+                server,
+            },
+            usage,
+            network,
+        })
     }
 
-    fn calculate_cost(&self, model: &str, input_tokens: u64, output_tokens: u64) -> Option<f64> {
-        // Gemini pricing (as of 2024)
-        let (input_price, output_price) = match model {
-            // Gemini 1.5 Pro
-            "gemini-1.5-pro" | "gemini-1.5-pro-001" | "gemini-1.5-pro-002" => (1.25, 5.0),
+    async fn complete_non_streaming(
+        &self,
+        request: StreamingRequest,
+        timing_engine: &TimingEngine,
+    ) -> Result<CompletionResult> {
+        self.validate_model(&request.model)?;
 
-            // Gemini 1.5 Flash
-            "gemini-1.5-flash" | "gemini-1.5-flash-001" | "gemini-1.5-flash-002" => (0.075, 0.30),
+        let timing = timing_engine.start();
+        let network = NetworkProbe::new(timing_engine.clock());
 
-            // Gemini 1.5 Flash-8B
-            "gemini-1.5-flash-8b" | "gemini-1.5-flash-8b-001" => (0.0375, 0.15),
+        let payload = Self::build_payload(&request);
+        let url = format!("{}/models/{}:generateContent", self.base_url, request.model);
+        let req_builder = self
+            .client
+            .post(&url)
+            .headers(self.build_headers())
+            .json(&payload);
 
-            // Gemini 1.0 Pro
-            "gemini-1.0-pro" | "gemini-1.0-pro-001" | "gemini-1.0-pro-002" => (0.50, 1.50),
+        let response = retry::send(
+            RetryPolicy::new(self.max_retries),
+            &network,
+            req_builder,
+            parse_google_error,
+        )
+        .await?;
 
-            // Unknown model
-            _ => return None,
-        };
+        let response_headers =
+            crate::headers::capture(response.headers(), &request.redact_headers);
+        let server = ServerSignals::from_headers(response.headers());
+        // The complete response has the same shape as a stream chunk
+        let (completion, body): (GenerateContentChunk, _) =
+            crate::body::read_json(response, timing_engine.clock(), timing.start_time()).await?;
 
-        let input_cost = (input_tokens as f64 / 1_000_000.0) * input_price;
-        let output_cost = (output_tokens as f64 / 1_000_000.0) * output_price;
+        if let Some(err) = completion.blocked() {
+            return Err(err);
+        }
 
-        Some(input_cost + output_cost)
+        let mut metadata = ResponseMetadata {
+            model: request.model.clone(),
+            input_tokens: None,
+            output_tokens: None,
+            thinking_tokens: None,
+            cache_creation_tokens: None,
+            cache_read_tokens: None,
+            estimated_cost: None,
+            headers: response_headers,
+            server,
+        };
+        if let Some(ref meta) = completion.usage_metadata {
+            metadata.apply_usage(meta.token_usage());
+        }
+        metadata.estimate_cost(|usage| self.usage_cost(&request.model, usage));
+
+        Ok(CompletionResult::from_body(
+            request.request_id,
+            completion.text().unwrap_or_default(),
+            String::new(),
+            metadata,
+            &network,
+            body,
+        ))
     }
 
     fn supported_models(&self) -> Vec<String> {
@@ -171,11 +451,544 @@ impl Provider for GoogleProvider {
             "gemini-1.0-pro-002".to_string(),
         ]
     }
+
+    async fn list_models(&self) -> Result<Vec<String>> {
+        let mut ids = Vec::new();
+        let mut page_token: Option<String> = None;
+        loop {
+            let mut url = format!("{}/models?pageSize=1000", self.base_url);
+            if let Some(ref token) = page_token {
+                url.push_str("&pageToken=");
+                url.push_str(token);
+            }
+            let response = self
+                .client
+                .get(&url)
+                .headers(self.build_headers())
+                .send()
+                .await
+                .map_err(ProviderError::from_reqwest)?;
+
+            if !response.status().is_success() {
+                return Err(parse_google_error(response).await);
+            }
+            let list: GoogleModelList = models::read_list(response).await?;
+            ids.extend(list.models.into_iter().filter_map(GoogleModel::chat_model_id));
+            page_token = list.next_page_token.filter(|token| !token.is_empty());
+            if page_token.is_none() {
+                break;
+            }
+        }
+        ids.sort();
+        Ok(ids)
+    }
+
+    fn model_cache(&self) -> Option<&ModelCache> {
+        Some(&self.models)
+    }
+}
+
+/// Parse a Gemini error response
+///
+/// Gemini returns errors as `{"error": {"code", "message", "status", "details"}}`
+/// where `status` is a `google.rpc.Code` name such as `RESOURCE_EXHAUSTED`.
+async fn parse_google_error(response: reqwest::Response) -> ProviderError {
+    let status_code = response.status().as_u16();
+
+    let body = match response.text().await {
+        Ok(text) => text,
+        Err(e) => {
+            return ProviderError::api_error(
+                status_code,
+                format!("Failed to read error response: {}", e),
+            );
+        }
+    };
+
+    map_google_error(status_code, &body)
+}
+
+/// Map a Gemini error body to a provider error
+fn map_google_error(status_code: u16, body: &str) -> ProviderError {
+    match serde_json::from_str::<GoogleErrorResponse>(body) {
+        Ok(response) => response.error.into_provider_error(status_code),
+        Err(_) => ProviderError::api_error_with_body(
+            status_code,
+            format!("HTTP {}", status_code),
+            body,
+        ),
+    }
+}
+
+/// Parse a protobuf duration string such as `"30s"` or `"1.5s"` into whole seconds
+fn parse_retry_delay(delay: &str) -> Option<u64> {
+    let seconds: f64 = delay.strip_suffix('s')?.parse().ok()?;
+    Some(seconds.ceil() as u64)
+}
+
+#[async_trait]
+impl EmbeddingProvider for GoogleProvider {
+    async fn embed(
+        &self,
+        request: EmbeddingRequest,
+        timing_engine: &TimingEngine,
+    ) -> Result<EmbeddingResult> {
+        self.validate_embedding_model(&request.model)?;
+
+        let timing = timing_engine.start();
+        let network = NetworkProbe::new(timing_engine.clock());
+
+        let content = |text: &String| EmbedContentRequest {
+            model: format!("models/{}", request.model),
+            content: Content {
+                role: None,
+                parts: vec![Part::text(text.clone())],
+            },
+            output_dimensionality: request.dimensions,
+        };
+
+        // A single input uses embedContent; batches use batchEmbedContents
+        let req_builder = match request.inputs.as_slice() {
+            [input] => self
+                .client
+                .post(format!("{}/models/{}:embedContent", self.base_url, request.model))
+                .json(&content(input)),
+            inputs => self
+                .client
+                .post(format!("{}/models/{}:batchEmbedContents", self.base_url, request.model))
+                .json(&BatchEmbedContentsRequest {
+                    requests: inputs.iter().map(content).collect(),
+                }),
+        };
+
+        let response = retry::send(
+            RetryPolicy::new(self.max_retries),
+            &network,
+            req_builder.headers(self.build_headers()),
+            parse_google_error,
+        )
+        .await?;
+
+        let response_headers =
+            crate::headers::capture(response.headers(), &request.redact_headers);
+        let server = ServerSignals::from_headers(response.headers());
+        let (response, body): (EmbedContentResponse, _) =
+            crate::body::read_json(response, timing_engine.clock(), timing.start_time()).await?;
+
+        let embeddings = response
+            .embedding
+            .into_iter()
+            .chain(response.embeddings)
+            .map(|e| e.values)
+            .collect();
+
+        Ok(EmbeddingResult {
+            request_id: request.request_id,
+            input_count: request.inputs.len(),
+            embeddings,
+            metadata: ResponseMetadata {
+                model: request.model,
+                // Gemini does not report token usage for embeddings
+                input_tokens: None,
+                output_tokens: None,
+                thinking_tokens: None,
+                cache_creation_tokens: None,
+                cache_read_tokens: None,
+                estimated_cost: None,
+                headers: response_headers,
+                server,
+            },
+            network: network.timings(),
+            retries: network.retry_stats(),
+            body,
+        })
+    }
+
+    fn calculate_embedding_cost(&self, model: &str, input_tokens: u64) -> Option<f64> {
+        // Price per 1M input tokens (as of 2024)
+        let price = match model {
+            "text-embedding-004" => 0.0,
+            "gemini-embedding-001" => 0.15,
+            _ => return None,
+        };
+        Some((input_tokens as f64 / 1_000_000.0) * price)
+    }
+
+    fn supported_embedding_models(&self) -> Vec<String> {
+        vec![
+            "text-embedding-004".to_string(),
+            "gemini-embedding-001".to_string(),
+        ]
+    }
+}
+
+// Gemini API request/response types
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GenerateContentRequest {
+    contents: Vec<Content>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    system_instruction: Option<Content>,
+    generation_config: GenerationConfig,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Content {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    role: Option<String>,
+    #[serde(default)]
+    parts: Vec<Part>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Part {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    text: Option<String>,
+    /// Set on thought summaries from thinking models
+    #[serde(default, skip_serializing)]
+    thought: Option<bool>,
+    #[serde(rename = "inlineData", default, skip_serializing_if = "Option::is_none")]
+    inline_data: Option<Blob>,
+    #[serde(rename = "fileData", default, skip_serializing_if = "Option::is_none")]
+    file_data: Option<FileData>,
+}
+
+impl Part {
+    fn text(text: String) -> Self {
+        Self {
+            text: Some(text),
+            thought: None,
+            inline_data: None,
+            file_data: None,
+        }
+    }
+
+    /// Map a message content part to a Gemini part
+    fn from_content(part: &ContentPart) -> Self {
+        let mut mapped = Self {
+            text: None,
+            thought: None,
+            inline_data: None,
+            file_data: None,
+        };
+        match part {
+            ContentPart::Text { text } => mapped.text = Some(text.clone()),
+            ContentPart::ImageUrl { url } => {
+                // fileData requires a MIME type; guess it from the URL path
+                let mime_type = media_type_for_path(std::path::Path::new(
+                    url.split(['?', '#']).next().unwrap_or(url),
+                ))
+                .unwrap_or("image/jpeg");
+                mapped.file_data = Some(FileData {
+                    mime_type: mime_type.to_string(),
+                    file_uri: url.clone(),
+                });
+            }
+            ContentPart::Image { media_type, data } | ContentPart::Document { media_type, data } => {
+                mapped.inline_data = Some(Blob {
+                    mime_type: media_type.clone(),
+                    data: data.clone(),
+                });
+            }
+        }
+        mapped
+    }
+}
+
+/// Inline base64 data
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Blob {
+    mime_type: String,
+    data: String,
+}
+
+/// Data referenced by URI
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FileData {
+    mime_type: String,
+    file_uri: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GenerationConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_output_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stop_sequences: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[allow(dead_code)]
+struct GenerateContentChunk {
+    #[serde(default)]
+    candidates: Vec<Candidate>,
+    #[serde(default)]
+    prompt_feedback: Option<PromptFeedback>,
+    #[serde(default)]
+    usage_metadata: Option<UsageMetadata>,
+    #[serde(default)]
+    model_version: Option<String>,
+    #[serde(default)]
+    error: Option<GoogleError>,
+}
+
+impl GenerateContentChunk {
+    /// Concatenated visible text of the first candidate
+    fn text(&self) -> Option<String> {
+        let content = self.candidates.first()?.content.as_ref()?;
+        let text: String = content
+            .parts
+            .iter()
+            .filter(|p| p.thought != Some(true))
+            .filter_map(|p| p.text.as_deref())
+            .collect();
+
+        if text.is_empty() {
+            None
+        } else {
+            Some(text)
+        }
+    }
+
+    /// Error for a prompt or candidate blocked by safety filters
+    fn blocked(&self) -> Option<ProviderError> {
+        if let Some(reason) = self
+            .prompt_feedback
+            .as_ref()
+            .and_then(|f| f.block_reason.as_ref())
+        {
+            return Some(ProviderError::ContentFilterError(format!(
+                "Prompt blocked: {}",
+                reason
+            )));
+        }
+
+        match self
+            .candidates
+            .first()
+            .and_then(|c| c.finish_reason.as_deref())
+        {
+            Some(
+                reason @ ("SAFETY" | "RECITATION" | "BLOCKLIST" | "PROHIBITED_CONTENT" | "SPII"),
+            ) => Some(ProviderError::ContentFilterError(format!(
+                "Response blocked: {}",
+                reason
+            ))),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[allow(dead_code)]
+struct Candidate {
+    #[serde(default)]
+    content: Option<Content>,
+    #[serde(default)]
+    finish_reason: Option<String>,
+    #[serde(default)]
+    index: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PromptFeedback {
+    #[serde(default)]
+    block_reason: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[allow(dead_code)]
+struct UsageMetadata {
+    #[serde(default)]
+    prompt_token_count: Option<u64>,
+    #[serde(default)]
+    candidates_token_count: Option<u64>,
+    #[serde(default)]
+    thoughts_token_count: Option<u64>,
+    /// Context-cache hits, part of `prompt_token_count`
+    #[serde(default)]
+    cached_content_token_count: Option<u64>,
+    #[serde(default)]
+    total_token_count: Option<u64>,
+}
+
+impl UsageMetadata {
+    /// Usage counts of a complete (non-streaming) response
+    fn token_usage(&self) -> TokenUsage {
+        TokenUsage {
+            input_tokens: self.prompt_token_count,
+            output_tokens: self.candidates_token_count,
+            thinking_tokens: self.thoughts_token_count,
+            cache_creation_tokens: None,
+            cache_read_tokens: self.cached_content_token_count,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct GoogleErrorResponse {
+    error: GoogleError,
+}
+
+#[derive(Debug, Deserialize)]
+struct GoogleError {
+    #[serde(default)]
+    code: u16,
+    #[serde(default)]
+    message: String,
+    #[serde(default)]
+    status: String,
+    #[serde(default)]
+    details: Vec<serde_json::Value>,
+}
+
+impl GoogleError {
+    /// Convert to a provider error, falling back to the HTTP status code
+    fn into_provider_error(self, status_code: u16) -> ProviderError {
+        let code = if self.code != 0 { self.code } else { status_code };
+        let api_key_invalid = self.details.iter().any(|d| {
+            d.get("reason").and_then(|r| r.as_str()) == Some("API_KEY_INVALID")
+        });
+        let retry_after = self
+            .details
+            .iter()
+            .filter_map(|d| d.get("retryDelay").and_then(|r| r.as_str()))
+            .find_map(parse_retry_delay);
+
+        match self.status.as_str() {
+            "UNAUTHENTICATED" | "PERMISSION_DENIED" => {
+                ProviderError::AuthenticationError(self.message)
+            }
+            _ if api_key_invalid || code == 401 || code == 403 => {
+                ProviderError::AuthenticationError(self.message)
+            }
+            "RESOURCE_EXHAUSTED" => ProviderError::rate_limit(self.message, retry_after),
+            _ if code == 429 => ProviderError::rate_limit(self.message, retry_after),
+            "NOT_FOUND" => ProviderError::InvalidModel(self.message),
+            "UNAVAILABLE" => ProviderError::ServiceUnavailable(self.message),
+            _ if code == 503 => ProviderError::ServiceUnavailable(self.message),
+            _ if code == 413 => ProviderError::PayloadTooLarge(self.message),
+            _ => ProviderError::ApiError {
+                status_code: code,
+                message: format!("{}: {}", self.status, self.message),
+                body: None,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct EmbedContentRequest {
+    model: String,
+    content: Content,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    output_dimensionality: Option<u32>,
+}
+
+#[derive(Debug, Serialize)]
+struct BatchEmbedContentsRequest {
+    requests: Vec<EmbedContentRequest>,
+}
+
+/// `embedContent` returns `embedding`; `batchEmbedContents` returns `embeddings`
+#[derive(Debug, Deserialize)]
+struct EmbedContentResponse {
+    #[serde(default)]
+    embedding: Option<ContentEmbedding>,
+    #[serde(default)]
+    embeddings: Vec<ContentEmbedding>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ContentEmbedding {
+    values: Vec<f32>,
+}
+
+/// `GET /models` response
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GoogleModelList {
+    #[serde(default)]
+    models: Vec<GoogleModel>,
+    #[serde(default)]
+    next_page_token: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GoogleModel {
+    /// Resource name, e.g. `models/gemini-1.5-pro-002`
+    name: String,
+    #[serde(default)]
+    supported_generation_methods: Vec<String>,
+}
+
+impl GoogleModel {
+    /// Model ID without the `models/` prefix, for models that generate content
+    fn chat_model_id(self) -> Option<String> {
+        if !self
+            .supported_generation_methods
+            .iter()
+            .any(|method| method == "streamGenerateContent" || method == "generateContent")
+        {
+            return None;
+        }
+        Some(
+            self.name
+                .strip_prefix("models/")
+                .unwrap_or(&self.name)
+                .to_string(),
+        )
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::traits::MessageRole;
+    use wiremock::matchers::{body_partial_json, header, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn sse_body(chunks: &[&str]) -> String {
+        chunks
+            .iter()
+            .map(|c| {
+                let chunk: serde_json::Value = serde_json::from_str(c).unwrap();
+                format!("data: {}\r\n\r\n", chunk)
+            })
+            .collect()
+    }
+
+    fn test_request() -> StreamingRequest {
+        StreamingRequest::builder()
+            .model("gemini-1.5-flash")
+            .message(MessageRole::System, "Be brief")
+            .message(MessageRole::User, "Hello")
+            .message(MessageRole::Assistant, "Hi")
+            .message(MessageRole::User, "Count to three")
+            .max_tokens(64)
+            .build()
+    }
+
+    fn mock_provider(server: &MockServer) -> GoogleProvider {
+        GoogleProvider::builder()
+            .api_key("test-key")
+            .base_url(server.uri())
+            .build()
+    }
 
     #[test]
     fn test_provider_name() {
@@ -244,10 +1057,357 @@ mod tests {
         assert!(provider.validate_model("invalid-model").is_err());
     }
 
+    #[test]
+    fn test_build_payload() {
+        let payload = GoogleProvider::build_payload(&test_request());
+        let json = serde_json::to_value(&payload).unwrap();
+
+        assert_eq!(json["systemInstruction"]["parts"][0]["text"], "Be brief");
+        assert_eq!(json["contents"].as_array().unwrap().len(), 3);
+        assert_eq!(json["contents"][1]["role"], "model");
+        assert_eq!(json["generationConfig"]["maxOutputTokens"], 64);
+        assert!(json["generationConfig"].get("temperature").is_none());
+    }
+
+    #[test]
+    fn test_build_payload_attachments() {
+        let request = StreamingRequest::builder()
+            .model("gemini-1.5-flash")
+            .message(MessageRole::User, "What is in these?")
+            .attachment(ContentPart::image_url("https://example.com/cat.png?size=large"))
+            .attachment(ContentPart::Document {
+                media_type: "application/pdf".to_string(),
+                data: "JVBE".to_string(),
+            })
+            .build();
+        let payload = GoogleProvider::build_payload(&request);
+        let json = serde_json::to_value(&payload).unwrap();
+        let parts = &json["contents"][0]["parts"];
+
+        assert_eq!(parts[0], serde_json::json!({"text": "What is in these?"}));
+        assert_eq!(
+            parts[1],
+            serde_json::json!({"fileData": {"mimeType": "image/png", "fileUri": "https://example.com/cat.png?size=large"}})
+        );
+        assert_eq!(
+            parts[2],
+            serde_json::json!({"inlineData": {"mimeType": "application/pdf", "data": "JVBE"}})
+        );
+    }
+
+    #[test]
+    fn test_map_google_error() {
+        let body = r#"{"error": {"code": 429, "message": "Quota exceeded", "status": "RESOURCE_EXHAUSTED",
+            "details": [{"@type": "type.googleapis.com/google.rpc.RetryInfo", "retryDelay": "29.5s"}]}}"#;
+        match map_google_error(429, body) {
+            ProviderError::RateLimitError { retry_after, .. } => assert_eq!(retry_after, Some(30)),
+            e => panic!("unexpected error: {:?}", e),
+        }
+
+        let body = r#"{"error": {"code": 400, "message": "API key not valid", "status": "INVALID_ARGUMENT",
+            "details": [{"@type": "type.googleapis.com/google.rpc.ErrorInfo", "reason": "API_KEY_INVALID"}]}}"#;
+        assert!(matches!(
+            map_google_error(400, body),
+            ProviderError::AuthenticationError(_)
+        ));
+
+        let body = r#"{"error": {"code": 404, "message": "models/foo is not found", "status": "NOT_FOUND"}}"#;
+        assert!(matches!(map_google_error(404, body), ProviderError::InvalidModel(_)));
+
+        let body = r#"{"error": {"code": 500, "message": "Internal error", "status": "INTERNAL"}}"#;
+        let err = map_google_error(500, body);
+        assert!(err.is_retryable());
+
+        assert!(matches!(
+            map_google_error(502, "Bad Gateway"),
+            ProviderError::ApiError { status_code: 502, .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_discover_models_pages_and_validates() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/models"))
+            .and(query_param("pageToken", "page-2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "models": [{
+                    "name": "models/gemini-2.0-flash",
+                    "supportedGenerationMethods": ["generateContent", "countTokens"]
+                }]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/models"))
+            .and(header("x-goog-api-key", "test-key"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "models": [
+                    {
+                        "name": "models/gemini-1.5-pro-002",
+                        "supportedGenerationMethods": ["generateContent"]
+                    },
+                    {
+                        "name": "models/text-embedding-004",
+                        "supportedGenerationMethods": ["embedContent"]
+                    }
+                ],
+                "nextPageToken": "page-2"
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        assert!(provider.validate_model("gemini-2.0-flash").is_err());
+
+        let models = provider.discover_models().await.unwrap();
+        assert_eq!(models, vec!["gemini-1.5-pro-002", "gemini-2.0-flash"]);
+        assert!(provider.validate_model("gemini-2.0-flash").is_ok());
+        // The static list still applies
+        assert!(provider.validate_model("gemini-1.0-pro").is_ok());
+
+        // Served from the cache
+        assert_eq!(provider.discover_models().await.unwrap(), models);
+    }
+
+    #[tokio::test]
+    async fn test_stream_parses_chunks_and_usage() {
+        let server = MockServer::start().await;
+        let body = sse_body(&[
+            r#"{"candidates": [{"content": {"role": "model", "parts": [{"text": "One"}]}, "index": 0}]}"#,
+            r#"{"candidates": [{"content": {"role": "model", "parts": [{"text": ", two"}]}, "index": 0}],
+                "usageMetadata": {"promptTokenCount": 11}}"#,
+            r#"{"candidates": [{"content": {"role": "model", "parts": [{"text": ", three."}]}, "finishReason": "STOP", "index": 0}],
+                "usageMetadata": {"promptTokenCount": 11, "candidatesTokenCount": 6, "totalTokenCount": 17},
+                "modelVersion": "gemini-1.5-flash-002"}"#,
+        ]);
+
+        Mock::given(method("POST"))
+            .and(path("/models/gemini-1.5-flash:streamGenerateContent"))
+            .and(query_param("alt", "sse"))
+            .and(header("x-goog-api-key", "test-key"))
+            .and(body_partial_json(serde_json::json!({
+                "systemInstruction": {"parts": [{"text": "Be brief"}]},
+                "generationConfig": {"maxOutputTokens": 64}
+            })))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_raw(body, "text/event-stream"),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        let result = provider
+            .complete(test_request(), &TimingEngine::new())
+            .await
+            .unwrap();
+
+        assert_eq!(result.content, "One, two, three.");
+        assert_eq!(result.token_events.len(), 3);
+        assert_eq!(result.token_events[2].sequence, 2);
+        assert!(result.token_events[0].inter_token_latency.is_none());
+        assert!(result.token_events[1].inter_token_latency.is_some());
+        assert_eq!(result.metadata.input_tokens, Some(11));
+        assert_eq!(result.metadata.output_tokens, Some(6));
+        assert_eq!(result.metadata.thinking_tokens, None);
+    }
+
+    #[tokio::test]
+    async fn test_complete_non_streaming() {
+        let server = MockServer::start().await;
+        let body = r#"{"candidates": [{"content": {"role": "model", "parts": [{"text": "Plan", "thought": true}, {"text": "One, two, three."}]}, "finishReason": "STOP", "index": 0}],
+            "usageMetadata": {"promptTokenCount": 11, "candidatesTokenCount": 6, "thoughtsTokenCount": 4, "totalTokenCount": 21}}"#;
+
+        Mock::given(method("POST"))
+            .and(path("/models/gemini-1.5-flash:generateContent"))
+            .and(header("x-goog-api-key", "test-key"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(body, "application/json"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        let result = provider
+            .complete_non_streaming(test_request(), &TimingEngine::new())
+            .await
+            .unwrap();
+
+        assert_eq!(result.content, "One, two, three.");
+        assert!(result.token_events.is_empty());
+        assert_eq!(result.metadata.input_tokens, Some(11));
+        assert_eq!(result.metadata.output_tokens, Some(6));
+        assert_eq!(result.metadata.thinking_tokens, Some(4));
+        assert!(result.body.unwrap().time_to_parsed > Duration::ZERO);
+    }
+
+    #[tokio::test]
+    async fn test_embed_batch() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/models/text-embedding-004:batchEmbedContents"))
+            .and(body_partial_json(serde_json::json!({
+                "requests": [
+                    {"model": "models/text-embedding-004", "content": {"parts": [{"text": "a"}]}, "outputDimensionality": 2},
+                    {"model": "models/text-embedding-004", "content": {"parts": [{"text": "b"}]}, "outputDimensionality": 2}
+                ]
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "embeddings": [{"values": [0.1, 0.2]}, {"values": [0.3, 0.4]}]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        let request = EmbeddingRequest::builder()
+            .model("text-embedding-004")
+            .inputs(["a", "b"])
+            .dimensions(2)
+            .build();
+        let result = provider.embed(request, &TimingEngine::new()).await.unwrap();
+
+        assert_eq!(result.embeddings, vec![vec![0.1, 0.2], vec![0.3, 0.4]]);
+        assert_eq!(result.metadata.input_tokens, None);
+    }
+
+    #[tokio::test]
+    async fn test_embed_single() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/models/text-embedding-004:embedContent"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "embedding": {"values": [0.5, 0.5, 0.5]}
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        let request = EmbeddingRequest::builder()
+            .model("text-embedding-004")
+            .input("a")
+            .build();
+        let result = provider.embed(request, &TimingEngine::new()).await.unwrap();
+
+        assert_eq!(result.vector_count(), 1);
+        assert_eq!(result.dimensions(), Some(3));
+    }
+
+    #[tokio::test]
+    async fn test_stream_skips_thought_parts() {
+        let server = MockServer::start().await;
+        let body = sse_body(&[
+            r#"{"candidates": [{"content": {"parts": [{"text": "Planning...", "thought": true}]}}]}"#,
+            r#"{"candidates": [{"content": {"parts": [{"text": "Answer"}]}, "finishReason": "STOP"}],
+                "usageMetadata": {"promptTokenCount": 4, "candidatesTokenCount": 1, "thoughtsTokenCount": 20}}"#,
+        ]);
+
+        Mock::given(method("POST"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_raw(body, "text/event-stream"),
+            )
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        let result = provider
+            .complete(test_request(), &TimingEngine::new())
+            .await
+            .unwrap();
+
+        assert_eq!(result.content, "Answer");
+        assert_eq!(result.token_events.len(), 1);
+        assert_eq!(result.metadata.thinking_tokens, Some(20));
+    }
+
+    #[tokio::test]
+    async fn test_stream_safety_block() {
+        let server = MockServer::start().await;
+        let body = sse_body(&[
+            r#"{"candidates": [{"content": {"parts": [{"text": "Well"}]}}]}"#,
+            r#"{"candidates": [{"finishReason": "SAFETY"}]}"#,
+        ]);
+
+        Mock::given(method("POST"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_raw(body, "text/event-stream"),
+            )
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        let err = provider
+            .complete(test_request(), &TimingEngine::new())
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, ProviderError::ContentFilterError(_)));
+    }
+
+    #[tokio::test]
+    async fn test_stream_maps_error_status() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(429).set_body_string(
+                r#"{"error": {"code": 429, "message": "Resource has been exhausted", "status": "RESOURCE_EXHAUSTED"}}"#,
+            ))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = GoogleProvider::builder()
+            .api_key("test-key")
+            .base_url(server.uri())
+            .max_retries(0)
+            .build();
+        let result = provider.stream(test_request(), &TimingEngine::new()).await;
+
+        match result {
+            Err(ProviderError::RateLimitError { message, .. }) => {
+                assert_eq!(message, "Resource has been exhausted");
+            }
+            Err(e) => panic!("unexpected error: {:?}", e),
+            Ok(_) => panic!("expected an error"),
+        }
+    }
+
     #[tokio::test]
     async fn test_health_check() {
-        let provider = GoogleProvider::new("test-key");
-        // Stub implementation always returns Ok
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/models"))
+            .and(header("x-goog-api-key", "test-key"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(r#"{"models": []}"#))
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
         assert!(provider.health_check().await.is_ok());
     }
+
+    #[tokio::test]
+    async fn test_health_check_invalid_key() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/models"))
+            .respond_with(ResponseTemplate::new(403).set_body_string(
+                r#"{"error": {"code": 403, "message": "Permission denied", "status": "PERMISSION_DENIED"}}"#,
+            ))
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        assert!(matches!(
+            provider.health_check().await,
+            Err(ProviderError::AuthenticationError(_))
+        ));
+    }
 }
@@ -0,0 +1,186 @@
+//! Response header capture and server-reported signals
+//!
+//! Adapters record the response headers of each request in
+//! [`ResponseMetadata::headers`](crate::traits::ResponseMetadata::headers),
+//! with sensitive values redacted, and extract the headers providers use to
+//! report server-side timing, request IDs and rate-limit state into
+//! [`ServerSignals`].
+
+use reqwest::header::HeaderMap;
+use std::time::Duration;
+
+/// Headers whose values are never recorded
+pub const DEFAULT_REDACTED_HEADERS: &[&str] = &[
+    "authorization",
+    "proxy-authorization",
+    "cookie",
+    "set-cookie",
+    "x-api-key",
+    "api-key",
+    "x-goog-api-key",
+];
+
+/// Replacement for redacted header values
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Headers carrying the provider's request ID, in order of preference
+const REQUEST_ID_HEADERS: &[&str] = &[
+    "x-request-id",
+    "request-id",
+    "x-amzn-requestid",
+    "apim-request-id",
+];
+
+/// Record response headers, redacting the default list and `redact`
+///
+/// Header names are matched case-insensitively. Values that are not valid
+/// UTF-8 are skipped.
+pub fn capture(headers: &HeaderMap, redact: &[String]) -> Vec<(String, String)> {
+    headers
+        .iter()
+        .filter_map(|(name, value)| {
+            let name = name.as_str();
+            let redacted = DEFAULT_REDACTED_HEADERS.contains(&name)
+                || redact.iter().any(|r| r.eq_ignore_ascii_case(name));
+            let value = if redacted {
+                REDACTED.to_string()
+            } else {
+                value.to_str().ok()?.to_string()
+            };
+            Some((name.to_string(), value))
+        })
+        .collect()
+}
+
+/// Server-side signals reported in response headers
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ServerSignals {
+    /// Provider-assigned request ID (`x-request-id`, `request-id`,
+    /// `x-amzn-requestid` or `apim-request-id`)
+    pub request_id: Option<String>,
+    /// Server processing time (`openai-processing-ms`)
+    pub processing_time: Option<Duration>,
+    /// Rate-limit state after this request
+    pub rate_limit: RateLimitStatus,
+}
+
+/// Rate-limit headers (`x-ratelimit-*` or `anthropic-ratelimit-*`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RateLimitStatus {
+    /// Requests allowed per window
+    pub requests_limit: Option<u64>,
+    /// Requests left in the current window
+    pub requests_remaining: Option<u64>,
+    /// Tokens allowed per window
+    pub tokens_limit: Option<u64>,
+    /// Tokens left in the current window
+    pub tokens_remaining: Option<u64>,
+}
+
+impl ServerSignals {
+    /// Extract the signals from response headers
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        let text = |name: &str| headers.get(name)?.to_str().ok().map(str::trim);
+        let number = |names: [&str; 2]| names.iter().find_map(|n| text(n)?.parse::<u64>().ok());
+
+        // Fractional values appear behind some proxies
+        let processing_time = text("openai-processing-ms")
+            .and_then(|v| v.parse::<f64>().ok())
+            .filter(|ms| ms.is_finite() && *ms >= 0.0)
+            .map(|ms| Duration::from_secs_f64(ms / 1000.0));
+
+        Self {
+            request_id: REQUEST_ID_HEADERS
+                .iter()
+                .find_map(|n| text(n))
+                .map(str::to_string),
+            processing_time,
+            rate_limit: RateLimitStatus {
+                requests_limit: number([
+                    "x-ratelimit-limit-requests",
+                    "anthropic-ratelimit-requests-limit",
+                ]),
+                requests_remaining: number([
+                    "x-ratelimit-remaining-requests",
+                    "anthropic-ratelimit-requests-remaining",
+                ]),
+                tokens_limit: number([
+                    "x-ratelimit-limit-tokens",
+                    "anthropic-ratelimit-tokens-limit",
+                ]),
+                tokens_remaining: number([
+                    "x-ratelimit-remaining-tokens",
+                    "anthropic-ratelimit-tokens-remaining",
+                ]),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    fn header_map(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.insert(*name, HeaderValue::from_static(value));
+        }
+        headers
+    }
+
+    #[test]
+    fn test_capture_redacts() {
+        let headers = header_map(&[
+            ("content-type", "text/event-stream"),
+            ("set-cookie", "session=secret"),
+            ("x-internal-token", "secret"),
+        ]);
+
+        let captured = capture(&headers, &["X-Internal-Token".to_string()]);
+        let value = |name: &str| {
+            captured
+                .iter()
+                .find(|(n, _)| n == name)
+                .map(|(_, v)| v.as_str())
+        };
+        assert_eq!(value("content-type"), Some("text/event-stream"));
+        assert_eq!(value("set-cookie"), Some(REDACTED));
+        assert_eq!(value("x-internal-token"), Some(REDACTED));
+    }
+
+    #[test]
+    fn test_openai_signals() {
+        let headers = header_map(&[
+            ("x-request-id", "req_123"),
+            ("openai-processing-ms", "245"),
+            ("x-ratelimit-limit-requests", "5000"),
+            ("x-ratelimit-remaining-requests", "4999"),
+            ("x-ratelimit-remaining-tokens", "159000"),
+        ]);
+
+        let signals = ServerSignals::from_headers(&headers);
+        assert_eq!(signals.request_id.as_deref(), Some("req_123"));
+        assert_eq!(signals.processing_time, Some(Duration::from_millis(245)));
+        assert_eq!(signals.rate_limit.requests_limit, Some(5000));
+        assert_eq!(signals.rate_limit.requests_remaining, Some(4999));
+        assert_eq!(signals.rate_limit.tokens_remaining, Some(159000));
+        assert_eq!(signals.rate_limit.tokens_limit, None);
+    }
+
+    #[test]
+    fn test_anthropic_signals() {
+        let headers = header_map(&[
+            ("request-id", "req_018EeWyXxfu5pfWkrYcMdjWG"),
+            ("anthropic-ratelimit-requests-remaining", "49"),
+            ("anthropic-ratelimit-tokens-remaining", "39000"),
+        ]);
+
+        let signals = ServerSignals::from_headers(&headers);
+        assert_eq!(signals.request_id.as_deref(), Some("req_018EeWyXxfu5pfWkrYcMdjWG"));
+        assert_eq!(signals.processing_time, None);
+        assert_eq!(signals.rate_limit.requests_remaining, Some(49));
+        assert_eq!(signals.rate_limit.tokens_remaining, Some(39000));
+    }
+}
@@ -7,10 +7,32 @@
 //!
 //! - **OpenAI**: Full implementation with GPT-4, GPT-4o, and GPT-3.5 support
 //! - **Anthropic**: Complete Claude integration with extended thinking support
-//! - **Google**: Stub implementation for Gemini (coming soon)
-//! - **Streaming**: Server-Sent Events (SSE) with fine-grained token timing
-//! - **Retries**: Automatic retry logic with exponential backoff
-//! - **Cost Calculation**: Accurate pricing for all supported models
+//! - **Google**: Gemini streaming via `streamGenerateContent` with usage reporting
+//! - **AWS Bedrock**: SigV4-signed `ConverseStream`/`InvokeModelWithResponseStream`
+//! - **Azure OpenAI**: Deployment-based routing with content filter reporting
+//! - **OpenAI-compatible**: Self-hosted servers (vLLM, TGI, llama.cpp, Ollama)
+//! - **OpenAI Realtime**: Text sessions over WebSocket, with connection,
+//!   session setup and per-response TTFT timings
+//! - **External process**: Any backend behind a command speaking a JSON
+//!   stdin/stdout protocol
+//! - **Streaming**: Server-Sent Events (SSE) decoded in-house, timed at the
+//!   arrival of each network read
+//! - **Non-streaming**: `stream: false` requests with body size, download and
+//!   parse timing, for comparison against streamed latency
+//! - **Embeddings**: Batch embedding requests for OpenAI, Gemini and
+//!   OpenAI-compatible servers
+//! - **Network timing**: DNS, TCP connect, TLS handshake and time to first byte
+//! - **Server signals**: Redacted response headers, provider request IDs,
+//!   server processing time and rate-limit state
+//! - **Registry**: Provider factories by type, configured per named instance
+//! - **Model discovery**: Models listed from provider endpoints and cached,
+//!   with static lists as fallback for validation
+//! - **Retries**: Initial requests retried with exponential backoff,
+//!   honoring `Retry-After`; streams are never reconnected mid-response
+//! - **Cost Calculation**: Versioned price tables with cached-input, reasoning,
+//!   batch and long-context rates, bundled and overridable
+//! - **Token counting**: Local BPE (tiktoken) and SentencePiece tokenizers for
+//!   providers that report no usage
 //!
 //! # Example
 //!
@@ -70,6 +92,26 @@
 //!     .build();
 //! ```
 //!
+//! Requests go to Chat Completions by default; select the Responses API to
+//! compare the two for the same model:
+//!
+//! ```no_run
+//! use llm_latency_lens_providers::openai::{OpenAIApi, OpenAIProvider};
+//!
+//! let provider = OpenAIProvider::builder()
+//!     .api_key("sk-...")
+//!     .api(OpenAIApi::Responses)
+//!     .build();
+//! ```
+//!
+//! The Realtime API runs over a WebSocket session instead of SSE:
+//!
+//! ```no_run
+//! use llm_latency_lens_providers::realtime::RealtimeProvider;
+//!
+//! let provider = RealtimeProvider::new("sk-...");
+//! ```
+//!
 //! ## Anthropic
 //!
 //! The Anthropic provider supports all Claude models including extended
@@ -87,13 +129,118 @@
 //!
 //! ## Google
 //!
-//! The Google provider is currently a stub. Full implementation coming soon:
+//! The Google provider streams Gemini responses and reports the token counts
+//! from `usageMetadata`:
 //!
 //! ```no_run
 //! use llm_latency_lens_providers::google::GoogleProvider;
 //!
-//! let provider = GoogleProvider::new("AIza...");
-//! // Note: stream() will return an error until implemented
+//! let provider = GoogleProvider::builder()
+//!     .api_key("AIza...")
+//!     .max_retries(3)
+//!     .build();
+//! ```
+//!
+//! ## AWS Bedrock
+//!
+//! The Bedrock provider signs requests with SigV4 using credentials from the
+//! environment or the shared credentials file, and decodes the binary event
+//! stream framing of Bedrock's streaming APIs:
+//!
+//! ```no_run
+//! use llm_latency_lens_providers::bedrock::{BedrockApi, BedrockProvider};
+//!
+//! # fn example() -> Result<(), llm_latency_lens_providers::ProviderError> {
+//! let provider = BedrockProvider::builder()
+//!     .region("us-west-2")
+//!     .api(BedrockApi::Converse)
+//!     .build()?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! ## Azure OpenAI
+//!
+//! The Azure OpenAI provider routes requests by deployment name. Mapping each
+//! deployment to the model it serves enables cost calculation:
+//!
+//! ```no_run
+//! use llm_latency_lens_providers::azure::AzureOpenAIProvider;
+//!
+//! let provider = AzureOpenAIProvider::builder()
+//!     .endpoint("https://my-resource.openai.azure.com")
+//!     .api_key("...")
+//!     .api_version("2024-10-21")
+//!     .deployment("chat-prod", "gpt-4o")
+//!     .build();
+//! ```
+//!
+//! ## OpenAI-Compatible Servers
+//!
+//! The generic provider targets any server speaking the Chat Completions
+//! protocol and accepts any model name:
+//!
+//! ```no_run
+//! use llm_latency_lens_providers::generic::GenericOpenAIProvider;
+//!
+//! # fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let provider = GenericOpenAIProvider::builder()
+//!     .base_url("http://localhost:8000/v1")
+//!     .header("X-Tenant", "benchmarks")
+//!     .include_usage(true)
+//!     .build()?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! ## External Process
+//!
+//! The exec provider runs a command per request, sending the request as JSON
+//! on stdin and timing the newline-delimited events it writes to stdout (see
+//! [`exec`] for the protocol):
+//!
+//! ```no_run
+//! use llm_latency_lens_providers::exec::ExecProvider;
+//!
+//! # fn example() -> Result<(), llm_latency_lens_providers::ProviderError> {
+//! let provider = ExecProvider::builder()
+//!     .program("python3")
+//!     .arg("gateway_client.py")
+//!     .env("GATEWAY_REGION", "eu-west-1")
+//!     .build()?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! ## Provider Registry
+//!
+//! [`ProviderRegistry`] creates providers by type from a [`ProviderConfig`],
+//! so several instances of one type can target different endpoints. Custom
+//! adapters register a factory of their own, with `register_keyless` for
+//! those that can run without an API key:
+//!
+//! ```no_run
+//! use llm_latency_lens_providers::{GenericOpenAIProvider, ProviderConfig, ProviderRegistry};
+//!
+//! # fn example() -> Result<(), llm_latency_lens_providers::ProviderError> {
+//! let mut registry = ProviderRegistry::builtin();
+//! registry.register_keyless("internal-gateway", |config| {
+//!     let mut builder = GenericOpenAIProvider::builder()
+//!         .base_url(config.endpoint.clone().unwrap_or_default())
+//!         .header("X-Client", "latency-lens");
+//!     if let Some(ref key) = config.api_key {
+//!         builder = builder.api_key(key);
+//!     }
+//!     Ok(Box::new(builder.build()?))
+//! });
+//!
+//! let config = ProviderConfig {
+//!     endpoint: Some("http://gateway.internal:8000/v1".to_string()),
+//!     ..ProviderConfig::default()
+//! };
+//! let provider = registry.create("internal-gateway", &config)?;
+//! # Ok(())
+//! # }
 //! ```
 //!
 //! # Error Handling
@@ -145,51 +292,120 @@
 //!
 //! # Cost Calculation
 //!
-//! Each provider implements accurate cost calculation based on current
-//! pricing (as of 2024):
+//! Requests are priced from their reported usage with the [`PriceTable`]
+//! bundled with this crate. A user table can override or extend it, and
+//! prices are looked up by date, so older results can be re-costed:
 //!
 //! ```no_run
 //! use llm_latency_lens_providers::openai::OpenAIProvider;
-//! use llm_latency_lens_providers::traits::Provider;
+//! use llm_latency_lens_providers::traits::{Provider, TokenUsage};
+//! use llm_latency_lens_providers::{PriceTable, PricingMode};
+//! use std::path::Path;
 //!
+//! # fn example() -> Result<(), llm_latency_lens_providers::ProviderError> {
 //! let provider = OpenAIProvider::new("sk-...");
 //!
-//! // Calculate cost for GPT-4o
+//! // Calculate cost for GPT-4o with the bundled prices
 //! let cost = provider.calculate_cost("gpt-4o", 1000, 2000);
 //! if let Some(usd) = cost {
 //!     println!("Estimated cost: ${:.6}", usd);
 //! }
+//!
+//! // Price cached input in batch mode with an internal price list
+//! let table = PriceTable::bundled()
+//!     .clone()
+//!     .with_overrides(PriceTable::load(Path::new("prices.toml"))?);
+//! let usage = TokenUsage {
+//!     input_tokens: Some(12_000),
+//!     output_tokens: Some(800),
+//!     cache_read_tokens: Some(10_240),
+//!     ..TokenUsage::default()
+//! };
+//! let cost = provider.price_usage(&table, "gpt-4o", &usage, chrono::Utc::now(), PricingMode::Batch);
+//! # Ok(())
+//! # }
 //! ```
 
 pub mod anthropic;
+pub mod azure;
+pub mod bedrock;
+pub mod body;
+pub mod embeddings;
 pub mod error;
+pub mod exec;
+pub mod generic;
 pub mod google;
+pub mod headers;
+pub mod models;
+pub mod network;
 pub mod openai;
+pub mod pricing;
+pub mod realtime;
+pub mod registry;
+mod responses;
+pub mod retry;
+pub mod sse;
+pub mod tokenizer;
 pub mod traits;
 
 // Re-export commonly used types
+pub use body::BodyStats;
+pub use embeddings::{EmbeddingProvider, EmbeddingRequest, EmbeddingResult};
 pub use error::{ProviderError, Result};
+pub use headers::{RateLimitStatus, ServerSignals};
+pub use models::ModelCache;
+pub use network::{NetworkProbe, NetworkTimings, ReadStats};
+pub use pricing::{ModelPrice, PriceTable, PricingMode};
+pub use retry::{RetryPolicy, RetryStats};
 pub use traits::{
-    CompletionResult, Message, MessageRole, Provider, ResponseMetadata, StreamingRequest,
-    StreamingResponse,
+    CompletionResult, ContentPart, Message, MessageRole, Provider, ResponseMetadata, SharedUsage,
+    StreamingRequest, StreamingResponse, TokenUsage, Tool, ToolChoice,
 };
 
 // Re-export provider implementations
 pub use anthropic::AnthropicProvider;
+pub use azure::AzureOpenAIProvider;
+pub use bedrock::BedrockProvider;
+pub use exec::ExecProvider;
+pub use generic::GenericOpenAIProvider;
 pub use google::GoogleProvider;
-pub use openai::OpenAIProvider;
+pub use openai::{OpenAIApi, OpenAIProvider};
+pub use realtime::RealtimeProvider;
+pub use registry::{ProviderConfig, ProviderRegistry};
+pub use tokenizer::Tokenizer;
 
 /// Version of the providers crate
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 
 /// Create a provider from a string identifier
 ///
-/// This is a convenience function for dynamically selecting providers.
+/// This is a convenience function for dynamically selecting providers with
+/// the [built-in registry](ProviderRegistry::builtin) and only an API key;
+/// use [`ProviderRegistry::create`] to pass a full [`ProviderConfig`].
 ///
 /// # Arguments
 ///
-/// * `provider` - Provider identifier ("openai", "anthropic", "google")
-/// * `api_key` - API key for the provider
+/// * `provider` - Provider identifier ("openai", "openai-responses", "openai-realtime",
+///   "anthropic", "google", "bedrock", "azure", "generic", "exec")
+/// * `api_key` - API key for the provider. For Bedrock this is an optional
+///   Bedrock API key; when empty, AWS credentials are loaded from the environment.
+///   For the generic provider an empty key disables authentication.
+///
+/// "openai-responses" is the OpenAI provider using the Responses API in place
+/// of Chat Completions. "openai-realtime" runs text sessions on the Realtime
+/// API over WebSocket.
+///
+/// Azure OpenAI reads the resource endpoint from `AZURE_OPENAI_ENDPOINT` and
+/// the API version from `AZURE_OPENAI_API_VERSION` (optional); requests name
+/// a deployment in place of a model.
+///
+/// The generic OpenAI-compatible provider reads its base URL from
+/// `GENERIC_BASE_URL` and extra headers from `GENERIC_HEADERS`
+/// (comma-separated `Name=value` pairs, optional).
+///
+/// The exec provider reads its command line from `EXEC_COMMAND`; a non-empty
+/// API key is passed to the command in
+/// [`LLM_LENS_API_KEY`](exec::API_KEY_ENV).
 ///
 /// # Example
 ///
@@ -202,20 +418,36 @@ pub fn create_provider(
     provider: &str,
     api_key: impl Into<String>,
 ) -> Result<Box<dyn Provider>> {
-    match provider.to_lowercase().as_str() {
-        "openai" => Ok(Box::new(OpenAIProvider::new(api_key))),
-        "anthropic" => Ok(Box::new(AnthropicProvider::new(api_key))),
-        "google" => Ok(Box::new(GoogleProvider::new(api_key))),
-        _ => Err(ProviderError::ConfigError(format!(
-            "Unknown provider: {}. Supported providers: openai, anthropic, google",
-            provider
-        ))),
-    }
+    ProviderRegistry::builtin().create(provider, &ProviderConfig::with_api_key(api_key))
+}
+
+/// Create an embeddings provider from a string identifier
+///
+/// Supports "openai", "google" and "generic", configured as in
+/// [`create_provider`].
+///
+/// # Example
+///
+/// ```no_run
+/// use llm_latency_lens_providers::create_embedding_provider;
+///
+/// let provider = create_embedding_provider("openai", "sk-...").unwrap();
+/// ```
+pub fn create_embedding_provider(
+    provider: &str,
+    api_key: impl Into<String>,
+) -> Result<Box<dyn EmbeddingProvider>> {
+    ProviderRegistry::builtin().create_embedding(provider, &ProviderConfig::with_api_key(api_key))
 }
 
 /// List all supported providers
 pub fn supported_providers() -> Vec<&'static str> {
-    vec!["openai", "anthropic", "google"]
+    ProviderRegistry::builtin().providers()
+}
+
+/// List the providers that support embeddings
+pub fn supported_embedding_providers() -> Vec<&'static str> {
+    ProviderRegistry::builtin().embedding_providers()
 }
 
 #[cfg(test)]
@@ -230,6 +462,18 @@ mod tests {
         assert_eq!(provider.name(), "openai");
     }
 
+    #[test]
+    fn test_create_provider_openai_responses() {
+        let provider = create_provider("openai-responses", "test-key").unwrap();
+        assert_eq!(provider.name(), "openai");
+    }
+
+    #[test]
+    fn test_create_provider_openai_realtime() {
+        let provider = create_provider("openai-realtime", "test-key").unwrap();
+        assert_eq!(provider.name(), "openai-realtime");
+    }
+
     #[test]
     fn test_create_provider_anthropic() {
         let provider = create_provider("anthropic", "test-key");
@@ -246,6 +490,14 @@ mod tests {
         assert_eq!(provider.name(), "google");
     }
 
+    #[test]
+    fn test_create_provider_bedrock() {
+        let provider = create_provider("bedrock", "bedrock-api-key");
+        assert!(provider.is_ok());
+        let provider = provider.unwrap();
+        assert_eq!(provider.name(), "aws-bedrock");
+    }
+
     #[test]
     fn test_create_provider_unknown() {
         let provider = create_provider("unknown", "test-key");
@@ -259,13 +511,26 @@ mod tests {
         assert!(create_provider("Google", "test-key").is_ok());
     }
 
+    #[test]
+    fn test_create_embedding_provider() {
+        let provider = create_embedding_provider("openai", "test-key").unwrap();
+        assert_eq!(provider.name(), "openai");
+        assert!(create_embedding_provider("anthropic", "test-key").is_err());
+    }
+
     #[test]
     fn test_supported_providers() {
         let providers = supported_providers();
-        assert_eq!(providers.len(), 3);
+        assert_eq!(providers.len(), 9);
         assert!(providers.contains(&"openai"));
+        assert!(providers.contains(&"openai-responses"));
+        assert!(providers.contains(&"openai-realtime"));
         assert!(providers.contains(&"anthropic"));
         assert!(providers.contains(&"google"));
+        assert!(providers.contains(&"bedrock"));
+        assert!(providers.contains(&"azure"));
+        assert!(providers.contains(&"generic"));
+        assert!(providers.contains(&"exec"));
     }
 
     #[test]
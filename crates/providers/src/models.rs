@@ -0,0 +1,128 @@
+//! Model discovery through provider list-models endpoints
+//!
+//! Static [`supported_models`](crate::traits::Provider::supported_models)
+//! lists go stale as providers release models. Adapters with a models
+//! endpoint implement [`Provider::list_models`](crate::traits::Provider::list_models),
+//! and [`Provider::discover_models`](crate::traits::Provider::discover_models)
+//! keeps what it returns in the adapter's [`ModelCache`], which
+//! `validate_model` accepts models from alongside the static list. Until
+//! models are discovered, or when discovery fails, validation falls back to
+//! the static list.
+
+use crate::error::{ProviderError, Result};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
+
+/// How long discovered models are trusted before being listed again
+pub const DEFAULT_MODEL_CACHE_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// Models discovered from a provider's models endpoint
+#[derive(Debug)]
+pub struct ModelCache {
+    ttl: Duration,
+    entry: RwLock<Option<(Instant, Vec<String>)>>,
+}
+
+impl Default for ModelCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_MODEL_CACHE_TTL)
+    }
+}
+
+impl ModelCache {
+    /// Create an empty cache whose entries expire after `ttl`
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entry: RwLock::new(None),
+        }
+    }
+
+    /// Discovered models, unless none were discovered or they have expired
+    pub fn get(&self) -> Option<Vec<String>> {
+        let entry = self.entry.read().ok()?;
+        let (discovered_at, models) = entry.as_ref()?;
+        (discovered_at.elapsed() < self.ttl).then(|| models.clone())
+    }
+
+    /// Store newly discovered models
+    pub fn set(&self, models: Vec<String>) {
+        if let Ok(mut entry) = self.entry.write() {
+            *entry = Some((Instant::now(), models));
+        }
+    }
+
+    /// Forget discovered models, so the next discovery lists them again
+    pub fn clear(&self) {
+        if let Ok(mut entry) = self.entry.write() {
+            *entry = None;
+        }
+    }
+}
+
+/// Parse a successful models endpoint response
+pub(crate) async fn read_list<T: DeserializeOwned>(response: reqwest::Response) -> Result<T> {
+    let bytes = response.bytes().await.map_err(ProviderError::from_reqwest)?;
+    serde_json::from_slice(&bytes).map_err(ProviderError::from_json_error)
+}
+
+/// OpenAI-style model list (`GET /models`), also served by Anthropic and
+/// OpenAI-compatible servers
+#[derive(Debug, Deserialize)]
+pub(crate) struct ModelList {
+    pub data: Vec<ModelEntry>,
+    /// More pages follow (Anthropic)
+    #[serde(default)]
+    pub has_more: bool,
+    /// ID to continue after (Anthropic)
+    #[serde(default)]
+    pub last_id: Option<String>,
+}
+
+/// One entry of a [`ModelList`]
+#[derive(Debug, Deserialize)]
+pub(crate) struct ModelEntry {
+    pub id: String,
+}
+
+impl ModelList {
+    /// Model IDs, sorted
+    pub fn ids(self) -> Vec<String> {
+        let mut ids: Vec<String> = self.data.into_iter().map(|m| m.id).collect();
+        ids.sort();
+        ids
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_model_cache_expiry() {
+        let cache = ModelCache::default();
+        assert!(cache.get().is_none());
+
+        cache.set(vec!["gpt-4o".to_string()]);
+        assert_eq!(cache.get(), Some(vec!["gpt-4o".to_string()]));
+
+        cache.clear();
+        assert!(cache.get().is_none());
+
+        let expired = ModelCache::new(Duration::ZERO);
+        expired.set(vec!["gpt-4o".to_string()]);
+        assert!(expired.get().is_none());
+    }
+
+    #[test]
+    fn test_parse_model_list() {
+        let list: ModelList = serde_json::from_str(
+            r#"{"object":"list","data":[{"id":"gpt-4o","object":"model"},{"id":"dall-e-3"}]}"#,
+        )
+        .unwrap();
+        assert!(!list.has_more);
+        assert_eq!(list.ids(), vec!["dall-e-3", "gpt-4o"]);
+    }
+}
@@ -0,0 +1,591 @@
+//! Network-phase timing (DNS, TCP connect, TLS handshake, first byte, first
+//! stream event)
+//!
+//! reqwest does not report connection timings, so provider HTTP clients are
+//! instrumented at three points:
+//!
+//! - a DNS resolver that timestamps each lookup
+//! - a connector layer around reqwest's connector, whose future covers
+//!   DNS, TCP connect and the TLS handshake
+//! - a rustls session store; rustls consults it while building the
+//!   ClientHello, right after the TCP connection is established, so its first
+//!   lookup marks the end of TCP connect and the start of the TLS handshake
+//!
+//! Timestamps go to the [`NetworkProbe`] of the request whose task drives the
+//! connection. The probe is made current through a task-local while the
+//! request is sent.
+//!
+//! Connection phases are only recorded when a new connection is opened; a
+//! request served from the keep-alive pool reports just the first byte and
+//! first event.
+//!
+//! The probe also counts the reads of the response body and the stream events
+//! each read completed ([`ReadStats`]). Several events per read mean they were
+//! buffered somewhere on the way (often a proxy), which compresses the
+//! measured inter-token latency.
+//!
+//! When the initial request is retried ([`crate::retry`]), the probe counts
+//! the retries and the backoff between them. Phases stay measured from the
+//! start of the first attempt, so they include that backoff; the first byte
+//! is the one of the attempt that succeeded.
+//!
+//! Transports that do not go through reqwest (WebSocket) open their
+//! connection with [`connect`], which records the same phases directly. For
+//! them the first byte is the end of the WebSocket upgrade, and the probe
+//! also records when the session was ready and when the response was
+//! requested on it.
+
+use crate::retry::RetryStats;
+use llm_latency_lens_core::timing::TimingMeasurement;
+use llm_latency_lens_core::{Clock, Timestamp};
+use rustls::client::{
+    ClientSessionMemoryCache, ClientSessionStore, Tls12ClientSessionValue, Tls13ClientSessionValue,
+};
+use rustls::pki_types::ServerName;
+use rustls::NamedGroup;
+use std::future::Future;
+use std::io;
+use std::net::{IpAddr, SocketAddr};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio::net::TcpStream;
+use tower_layer::Layer;
+use tower_service::Service;
+
+const DNS_START: &str = "dns_start";
+const DNS_END: &str = "dns_end";
+const CONNECT_START: &str = "connect_start";
+const TLS_START: &str = "tls_start";
+const CONNECT_END: &str = "connect_end";
+const FIRST_BYTE: &str = "first_byte";
+const FIRST_EVENT: &str = "first_event";
+const SESSION_READY: &str = "session_ready";
+const RESPONSE_REQUESTED: &str = "response_requested";
+
+tokio::task_local! {
+    static CURRENT_PROBE: NetworkProbe;
+}
+
+/// Network-phase timings of a single request
+///
+/// Phases that did not happen (DNS for IP literals, connect and TLS on a
+/// reused connection, TLS for plain HTTP) are `None`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NetworkTimings {
+    /// DNS resolution time
+    pub dns: Option<Duration>,
+    /// TCP connect time
+    pub tcp_connect: Option<Duration>,
+    /// TLS handshake time
+    pub tls_handshake: Option<Duration>,
+    /// Time from the request start to the response headers
+    pub time_to_first_byte: Option<Duration>,
+    /// Time from the request start to the first stream event (SSE message or
+    /// event-stream frame), whether or not it carried a token
+    pub time_to_first_event: Option<Duration>,
+    /// Time from the request start until a realtime session was ready
+    /// (WebSocket transports only)
+    pub session_ready: Option<Duration>,
+    /// Time from the request start until the response was requested on the
+    /// open session (WebSocket transports only)
+    pub response_requested: Option<Duration>,
+}
+
+impl NetworkTimings {
+    /// Whether the request went over an already established connection
+    pub fn connection_reused(&self) -> bool {
+        self.time_to_first_byte.is_some() && self.tcp_connect.is_none()
+    }
+}
+
+/// Response-body reads of a single request and the events they delivered
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReadStats {
+    /// Number of reads (network chunks) of the response body
+    pub reads: u64,
+    /// Number of stream events decoded from the body
+    pub events: u64,
+    /// Most events completed by a single read
+    pub max_events_per_read: u64,
+}
+
+impl ReadStats {
+    /// Record one read that completed `events` events
+    pub fn record(&mut self, events: u64) {
+        self.reads += 1;
+        self.events += events;
+        self.max_events_per_read = self.max_events_per_read.max(events);
+    }
+
+    /// Mean events per read (None before the first read)
+    pub fn mean_events_per_read(&self) -> Option<f64> {
+        (self.reads > 0).then(|| self.events as f64 / self.reads as f64)
+    }
+}
+
+/// Per-request recorder for network-phase timestamps and body reads
+#[derive(Clone)]
+pub struct NetworkProbe(Arc<Mutex<ProbeState>>);
+
+struct ProbeState {
+    measurement: TimingMeasurement,
+    reads: ReadStats,
+    retries: RetryStats,
+}
+
+impl std::fmt::Debug for NetworkProbe {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("NetworkProbe").field(&self.timings()).finish()
+    }
+}
+
+impl NetworkProbe {
+    /// Start a probe; phases are measured from this point
+    pub fn new(clock: &Clock) -> Self {
+        Self(Arc::new(Mutex::new(ProbeState {
+            measurement: TimingMeasurement::new(clock),
+            reads: ReadStats::default(),
+            retries: RetryStats::default(),
+        })))
+    }
+
+    fn state(&self) -> MutexGuard<'_, ProbeState> {
+        self.0.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// The probe of the request being polled on this task, if any
+    fn current() -> Option<Self> {
+        CURRENT_PROBE.try_with(Clone::clone).ok()
+    }
+
+    /// Record a phase timestamp, keeping the first one for each label
+    fn mark(&self, label: &str) {
+        let mut state = self.state();
+        if state.measurement.checkpoint_time(label).is_none() {
+            state.measurement.checkpoint(label);
+        }
+    }
+
+    /// Record a phase at a timestamp taken earlier, keeping the first one
+    fn mark_at(&self, label: &str, at: Timestamp) {
+        let mut state = self.state();
+        if state.measurement.checkpoint_time(label).is_none() {
+            state.measurement.checkpoint_at(label, at);
+        }
+    }
+
+    /// Record the arrival of the response headers
+    pub fn first_byte(&self) {
+        self.mark(FIRST_BYTE);
+    }
+
+    /// Record the arrival of the first stream event
+    pub fn first_event(&self) {
+        self.mark(FIRST_EVENT);
+    }
+
+    /// Record the first stream event as arriving with the read at
+    /// `received_at`, the timestamp its tokens are timed from
+    pub fn first_event_at(&self, received_at: Timestamp) {
+        self.mark_at(FIRST_EVENT, received_at);
+    }
+
+    /// Record that a realtime session is ready for requests
+    pub fn session_ready(&self) {
+        self.mark(SESSION_READY);
+    }
+
+    /// Record that the response was requested on an open session
+    pub fn response_requested(&self) {
+        self.mark(RESPONSE_REQUESTED);
+    }
+
+    /// Record a read of the response body that completed `events` events
+    pub fn record_read(&self, events: u64) {
+        self.state().reads.record(events);
+    }
+
+    /// Body reads recorded so far
+    pub fn read_stats(&self) -> ReadStats {
+        self.state().reads
+    }
+
+    /// Record a failed attempt that is retried after `backoff`, discarding
+    /// its first byte
+    pub fn retried(&self, backoff: Duration) {
+        let mut state = self.state();
+        state.retries.record(backoff);
+        state.measurement.remove_checkpoint(FIRST_BYTE);
+    }
+
+    /// Retries recorded so far
+    pub fn retry_stats(&self) -> RetryStats {
+        self.state().retries
+    }
+
+    /// Make this probe current while `future` runs
+    pub async fn run<F: Future>(&self, future: F) -> F::Output {
+        CURRENT_PROBE.scope(self.clone(), future).await
+    }
+
+    /// Phase durations recorded so far
+    pub fn timings(&self) -> NetworkTimings {
+        let state = self.state();
+        let m = &state.measurement;
+
+        // TCP starts after DNS (when there was a lookup) and ends where TLS
+        // starts, or where the connector finished for plain HTTP
+        let tcp_from = if m.checkpoint_time(DNS_END).is_some() {
+            DNS_END
+        } else {
+            CONNECT_START
+        };
+        let tcp_to = if m.checkpoint_time(TLS_START).is_some() {
+            TLS_START
+        } else {
+            CONNECT_END
+        };
+
+        NetworkTimings {
+            dns: m.between(DNS_START, DNS_END),
+            tcp_connect: m.between(tcp_from, tcp_to),
+            tls_handshake: m.between(TLS_START, CONNECT_END),
+            time_to_first_byte: m.since_start(FIRST_BYTE),
+            time_to_first_event: m.since_start(FIRST_EVENT),
+            session_ready: m.since_start(SESSION_READY),
+            response_requested: m.since_start(RESPONSE_REQUESTED),
+        }
+    }
+
+    /// Recorded checkpoints as durations between consecutive phases
+    pub fn checkpoints(&self) -> Vec<(String, Duration)> {
+        self.state().measurement.checkpoint_durations()
+    }
+}
+
+/// Request timeout of provider HTTP clients unless configured otherwise
+pub(crate) const DEFAULT_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Add network-phase instrumentation to a provider's HTTP client
+pub(crate) fn instrument(builder: reqwest::ClientBuilder) -> reqwest::ClientBuilder {
+    builder
+        .dns_resolver(Arc::new(TimedResolver))
+        .connector_layer(ConnectTimerLayer)
+        .use_preconfigured_tls(tls_config())
+}
+
+/// A connection opened outside reqwest
+pub(crate) enum Connection {
+    /// Plain TCP
+    Plain(TcpStream),
+    /// TLS over TCP
+    Tls(Box<tokio_rustls::client::TlsStream<TcpStream>>),
+}
+
+/// Open a connection to `host:port`, with TLS when `tls` is given, recording
+/// DNS, TCP connect and the TLS handshake on `probe`
+pub(crate) async fn connect(
+    probe: &NetworkProbe,
+    host: &str,
+    port: u16,
+    tls: Option<Arc<rustls::ClientConfig>>,
+) -> io::Result<Connection> {
+    probe.mark(CONNECT_START);
+
+    // IP literals need no lookup
+    let addrs: Vec<SocketAddr> = match host.parse::<IpAddr>() {
+        Ok(ip) => vec![SocketAddr::new(ip, port)],
+        Err(_) => {
+            probe.mark(DNS_START);
+            let addrs = tokio::net::lookup_host((host, port)).await?.collect();
+            probe.mark(DNS_END);
+            addrs
+        }
+    };
+
+    let mut last_error = None;
+    let mut stream = None;
+    for addr in addrs {
+        match TcpStream::connect(addr).await {
+            Ok(s) => {
+                stream = Some(s);
+                break;
+            }
+            Err(e) => last_error = Some(e),
+        }
+    }
+    let stream = stream.ok_or_else(|| {
+        last_error.unwrap_or_else(|| {
+            io::Error::new(io::ErrorKind::NotFound, format!("No addresses for {}", host))
+        })
+    })?;
+    stream.set_nodelay(true)?;
+
+    let connection = match tls {
+        Some(config) => {
+            probe.mark(TLS_START);
+            let server_name = ServerName::try_from(host.to_string())
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+            let stream = tokio_rustls::TlsConnector::from(config)
+                .connect(server_name, stream)
+                .await?;
+            Connection::Tls(Box::new(stream))
+        }
+        None => Connection::Plain(stream),
+    };
+    probe.mark(CONNECT_END);
+    Ok(connection)
+}
+
+/// rustls configuration matching reqwest's defaults, plus the handshake timer
+pub(crate) fn tls_config() -> rustls::ClientConfig {
+    let mut roots = rustls::RootCertStore::empty();
+    roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+    let mut config = rustls::ClientConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()
+        .expect("Default TLS protocol versions are supported")
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+    config.resumption = rustls::client::Resumption::store(Arc::new(HandshakeTimer::default()));
+    config
+}
+
+/// DNS resolver that timestamps each lookup
+struct TimedResolver;
+
+impl reqwest::dns::Resolve for TimedResolver {
+    fn resolve(&self, name: reqwest::dns::Name) -> reqwest::dns::Resolving {
+        let probe = NetworkProbe::current();
+        Box::pin(async move {
+            if let Some(ref probe) = probe {
+                probe.mark(DNS_START);
+            }
+            // The port is replaced by reqwest
+            let addrs: Vec<_> = tokio::net::lookup_host((name.as_str(), 0)).await?.collect();
+            if let Some(probe) = probe {
+                probe.mark(DNS_END);
+            }
+            Ok(Box::new(addrs.into_iter()) as reqwest::dns::Addrs)
+        })
+    }
+}
+
+/// Layer timing reqwest's connector (DNS + TCP + TLS)
+#[derive(Clone)]
+struct ConnectTimerLayer;
+
+impl<S> Layer<S> for ConnectTimerLayer {
+    type Service = ConnectTimer<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ConnectTimer { inner }
+    }
+}
+
+#[derive(Clone)]
+struct ConnectTimer<S> {
+    inner: S,
+}
+
+impl<S, R> Service<R> for ConnectTimer<S>
+where
+    S: Service<R>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<S::Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), S::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: R) -> Self::Future {
+        let probe = NetworkProbe::current();
+        if let Some(ref probe) = probe {
+            probe.mark(CONNECT_START);
+        }
+        let connecting = self.inner.call(request);
+        Box::pin(async move {
+            let connection = connecting.await?;
+            if let Some(probe) = probe {
+                probe.mark(CONNECT_END);
+            }
+            Ok(connection)
+        })
+    }
+}
+
+/// Session cache whose lookups mark the start of the TLS handshake
+#[derive(Debug)]
+struct HandshakeTimer {
+    inner: ClientSessionMemoryCache,
+}
+
+impl Default for HandshakeTimer {
+    fn default() -> Self {
+        Self {
+            inner: ClientSessionMemoryCache::new(256),
+        }
+    }
+}
+
+impl HandshakeTimer {
+    fn mark_start() {
+        if let Some(probe) = NetworkProbe::current() {
+            probe.mark(TLS_START);
+        }
+    }
+}
+
+impl ClientSessionStore for HandshakeTimer {
+    fn set_kx_hint(&self, server_name: ServerName<'static>, group: NamedGroup) {
+        self.inner.set_kx_hint(server_name, group);
+    }
+
+    fn kx_hint(&self, server_name: &ServerName<'_>) -> Option<NamedGroup> {
+        Self::mark_start();
+        self.inner.kx_hint(server_name)
+    }
+
+    fn set_tls12_session(&self, server_name: ServerName<'static>, value: Tls12ClientSessionValue) {
+        self.inner.set_tls12_session(server_name, value);
+    }
+
+    fn tls12_session(&self, server_name: &ServerName<'_>) -> Option<Tls12ClientSessionValue> {
+        Self::mark_start();
+        self.inner.tls12_session(server_name)
+    }
+
+    fn remove_tls12_session(&self, server_name: &ServerName<'static>) {
+        self.inner.remove_tls12_session(server_name);
+    }
+
+    fn insert_tls13_ticket(
+        &self,
+        server_name: ServerName<'static>,
+        value: Tls13ClientSessionValue,
+    ) {
+        self.inner.insert_tls13_ticket(server_name, value);
+    }
+
+    fn take_tls13_ticket(
+        &self,
+        server_name: &ServerName<'static>,
+    ) -> Option<Tls13ClientSessionValue> {
+        Self::mark_start();
+        self.inner.take_tls13_ticket(server_name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_probe_phases() {
+        let probe = NetworkProbe::new(&Clock::new());
+        for label in [CONNECT_START, DNS_START, DNS_END, TLS_START, CONNECT_END] {
+            std::thread::sleep(Duration::from_millis(1));
+            probe.mark(label);
+        }
+        probe.first_byte();
+        std::thread::sleep(Duration::from_millis(1));
+        probe.first_event();
+        // Later marks for the same phase are ignored
+        probe.mark(DNS_START);
+        probe.first_event();
+
+        let timings = probe.timings();
+        assert!(timings.dns.unwrap() >= Duration::from_millis(1));
+        assert!(timings.tcp_connect.unwrap() >= Duration::from_millis(1));
+        assert!(timings.tls_handshake.unwrap() >= Duration::from_millis(1));
+        assert!(timings.time_to_first_byte.unwrap() >= Duration::from_millis(5));
+        assert!(timings.time_to_first_event.unwrap() > timings.time_to_first_byte.unwrap());
+        assert!(!timings.connection_reused());
+        assert!(timings.session_ready.is_none());
+        assert_eq!(probe.checkpoints().len(), 7);
+    }
+
+    #[tokio::test]
+    async fn test_connect_plain() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let probe = NetworkProbe::new(&Clock::new());
+        let connection = connect(&probe, "127.0.0.1", port, None).await.unwrap();
+        assert!(matches!(connection, Connection::Plain(_)));
+        probe.session_ready();
+        probe.response_requested();
+
+        let timings = probe.timings();
+        assert!(timings.dns.is_none());
+        assert!(timings.tcp_connect.is_some());
+        assert!(timings.tls_handshake.is_none());
+        assert!(timings.response_requested.unwrap() >= timings.session_ready.unwrap());
+    }
+
+    #[test]
+    fn test_probe_plain_http_and_reuse() {
+        let probe = NetworkProbe::new(&Clock::new());
+        probe.mark(CONNECT_START);
+        probe.mark(CONNECT_END);
+        let timings = probe.timings();
+        assert!(timings.dns.is_none());
+        assert!(timings.tcp_connect.is_some());
+        assert!(timings.tls_handshake.is_none());
+
+        let reused = NetworkProbe::new(&Clock::new());
+        reused.first_byte();
+        assert!(reused.timings().connection_reused());
+    }
+
+    #[test]
+    fn test_read_stats() {
+        let probe = NetworkProbe::new(&Clock::new());
+        assert_eq!(probe.read_stats().mean_events_per_read(), None);
+        for events in [1, 0, 3, 2] {
+            probe.record_read(events);
+        }
+
+        let reads = probe.read_stats();
+        assert_eq!(reads.reads, 4);
+        assert_eq!(reads.events, 6);
+        assert_eq!(reads.max_events_per_read, 3);
+        assert_eq!(reads.mean_events_per_read(), Some(1.5));
+    }
+
+    #[test]
+    fn test_retried_discards_first_byte() {
+        let probe = NetworkProbe::new(&Clock::new());
+        probe.mark(CONNECT_START);
+        probe.first_byte();
+        let failed = probe.timings().time_to_first_byte.unwrap();
+
+        probe.retried(Duration::from_millis(2));
+        assert!(probe.timings().time_to_first_byte.is_none());
+        std::thread::sleep(Duration::from_millis(2));
+        probe.first_byte();
+
+        assert!(probe.timings().time_to_first_byte.unwrap() > failed);
+        assert_eq!(probe.checkpoints().len(), 2);
+        assert_eq!(probe.retry_stats().attempts(), 2);
+        assert_eq!(probe.retry_stats().backoff, Duration::from_millis(2));
+    }
+
+    #[tokio::test]
+    async fn test_probe_is_current_while_running() {
+        let probe = NetworkProbe::new(&Clock::new());
+        assert!(NetworkProbe::current().is_none());
+        probe
+            .run(async {
+                NetworkProbe::current().unwrap().mark(CONNECT_START);
+            })
+            .await;
+        assert_eq!(probe.checkpoints().len(), 1);
+    }
+}
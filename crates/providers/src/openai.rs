@@ -3,22 +3,54 @@
 //! This module provides a production-ready adapter for OpenAI's Chat Completions API
 //! with support for:
 //! - Server-Sent Events (SSE) streaming
+//! - The Responses API (`/responses`) as an alternative to Chat Completions
+//! - Token usage reporting via `stream_options.include_usage`
+//! - Reasoning models (o-series `reasoning_tokens`, `reasoning_content` deltas)
+//! - Tool calling (`tool_calls` deltas streamed as timed tool-call events)
 //! - Fine-grained timing measurements (DNS, TLS, TTFT, inter-token latency)
-//! - Automatic retries with exponential backoff
+//! - Retries of the initial request with exponential backoff
 //! - Cost calculation for all GPT models
+//! - Embeddings (`/embeddings`) with batch inputs and shortened vectors
 //! - Comprehensive error handling
 
+use crate::embeddings::{EmbeddingProvider, EmbeddingRequest, EmbeddingResult};
 use crate::error::{parse_api_error, ProviderError, Result};
+use crate::headers::ServerSignals;
+use crate::models::{self, ModelCache, ModelList};
+use crate::network::NetworkProbe;
+use crate::responses::{self, ResponsesUsage};
+use crate::retry::{self, RetryPolicy};
 use crate::traits::{
-    MessageRole, Provider, ResponseMetadata, StreamingRequest, StreamingResponse,
+    CompletionResult, ContentPart, Message, MessageRole, Provider, ResponseMetadata, SharedUsage,
+    StreamingRequest, StreamingResponse, TokenUsage, Tool, ToolChoice,
 };
 use async_trait::async_trait;
 use futures::StreamExt;
-use llm_latency_lens_core::{TimingEngine, Timestamp, TokenEvent};
+use llm_latency_lens_core::{TimingEngine, Timestamp, TokenEvent, TokenKind};
 use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION, CONTENT_TYPE};
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
+/// OpenAI API used for completions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OpenAIApi {
+    /// Chat Completions (`/chat/completions`), streamed as `chat.completion.chunk` objects
+    #[default]
+    ChatCompletions,
+    /// Responses (`/responses`), streamed as typed `response.*` events
+    Responses,
+}
+
+impl OpenAIApi {
+    /// Endpoint path, relative to the base URL
+    fn path(self) -> &'static str {
+        match self {
+            Self::ChatCompletions => "chat/completions",
+            Self::Responses => "responses",
+        }
+    }
+}
+
 /// OpenAI provider adapter
 pub struct OpenAIProvider {
     /// HTTP client
@@ -29,8 +61,11 @@ pub struct OpenAIProvider {
     base_url: String,
     /// Organization ID (optional)
     organization: Option<String>,
-    /// Maximum retry attempts
-    #[allow(dead_code)]
+    /// API used for completions
+    api: OpenAIApi,
+    /// Models discovered from `/models`
+    models: ModelCache,
+    /// Retries of the initial request
     max_retries: u32,
 }
 
@@ -50,10 +85,12 @@ impl OpenAIProvider {
     /// ```
     pub fn new(api_key: impl Into<String>) -> Self {
         Self {
-            client: Self::build_client(),
+            client: Self::build_client(crate::network::DEFAULT_TIMEOUT),
             api_key: api_key.into(),
             base_url: "https://api.openai.com/v1".to_string(),
             organization: None,
+            api: OpenAIApi::default(),
+            models: ModelCache::default(),
             max_retries: 3,
         }
     }
@@ -64,9 +101,9 @@ impl OpenAIProvider {
     }
 
     /// Build HTTP client with optimized settings
-    fn build_client() -> reqwest::Client {
-        reqwest::Client::builder()
-            .timeout(Duration::from_secs(120))
+    fn build_client(timeout: Duration) -> reqwest::Client {
+        crate::network::instrument(reqwest::Client::builder())
+            .timeout(timeout)
             .tcp_keepalive(Duration::from_secs(60))
             .pool_idle_timeout(Duration::from_secs(90))
             .build()
@@ -93,53 +130,38 @@ impl OpenAIProvider {
         headers
     }
 
-    /// Execute request with retries
-    #[allow(dead_code)]
-    async fn execute_with_retries<F, Fut, T>(
-        &self,
-        operation: F,
-    ) -> Result<T>
-    where
-        F: Fn() -> Fut,
-        Fut: std::future::Future<Output = Result<T>>,
-    {
-        let mut attempts = 0;
-        let mut last_error = None;
-
-        while attempts < self.max_retries {
-            match operation().await {
-                Ok(result) => return Ok(result),
-                Err(e) => {
-                    if !e.is_retryable() {
-                        return Err(e);
-                    }
-
-                    last_error = Some(e.clone());
-                    attempts += 1;
-
-                    if attempts < self.max_retries {
-                        let delay = if let Some(retry_after) = e.retry_delay() {
-                            Duration::from_secs(retry_after)
-                        } else {
-                            // Exponential backoff: 1s, 2s, 4s
-                            Duration::from_secs(2_u64.pow(attempts - 1))
-                        };
-
-                        tracing::warn!(
-                            "Request failed (attempt {}/{}), retrying after {:?}: {}",
-                            attempts,
-                            self.max_retries,
-                            delay,
-                            e
-                        );
-
-                        tokio::time::sleep(delay).await;
-                    }
-                }
-            }
-        }
+    /// Build the Chat Completions payload, streamed or not
+    fn chat_request(&self, request: &StreamingRequest, stream: bool) -> ChatCompletionRequest {
+        // Reasoning models take max_completion_tokens (which also covers the
+        // hidden reasoning) and reject sampling parameters
+        let reasoning = is_reasoning_model(&request.model);
 
-        Err(last_error.unwrap_or_else(|| ProviderError::Other("Max retries exceeded".to_string())))
+        ChatCompletionRequest {
+            model: request.model.clone(),
+            messages: request
+                .messages
+                .iter()
+                .map(|m| ChatMessage {
+                    role: match m.role {
+                        MessageRole::System => "system".to_string(),
+                        MessageRole::User => "user".to_string(),
+                        MessageRole::Assistant => "assistant".to_string(),
+                    },
+                    content: message_content(m),
+                })
+                .collect(),
+            stream,
+            stream_options: stream.then_some(StreamOptions {
+                include_usage: true,
+            }),
+            max_tokens: request.max_tokens.filter(|_| !reasoning),
+            max_completion_tokens: request.max_tokens.filter(|_| reasoning),
+            temperature: request.temperature.filter(|_| !reasoning),
+            top_p: request.top_p.filter(|_| !reasoning),
+            stop: request.stop.clone(),
+            tools: chat_tools(&request.tools),
+            tool_choice: request.tool_choice.as_ref().map(tool_choice_value),
+        }
     }
 }
 
@@ -149,7 +171,9 @@ pub struct OpenAIProviderBuilder {
     api_key: Option<String>,
     base_url: Option<String>,
     organization: Option<String>,
+    api: Option<OpenAIApi>,
     max_retries: Option<u32>,
+    timeout: Option<Duration>,
 }
 
 impl OpenAIProviderBuilder {
@@ -171,19 +195,35 @@ impl OpenAIProviderBuilder {
         self
     }
 
-    /// Set maximum retry attempts
+    /// Set the API used for completions (Chat Completions by default)
+    pub fn api(mut self, api: OpenAIApi) -> Self {
+        self.api = Some(api);
+        self
+    }
+
+    /// Set how many times a failed initial request is retried (3 by default)
     pub fn max_retries(mut self, retries: u32) -> Self {
         self.max_retries = Some(retries);
         self
     }
 
+    /// Set the HTTP request timeout (120 seconds by default)
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
     /// Build the provider
     pub fn build(self) -> OpenAIProvider {
         OpenAIProvider {
-            client: OpenAIProvider::build_client(),
+            client: OpenAIProvider::build_client(
+                self.timeout.unwrap_or(crate::network::DEFAULT_TIMEOUT),
+            ),
             api_key: self.api_key.expect("API key is required"),
             base_url: self.base_url.unwrap_or_else(|| "https://api.openai.com/v1".to_string()),
             organization: self.organization,
+            api: self.api.unwrap_or_default(),
+            models: ModelCache::default(),
             max_retries: self.max_retries.unwrap_or(3),
         }
     }
@@ -195,6 +235,10 @@ impl Provider for OpenAIProvider {
         "openai"
     }
 
+    fn provider_kind(&self) -> llm_latency_lens_core::Provider {
+        llm_latency_lens_core::Provider::OpenAI
+    }
+
     async fn health_check(&self) -> Result<()> {
         let url = format!("{}/models", self.base_url);
         let response = self
@@ -222,95 +266,73 @@ impl Provider for OpenAIProvider {
 
         // Start timing measurement
         let mut timing = timing_engine.start();
+        let network = NetworkProbe::new(timing_engine.clock());
         timing.checkpoint("request_start");
 
         // Build request payload
-        let payload = ChatCompletionRequest {
-            model: request.model.clone(),
-            messages: request
-                .messages
-                .iter()
-                .map(|m| ChatMessage {
-                    role: match m.role {
-                        MessageRole::System => "system".to_string(),
-                        MessageRole::User => "user".to_string(),
-                        MessageRole::Assistant => "assistant".to_string(),
-                    },
-                    content: m.content.clone(),
-                })
-                .collect(),
-            stream: true,
-            max_tokens: request.max_tokens,
-            temperature: request.temperature,
-            top_p: request.top_p,
-            stop: request.stop.clone(),
+        let req_builder = self.client.post(format!("{}/{}", self.base_url, self.api.path()));
+        let req_builder = match self.api {
+            OpenAIApi::ChatCompletions => req_builder.json(&self.chat_request(&request, true)),
+            OpenAIApi::Responses => req_builder.json(&responses::request_body(&request, true)),
         };
 
         timing.checkpoint("payload_built");
 
-        let url = format!("{}/chat/completions", self.base_url);
         let headers = self.build_headers();
 
         timing.checkpoint("headers_built");
 
-        // Create event source for SSE streaming
+        // Build the SSE request
         let request_id = request.request_id;
-        let req_builder = self
-            .client
-            .post(&url)
-            .headers(headers)
-            .json(&payload);
+        let api = self.api;
+        let req_builder = req_builder.headers(headers);
 
         timing.checkpoint("http_request_built");
 
-        let event_source = reqwest_eventsource::EventSource::new(req_builder)
-            .map_err(|e| ProviderError::streaming(format!("Failed to create event source: {}", e)))?;
+        let response = retry::send(
+            RetryPolicy::new(self.max_retries),
+            &network,
+            req_builder,
+            parse_api_error,
+        )
+        .await?;
+
+        timing.checkpoint("response_received");
+
+        let response_headers =
+            crate::headers::capture(response.headers(), &request.redact_headers);
+        let server = ServerSignals::from_headers(response.headers());
+        let event_source = crate::sse::events(
+            response.bytes_stream(),
+            timing_engine.clock().clone(),
+            network.clone(),
+        );
 
         timing.checkpoint("event_source_created");
 
         // Create token stream
-        let clock = timing_engine.clock().clone();
         let request_start = timing.start_time();
+        let usage = SharedUsage::new();
+        let stream_usage = usage.clone();
         let mut sequence = 0u64;
         let mut last_token_time: Option<Timestamp> = None;
 
         let token_stream = event_source.map(move |event_result| {
             match event_result {
-                Ok(reqwest_eventsource::Event::Open) => {
-                    tracing::debug!("SSE stream opened");
-                    return None;
-                }
-                Ok(reqwest_eventsource::Event::Message(message)) => {
-                    if message.data == "[DONE]" {
-                        tracing::debug!("SSE stream completed");
-                        return None;
-                    }
-
-                    // Parse SSE chunk
-                    let chunk: ChatCompletionChunk = match serde_json::from_str(&message.data) {
-                        Ok(c) => c,
-                        Err(e) => {
-                            tracing::error!("Failed to parse SSE chunk: {}", e);
-                            return Some(Err(ProviderError::sse_parse(format!(
-                                "Invalid JSON in SSE event: {}",
-                                e
-                            ))));
-                        }
+                Ok(message) => {
+                    // Extract visible, reasoning or tool-call text, skipping
+                    // events that carry none
+                    let decoded = match api {
+                        OpenAIApi::ChatCompletions => chunk_text(&message.data, &stream_usage),
+                        OpenAIApi::Responses => responses::event_text(&message, &stream_usage),
+                    };
+                    let (kind, content) = match decoded? {
+                        Ok(text) => text,
+                        Err(e) => return Some(Err(e)),
                     };
 
-                    // Extract token content
-                    let content = chunk
-                        .choices
-                        .first()
-                        .and_then(|c| c.delta.content.clone());
-
-                    if content.is_none() {
-                        // Skip empty chunks (role, function calls, etc.)
-                        return None;
-                    }
-
-                    // Record timing
-                    let now = clock.now();
+                    // Record timing at the arrival of the read carrying the event
+                    let now = message.received_at;
                     let time_since_start = now.duration_since(request_start);
                     let inter_token_latency = last_token_time.map(|t| now.duration_since(t));
                     last_token_time = Some(now);
@@ -318,7 +340,8 @@ impl Provider for OpenAIProvider {
                     let event = TokenEvent {
                         request_id,
                         sequence,
-                        content,
+                        kind,
+                        content: Some(content),
                         timestamp_nanos: now.as_nanos(),
                         time_since_start,
                         inter_token_latency,
@@ -328,10 +351,7 @@ impl Provider for OpenAIProvider {
 
                     Some(Ok(event))
                 }
-                Err(e) => {
-                    tracing::error!("SSE stream error: {}", e);
-                    Some(Err(ProviderError::streaming(format!("SSE error: {}", e))))
-                }
+                Err(e) => Some(Err(e)),
             }
         })
         .filter_map(|x| async move { x })
@@ -344,46 +364,112 @@ impl Provider for OpenAIProvider {
             token_stream: Box::pin(token_stream),
             metadata: ResponseMetadata {
                 model: request.model,
-                input_tokens: None,  // Not available until completion
+                input_tokens: None, // Reported in the final chunk
                 output_tokens: None,
                 thinking_tokens: None,
+                cache_creation_tokens: None,
+                cache_read_tokens: None,
                 estimated_cost: None,
-                headers: vec![],
+                headers: response_headers,
+                server,
             },
+            usage,
+            network,
         })
     }
 
-    fn calculate_cost(&self, model: &str, input_tokens: u64, output_tokens: u64) -> Option<f64> {
-        // Pricing per 1M tokens (as of 2024)
-        let (input_price, output_price) = match model {
-            // GPT-4 Turbo
-            "gpt-4-turbo" | "gpt-4-turbo-2024-04-09" => (10.0, 30.0),
-            "gpt-4-turbo-preview" => (10.0, 30.0),
-
-            // GPT-4
-            "gpt-4" => (30.0, 60.0),
-            "gpt-4-32k" => (60.0, 120.0),
+    async fn complete_non_streaming(
+        &self,
+        request: StreamingRequest,
+        timing_engine: &TimingEngine,
+    ) -> Result<CompletionResult> {
+        self.validate_model(&request.model)?;
 
-            // GPT-4o
-            "gpt-4o" | "gpt-4o-2024-08-06" | "gpt-4o-2024-05-13" => (2.50, 10.0),
-            "gpt-4o-mini" | "gpt-4o-mini-2024-07-18" => (0.15, 0.60),
+        let timing = timing_engine.start();
+        let network = NetworkProbe::new(timing_engine.clock());
 
-            // GPT-3.5 Turbo
-            "gpt-3.5-turbo" | "gpt-3.5-turbo-0125" => (0.50, 1.50),
-            "gpt-3.5-turbo-instruct" => (1.50, 2.0),
-
-            // Unknown model
-            _ => return None,
+        let req_builder = self
+            .client
+            .post(format!("{}/{}", self.base_url, self.api.path()))
+            .headers(self.build_headers());
+        let req_builder = match self.api {
+            OpenAIApi::ChatCompletions => req_builder.json(&self.chat_request(&request, false)),
+            OpenAIApi::Responses => req_builder.json(&responses::request_body(&request, false)),
         };
 
-        let input_cost = (input_tokens as f64 / 1_000_000.0) * input_price;
-        let output_cost = (output_tokens as f64 / 1_000_000.0) * output_price;
+        let response = retry::send(
+            RetryPolicy::new(self.max_retries),
+            &network,
+            req_builder,
+            parse_api_error,
+        )
+        .await?;
+
+        let response_headers =
+            crate::headers::capture(response.headers(), &request.redact_headers);
+        let server = ServerSignals::from_headers(response.headers());
+        let clock = timing_engine.clock();
+        let (content, thinking, usage, body) = match self.api {
+            OpenAIApi::ChatCompletions => {
+                let (completion, body): (ChatCompletion, _) =
+                    crate::body::read_json(response, clock, timing.start_time()).await?;
+                let usage = completion.usage.as_ref().map(Usage::token_usage);
+                let message = completion
+                    .choices
+                    .into_iter()
+                    .next()
+                    .map(|c| c.message)
+                    .unwrap_or_default();
+                (
+                    message.content.unwrap_or_default(),
+                    message.reasoning_content.unwrap_or_default(),
+                    usage,
+                    body,
+                )
+            }
+            OpenAIApi::Responses => {
+                let (response, body): (responses::Response, _) =
+                    crate::body::read_json(response, clock, timing.start_time()).await?;
+                let usage = response.usage.as_ref().map(ResponsesUsage::token_usage);
+                (response.output_text(), response.reasoning_text(), usage, body)
+            }
+        };
 
-        Some(input_cost + output_cost)
+        let mut metadata = ResponseMetadata {
+            model: request.model.clone(),
+            input_tokens: None,
+            output_tokens: None,
+            thinking_tokens: None,
+            cache_creation_tokens: None,
+            cache_read_tokens: None,
+            estimated_cost: None,
+            headers: response_headers,
+            server,
+        };
+        if let Some(usage) = usage {
+            metadata.apply_usage(usage);
+        }
+        metadata.estimate_cost(|usage| self.usage_cost(&request.model, usage));
+
+        Ok(CompletionResult::from_body(
+            request.request_id,
+            content,
+            thinking,
+            metadata,
+            &network,
+            body,
+        ))
     }
 
     fn supported_models(&self) -> Vec<String> {
         vec![
+            // o-series reasoning models
+            "o1".to_string(),
+            "o1-2024-12-17".to_string(),
+            "o1-mini".to_string(),
+            "o1-mini-2024-09-12".to_string(),
+            "o3-mini".to_string(),
+            "o3-mini-2025-01-31".to_string(),
             // GPT-4o
             "gpt-4o".to_string(),
             "gpt-4o-2024-08-06".to_string(),
@@ -403,6 +489,224 @@ impl Provider for OpenAIProvider {
             "gpt-3.5-turbo-instruct".to_string(),
         ]
     }
+
+    async fn list_models(&self) -> Result<Vec<String>> {
+        let url = format!("{}/models", self.base_url);
+        let response = self
+            .client
+            .get(&url)
+            .headers(self.build_headers())
+            .send()
+            .await
+            .map_err(ProviderError::from_reqwest)?;
+
+        if !response.status().is_success() {
+            return Err(parse_api_error(response).await);
+        }
+        let list: ModelList = models::read_list(response).await?;
+        Ok(list.ids())
+    }
+
+    fn model_cache(&self) -> Option<&ModelCache> {
+        Some(&self.models)
+    }
+}
+
+#[async_trait]
+impl EmbeddingProvider for OpenAIProvider {
+    async fn embed(
+        &self,
+        request: EmbeddingRequest,
+        timing_engine: &TimingEngine,
+    ) -> Result<EmbeddingResult> {
+        self.validate_embedding_model(&request.model)?;
+
+        let url = format!("{}/embeddings", self.base_url);
+        let model = request.model.clone();
+        embed(
+            &self.client,
+            &url,
+            self.build_headers(),
+            request,
+            timing_engine,
+            RetryPolicy::new(self.max_retries),
+            |input_tokens| self.calculate_embedding_cost(&model, input_tokens),
+        )
+        .await
+    }
+
+    fn calculate_embedding_cost(&self, model: &str, input_tokens: u64) -> Option<f64> {
+        let price = embedding_pricing(model)?;
+        Some((input_tokens as f64 / 1_000_000.0) * price)
+    }
+
+    fn supported_embedding_models(&self) -> Vec<String> {
+        vec![
+            "text-embedding-3-small".to_string(),
+            "text-embedding-3-large".to_string(),
+            "text-embedding-ada-002".to_string(),
+        ]
+    }
+}
+
+/// Send an OpenAI-format embeddings request and time the response
+///
+/// Shared with the OpenAI-compatible adapter; `price` maps the reported
+/// input tokens to a cost.
+pub(crate) async fn embed(
+    client: &reqwest::Client,
+    url: &str,
+    headers: HeaderMap,
+    request: EmbeddingRequest,
+    timing_engine: &TimingEngine,
+    retry: RetryPolicy,
+    price: impl FnOnce(u64) -> Option<f64>,
+) -> Result<EmbeddingResult> {
+    let timing = timing_engine.start();
+    let network = NetworkProbe::new(timing_engine.clock());
+
+    let payload = EmbeddingsRequest {
+        model: &request.model,
+        input: &request.inputs,
+        dimensions: request.dimensions,
+    };
+    let req_builder = client.post(url).headers(headers).json(&payload);
+    let response = retry::send(retry, &network, req_builder, parse_api_error).await?;
+
+    let response_headers = crate::headers::capture(response.headers(), &request.redact_headers);
+    let server = ServerSignals::from_headers(response.headers());
+    let (response, body): (EmbeddingsResponse, _) =
+        crate::body::read_json(response, timing_engine.clock(), timing.start_time()).await?;
+
+    // Vectors are matched to inputs by index, which servers need not keep in order
+    let mut data = response.data;
+    data.sort_by_key(|d| d.index);
+
+    let input_tokens = response.usage.map(|u| u.prompt_tokens);
+    Ok(EmbeddingResult {
+        request_id: request.request_id,
+        input_count: request.inputs.len(),
+        embeddings: data.into_iter().map(|d| d.embedding).collect(),
+        metadata: ResponseMetadata {
+            model: request.model,
+            input_tokens,
+            output_tokens: None,
+            thinking_tokens: None,
+            cache_creation_tokens: None,
+            cache_read_tokens: None,
+            estimated_cost: input_tokens.and_then(price),
+            headers: response_headers,
+            server,
+        },
+        network: network.timings(),
+        retries: network.retry_stats(),
+        body,
+    })
+}
+
+/// Price per 1M input tokens for an OpenAI embedding model (as of 2024)
+pub(crate) fn embedding_pricing(model: &str) -> Option<f64> {
+    match model {
+        "text-embedding-3-small" => Some(0.02),
+        "text-embedding-3-large" => Some(0.13),
+        "text-embedding-ada-002" => Some(0.10),
+        _ => None,
+    }
+}
+
+/// Decode one Chat Completions chunk into generated text, recording usage
+///
+/// Returns None for `[DONE]` and for chunks that carry no text (role,
+/// finish reason, the final usage chunk).
+fn chunk_text(data: &str, usage: &SharedUsage) -> Option<Result<(TokenKind, String)>> {
+    if data == "[DONE]" {
+        tracing::debug!("SSE stream completed");
+        return None;
+    }
+
+    // Parse SSE chunk
+    let chunk: ChatCompletionChunk = match serde_json::from_str(data) {
+        Ok(c) => c,
+        Err(e) => {
+            tracing::error!("Failed to parse SSE chunk: {}", e);
+            return Some(Err(ProviderError::sse_parse(format!(
+                "Invalid JSON in SSE event: {}",
+                e
+            ))));
+        }
+    };
+
+    // The final chunk carries usage and no choices
+    if let Some(ref reported) = chunk.usage {
+        usage.update(|u| *u = reported.token_usage());
+    }
+
+    chunk.choices.first()?.delta.text().map(Ok)
+}
+
+/// Chat Completions message `content` value
+///
+/// Text-only messages stay a plain string; anything with attachments becomes
+/// an array of typed parts, with inline data sent as `data:` URLs.
+pub(crate) fn message_content(message: &Message) -> serde_json::Value {
+    if message.is_text_only() {
+        return serde_json::json!(message.text());
+    }
+
+    let parts: Vec<serde_json::Value> = message
+        .content
+        .iter()
+        .map(|part| match part {
+            ContentPart::Text { text } => serde_json::json!({"type": "text", "text": text}),
+            ContentPart::ImageUrl { .. } | ContentPart::Image { .. } => serde_json::json!({
+                "type": "image_url",
+                "image_url": {"url": part.to_url()}
+            }),
+            ContentPart::Document { .. } => serde_json::json!({
+                "type": "file",
+                "file": {"filename": "document.pdf", "file_data": part.to_url()}
+            }),
+        })
+        .collect();
+    serde_json::json!(parts)
+}
+
+/// Chat Completions `tools` entries
+pub(crate) fn chat_tools(tools: &[Tool]) -> Vec<ChatTool> {
+    tools
+        .iter()
+        .map(|t| ChatTool {
+            tool_type: "function",
+            function: ChatFunction {
+                name: t.name.clone(),
+                description: t.description.clone(),
+                parameters: t.parameters.clone(),
+            },
+        })
+        .collect()
+}
+
+/// Chat Completions `tool_choice` value
+pub(crate) fn tool_choice_value(choice: &ToolChoice) -> serde_json::Value {
+    match choice {
+        ToolChoice::Auto => serde_json::json!("auto"),
+        ToolChoice::None => serde_json::json!("none"),
+        ToolChoice::Required => serde_json::json!("required"),
+        ToolChoice::Tool(name) => serde_json::json!({
+            "type": "function",
+            "function": {"name": name}
+        }),
+    }
+}
+
+/// Whether a model is an o-series reasoning model
+///
+/// These take `max_completion_tokens` instead of `max_tokens` and reject
+/// `temperature`/`top_p`.
+pub(crate) fn is_reasoning_model(model: &str) -> bool {
+    ["o1", "o3", "o4"]
+        .iter()
+        .any(|prefix| model == *prefix || model.starts_with(&format!("{}-", prefix)))
 }
 
 // OpenAI API request/response types
@@ -413,19 +717,47 @@ struct ChatCompletionRequest {
     messages: Vec<ChatMessage>,
     stream: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
+    stream_options: Option<StreamOptions>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     max_tokens: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    max_completion_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     temperature: Option<f32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     top_p: Option<f32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     stop: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tools: Vec<ChatTool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_choice: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct ChatTool {
+    #[serde(rename = "type")]
+    tool_type: &'static str,
+    function: ChatFunction,
+}
+
+#[derive(Debug, Serialize)]
+struct ChatFunction {
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    parameters: serde_json::Value,
+}
+
+#[derive(Debug, Serialize)]
+struct StreamOptions {
+    include_usage: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 struct ChatMessage {
     role: String,
-    content: String,
+    content: serde_json::Value,
 }
 
 #[derive(Debug, Deserialize)]
@@ -435,7 +767,89 @@ struct ChatCompletionChunk {
     object: String,
     created: u64,
     model: String,
+    #[serde(default)]
     choices: Vec<StreamChoice>,
+    #[serde(default)]
+    usage: Option<Usage>,
+}
+
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct Usage {
+    prompt_tokens: u64,
+    completion_tokens: u64,
+    total_tokens: u64,
+    #[serde(default)]
+    completion_tokens_details: Option<CompletionTokensDetails>,
+    #[serde(default)]
+    prompt_tokens_details: Option<PromptTokensDetails>,
+}
+
+impl Usage {
+    /// Usage counts, with reasoning tokens split out of the completion tokens
+    fn token_usage(&self) -> TokenUsage {
+        let reasoning_tokens = self.reasoning_tokens();
+        TokenUsage {
+            input_tokens: Some(self.prompt_tokens),
+            // completion_tokens includes the reasoning tokens
+            output_tokens: Some(
+                self.completion_tokens.saturating_sub(reasoning_tokens.unwrap_or(0)),
+            ),
+            thinking_tokens: reasoning_tokens,
+            cache_creation_tokens: None,
+            // Caching is automatic; cached_tokens is part of prompt_tokens
+            cache_read_tokens: self.cached_tokens(),
+        }
+    }
+
+    /// Reasoning tokens reported for o-series models
+    fn reasoning_tokens(&self) -> Option<u64> {
+        self.completion_tokens_details
+            .as_ref()
+            .and_then(|d| d.reasoning_tokens)
+    }
+
+    /// Prompt tokens served from the prompt cache
+    fn cached_tokens(&self) -> Option<u64> {
+        self.prompt_tokens_details
+            .as_ref()
+            .and_then(|d| d.cached_tokens)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct PromptTokensDetails {
+    #[serde(default)]
+    cached_tokens: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CompletionTokensDetails {
+    #[serde(default)]
+    reasoning_tokens: Option<u64>,
+}
+
+/// Non-streaming Chat Completions response
+#[derive(Debug, Deserialize)]
+struct ChatCompletion {
+    #[serde(default)]
+    choices: Vec<CompletionChoice>,
+    #[serde(default)]
+    usage: Option<Usage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CompletionChoice {
+    message: CompletionMessage,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct CompletionMessage {
+    #[serde(default)]
+    content: Option<String>,
+    /// Reasoning text from DeepSeek-style APIs
+    #[serde(default)]
+    reasoning_content: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -449,16 +863,92 @@ struct StreamChoice {
 
 #[derive(Debug, Deserialize)]
 #[allow(dead_code)]
-struct Delta {
+pub(crate) struct Delta {
     #[serde(default)]
     role: Option<String>,
     #[serde(default)]
     content: Option<String>,
+    /// Reasoning text from DeepSeek-style APIs
+    #[serde(default)]
+    reasoning_content: Option<String>,
+    #[serde(default)]
+    tool_calls: Option<Vec<ToolCallDelta>>,
+}
+
+impl Delta {
+    /// Visible, reasoning or tool-call text carried by this delta, if any
+    ///
+    /// A tool call's first delta carries its name and usually no argument
+    /// bytes; it still yields an (empty) event so the tool call's start is
+    /// timed.
+    pub(crate) fn text(&self) -> Option<(TokenKind, String)> {
+        let non_empty = |text: &Option<String>| text.clone().filter(|t| !t.is_empty());
+        non_empty(&self.content)
+            .map(|t| (TokenKind::Content, t))
+            .or_else(|| non_empty(&self.reasoning_content).map(|t| (TokenKind::Thinking, t)))
+            .or_else(|| {
+                let calls = self.tool_calls.as_ref().filter(|c| !c.is_empty())?;
+                let arguments = calls
+                    .iter()
+                    .filter_map(|c| c.function.as_ref()?.arguments.as_deref())
+                    .collect();
+                Some((TokenKind::ToolCall, arguments))
+            })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct ToolCallDelta {
+    #[serde(default)]
+    index: u32,
+    #[serde(default)]
+    id: Option<String>,
+    #[serde(default)]
+    function: Option<FunctionDelta>,
+}
+
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct FunctionDelta {
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    arguments: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct EmbeddingsRequest<'a> {
+    model: &'a str,
+    input: &'a [String],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dimensions: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct EmbeddingsResponse {
+    data: Vec<EmbeddingData>,
+    #[serde(default)]
+    usage: Option<EmbeddingUsage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct EmbeddingData {
+    embedding: Vec<f32>,
+    #[serde(default)]
+    index: usize,
+}
+
+#[derive(Debug, Deserialize)]
+struct EmbeddingUsage {
+    prompt_tokens: u64,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use wiremock::matchers::{body_partial_json, header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
 
     #[test]
     fn test_provider_name() {
@@ -478,6 +968,7 @@ mod tests {
         assert_eq!(provider.api_key, "test-key");
         assert_eq!(provider.base_url, "https://custom.endpoint.com");
         assert_eq!(provider.organization, Some("org-123".to_string()));
+        assert_eq!(provider.api, OpenAIApi::ChatCompletions);
         assert_eq!(provider.max_retries, 5);
     }
 
@@ -547,4 +1038,553 @@ mod tests {
             "org-123"
         );
     }
+
+    #[tokio::test]
+    async fn test_stream_reports_usage() {
+        let server = MockServer::start().await;
+        let body = [
+            r#"{"id":"chatcmpl-1","object":"chat.completion.chunk","created":1,"model":"gpt-4o-mini","choices":[{"index":0,"delta":{"role":"assistant","content":""},"finish_reason":null}],"usage":null}"#,
+            r#"{"id":"chatcmpl-1","object":"chat.completion.chunk","created":1,"model":"gpt-4o-mini","choices":[{"index":0,"delta":{"content":"Hello"},"finish_reason":null}],"usage":null}"#,
+            r#"{"id":"chatcmpl-1","object":"chat.completion.chunk","created":1,"model":"gpt-4o-mini","choices":[{"index":0,"delta":{"content":" world"},"finish_reason":null}],"usage":null}"#,
+            r#"{"id":"chatcmpl-1","object":"chat.completion.chunk","created":1,"model":"gpt-4o-mini","choices":[{"index":0,"delta":{},"finish_reason":"stop"}],"usage":null}"#,
+            r#"{"id":"chatcmpl-1","object":"chat.completion.chunk","created":1,"model":"gpt-4o-mini","choices":[],"usage":{"prompt_tokens":1000,"completion_tokens":1000,"total_tokens":2000,"prompt_tokens_details":{"cached_tokens":768}}}"#,
+            "[DONE]",
+        ]
+        .iter()
+        .map(|c| format!("data: {}\n\n", c))
+        .collect::<String>();
+
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .and(header("authorization", "Bearer test-key"))
+            .and(body_partial_json(serde_json::json!({
+                "stream": true,
+                "stream_options": {"include_usage": true}
+            })))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("x-request-id", "req_abc123")
+                    .insert_header("openai-processing-ms", "180")
+                    .insert_header("x-ratelimit-remaining-requests", "9999")
+                    .insert_header("set-cookie", "__cf_bm=secret")
+                    .set_body_raw(body, "text/event-stream"),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = OpenAIProvider::builder()
+            .api_key("test-key")
+            .base_url(server.uri())
+            .build();
+        let request = StreamingRequest::builder()
+            .model("gpt-4o-mini")
+            .message(MessageRole::User, "Hi")
+            .build();
+
+        let result = provider.complete(request, &TimingEngine::new()).await.unwrap();
+
+        assert_eq!(result.content, "Hello world");
+        assert_eq!(result.metadata.input_tokens, Some(1000));
+        assert_eq!(result.metadata.output_tokens, Some(1000));
+        assert_eq!(result.metadata.cache_read_tokens, Some(768));
+        assert_eq!(result.metadata.cache_hit(), Some(true));
+        // GPT-4o mini: $0.15/1M input, $0.075/1M cached input, $0.60/1M output
+        let cost = result.metadata.estimated_cost.unwrap();
+        assert!((cost - 0.0006924).abs() < 0.000001);
+        // wiremock serves plain HTTP on a fresh connection
+        assert!(result.network.tcp_connect.is_some());
+        assert!(result.network.tls_handshake.is_none());
+        assert!(result.network.time_to_first_byte.is_some());
+        // The role-only chunk arrives before the first token
+        assert!(result.network.time_to_first_event.unwrap() <= result.ttft().unwrap());
+
+        let server = &result.metadata.server;
+        assert_eq!(server.request_id.as_deref(), Some("req_abc123"));
+        assert_eq!(server.processing_time, Some(Duration::from_millis(180)));
+        assert_eq!(server.rate_limit.requests_remaining, Some(9999));
+        assert!(result
+            .metadata
+            .headers
+            .contains(&("set-cookie".to_string(), crate::headers::REDACTED.to_string())));
+    }
+
+    #[tokio::test]
+    async fn test_stream_retries_rate_limited_request() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .respond_with(
+                ResponseTemplate::new(429)
+                    .insert_header("retry-after", "0")
+                    .set_body_json(serde_json::json!({
+                        "error": {"message": "Rate limit reached", "type": "requests"}
+                    })),
+            )
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        let body = [
+            r#"{"id":"chatcmpl-1","object":"chat.completion.chunk","created":1,"model":"gpt-4o-mini","choices":[{"index":0,"delta":{"content":"Hi"},"finish_reason":null}]}"#,
+            "[DONE]",
+        ]
+        .iter()
+        .map(|c| format!("data: {}\n\n", c))
+        .collect::<String>();
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(body, "text/event-stream"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = OpenAIProvider::builder()
+            .api_key("test-key")
+            .base_url(server.uri())
+            .max_retries(1)
+            .build();
+        let request = StreamingRequest::builder()
+            .model("gpt-4o-mini")
+            .message(MessageRole::User, "Hi")
+            .build();
+
+        let result = provider.complete(request, &TimingEngine::new()).await.unwrap();
+
+        assert_eq!(result.content, "Hi");
+        assert_eq!(result.retries.attempts(), 2);
+        assert_eq!(result.retries.backoff, Duration::ZERO);
+    }
+
+    #[tokio::test]
+    async fn test_request_timeout() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_secs(5)))
+            .mount(&server)
+            .await;
+
+        let provider = OpenAIProvider::builder()
+            .api_key("test-key")
+            .base_url(server.uri())
+            .max_retries(0)
+            .timeout(Duration::from_millis(100))
+            .build();
+        let request = StreamingRequest::builder()
+            .model("gpt-4o-mini")
+            .message(MessageRole::User, "Hi")
+            .build();
+
+        let result = provider.stream(request, &TimingEngine::new()).await;
+        assert!(matches!(result, Err(ProviderError::TimeoutError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_complete_non_streaming() {
+        let server = MockServer::start().await;
+        let body = r#"{"id":"chatcmpl-2","object":"chat.completion","created":1,"model":"gpt-4o-mini","choices":[{"index":0,"message":{"role":"assistant","content":"Hello world"},"finish_reason":"stop"}],"usage":{"prompt_tokens":1000,"completion_tokens":1000,"total_tokens":2000}}"#;
+
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .and(body_partial_json(serde_json::json!({"stream": false})))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("x-request-id", "req_def456")
+                    .set_body_raw(body, "application/json"),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = OpenAIProvider::builder()
+            .api_key("test-key")
+            .base_url(server.uri())
+            .build();
+        let request = StreamingRequest::builder()
+            .model("gpt-4o-mini")
+            .message(MessageRole::User, "Hi")
+            .build();
+
+        let result = provider
+            .complete_non_streaming(request, &TimingEngine::new())
+            .await
+            .unwrap();
+
+        assert!(!result.is_streamed());
+        assert_eq!(result.content, "Hello world");
+        assert!(result.token_events.is_empty());
+        assert_eq!(result.metadata.output_tokens, Some(1000));
+        assert_eq!(result.metadata.server.request_id.as_deref(), Some("req_def456"));
+        let cost = result.metadata.estimated_cost.unwrap();
+        assert!((cost - 0.00075).abs() < 0.000001);
+
+        let body_stats = result.body.unwrap();
+        assert_eq!(body_stats.size_bytes, body.len() as u64);
+        assert_eq!(result.ttft(), Some(body_stats.time_to_parsed));
+        assert!(result.network.time_to_first_byte.unwrap() <= body_stats.time_to_parsed);
+    }
+
+    #[tokio::test]
+    async fn test_responses_stream() {
+        let server = MockServer::start().await;
+        let body = [
+            ("response.created", r#"{"type":"response.created","sequence_number":0,"response":{"id":"resp_1","status":"in_progress","output":[]}}"#),
+            ("response.output_item.added", r#"{"type":"response.output_item.added","sequence_number":1,"output_index":0,"item":{"id":"rs_1","type":"reasoning","summary":[]}}"#),
+            ("response.reasoning_summary_text.delta", r#"{"type":"response.reasoning_summary_text.delta","sequence_number":2,"item_id":"rs_1","output_index":0,"summary_index":0,"delta":"Adding"}"#),
+            ("response.output_item.added", r#"{"type":"response.output_item.added","sequence_number":3,"output_index":1,"item":{"id":"msg_1","type":"message","role":"assistant","content":[]}}"#),
+            ("response.output_text.delta", r#"{"type":"response.output_text.delta","sequence_number":4,"item_id":"msg_1","output_index":1,"content_index":0,"delta":"4"}"#),
+            ("response.output_text.delta", r#"{"type":"response.output_text.delta","sequence_number":5,"item_id":"msg_1","output_index":1,"content_index":0,"delta":"2"}"#),
+            ("response.output_text.done", r#"{"type":"response.output_text.done","sequence_number":6,"item_id":"msg_1","output_index":1,"content_index":0,"text":"42"}"#),
+            ("response.completed", r#"{"type":"response.completed","sequence_number":7,"response":{"id":"resp_1","status":"completed","output":[],"usage":{"input_tokens":1000,"input_tokens_details":{"cached_tokens":0},"output_tokens":1000,"output_tokens_details":{"reasoning_tokens":960},"total_tokens":2000}}}"#),
+        ]
+        .iter()
+        .map(|(event, data)| format!("event: {}\ndata: {}\n\n", event, data))
+        .collect::<String>();
+
+        Mock::given(method("POST"))
+            .and(path("/responses"))
+            .and(body_partial_json(serde_json::json!({
+                "model": "o3-mini",
+                "stream": true,
+                "max_output_tokens": 2000,
+                "input": [{"role": "user", "content": "Hi"}]
+            })))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("x-request-id", "req_resp1")
+                    .set_body_raw(body, "text/event-stream"),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = OpenAIProvider::builder()
+            .api_key("test-key")
+            .base_url(server.uri())
+            .api(OpenAIApi::Responses)
+            .build();
+        let request = StreamingRequest::builder()
+            .model("o3-mini")
+            .message(MessageRole::User, "Hi")
+            .max_tokens(2000)
+            .build();
+
+        let result = provider.complete(request, &TimingEngine::new()).await.unwrap();
+
+        assert_eq!(result.thinking, "Adding");
+        assert_eq!(result.content, "42");
+        assert_eq!(result.token_events.len(), 3);
+        assert_eq!(result.token_events[0].kind, TokenKind::Thinking);
+        assert!(result.reasoning_time().is_some());
+        assert_eq!(result.metadata.input_tokens, Some(1000));
+        assert_eq!(result.metadata.output_tokens, Some(40));
+        assert_eq!(result.metadata.thinking_tokens, Some(960));
+        assert_eq!(result.metadata.server.request_id.as_deref(), Some("req_resp1"));
+        // o3-mini: $1.10/1M input, $4.40/1M output (reasoning billed as output)
+        let cost = result.metadata.estimated_cost.unwrap();
+        assert!((cost - 0.0055).abs() < 0.000001);
+    }
+
+    #[tokio::test]
+    async fn test_responses_stream_failed() {
+        let server = MockServer::start().await;
+        let body = [
+            r#"{"type":"response.created","sequence_number":0,"response":{"id":"resp_2","status":"in_progress","output":[]}}"#,
+            r#"{"type":"response.failed","sequence_number":1,"response":{"id":"resp_2","status":"failed","output":[],"error":{"code":"server_error","message":"The server had an error"}}}"#,
+        ]
+        .iter()
+        .map(|c| format!("data: {}\n\n", c))
+        .collect::<String>();
+
+        Mock::given(method("POST"))
+            .and(path("/responses"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(body, "text/event-stream"))
+            .mount(&server)
+            .await;
+
+        let provider = OpenAIProvider::builder()
+            .api_key("test-key")
+            .base_url(server.uri())
+            .api(OpenAIApi::Responses)
+            .build();
+        let request = StreamingRequest::builder()
+            .model("gpt-4o")
+            .message(MessageRole::User, "Hi")
+            .build();
+
+        let error = provider
+            .complete(request, &TimingEngine::new())
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("The server had an error"));
+    }
+
+    #[tokio::test]
+    async fn test_responses_non_streaming() {
+        let server = MockServer::start().await;
+        let body = r#"{"id":"resp_3","object":"response","status":"completed","model":"gpt-4o-mini","output":[{"id":"msg_3","type":"message","role":"assistant","content":[{"type":"output_text","text":"Hello world","annotations":[]}]}],"usage":{"input_tokens":1000,"input_tokens_details":{"cached_tokens":768},"output_tokens":1000,"output_tokens_details":{"reasoning_tokens":0},"total_tokens":2000}}"#;
+
+        Mock::given(method("POST"))
+            .and(path("/responses"))
+            .and(body_partial_json(serde_json::json!({"stream": false})))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(body, "application/json"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = OpenAIProvider::builder()
+            .api_key("test-key")
+            .base_url(server.uri())
+            .api(OpenAIApi::Responses)
+            .build();
+        let request = StreamingRequest::builder()
+            .model("gpt-4o-mini")
+            .message(MessageRole::User, "Hi")
+            .build();
+
+        let result = provider
+            .complete_non_streaming(request, &TimingEngine::new())
+            .await
+            .unwrap();
+
+        assert!(!result.is_streamed());
+        assert_eq!(result.content, "Hello world");
+        assert_eq!(result.metadata.output_tokens, Some(1000));
+        assert_eq!(result.metadata.cache_read_tokens, Some(768));
+        // 232 uncached and 768 cached input tokens
+        let cost = result.metadata.estimated_cost.unwrap();
+        assert!((cost - 0.0006924).abs() < 0.000001);
+        assert_eq!(result.body.unwrap().size_bytes, body.len() as u64);
+    }
+
+    #[tokio::test]
+    async fn test_embed() {
+        let server = MockServer::start().await;
+        let body = r#"{"object":"list","data":[{"object":"embedding","index":1,"embedding":[0.3,0.4]},{"object":"embedding","index":0,"embedding":[0.1,0.2]}],"model":"text-embedding-3-small","usage":{"prompt_tokens":500000,"total_tokens":500000}}"#;
+
+        Mock::given(method("POST"))
+            .and(path("/embeddings"))
+            .and(body_partial_json(serde_json::json!({
+                "model": "text-embedding-3-small",
+                "input": ["first", "second"],
+                "dimensions": 2
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(body, "application/json"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = OpenAIProvider::builder()
+            .api_key("test-key")
+            .base_url(server.uri())
+            .build();
+        let request = EmbeddingRequest::builder()
+            .model("text-embedding-3-small")
+            .inputs(["first", "second"])
+            .dimensions(2)
+            .build();
+
+        let result = provider.embed(request, &TimingEngine::new()).await.unwrap();
+
+        assert_eq!(result.input_count, 2);
+        assert_eq!(result.embeddings, vec![vec![0.1, 0.2], vec![0.3, 0.4]]);
+        assert_eq!(result.dimensions(), Some(2));
+        assert_eq!(result.metadata.input_tokens, Some(500000));
+        assert_eq!(result.metadata.estimated_cost, Some(0.01));
+        assert_eq!(result.body.size_bytes, body.len() as u64);
+    }
+
+    #[tokio::test]
+    async fn test_embed_rejects_chat_model() {
+        let provider = OpenAIProvider::new("test-key");
+        let request = EmbeddingRequest::builder().model("gpt-4o").input("hi").build();
+        let result = provider.embed(request, &TimingEngine::new()).await;
+        assert!(matches!(result, Err(ProviderError::InvalidModel(_))));
+    }
+
+    #[test]
+    fn test_is_reasoning_model() {
+        assert!(is_reasoning_model("o1"));
+        assert!(is_reasoning_model("o1-mini"));
+        assert!(is_reasoning_model("o3-mini-2025-01-31"));
+        assert!(!is_reasoning_model("gpt-4o"));
+        assert!(!is_reasoning_model("o1x"));
+    }
+
+    #[test]
+    fn test_message_content_parts() {
+        let text = Message::new(MessageRole::User, "Hi");
+        assert_eq!(message_content(&text), serde_json::json!("Hi"));
+
+        let message = Message {
+            role: MessageRole::User,
+            content: vec![
+                ContentPart::text("Compare these"),
+                ContentPart::image_url("https://example.com/a.png"),
+                ContentPart::Image {
+                    media_type: "image/jpeg".to_string(),
+                    data: "AAAA".to_string(),
+                },
+                ContentPart::Document {
+                    media_type: "application/pdf".to_string(),
+                    data: "JVBE".to_string(),
+                },
+            ],
+            cache_breakpoint: false,
+        };
+        assert_eq!(
+            message_content(&message),
+            serde_json::json!([
+                {"type": "text", "text": "Compare these"},
+                {"type": "image_url", "image_url": {"url": "https://example.com/a.png"}},
+                {"type": "image_url", "image_url": {"url": "data:image/jpeg;base64,AAAA"}},
+                {"type": "file", "file": {"filename": "document.pdf", "file_data": "data:application/pdf;base64,JVBE"}}
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_stream_reports_reasoning() {
+        let server = MockServer::start().await;
+        let body = [
+            r#"{"id":"chatcmpl-2","object":"chat.completion.chunk","created":1,"model":"o3-mini","choices":[{"index":0,"delta":{"role":"assistant","content":""},"finish_reason":null}],"usage":null}"#,
+            r#"{"id":"chatcmpl-2","object":"chat.completion.chunk","created":1,"model":"o3-mini","choices":[{"index":0,"delta":{"content":"42"},"finish_reason":null}],"usage":null}"#,
+            r#"{"id":"chatcmpl-2","object":"chat.completion.chunk","created":1,"model":"o3-mini","choices":[{"index":0,"delta":{},"finish_reason":"stop"}],"usage":null}"#,
+            r#"{"id":"chatcmpl-2","object":"chat.completion.chunk","created":1,"model":"o3-mini","choices":[],"usage":{"prompt_tokens":1000,"completion_tokens":1000,"total_tokens":2000,"completion_tokens_details":{"reasoning_tokens":960}}}"#,
+            "[DONE]",
+        ]
+        .iter()
+        .map(|c| format!("data: {}\n\n", c))
+        .collect::<String>();
+
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .and(body_partial_json(serde_json::json!({
+                "model": "o3-mini",
+                "max_completion_tokens": 2000
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(body, "text/event-stream"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = OpenAIProvider::builder()
+            .api_key("test-key")
+            .base_url(server.uri())
+            .build();
+        let request = StreamingRequest::builder()
+            .model("o3-mini")
+            .message(MessageRole::User, "Hi")
+            .max_tokens(2000)
+            .temperature(0.7)
+            .build();
+
+        let result = provider.complete(request, &TimingEngine::new()).await.unwrap();
+
+        assert_eq!(result.content, "42");
+        assert_eq!(result.token_events.len(), 1);
+        assert_eq!(result.metadata.output_tokens, Some(40));
+        assert_eq!(result.metadata.thinking_tokens, Some(960));
+        assert!(result.reasoning_time().is_some());
+        // o3-mini: $1.10/1M input, $4.40/1M output (reasoning billed as output)
+        let cost = result.metadata.estimated_cost.unwrap();
+        assert!((cost - 0.0055).abs() < 0.000001);
+    }
+
+    #[tokio::test]
+    async fn test_stream_reasoning_content_deltas() {
+        let server = MockServer::start().await;
+        let body = [
+            r#"{"id":"c","object":"chat.completion.chunk","created":1,"model":"gpt-4o","choices":[{"index":0,"delta":{"role":"assistant","content":null,"reasoning_content":"Let me"},"finish_reason":null}]}"#,
+            r#"{"id":"c","object":"chat.completion.chunk","created":1,"model":"gpt-4o","choices":[{"index":0,"delta":{"content":null,"reasoning_content":" think"},"finish_reason":null}]}"#,
+            r#"{"id":"c","object":"chat.completion.chunk","created":1,"model":"gpt-4o","choices":[{"index":0,"delta":{"content":"Done","reasoning_content":null},"finish_reason":"stop"}]}"#,
+            "[DONE]",
+        ]
+        .iter()
+        .map(|c| format!("data: {}\n\n", c))
+        .collect::<String>();
+
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(body, "text/event-stream"))
+            .mount(&server)
+            .await;
+
+        let provider = OpenAIProvider::builder()
+            .api_key("test-key")
+            .base_url(server.uri())
+            .build();
+        let request = StreamingRequest::builder()
+            .model("gpt-4o")
+            .message(MessageRole::User, "Hi")
+            .build();
+
+        let result = provider.complete(request, &TimingEngine::new()).await.unwrap();
+
+        assert_eq!(result.thinking, "Let me think");
+        assert_eq!(result.content, "Done");
+        assert_eq!(result.token_events[0].kind, TokenKind::Thinking);
+        assert_eq!(result.token_events[2].kind, TokenKind::Content);
+        assert_eq!(result.reasoning_time(), result.time_to_first_visible_token());
+    }
+
+    #[tokio::test]
+    async fn test_stream_tool_calls() {
+        let server = MockServer::start().await;
+        let body = [
+            r#"{"id":"c","object":"chat.completion.chunk","created":1,"model":"gpt-4o","choices":[{"index":0,"delta":{"role":"assistant","content":null,"tool_calls":[{"index":0,"id":"call_1","type":"function","function":{"name":"get_weather","arguments":""}}]},"finish_reason":null}]}"#,
+            r#"{"id":"c","object":"chat.completion.chunk","created":1,"model":"gpt-4o","choices":[{"index":0,"delta":{"tool_calls":[{"index":0,"function":{"arguments":"{\"city\":"}}]},"finish_reason":null}]}"#,
+            r#"{"id":"c","object":"chat.completion.chunk","created":1,"model":"gpt-4o","choices":[{"index":0,"delta":{"tool_calls":[{"index":0,"function":{"arguments":"\"Paris\"}"}}]},"finish_reason":null}]}"#,
+            r#"{"id":"c","object":"chat.completion.chunk","created":1,"model":"gpt-4o","choices":[{"index":0,"delta":{},"finish_reason":"tool_calls"}]}"#,
+            "[DONE]",
+        ]
+        .iter()
+        .map(|c| format!("data: {}\n\n", c))
+        .collect::<String>();
+
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .and(body_partial_json(serde_json::json!({
+                "tools": [{
+                    "type": "function",
+                    "function": {"name": "get_weather", "description": "Current weather"}
+                }],
+                "tool_choice": "required"
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(body, "text/event-stream"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = OpenAIProvider::builder()
+            .api_key("test-key")
+            .base_url(server.uri())
+            .build();
+        let request = StreamingRequest::builder()
+            .model("gpt-4o")
+            .message(MessageRole::User, "Weather in Paris?")
+            .tool(
+                crate::traits::Tool::new(
+                    "get_weather",
+                    serde_json::json!({"type": "object", "properties": {"city": {"type": "string"}}}),
+                )
+                .with_description("Current weather"),
+            )
+            .tool_choice(ToolChoice::Required)
+            .build();
+
+        let result = provider.complete(request, &TimingEngine::new()).await.unwrap();
+
+        assert_eq!(result.content, "");
+        assert_eq!(result.token_events.len(), 3);
+        assert!(result.token_events.iter().all(|e| e.kind == TokenKind::ToolCall));
+        let arguments: String = result
+            .token_events
+            .iter()
+            .filter_map(|e| e.content.as_deref())
+            .collect();
+        assert_eq!(arguments, r#"{"city":"Paris"}"#);
+        assert_eq!(result.time_to_first_tool_call(), result.ttft());
+        assert!(result.tool_call_duration().is_some());
+    }
 }
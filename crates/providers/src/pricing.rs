@@ -0,0 +1,452 @@
+//! Model price tables
+//!
+//! Prices are data rather than code: a table of [`ModelPrice`] entries,
+//! each naming a provider, the models it covers and the date it took effect.
+//! A default table is bundled with the crate (`pricing.toml`); users extend
+//! or correct it with their own table through
+//! [`with_overrides`](PriceTable::with_overrides).
+//!
+//! Requests are priced at the entry valid on the day they ran, so results
+//! recorded before a price change can be re-costed with the prices they were
+//! billed at. Entries cover prompt-cache reads and writes, reasoning tokens,
+//! batch discounts and tiered long-context rates.
+//!
+//! Tables are read from TOML or JSON:
+//!
+//! ```toml
+//! [[prices]]
+//! provider = "generic"
+//! models = ["llama-3.1-70b"]
+//! effective_from = "2025-01-01"
+//! input = 0.60
+//! output = 0.80
+//! ```
+
+use crate::error::{ProviderError, Result};
+use crate::traits::TokenUsage;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// Bundled price table, in TOML
+const BUNDLED: &str = include_str!("../pricing.toml");
+
+/// How requests are billed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PricingMode {
+    /// Standard (synchronous) requests
+    #[default]
+    Standard,
+    /// Batch requests, at the entry's batch discount
+    Batch,
+}
+
+/// Per-token rates, in USD per 1M tokens
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TokenRates {
+    /// Uncached input tokens
+    pub input: f64,
+
+    /// Output tokens
+    pub output: f64,
+
+    /// Input tokens served from the prompt cache (defaults to `input`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cached_input: Option<f64>,
+
+    /// Input tokens written to the prompt cache (defaults to `input`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_write: Option<f64>,
+
+    /// Thinking/reasoning tokens (defaults to `output`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reasoning: Option<f64>,
+}
+
+/// Rates for prompts longer than a threshold
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PriceTier {
+    /// Prompts above this many input tokens use the tier's rates
+    pub above_input_tokens: u64,
+
+    /// Rates of the tier
+    #[serde(flatten)]
+    pub rates: TokenRates,
+}
+
+/// Prices of one or more models from a given date
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModelPrice {
+    /// Provider name (e.g. "openai", "aws-bedrock", "generic")
+    pub provider: String,
+
+    /// Model names the prices apply to
+    pub models: Vec<String>,
+
+    /// First day the prices apply (None: since the model was available)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub effective_from: Option<NaiveDate>,
+
+    /// Base rates
+    #[serde(flatten)]
+    pub rates: TokenRates,
+
+    /// Fraction taken off in batch mode (None: no batch pricing)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub batch_discount: Option<f64>,
+
+    /// Long-context tiers
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tiers: Vec<PriceTier>,
+}
+
+impl ModelPrice {
+    /// Rates for a prompt of `prompt_tokens` input tokens
+    pub fn rates_for(&self, prompt_tokens: u64) -> &TokenRates {
+        self.tiers
+            .iter()
+            .filter(|tier| prompt_tokens > tier.above_input_tokens)
+            .max_by_key(|tier| tier.above_input_tokens)
+            .map_or(&self.rates, |tier| &tier.rates)
+    }
+
+    /// Cost of a request in USD
+    ///
+    /// `input_includes_cache` tells whether the reported input tokens count
+    /// cache reads and writes (OpenAI, Gemini) or exclude them (Anthropic,
+    /// Bedrock).
+    /// Returns None without input and output counts, or in batch mode
+    /// without a batch discount.
+    pub fn cost(
+        &self,
+        usage: &TokenUsage,
+        mode: PricingMode,
+        input_includes_cache: bool,
+    ) -> Option<f64> {
+        let input = usage.input_tokens?;
+        let output = usage.output_tokens?;
+        let cache_read = usage.cache_read_tokens.unwrap_or(0);
+        let cache_write = usage.cache_creation_tokens.unwrap_or(0);
+        let thinking = usage.thinking_tokens.unwrap_or(0);
+
+        let (uncached, prompt) = if input_includes_cache {
+            (input.saturating_sub(cache_read + cache_write), input)
+        } else {
+            (input, input + cache_read + cache_write)
+        };
+
+        let rates = self.rates_for(prompt);
+        let per_million = |tokens: u64, price: f64| tokens as f64 / 1_000_000.0 * price;
+        let cost = per_million(uncached, rates.input)
+            + per_million(cache_read, rates.cached_input.unwrap_or(rates.input))
+            + per_million(cache_write, rates.cache_write.unwrap_or(rates.input))
+            + per_million(output, rates.output)
+            + per_million(thinking, rates.reasoning.unwrap_or(rates.output));
+
+        match mode {
+            PricingMode::Standard => Some(cost),
+            PricingMode::Batch => self.batch_discount.map(|discount| cost * (1.0 - discount)),
+        }
+    }
+}
+
+/// Versioned table of model prices
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PriceTable {
+    /// Table version (e.g. the date it was last updated)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+
+    /// Price entries
+    #[serde(default)]
+    pub prices: Vec<ModelPrice>,
+}
+
+impl PriceTable {
+    /// Create a table from entries
+    pub fn new(prices: Vec<ModelPrice>) -> Self {
+        Self {
+            version: None,
+            prices,
+        }
+    }
+
+    /// The table bundled with this crate
+    pub fn bundled() -> &'static PriceTable {
+        static TABLE: OnceLock<PriceTable> = OnceLock::new();
+        TABLE.get_or_init(|| {
+            PriceTable::from_toml(BUNDLED).expect("bundled price table is valid")
+        })
+    }
+
+    /// Parse a table from TOML
+    pub fn from_toml(content: &str) -> Result<Self> {
+        toml::from_str(content)
+            .map_err(|e| ProviderError::ConfigError(format!("Invalid price table: {}", e)))
+    }
+
+    /// Parse a table from JSON
+    pub fn from_json(content: &str) -> Result<Self> {
+        serde_json::from_str(content)
+            .map_err(|e| ProviderError::ConfigError(format!("Invalid price table: {}", e)))
+    }
+
+    /// Load a table from a `.json` or TOML file
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            ProviderError::ConfigError(format!(
+                "Failed to read price table {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        if path.extension().and_then(|ext| ext.to_str()) == Some("json") {
+            Self::from_json(&content)
+        } else {
+            Self::from_toml(&content)
+        }
+    }
+
+    /// Apply another table over this one
+    ///
+    /// Every model priced in `overrides` takes its whole price history from
+    /// `overrides`; this table's entries for it are dropped. Other models keep
+    /// their prices.
+    pub fn with_overrides(mut self, overrides: PriceTable) -> Self {
+        for entry in &mut self.prices {
+            let provider = pricing_provider(&entry.provider);
+            entry.models.retain(|model| {
+                !overrides.prices.iter().any(|o| {
+                    pricing_provider(&o.provider) == provider && o.models.contains(model)
+                })
+            });
+        }
+        self.prices.retain(|entry| !entry.models.is_empty());
+        self.prices.extend(overrides.prices);
+        if overrides.version.is_some() {
+            self.version = overrides.version;
+        }
+        self
+    }
+
+    /// Prices of `model` on `provider` valid at `at`
+    pub fn price(&self, provider: &str, model: &str, at: DateTime<Utc>) -> Option<&ModelPrice> {
+        let provider = pricing_provider(provider);
+        let model = pricing_model(provider, model);
+        let day = at.date_naive();
+        self.prices
+            .iter()
+            .filter(|entry| {
+                pricing_provider(&entry.provider) == provider
+                    && entry.models.iter().any(|m| m == model)
+                    && entry.effective_from.map_or(true, |from| from <= day)
+            })
+            .max_by_key(|entry| entry.effective_from)
+    }
+
+    /// Cost of a request to `model` on `provider` made at `at`, in USD
+    pub fn cost(
+        &self,
+        provider: &str,
+        model: &str,
+        usage: &TokenUsage,
+        at: DateTime<Utc>,
+        mode: PricingMode,
+    ) -> Option<f64> {
+        self.price(provider, model, at)?
+            .cost(usage, mode, input_includes_cache(provider))
+    }
+}
+
+/// Provider name prices are listed under
+///
+/// Accepts adapter names and CLI identifiers. Azure OpenAI and the Responses
+/// API share OpenAI's list prices.
+fn pricing_provider(provider: &str) -> &str {
+    match provider {
+        "openai-responses" | "azure" | "azure-openai" => "openai",
+        "realtime" => "openai-realtime",
+        "bedrock" => "aws-bedrock",
+        _ => provider,
+    }
+}
+
+/// Whether the adapter's reported input tokens count cache reads and writes
+///
+/// Depends on the API the adapter speaks, not on whose prices apply: Claude
+/// on Bedrock is priced by Bedrock, and Converse reports cache tokens apart
+/// from `inputTokens` like Anthropic's Messages API does.
+fn input_includes_cache(provider: &str) -> bool {
+    !matches!(provider, "anthropic" | "aws-bedrock" | "bedrock")
+}
+
+/// Model name prices are listed under
+fn pricing_model<'a>(provider: &str, model: &'a str) -> &'a str {
+    match provider {
+        "aws-bedrock" => crate::bedrock::base_model_id(model),
+        _ => model,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn usage(input: u64, output: u64) -> TokenUsage {
+        TokenUsage {
+            input_tokens: Some(input),
+            output_tokens: Some(output),
+            ..TokenUsage::default()
+        }
+    }
+
+    fn day(year: i32, month: u32, day: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(year, month, day, 12, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_bundled_table() {
+        let table = PriceTable::bundled();
+        assert!(table.version.is_some());
+
+        let cost = table
+            .cost("openai", "gpt-4o", &usage(1000, 1000), Utc::now(), PricingMode::Standard)
+            .unwrap();
+        assert!((cost - 0.0125).abs() < 1e-9);
+
+        // Azure and cross-region Bedrock profiles resolve to listed prices
+        assert!(table.price("azure-openai", "gpt-4o-mini", Utc::now()).is_some());
+        assert!(table
+            .price("bedrock", "us.anthropic.claude-3-5-haiku-20241022-v1:0", Utc::now())
+            .is_some());
+        assert!(table.price("openai", "unknown-model", Utc::now()).is_none());
+    }
+
+    #[test]
+    fn test_cache_accounting_by_adapter() {
+        let table = PriceTable::bundled();
+        let cached = TokenUsage {
+            cache_read_tokens: Some(1000),
+            ..usage(1000, 0)
+        };
+
+        // Bedrock reports cache reads apart from the input: 2000 tokens at
+        // $0.80/1M (no cached rate listed)
+        let cost = table
+            .cost(
+                "aws-bedrock",
+                "anthropic.claude-3-5-haiku-20241022-v1:0",
+                &cached,
+                Utc::now(),
+                PricingMode::Standard,
+            )
+            .unwrap();
+        assert!((cost - 0.0016).abs() < 1e-9);
+        assert!(!input_includes_cache("bedrock"));
+        assert!(input_includes_cache("azure-openai"));
+    }
+
+    #[test]
+    fn test_effective_dates() {
+        let table = PriceTable::bundled();
+
+        // Gemini 1.5 Pro was repriced on 2024-10-01
+        let before = table.price("google", "gemini-1.5-pro", day(2024, 9, 30)).unwrap();
+        let after = table.price("google", "gemini-1.5-pro", day(2024, 10, 1)).unwrap();
+        assert_eq!(before.rates.input, 3.50);
+        assert_eq!(after.rates.input, 1.25);
+
+        // Nothing before the model's first price
+        assert!(table.price("google", "gemini-1.5-pro", day(2024, 1, 1)).is_none());
+    }
+
+    #[test]
+    fn test_cache_reasoning_and_tiers() {
+        let price = ModelPrice {
+            provider: "test".to_string(),
+            models: vec!["m".to_string()],
+            effective_from: None,
+            rates: TokenRates {
+                input: 2.0,
+                output: 8.0,
+                cached_input: Some(0.5),
+                cache_write: Some(2.5),
+                reasoning: Some(10.0),
+            },
+            batch_discount: Some(0.5),
+            tiers: vec![PriceTier {
+                above_input_tokens: 200_000,
+                rates: TokenRates {
+                    input: 4.0,
+                    output: 16.0,
+                    cached_input: None,
+                    cache_write: None,
+                    reasoning: None,
+                },
+            }],
+        };
+
+        let long = TokenUsage {
+            input_tokens: Some(1_000_000),
+            output_tokens: Some(1_000_000),
+            thinking_tokens: Some(1_000_000),
+            cache_creation_tokens: None,
+            cache_read_tokens: Some(500_000),
+        };
+
+        // Cache reads counted in the input leave 0.5M uncached. Upper tier:
+        // 0.5M * 4 + 0.5M * 4 (no tier cache rate) + 1M * 16 + 1M * 16
+        let cost = price.cost(&long, PricingMode::Standard, true).unwrap();
+        assert!((cost - 36.0).abs() < 1e-9);
+
+        let small = TokenUsage {
+            input_tokens: Some(100_000),
+            output_tokens: Some(0),
+            thinking_tokens: Some(100_000),
+            cache_creation_tokens: Some(20_000),
+            cache_read_tokens: Some(50_000),
+        };
+        // Cache excluded from the input: 0.1M * 2 + 0.05M * 0.5 + 0.02M * 2.5 + 0.1M * 10
+        let cost = price.cost(&small, PricingMode::Standard, false).unwrap();
+        assert!((cost - 1.275).abs() < 1e-9);
+        let batch = price.cost(&small, PricingMode::Batch, false).unwrap();
+        assert!((batch - cost / 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_overrides() {
+        let overrides = PriceTable::from_json(
+            r#"{
+                "version": "internal-2025-03",
+                "prices": [
+                    {"provider": "openai", "models": ["gpt-4o"], "input": 2.0, "output": 8.0},
+                    {"provider": "generic", "models": ["llama-3.1-70b"], "input": 0.6, "output": 0.8}
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        let table = PriceTable::bundled().clone().with_overrides(overrides);
+        assert_eq!(table.version.as_deref(), Some("internal-2025-03"));
+
+        // The override replaces the whole history of gpt-4o
+        let price = table.price("openai", "gpt-4o", day(2024, 6, 1)).unwrap();
+        assert_eq!(price.rates.input, 2.0);
+        // Snapshots listed with it keep their bundled prices
+        let price = table.price("openai", "gpt-4o-2024-05-13", day(2024, 6, 1)).unwrap();
+        assert_eq!(price.rates.input, 5.0);
+
+        let usage_1m = usage(1_000_000, 1_000_000);
+        let cost = table
+            .cost("generic", "llama-3.1-70b", &usage_1m, Utc::now(), PricingMode::Standard)
+            .unwrap();
+        assert!((cost - 1.4).abs() < 1e-9);
+        // No batch discount listed
+        assert!(table
+            .cost("generic", "llama-3.1-70b", &usage(1, 1), Utc::now(), PricingMode::Batch)
+            .is_none());
+    }
+}
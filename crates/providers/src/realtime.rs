@@ -0,0 +1,837 @@
+//! OpenAI Realtime provider (WebSocket transport, text mode)
+//!
+//! The Realtime API runs a session over a WebSocket instead of answering an
+//! HTTP request with SSE. A request goes through several steps, each timed:
+//!
+//! 1. Connection setup: DNS, TCP connect, TLS and the WebSocket upgrade (the
+//!    upgrade response stands in for the first byte)
+//! 2. Session creation: the server announces `session.created`, the client
+//!    sends `session.update` for text-only output and waits for
+//!    `session.updated` ([`NetworkTimings::session_ready`])
+//! 3. The conversation items are added and `response.create` is sent
+//!    ([`NetworkTimings::response_requested`])
+//! 4. The response streams as `response.text.delta` events until
+//!    `response.done`, which carries the usage
+//!
+//! [`CompletionResult::response_ttft`](crate::traits::CompletionResult::response_ttft)
+//! measures the time to first token from step 3, without the session setup.
+//!
+//! Top-p and stop sequences have no Realtime equivalent and are ignored.
+//! There is no non-streaming mode.
+//!
+//! [`NetworkTimings::session_ready`]: crate::network::NetworkTimings::session_ready
+//! [`NetworkTimings::response_requested`]: crate::network::NetworkTimings::response_requested
+
+use crate::error::{error_from_body, ProviderError, Result};
+use crate::headers::ServerSignals;
+use crate::network::{self, Connection, NetworkProbe};
+use crate::traits::{
+    CompletionResult, MessageRole, Provider, ResponseMetadata, SharedUsage, StreamingRequest,
+    StreamingResponse, TokenUsage, ToolChoice,
+};
+use async_trait::async_trait;
+use futures::{SinkExt, StreamExt};
+use llm_latency_lens_core::{Clock, RequestId, TimingEngine, Timestamp, TokenEvent, TokenKind};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::TcpStream;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::http::header::{HeaderValue, AUTHORIZATION};
+use tokio_tungstenite::tungstenite::{Error as WsError, Message};
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+
+/// Default time allowed for connection and session setup
+const DEFAULT_SETUP_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Model used to open a session for health checks
+const HEALTH_CHECK_MODEL: &str = "gpt-4o-mini-realtime-preview";
+
+type Socket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// OpenAI Realtime provider adapter
+pub struct RealtimeProvider {
+    /// API key
+    api_key: String,
+    /// WebSocket URL, without the model query
+    base_url: String,
+    /// TLS configuration, shared so sessions can be resumed
+    tls: Arc<rustls::ClientConfig>,
+    /// Time allowed for connection and session setup
+    setup_timeout: Duration,
+}
+
+impl RealtimeProvider {
+    /// Create a new Realtime provider
+    ///
+    /// # Arguments
+    ///
+    /// * `api_key` - OpenAI API key
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use llm_latency_lens_providers::realtime::RealtimeProvider;
+    ///
+    /// let provider = RealtimeProvider::new("sk-...");
+    /// ```
+    pub fn new(api_key: impl Into<String>) -> Self {
+        Self::builder().api_key(api_key).build()
+    }
+
+    /// Create a provider with custom configuration
+    pub fn builder() -> RealtimeProviderBuilder {
+        RealtimeProviderBuilder::default()
+    }
+
+    /// Open a WebSocket for `model`, recording the connection phases
+    async fn open(
+        &self,
+        model: &str,
+        network: &NetworkProbe,
+    ) -> Result<(Socket, tokio_tungstenite::tungstenite::handshake::client::Response)> {
+        let url = reqwest::Url::parse(&format!("{}?model={}", self.base_url, model))
+            .map_err(|e| ProviderError::ConfigError(format!("Invalid Realtime URL: {}", e)))?;
+        let tls = match url.scheme() {
+            "wss" => Some(Arc::clone(&self.tls)),
+            "ws" => None,
+            scheme => {
+                return Err(ProviderError::ConfigError(format!(
+                    "Unsupported Realtime URL scheme: {}",
+                    scheme
+                )))
+            }
+        };
+        // IPv6 literals are bracketed in URLs
+        let host = url
+            .host_str()
+            .map(|h| h.trim_start_matches('[').trim_end_matches(']'))
+            .ok_or_else(|| ProviderError::ConfigError("Realtime URL has no host".to_string()))?;
+        let port = url
+            .port_or_known_default()
+            .ok_or_else(|| ProviderError::ConfigError("Realtime URL has no port".to_string()))?;
+
+        let stream = match network::connect(network, host, port, tls).await {
+            Ok(Connection::Plain(stream)) => MaybeTlsStream::Plain(stream),
+            Ok(Connection::Tls(stream)) => MaybeTlsStream::Rustls(*stream),
+            Err(e) => return Err(ProviderError::NetworkError(format!("Connection failed: {}", e))),
+        };
+
+        let mut handshake = url.as_str().into_client_request().map_err(ws_error)?;
+        let headers = handshake.headers_mut();
+        headers.insert(
+            AUTHORIZATION,
+            HeaderValue::from_str(&format!("Bearer {}", self.api_key))
+                .map_err(|_| ProviderError::ConfigError("Invalid API key format".to_string()))?,
+        );
+        headers.insert("OpenAI-Beta", HeaderValue::from_static("realtime=v1"));
+
+        let opened = tokio_tungstenite::client_async(handshake, stream)
+            .await
+            .map_err(ws_error)?;
+        network.first_byte();
+        Ok(opened)
+    }
+
+    /// Set up a text session for `request` and request the response
+    async fn start_response(
+        &self,
+        request: &StreamingRequest,
+        network: &NetworkProbe,
+    ) -> Result<(Socket, tokio_tungstenite::tungstenite::handshake::client::Response)> {
+        let (mut socket, response) = self.open(&request.model, network).await?;
+
+        expect_event(&mut socket, "session.created", |e| {
+            matches!(e, ServerEvent::SessionCreated)
+        })
+        .await?;
+        send(
+            &mut socket,
+            &ClientEvent::SessionUpdate {
+                session: session_config(request),
+            },
+        )
+        .await?;
+        expect_event(&mut socket, "session.updated", |e| {
+            matches!(e, ServerEvent::SessionUpdated)
+        })
+        .await?;
+        network.session_ready();
+
+        for item in conversation_items(request) {
+            send(&mut socket, &ClientEvent::ConversationItemCreate { item }).await?;
+        }
+        send(&mut socket, &ClientEvent::ResponseCreate {}).await?;
+        network.response_requested();
+
+        Ok((socket, response))
+    }
+}
+
+/// Builder for the Realtime provider
+#[derive(Default)]
+pub struct RealtimeProviderBuilder {
+    api_key: Option<String>,
+    base_url: Option<String>,
+    timeout: Option<Duration>,
+}
+
+impl RealtimeProviderBuilder {
+    /// Set the API key
+    pub fn api_key(mut self, key: impl Into<String>) -> Self {
+        self.api_key = Some(key.into());
+        self
+    }
+
+    /// Set the WebSocket URL (`ws://` or `wss://`, for custom endpoints)
+    pub fn base_url(mut self, url: impl Into<String>) -> Self {
+        self.base_url = Some(url.into());
+        self
+    }
+
+    /// Set the time allowed for connection and session setup (120 seconds
+    /// by default)
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Build the provider
+    pub fn build(self) -> RealtimeProvider {
+        RealtimeProvider {
+            api_key: self.api_key.expect("API key is required"),
+            base_url: self
+                .base_url
+                .unwrap_or_else(|| "wss://api.openai.com/v1/realtime".to_string()),
+            tls: Arc::new(network::tls_config()),
+            setup_timeout: self.timeout.unwrap_or(DEFAULT_SETUP_TIMEOUT),
+        }
+    }
+}
+
+#[async_trait]
+impl Provider for RealtimeProvider {
+    fn name(&self) -> &'static str {
+        "openai-realtime"
+    }
+
+    fn provider_kind(&self) -> llm_latency_lens_core::Provider {
+        llm_latency_lens_core::Provider::OpenAI
+    }
+
+    async fn health_check(&self) -> Result<()> {
+        let network = NetworkProbe::new(&Clock::new());
+        let (mut socket, _) = self.open(HEALTH_CHECK_MODEL, &network).await?;
+        expect_event(&mut socket, "session.created", |e| {
+            matches!(e, ServerEvent::SessionCreated)
+        })
+        .await?;
+        let _ = socket.close(None).await;
+        Ok(())
+    }
+
+    async fn stream(
+        &self,
+        request: StreamingRequest,
+        timing_engine: &TimingEngine,
+    ) -> Result<StreamingResponse> {
+        // Validate model
+        self.validate_model(&request.model)?;
+
+        // Start timing measurement
+        let mut timing = timing_engine.start();
+        let network = NetworkProbe::new(timing_engine.clock());
+        timing.checkpoint("request_start");
+
+        let (socket, handshake) =
+            tokio::time::timeout(self.setup_timeout, self.start_response(&request, &network))
+                .await
+                .map_err(|_| ProviderError::TimeoutError(self.setup_timeout))??;
+
+        timing.checkpoint("response_requested");
+
+        let response_headers = crate::headers::capture(handshake.headers(), &request.redact_headers);
+        let server = ServerSignals::from_headers(handshake.headers());
+
+        let usage = SharedUsage::new();
+        let state = ResponseState {
+            socket,
+            clock: timing_engine.clock().clone(),
+            network: network.clone(),
+            usage: usage.clone(),
+            request_id: request.request_id,
+            request_start: timing.start_time(),
+            sequence: 0,
+            last_token_time: None,
+            finished: false,
+        };
+        let token_stream = futures::stream::unfold(state, next_token).boxed();
+
+        timing.checkpoint("stream_initialized");
+
+        Ok(StreamingResponse {
+            request_id: request.request_id,
+            token_stream: Box::pin(token_stream),
+            metadata: ResponseMetadata {
+                model: request.model,
+                input_tokens: None, // Reported in response.done
+                output_tokens: None,
+                thinking_tokens: None,
+                cache_creation_tokens: None,
+                cache_read_tokens: None,
+                estimated_cost: None,
+                headers: response_headers,
+                server,
+            },
+            usage,
+            network,
+        })
+    }
+
+    async fn complete_non_streaming(
+        &self,
+        _request: StreamingRequest,
+        _timing_engine: &TimingEngine,
+    ) -> Result<CompletionResult> {
+        Err(ProviderError::ConfigError(
+            "The Realtime API has no non-streaming mode".to_string(),
+        ))
+    }
+
+    fn supported_models(&self) -> Vec<String> {
+        vec![
+            "gpt-4o-realtime-preview".to_string(),
+            "gpt-4o-realtime-preview-2024-12-17".to_string(),
+            "gpt-4o-realtime-preview-2024-10-01".to_string(),
+            "gpt-4o-mini-realtime-preview".to_string(),
+            "gpt-4o-mini-realtime-preview-2024-12-17".to_string(),
+        ]
+    }
+}
+
+/// State of a response being streamed over an open session
+struct ResponseState {
+    socket: Socket,
+    clock: Clock,
+    network: NetworkProbe,
+    usage: SharedUsage,
+    request_id: RequestId,
+    request_start: Timestamp,
+    sequence: u64,
+    last_token_time: Option<Timestamp>,
+    finished: bool,
+}
+
+/// Read server events until the next token, the end of the response or an error
+async fn next_token(mut state: ResponseState) -> Option<(Result<TokenEvent>, ResponseState)> {
+    if state.finished {
+        return None;
+    }
+
+    loop {
+        let frame = state.socket.next().await;
+        let now = state.clock.now();
+        let text = match frame {
+            Some(Ok(Message::Text(text))) => text,
+            Some(Ok(Message::Close(_))) | None => {
+                state.finished = true;
+                return Some((
+                    Err(ProviderError::streaming("Session closed before response.done")),
+                    state,
+                ));
+            }
+            // Pings are answered by the socket
+            Some(Ok(_)) => continue,
+            Some(Err(e)) => {
+                state.finished = true;
+                return Some((Err(ws_error(e)), state));
+            }
+        };
+        state.network.first_event();
+
+        let event = match serde_json::from_str::<ServerEvent>(&text) {
+            Ok(event) => event,
+            Err(e) => {
+                state.finished = true;
+                return Some((Err(ProviderError::from_json_error(e)), state));
+            }
+        };
+        let (kind, content) = match event {
+            ServerEvent::TextDelta { delta } => (TokenKind::Content, delta),
+            ServerEvent::FunctionCallArgumentsDelta { delta } => (TokenKind::ToolCall, delta),
+            // A function call starts before its arguments stream
+            ServerEvent::OutputItemAdded { item } if item.item_type == "function_call" => {
+                (TokenKind::ToolCall, String::new())
+            }
+            ServerEvent::ResponseDone { response } => {
+                state.finished = true;
+                if let Some(usage) = response.usage {
+                    state.usage.update(|u| *u = usage.token_usage());
+                }
+                if response.status == "failed" {
+                    let message = response
+                        .status_details
+                        .and_then(|d| d.error)
+                        .map(|e| e.message)
+                        .unwrap_or_else(|| "Response failed".to_string());
+                    return Some((Err(ProviderError::streaming(message)), state));
+                }
+                let _ = state.socket.close(None).await;
+                return None;
+            }
+            ServerEvent::Error { error } => {
+                state.finished = true;
+                return Some((Err(error.into()), state));
+            }
+            _ => continue,
+        };
+
+        let time_since_start = now.duration_since(state.request_start);
+        let inter_token_latency = state.last_token_time.map(|t| now.duration_since(t));
+        state.last_token_time = Some(now);
+
+        let event = TokenEvent {
+            request_id: state.request_id,
+            sequence: state.sequence,
+            kind,
+            content: Some(content),
+            timestamp_nanos: now.as_nanos(),
+            time_since_start,
+            inter_token_latency,
+        };
+        state.sequence += 1;
+
+        return Some((Ok(event), state));
+    }
+}
+
+/// Read server events until one matching `expected`
+async fn expect_event(
+    socket: &mut Socket,
+    name: &str,
+    expected: impl Fn(&ServerEvent) -> bool,
+) -> Result<()> {
+    while let Some(frame) = socket.next().await {
+        let Message::Text(text) = frame.map_err(ws_error)? else {
+            continue;
+        };
+        let event: ServerEvent =
+            serde_json::from_str(&text).map_err(ProviderError::from_json_error)?;
+        if let ServerEvent::Error { error } = event {
+            return Err(error.into());
+        }
+        if expected(&event) {
+            return Ok(());
+        }
+    }
+    Err(ProviderError::streaming(format!(
+        "Session closed while waiting for {}",
+        name
+    )))
+}
+
+/// Send a client event
+async fn send(socket: &mut Socket, event: &ClientEvent) -> Result<()> {
+    let text = serde_json::to_string(event).map_err(ProviderError::from_json_error)?;
+    socket.send(Message::Text(text)).await.map_err(ws_error)
+}
+
+/// Map a WebSocket error; a rejected upgrade is mapped like an HTTP error response
+fn ws_error(error: WsError) -> ProviderError {
+    match error {
+        WsError::Http(response) => {
+            let status = response.status().as_u16();
+            let body = response
+                .into_body()
+                .map(|b| String::from_utf8_lossy(&b).into_owned())
+                .unwrap_or_default();
+            error_from_body(status, body)
+        }
+        WsError::Io(e) => ProviderError::NetworkError(e.to_string()),
+        e => ProviderError::streaming(e.to_string()),
+    }
+}
+
+/// Session settings: text output, system messages as instructions
+fn session_config(request: &StreamingRequest) -> SessionConfig {
+    let instructions: Vec<String> = request
+        .messages
+        .iter()
+        .filter(|m| m.role == MessageRole::System)
+        .map(|m| m.text())
+        .collect();
+
+    SessionConfig {
+        modalities: vec!["text"],
+        instructions: (!instructions.is_empty()).then(|| instructions.join("\n\n")),
+        temperature: request.temperature,
+        max_response_output_tokens: request.max_tokens,
+        tools: request
+            .tools
+            .iter()
+            .map(|t| RealtimeTool {
+                tool_type: "function",
+                name: t.name.clone(),
+                description: t.description.clone(),
+                parameters: t.parameters.clone(),
+            })
+            .collect(),
+        tool_choice: request.tool_choice.as_ref().map(|choice| match choice {
+            ToolChoice::Auto => serde_json::json!("auto"),
+            ToolChoice::None => serde_json::json!("none"),
+            ToolChoice::Required => serde_json::json!("required"),
+            ToolChoice::Tool(name) => serde_json::json!({"type": "function", "name": name}),
+        }),
+    }
+}
+
+/// User and assistant messages as conversation items
+fn conversation_items(request: &StreamingRequest) -> Vec<ConversationItem> {
+    request
+        .messages
+        .iter()
+        .filter_map(|m| {
+            let (role, part_type) = match m.role {
+                MessageRole::System => return None,
+                MessageRole::User => ("user", "input_text"),
+                MessageRole::Assistant => ("assistant", "text"),
+            };
+            Some(ConversationItem {
+                item_type: "message",
+                role,
+                content: vec![ItemContent {
+                    part_type,
+                    text: m.text(),
+                }],
+            })
+        })
+        .collect()
+}
+
+// Realtime API event types
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "type")]
+enum ClientEvent {
+    #[serde(rename = "session.update")]
+    SessionUpdate { session: SessionConfig },
+    #[serde(rename = "conversation.item.create")]
+    ConversationItemCreate { item: ConversationItem },
+    #[serde(rename = "response.create")]
+    ResponseCreate {},
+}
+
+#[derive(Debug, Serialize)]
+struct SessionConfig {
+    modalities: Vec<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    instructions: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_response_output_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tools: Vec<RealtimeTool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_choice: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Serialize)]
+struct RealtimeTool {
+    #[serde(rename = "type")]
+    tool_type: &'static str,
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    parameters: serde_json::Value,
+}
+
+#[derive(Debug, Serialize)]
+struct ConversationItem {
+    #[serde(rename = "type")]
+    item_type: &'static str,
+    role: &'static str,
+    content: Vec<ItemContent>,
+}
+
+#[derive(Debug, Serialize)]
+struct ItemContent {
+    #[serde(rename = "type")]
+    part_type: &'static str,
+    text: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type")]
+enum ServerEvent {
+    #[serde(rename = "session.created")]
+    SessionCreated,
+    #[serde(rename = "session.updated")]
+    SessionUpdated,
+    #[serde(rename = "response.text.delta", alias = "response.output_text.delta")]
+    TextDelta { delta: String },
+    #[serde(rename = "response.function_call_arguments.delta")]
+    FunctionCallArgumentsDelta { delta: String },
+    #[serde(rename = "response.output_item.added")]
+    OutputItemAdded { item: OutputItem },
+    #[serde(rename = "response.done")]
+    ResponseDone { response: RealtimeResponse },
+    #[serde(rename = "error")]
+    Error { error: RealtimeError },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Deserialize)]
+struct OutputItem {
+    #[serde(rename = "type")]
+    item_type: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct RealtimeResponse {
+    status: String,
+    #[serde(default)]
+    status_details: Option<StatusDetails>,
+    #[serde(default)]
+    usage: Option<RealtimeUsage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StatusDetails {
+    #[serde(default)]
+    error: Option<RealtimeError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RealtimeError {
+    message: String,
+}
+
+impl From<RealtimeError> for ProviderError {
+    fn from(error: RealtimeError) -> Self {
+        ProviderError::streaming(error.message)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct RealtimeUsage {
+    input_tokens: u64,
+    output_tokens: u64,
+    #[serde(default)]
+    input_token_details: Option<InputTokenDetails>,
+}
+
+impl RealtimeUsage {
+    fn token_usage(&self) -> TokenUsage {
+        TokenUsage {
+            input_tokens: Some(self.input_tokens),
+            output_tokens: Some(self.output_tokens),
+            thinking_tokens: None,
+            cache_creation_tokens: None,
+            // Caching is automatic; cached_tokens is part of input_tokens
+            cache_read_tokens: self.input_token_details.as_ref().and_then(|d| d.cached_tokens),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct InputTokenDetails {
+    #[serde(default)]
+    cached_tokens: Option<u64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+    use tokio::net::TcpListener;
+    use tokio_tungstenite::tungstenite::handshake::server::{ErrorResponse, Request, Response};
+
+    /// Realtime stand-in: runs a text session, then replies to
+    /// `response.create` with `events`. Returns the URL and the client events
+    /// it received.
+    async fn serve(events: Vec<Value>) -> (String, tokio::task::JoinHandle<Vec<Value>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}/v1/realtime", listener.local_addr().unwrap());
+
+        let server = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            // The handshake callback's error type is fixed by tungstenite
+            #[allow(clippy::result_large_err)]
+            let check_headers = |request: &Request, response: Response| {
+                assert_eq!(request.headers()["authorization"], "Bearer test-key");
+                assert_eq!(request.headers()["openai-beta"], "realtime=v1");
+                assert_eq!(request.uri().query(), Some("model=gpt-4o-realtime-preview"));
+                Ok::<_, ErrorResponse>(response)
+            };
+            let mut socket = tokio_tungstenite::accept_hdr_async(stream, check_headers)
+                .await
+                .unwrap();
+            let reply = |event: Value| Message::Text(event.to_string());
+
+            socket
+                .send(reply(json!({"type": "session.created", "session": {"id": "sess_1"}})))
+                .await
+                .unwrap();
+
+            let mut received = Vec::new();
+            while let Some(Ok(Message::Text(text))) = socket.next().await {
+                let event: Value = serde_json::from_str(&text).unwrap();
+                let event_type = event["type"].as_str().unwrap().to_string();
+                received.push(event);
+                match event_type.as_str() {
+                    "session.update" => socket
+                        .send(reply(json!({"type": "session.updated", "session": {"id": "sess_1"}})))
+                        .await
+                        .unwrap(),
+                    "response.create" => break,
+                    _ => {}
+                }
+            }
+
+            for event in events {
+                socket.send(reply(event)).await.unwrap();
+            }
+            // Drain until the client closes
+            while let Some(Ok(_)) = socket.next().await {}
+            received
+        });
+
+        (url, server)
+    }
+
+    fn request() -> StreamingRequest {
+        StreamingRequest::builder()
+            .model("gpt-4o-realtime-preview")
+            .message(MessageRole::System, "Be brief")
+            .message(MessageRole::User, "Hi")
+            .max_tokens(64)
+            .build()
+    }
+
+    #[tokio::test]
+    async fn test_stream_text_session() {
+        let (url, server) = serve(vec![
+            json!({"type": "response.created", "response": {"id": "resp_1", "status": "in_progress"}}),
+            json!({"type": "response.output_item.added", "item": {"id": "item_1", "type": "message"}}),
+            json!({"type": "response.text.delta", "item_id": "item_1", "delta": "4"}),
+            json!({"type": "response.text.delta", "item_id": "item_1", "delta": "2"}),
+            json!({"type": "response.text.done", "item_id": "item_1", "text": "42"}),
+            json!({"type": "response.done", "response": {
+                "id": "resp_1",
+                "status": "completed",
+                "usage": {
+                    "input_tokens": 1000,
+                    "output_tokens": 1000,
+                    "input_token_details": {"cached_tokens": 500}
+                }
+            }}),
+        ])
+        .await;
+
+        let provider = RealtimeProvider::builder()
+            .api_key("test-key")
+            .base_url(url)
+            .build();
+        let result = provider.complete(request(), &TimingEngine::new()).await.unwrap();
+
+        assert_eq!(result.content, "42");
+        assert_eq!(result.token_events.len(), 2);
+        assert_eq!(result.metadata.input_tokens, Some(1000));
+        assert_eq!(result.metadata.cache_read_tokens, Some(500));
+        // gpt-4o-realtime-preview: $5/1M input, $2.50/1M cached input, $20/1M output
+        let cost = result.metadata.estimated_cost.unwrap();
+        assert!((cost - 0.02375).abs() < 0.000001);
+
+        // Plain ws:// to an IP literal: no DNS or TLS
+        let network = result.network;
+        assert!(network.dns.is_none());
+        assert!(network.tcp_connect.is_some());
+        assert!(network.tls_handshake.is_none());
+        let session_ready = network.session_ready.unwrap();
+        assert!(network.time_to_first_byte.unwrap() <= session_ready);
+        assert!(network.response_requested.unwrap() >= session_ready);
+        assert!(result.response_ttft().unwrap() <= result.ttft().unwrap());
+
+        let received = server.await.unwrap();
+        let types: Vec<_> = received.iter().map(|e| e["type"].as_str().unwrap()).collect();
+        assert_eq!(
+            types,
+            ["session.update", "conversation.item.create", "response.create"]
+        );
+        assert_eq!(received[0]["session"]["modalities"], json!(["text"]));
+        assert_eq!(received[0]["session"]["instructions"], "Be brief");
+        assert_eq!(received[0]["session"]["max_response_output_tokens"], 64);
+        assert_eq!(
+            received[1]["item"],
+            json!({"type": "message", "role": "user", "content": [{"type": "input_text", "text": "Hi"}]})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_stream_error_event() {
+        let (url, _server) = serve(vec![json!({
+            "type": "error",
+            "error": {"type": "invalid_request_error", "message": "Invalid model"}
+        })])
+        .await;
+
+        let provider = RealtimeProvider::builder()
+            .api_key("test-key")
+            .base_url(url)
+            .build();
+        let result = provider.complete(request(), &TimingEngine::new()).await;
+
+        assert!(matches!(result, Err(ProviderError::StreamingError(m)) if m == "Invalid model"));
+    }
+
+    #[tokio::test]
+    async fn test_upgrade_rejected() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}/v1/realtime", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            #[allow(clippy::result_large_err)]
+            let reject = |_: &Request, _: Response| -> std::result::Result<Response, ErrorResponse> {
+                Err(Response::builder()
+                    .status(401)
+                    .body(Some("Unauthorized".to_string()))
+                    .unwrap())
+            };
+            let _ = tokio_tungstenite::accept_hdr_async(stream, reject).await;
+        });
+
+        let provider = RealtimeProvider::builder()
+            .api_key("bad-key")
+            .base_url(url)
+            .build();
+        let result = provider.stream(request(), &TimingEngine::new()).await;
+
+        assert!(matches!(result, Err(ProviderError::AuthenticationError(_))));
+    }
+
+    #[test]
+    fn test_session_config_tools() {
+        let request = StreamingRequest::builder()
+            .model("gpt-4o-realtime-preview")
+            .message(MessageRole::User, "Weather?")
+            .tool(crate::traits::Tool::new("get_weather", json!({"type": "object"})))
+            .tool_choice(ToolChoice::Tool("get_weather".to_string()))
+            .build();
+
+        let event = ClientEvent::SessionUpdate {
+            session: session_config(&request),
+        };
+        let value = serde_json::to_value(&event).unwrap();
+
+        assert_eq!(value["type"], "session.update");
+        assert!(value["session"].get("instructions").is_none());
+        assert_eq!(value["session"]["tools"][0]["type"], "function");
+        assert_eq!(value["session"]["tools"][0]["name"], "get_weather");
+        assert_eq!(
+            value["session"]["tool_choice"],
+            json!({"type": "function", "name": "get_weather"})
+        );
+    }
+}
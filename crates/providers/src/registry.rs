@@ -0,0 +1,581 @@
+//! Provider registry
+//!
+//! Adapters are created by factories registered under a provider type
+//! ("openai", "generic", ...). A factory receives the full
+//! [`ProviderConfig`] of a provider instance: credentials, endpoint,
+//! organization, API version, timeout, retries and extra headers.
+//!
+//! Instances are named in the application configuration, and an instance
+//! names its type explicitly when the two differ. Two OpenAI-compatible
+//! gateways can then be benchmarked side by side:
+//!
+//! ```toml
+//! [providers.gateway-a]
+//! type = "generic"
+//! endpoint = "http://gateway-a:8000/v1"
+//!
+//! [providers.gateway-b]
+//! type = "generic"
+//! endpoint = "http://gateway-b:8000/v1"
+//! headers = { X-Tenant = "benchmarks" }
+//! ```
+//!
+//! Settings missing from the configuration fall back to the environment
+//! variables each adapter documents (e.g. `GENERIC_BASE_URL`).
+
+use crate::anthropic::AnthropicProvider;
+use crate::azure::AzureOpenAIProvider;
+use crate::bedrock::BedrockProvider;
+use crate::embeddings::EmbeddingProvider;
+use crate::error::{ProviderError, Result};
+use crate::exec::{self, ExecProvider};
+use crate::generic::GenericOpenAIProvider;
+use crate::google::GoogleProvider;
+use crate::openai::{OpenAIApi, OpenAIProvider};
+use crate::realtime::RealtimeProvider;
+use crate::traits::Provider;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Configuration of a provider instance
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProviderConfig {
+    /// Provider type (registry name); defaults to the instance name
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub provider_type: Option<String>,
+
+    /// API key
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+
+    /// API endpoint URL
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub endpoint: Option<String>,
+
+    /// Organization ID (for OpenAI)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub organization: Option<String>,
+
+    /// API version (for Anthropic and Azure OpenAI)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_version: Option<String>,
+
+    /// Extra request headers (for OpenAI-compatible servers)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, String>,
+
+    /// Command line to run (for the exec provider)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+
+    /// Default model for this provider
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_model: Option<String>,
+
+    /// Request timeout in seconds (connection and session setup for the
+    /// Realtime API; not applied to the exec provider)
+    #[serde(default = "default_timeout")]
+    pub timeout_secs: u64,
+
+    /// Retries of a failed initial request
+    #[serde(default = "default_retries")]
+    pub max_retries: u32,
+
+    /// Enable extended thinking (Claude)
+    #[serde(default)]
+    pub extended_thinking: bool,
+
+    /// Token budget for extended thinking (defaults to 1024 when enabled)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thinking_budget: Option<u32>,
+
+    /// Vocabulary file for counting tokens locally when the provider
+    /// reports no usage (tiktoken BPE, or SentencePiece `.vocab`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tokenizer: Option<PathBuf>,
+}
+
+impl Default for ProviderConfig {
+    fn default() -> Self {
+        Self {
+            provider_type: None,
+            api_key: None,
+            endpoint: None,
+            organization: None,
+            api_version: None,
+            headers: BTreeMap::new(),
+            command: None,
+            default_model: None,
+            timeout_secs: default_timeout(),
+            max_retries: default_retries(),
+            extended_thinking: false,
+            thinking_budget: None,
+            tokenizer: None,
+        }
+    }
+}
+
+impl ProviderConfig {
+    /// Configuration with only an API key
+    pub fn with_api_key(api_key: impl Into<String>) -> Self {
+        Self {
+            api_key: Some(api_key.into()),
+            ..Self::default()
+        }
+    }
+
+    /// Provider type of the instance named `name`
+    pub fn provider_type<'a>(&'a self, name: &'a str) -> &'a str {
+        self.provider_type.as_deref().unwrap_or(name)
+    }
+
+    /// Thinking budget to request, or None when extended thinking is disabled
+    pub fn effective_thinking_budget(&self) -> Option<u32> {
+        self.extended_thinking
+            .then(|| self.thinking_budget.unwrap_or_else(default_thinking_budget))
+    }
+
+    /// Request timeout
+    fn timeout(&self) -> Duration {
+        Duration::from_secs(self.timeout_secs)
+    }
+
+    /// API key, for providers that cannot run without one
+    fn required_api_key(&self) -> Result<String> {
+        self.api_key
+            .clone()
+            .ok_or_else(|| ProviderError::ConfigError("API key not set".to_string()))
+    }
+
+    /// Configured value, or the environment variable `env`
+    fn or_env(value: &Option<String>, env: &str) -> Option<String> {
+        value.clone().or_else(|| std::env::var(env).ok())
+    }
+}
+
+fn default_thinking_budget() -> u32 {
+    1024
+}
+
+fn default_timeout() -> u64 {
+    120
+}
+
+fn default_retries() -> u32 {
+    3
+}
+
+/// Factory creating a provider from its configuration
+pub type ProviderFactory = Arc<dyn Fn(&ProviderConfig) -> Result<Box<dyn Provider>> + Send + Sync>;
+
+/// Factory creating an embeddings provider from its configuration
+pub type EmbeddingProviderFactory =
+    Arc<dyn Fn(&ProviderConfig) -> Result<Box<dyn EmbeddingProvider>> + Send + Sync>;
+
+/// A registered provider type
+#[derive(Clone)]
+struct ProviderEntry {
+    name: &'static str,
+    factory: ProviderFactory,
+    /// Whether the factory fails without an API key
+    requires_api_key: bool,
+}
+
+/// Provider factories by type
+///
+/// Type names are matched case-insensitively, and aliases map alternative
+/// names to a registered type. Types are listed in registration order.
+#[derive(Clone, Default)]
+pub struct ProviderRegistry {
+    providers: Vec<ProviderEntry>,
+    embedding_providers: Vec<(&'static str, EmbeddingProviderFactory)>,
+    aliases: Vec<(&'static str, &'static str)>,
+}
+
+impl ProviderRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a registry with the built-in adapters
+    pub fn builtin() -> Self {
+        let mut registry = Self::new();
+
+        registry.register("openai", |config| {
+            Ok(Box::new(openai(config, OpenAIApi::ChatCompletions)?))
+        });
+        registry.register("openai-responses", |config| {
+            Ok(Box::new(openai(config, OpenAIApi::Responses)?))
+        });
+        registry.register("openai-realtime", |config| {
+            let mut builder = RealtimeProvider::builder()
+                .api_key(config.required_api_key()?)
+                .timeout(config.timeout());
+            if let Some(ref endpoint) = config.endpoint {
+                builder = builder.base_url(endpoint);
+            }
+            Ok(Box::new(builder.build()))
+        });
+        registry.register("anthropic", |config| {
+            let mut builder = AnthropicProvider::builder()
+                .api_key(config.required_api_key()?)
+                .max_retries(config.max_retries)
+                .timeout(config.timeout());
+            if let Some(ref endpoint) = config.endpoint {
+                builder = builder.base_url(endpoint);
+            }
+            if let Some(ref version) = config.api_version {
+                builder = builder.api_version(version);
+            }
+            Ok(Box::new(builder.build()))
+        });
+        registry.register("google", |config| Ok(Box::new(google(config)?)));
+        registry.register_keyless("bedrock", |config| {
+            // Without an API key, AWS credentials come from the environment
+            let mut builder = BedrockProvider::builder()
+                .max_retries(config.max_retries)
+                .timeout(config.timeout());
+            if let Some(key) = config.api_key.as_ref().filter(|k| !k.is_empty()) {
+                builder = builder.bearer_token(key);
+            }
+            if let Some(ref endpoint) = config.endpoint {
+                builder = builder.base_url(endpoint);
+            }
+            Ok(Box::new(builder.build()?))
+        });
+        registry.register("azure", |config| {
+            let endpoint = ProviderConfig::or_env(&config.endpoint, "AZURE_OPENAI_ENDPOINT")
+                .ok_or_else(|| {
+                    ProviderError::ConfigError(
+                        "An endpoint or AZURE_OPENAI_ENDPOINT must be set for Azure OpenAI"
+                            .to_string(),
+                    )
+                })?;
+            let mut builder = AzureOpenAIProvider::builder()
+                .endpoint(endpoint)
+                .api_key(config.required_api_key()?)
+                .max_retries(config.max_retries)
+                .timeout(config.timeout());
+            if let Some(version) =
+                ProviderConfig::or_env(&config.api_version, "AZURE_OPENAI_API_VERSION")
+            {
+                builder = builder.api_version(version);
+            }
+            Ok(Box::new(builder.build()))
+        });
+        registry.register_keyless("generic", |config| Ok(Box::new(generic(config)?)));
+        registry.register_keyless("exec", |config| {
+            let command = ProviderConfig::or_env(&config.command, "EXEC_COMMAND").ok_or_else(|| {
+                ProviderError::ConfigError(
+                    "A command or EXEC_COMMAND must be set for the exec provider".to_string(),
+                )
+            })?;
+            let mut builder = ExecProvider::builder().command_line(&command);
+            if let Some(key) = config.api_key.as_ref().filter(|k| !k.is_empty()) {
+                builder = builder.env(exec::API_KEY_ENV, key);
+            }
+            Ok(Box::new(builder.build()?))
+        });
+
+        registry.register_embedding("openai", |config| {
+            Ok(Box::new(openai(config, OpenAIApi::ChatCompletions)?))
+        });
+        registry.register_embedding("google", |config| Ok(Box::new(google(config)?)));
+        registry.register_embedding("generic", |config| Ok(Box::new(generic(config)?)));
+
+        registry.alias("realtime", "openai-realtime");
+        registry.alias("aws-bedrock", "bedrock");
+        registry.alias("azure-openai", "azure");
+        registry.alias("openai-compatible", "generic");
+
+        registry
+    }
+
+    /// Register a provider type that needs an API key, replacing any factory
+    /// of the same name
+    pub fn register(
+        &mut self,
+        name: &'static str,
+        factory: impl Fn(&ProviderConfig) -> Result<Box<dyn Provider>> + Send + Sync + 'static,
+    ) {
+        self.insert(name, Arc::new(factory), true);
+    }
+
+    /// Register a provider type that can run without an API key, replacing
+    /// any factory of the same name
+    pub fn register_keyless(
+        &mut self,
+        name: &'static str,
+        factory: impl Fn(&ProviderConfig) -> Result<Box<dyn Provider>> + Send + Sync + 'static,
+    ) {
+        self.insert(name, Arc::new(factory), false);
+    }
+
+    fn insert(&mut self, name: &'static str, factory: ProviderFactory, requires_api_key: bool) {
+        let entry = ProviderEntry {
+            name,
+            factory,
+            requires_api_key,
+        };
+        match self.providers.iter_mut().find(|e| e.name == name) {
+            Some(existing) => *existing = entry,
+            None => self.providers.push(entry),
+        }
+    }
+
+    /// Register an embeddings provider type, replacing any factory of the
+    /// same name
+    pub fn register_embedding(
+        &mut self,
+        name: &'static str,
+        factory: impl Fn(&ProviderConfig) -> Result<Box<dyn EmbeddingProvider>>
+            + Send
+            + Sync
+            + 'static,
+    ) {
+        let factory: EmbeddingProviderFactory = Arc::new(factory);
+        match self.embedding_providers.iter_mut().find(|(n, _)| *n == name) {
+            Some(entry) => entry.1 = factory,
+            None => self.embedding_providers.push((name, factory)),
+        }
+    }
+
+    /// Accept `alias` in place of the type `name`
+    pub fn alias(&mut self, alias: &'static str, name: &'static str) {
+        self.aliases.push((alias, name));
+    }
+
+    /// Registered type for a name or alias
+    fn resolve(&self, name: &str) -> String {
+        let name = name.to_lowercase();
+        self.aliases
+            .iter()
+            .find(|(alias, _)| *alias == name)
+            .map(|(_, target)| target.to_string())
+            .unwrap_or(name)
+    }
+
+    /// Registered entry for a type name or alias
+    fn entry(&self, provider_type: &str) -> Option<&ProviderEntry> {
+        let name = self.resolve(provider_type);
+        self.providers.iter().find(|e| e.name == name)
+    }
+
+    /// Whether providers of type `provider_type` need an API key
+    ///
+    /// Unknown types are assumed to need one.
+    pub fn requires_api_key(&self, provider_type: &str) -> bool {
+        self.entry(provider_type).map_or(true, |e| e.requires_api_key)
+    }
+
+    /// Create a provider of type `provider_type`
+    pub fn create(
+        &self,
+        provider_type: &str,
+        config: &ProviderConfig,
+    ) -> Result<Box<dyn Provider>> {
+        let entry = self.entry(provider_type).ok_or_else(|| {
+            ProviderError::ConfigError(format!(
+                "Unknown provider: {}. Supported providers: {}",
+                provider_type,
+                self.providers().join(", ")
+            ))
+        })?;
+        (entry.factory)(config)
+    }
+
+    /// Create an embeddings provider of type `provider_type`
+    pub fn create_embedding(
+        &self,
+        provider_type: &str,
+        config: &ProviderConfig,
+    ) -> Result<Box<dyn EmbeddingProvider>> {
+        let name = self.resolve(provider_type);
+        let (_, factory) = self
+            .embedding_providers
+            .iter()
+            .find(|(n, _)| *n == name)
+            .ok_or_else(|| {
+                ProviderError::ConfigError(format!(
+                    "Provider {} does not support embeddings. Supported providers: {}",
+                    provider_type,
+                    self.embedding_providers().join(", ")
+                ))
+            })?;
+        factory(config)
+    }
+
+    /// Registered provider types
+    pub fn providers(&self) -> Vec<&'static str> {
+        self.providers.iter().map(|e| e.name).collect()
+    }
+
+    /// Registered embeddings provider types
+    pub fn embedding_providers(&self) -> Vec<&'static str> {
+        self.embedding_providers.iter().map(|(name, _)| *name).collect()
+    }
+}
+
+/// OpenAI provider using `api`
+fn openai(config: &ProviderConfig, api: OpenAIApi) -> Result<OpenAIProvider> {
+    let mut builder = OpenAIProvider::builder()
+        .api_key(config.required_api_key()?)
+        .api(api)
+        .max_retries(config.max_retries)
+        .timeout(config.timeout());
+    if let Some(ref endpoint) = config.endpoint {
+        builder = builder.base_url(endpoint);
+    }
+    if let Some(ref organization) = config.organization {
+        builder = builder.organization(organization);
+    }
+    Ok(builder.build())
+}
+
+/// Google Gemini provider
+fn google(config: &ProviderConfig) -> Result<GoogleProvider> {
+    let mut builder = GoogleProvider::builder()
+        .api_key(config.required_api_key()?)
+        .max_retries(config.max_retries)
+        .timeout(config.timeout());
+    if let Some(ref endpoint) = config.endpoint {
+        builder = builder.base_url(endpoint);
+    }
+    Ok(builder.build())
+}
+
+/// Generic OpenAI-compatible provider, falling back to `GENERIC_BASE_URL`
+/// and `GENERIC_HEADERS`
+fn generic(config: &ProviderConfig) -> Result<GenericOpenAIProvider> {
+    let base_url = ProviderConfig::or_env(&config.endpoint, "GENERIC_BASE_URL").ok_or_else(|| {
+        ProviderError::ConfigError(
+            "An endpoint or GENERIC_BASE_URL must be set for the generic OpenAI-compatible provider"
+                .to_string(),
+        )
+    })?;
+    let mut builder = GenericOpenAIProvider::builder()
+        .base_url(base_url)
+        .api_key(config.api_key.clone().unwrap_or_default())
+        .max_retries(config.max_retries)
+        .timeout(config.timeout());
+    if let Ok(headers) = std::env::var("GENERIC_HEADERS") {
+        for (name, value) in parse_header_list(&headers)? {
+            builder = builder.header(name, value);
+        }
+    }
+    // Configured headers take precedence over the environment
+    for (name, value) in &config.headers {
+        builder = builder.header(name, value);
+    }
+    builder.build()
+}
+
+/// Parse a comma-separated list of `Name=value` header pairs
+fn parse_header_list(headers: &str) -> Result<Vec<(String, String)>> {
+    headers
+        .split(',')
+        .map(str::trim)
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            pair.split_once('=')
+                .map(|(name, value)| (name.trim().to_string(), value.trim().to_string()))
+                .ok_or_else(|| {
+                    ProviderError::ConfigError(format!(
+                        "Invalid header '{}'. Expected 'Name=value'",
+                        pair
+                    ))
+                })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_providers() {
+        let registry = ProviderRegistry::builtin();
+        let config = ProviderConfig::with_api_key("test-key");
+
+        let provider = registry.create("openai-responses", &config).unwrap();
+        assert_eq!(provider.name(), "openai");
+        let provider = registry.create("Realtime", &config).unwrap();
+        assert_eq!(provider.name(), "openai-realtime");
+        assert!(registry.create("unknown", &config).is_err());
+
+        // Keyed providers need a key; Bedrock falls back to AWS credentials
+        assert!(registry.create("anthropic", &ProviderConfig::default()).is_err());
+        assert!(registry.requires_api_key("anthropic"));
+        assert!(!registry.requires_api_key("AWS-Bedrock"));
+        assert!(!registry.requires_api_key("openai-compatible"));
+        assert!(!registry.requires_api_key("exec"));
+        assert!(registry.requires_api_key("unknown"));
+        assert_eq!(registry.embedding_providers(), vec!["openai", "google", "generic"]);
+    }
+
+    #[test]
+    fn test_named_generic_instances() {
+        let config: BTreeMap<String, ProviderConfig> = toml::from_str(
+            r#"
+            [gateway-a]
+            type = "generic"
+            endpoint = "http://gateway-a:8000/v1"
+
+            [gateway-b]
+            type = "openai-compatible"
+            endpoint = "http://gateway-b:8000/v1"
+            headers = { X-Tenant = "benchmarks" }
+            max_retries = 0
+            "#,
+        )
+        .unwrap();
+
+        let registry = ProviderRegistry::builtin();
+        for (name, instance) in &config {
+            let provider = registry.create(instance.provider_type(name), instance).unwrap();
+            assert_eq!(provider.name(), "generic");
+        }
+        assert_eq!(config["gateway-b"].headers["X-Tenant"], "benchmarks");
+        assert_eq!(config["gateway-b"].max_retries, 0);
+        assert_eq!(config["gateway-a"].timeout_secs, 120);
+    }
+
+    #[test]
+    fn test_register_custom_provider() {
+        let mut registry = ProviderRegistry::new();
+        registry.register_keyless("internal", |config| {
+            let endpoint = config.endpoint.clone().unwrap_or_default();
+            Ok(Box::new(
+                GenericOpenAIProvider::builder().base_url(endpoint).build()?,
+            ))
+        });
+
+        let config = ProviderConfig {
+            endpoint: Some("http://localhost:9000/v1".to_string()),
+            ..ProviderConfig::default()
+        };
+        assert!(registry.create("INTERNAL", &config).is_ok());
+        assert_eq!(registry.providers(), vec!["internal"]);
+        assert!(!registry.requires_api_key("internal"));
+        assert!(registry.create("openai", &config).is_err());
+    }
+
+    #[test]
+    fn test_parse_header_list() {
+        let headers = parse_header_list("X-Tenant=bench, X-Trace = on,").unwrap();
+        assert_eq!(
+            headers,
+            vec![
+                ("X-Tenant".to_string(), "bench".to_string()),
+                ("X-Trace".to_string(), "on".to_string()),
+            ]
+        );
+
+        assert!(parse_header_list("missing-value").is_err());
+    }
+}
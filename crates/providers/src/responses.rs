@@ -0,0 +1,378 @@
+//! OpenAI Responses API (`/v1/responses`) payloads and stream events
+//!
+//! The Responses API streams typed events (`response.output_text.delta`,
+//! `response.reasoning_summary_text.delta`, `response.completed`, ...)
+//! instead of `chat.completion.chunk` objects. Usage arrives once, on the
+//! final `response.completed` (or `response.incomplete`) event.
+//!
+//! [`OpenAIProvider`](crate::openai::OpenAIProvider) uses these types when
+//! built with [`OpenAIApi::Responses`](crate::openai::OpenAIApi::Responses).
+
+use crate::error::{ProviderError, Result};
+use crate::openai::is_reasoning_model;
+use crate::sse::SseEvent;
+use crate::traits::{
+    ContentPart, Message, MessageRole, SharedUsage, StreamingRequest, TokenUsage, ToolChoice,
+};
+use llm_latency_lens_core::TokenKind;
+use serde::{Deserialize, Serialize};
+
+/// Build the Responses API payload, streamed or not
+pub(crate) fn request_body(request: &StreamingRequest, stream: bool) -> ResponsesRequest {
+    // Reasoning models reject sampling parameters; ask them for reasoning
+    // summaries so the reasoning phase shows up in the stream
+    let reasoning = is_reasoning_model(&request.model);
+
+    ResponsesRequest {
+        model: request.model.clone(),
+        input: request
+            .messages
+            .iter()
+            .map(|m| InputMessage {
+                role: match m.role {
+                    MessageRole::System => "system",
+                    MessageRole::User => "user",
+                    MessageRole::Assistant => "assistant",
+                },
+                content: input_content(m),
+            })
+            .collect(),
+        stream,
+        max_output_tokens: request.max_tokens,
+        temperature: request.temperature.filter(|_| !reasoning),
+        top_p: request.top_p.filter(|_| !reasoning),
+        reasoning: reasoning.then_some(ReasoningOptions { summary: "auto" }),
+        tools: request
+            .tools
+            .iter()
+            .map(|t| ResponsesTool {
+                tool_type: "function",
+                name: t.name.clone(),
+                description: t.description.clone(),
+                parameters: t.parameters.clone(),
+            })
+            .collect(),
+        tool_choice: request.tool_choice.as_ref().map(tool_choice_value),
+    }
+}
+
+/// Responses API message `content` value
+///
+/// Text-only messages stay a plain string; attachments use the
+/// `input_image`/`input_file` part types.
+fn input_content(message: &Message) -> serde_json::Value {
+    if message.is_text_only() {
+        return serde_json::json!(message.text());
+    }
+
+    let parts: Vec<serde_json::Value> = message
+        .content
+        .iter()
+        .map(|part| match part {
+            ContentPart::Text { text } => serde_json::json!({"type": "input_text", "text": text}),
+            ContentPart::ImageUrl { .. } | ContentPart::Image { .. } => serde_json::json!({
+                "type": "input_image",
+                "image_url": part.to_url()
+            }),
+            ContentPart::Document { .. } => serde_json::json!({
+                "type": "input_file",
+                "filename": "document.pdf",
+                "file_data": part.to_url()
+            }),
+        })
+        .collect();
+    serde_json::json!(parts)
+}
+
+/// Responses API `tool_choice` value
+fn tool_choice_value(choice: &ToolChoice) -> serde_json::Value {
+    match choice {
+        ToolChoice::Auto => serde_json::json!("auto"),
+        ToolChoice::None => serde_json::json!("none"),
+        ToolChoice::Required => serde_json::json!("required"),
+        ToolChoice::Tool(name) => serde_json::json!({"type": "function", "name": name}),
+    }
+}
+
+/// Decode one stream event into generated text, recording usage on `usage`
+///
+/// Returns None for events that carry no generated text. The start of a
+/// function call yields an empty tool-call token so its start is timed.
+pub(crate) fn event_text(
+    message: &SseEvent,
+    usage: &SharedUsage,
+) -> Option<Result<(TokenKind, String)>> {
+    let event: ResponsesEvent = match serde_json::from_str(&message.data) {
+        Ok(e) => e,
+        Err(e) => {
+            tracing::error!("Failed to parse Responses event: {}", e);
+            return Some(Err(ProviderError::sse_parse(format!(
+                "Invalid JSON in SSE event: {}",
+                e
+            ))));
+        }
+    };
+
+    let non_empty = |delta: Option<String>| delta.filter(|d| !d.is_empty());
+    match event.event_type.as_str() {
+        "response.output_text.delta" => {
+            non_empty(event.delta).map(|d| Ok((TokenKind::Content, d)))
+        }
+        "response.reasoning_summary_text.delta" | "response.reasoning_text.delta" => {
+            non_empty(event.delta).map(|d| Ok((TokenKind::Thinking, d)))
+        }
+        "response.output_item.added" => event
+            .item
+            .filter(|item| item.item_type == "function_call")
+            .map(|_| Ok((TokenKind::ToolCall, String::new()))),
+        "response.function_call_arguments.delta" => {
+            non_empty(event.delta).map(|d| Ok((TokenKind::ToolCall, d)))
+        }
+        "response.completed" | "response.incomplete" => {
+            if let Some(reported) = event.response.and_then(|r| r.usage) {
+                usage.update(|u| *u = reported.token_usage());
+            }
+            tracing::debug!("SSE stream completed");
+            None
+        }
+        "response.failed" => {
+            let message = event
+                .response
+                .and_then(|r| r.error)
+                .map(|e| e.message)
+                .unwrap_or_else(|| "response failed".to_string());
+            tracing::error!("Response failed: {}", message);
+            Some(Err(ProviderError::streaming(format!("API error: {}", message))))
+        }
+        "error" => {
+            tracing::error!("Error event received: {}", message.data);
+            Some(Err(ProviderError::streaming(format!(
+                "API error: {}",
+                event.message.unwrap_or_else(|| message.data.clone())
+            ))))
+        }
+        // Lifecycle events (created, in_progress, *.done, content_part.*)
+        _ => None,
+    }
+}
+
+// Responses API request/response types
+
+#[derive(Debug, Serialize)]
+pub(crate) struct ResponsesRequest {
+    model: String,
+    input: Vec<InputMessage>,
+    stream: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_output_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reasoning: Option<ReasoningOptions>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tools: Vec<ResponsesTool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_choice: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Serialize)]
+struct InputMessage {
+    role: &'static str,
+    content: serde_json::Value,
+}
+
+#[derive(Debug, Serialize)]
+struct ReasoningOptions {
+    summary: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+struct ResponsesTool {
+    #[serde(rename = "type")]
+    tool_type: &'static str,
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    parameters: serde_json::Value,
+}
+
+/// A stream event; only the fields used for timing and usage are decoded
+#[derive(Debug, Deserialize)]
+struct ResponsesEvent {
+    #[serde(rename = "type")]
+    event_type: String,
+    #[serde(default)]
+    delta: Option<String>,
+    #[serde(default)]
+    item: Option<OutputItem>,
+    #[serde(default)]
+    response: Option<Response>,
+    /// Message of an `error` event
+    #[serde(default)]
+    message: Option<String>,
+}
+
+/// A response object, as returned by a non-streaming request or carried by
+/// the final stream event
+#[derive(Debug, Deserialize)]
+pub(crate) struct Response {
+    #[serde(default)]
+    output: Vec<OutputItem>,
+    #[serde(default)]
+    pub(crate) usage: Option<ResponsesUsage>,
+    #[serde(default)]
+    error: Option<ResponseError>,
+}
+
+impl Response {
+    /// Visible text of the output messages
+    pub(crate) fn output_text(&self) -> String {
+        self.output
+            .iter()
+            .filter(|item| item.item_type == "message")
+            .flat_map(|item| &item.content)
+            .filter(|part| part.part_type == "output_text")
+            .filter_map(|part| part.text.as_deref())
+            .collect()
+    }
+
+    /// Text of the reasoning summaries
+    pub(crate) fn reasoning_text(&self) -> String {
+        self.output
+            .iter()
+            .filter(|item| item.item_type == "reasoning")
+            .flat_map(|item| &item.summary)
+            .filter_map(|part| part.text.as_deref())
+            .collect()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct OutputItem {
+    #[serde(rename = "type")]
+    item_type: String,
+    #[serde(default)]
+    content: Vec<OutputPart>,
+    #[serde(default)]
+    summary: Vec<OutputPart>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OutputPart {
+    #[serde(rename = "type")]
+    part_type: String,
+    #[serde(default)]
+    text: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ResponseError {
+    message: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct ResponsesUsage {
+    input_tokens: u64,
+    output_tokens: u64,
+    #[serde(default)]
+    input_tokens_details: Option<InputTokensDetails>,
+    #[serde(default)]
+    output_tokens_details: Option<OutputTokensDetails>,
+}
+
+impl ResponsesUsage {
+    /// Usage counts, with reasoning tokens split out of the output tokens
+    pub(crate) fn token_usage(&self) -> TokenUsage {
+        let reasoning_tokens = self
+            .output_tokens_details
+            .as_ref()
+            .and_then(|d| d.reasoning_tokens);
+        TokenUsage {
+            input_tokens: Some(self.input_tokens),
+            // output_tokens includes the reasoning tokens
+            output_tokens: Some(self.output_tokens.saturating_sub(reasoning_tokens.unwrap_or(0))),
+            thinking_tokens: reasoning_tokens,
+            cache_creation_tokens: None,
+            // Caching is automatic; cached_tokens is part of input_tokens
+            cache_read_tokens: self.input_tokens_details.as_ref().and_then(|d| d.cached_tokens),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct InputTokensDetails {
+    #[serde(default)]
+    cached_tokens: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OutputTokensDetails {
+    #[serde(default)]
+    reasoning_tokens: Option<u64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use llm_latency_lens_core::Clock;
+
+    fn event(data: &str) -> SseEvent {
+        SseEvent {
+            event: "message".to_string(),
+            data: data.to_string(),
+            id: None,
+            received_at: Clock::new().now(),
+            read: 0,
+        }
+    }
+
+    #[test]
+    fn test_request_body() {
+        let request = StreamingRequest::builder()
+            .model("o3-mini")
+            .message(MessageRole::System, "Be brief")
+            .message(MessageRole::User, "Hi")
+            .max_tokens(500)
+            .temperature(0.7)
+            .tool_choice(ToolChoice::Tool("lookup".to_string()))
+            .build();
+
+        let body = serde_json::to_value(request_body(&request, true)).unwrap();
+        assert_eq!(body["input"][0], serde_json::json!({"role": "system", "content": "Be brief"}));
+        assert_eq!(body["max_output_tokens"], 500);
+        assert_eq!(body["reasoning"]["summary"], "auto");
+        assert!(body.get("temperature").is_none());
+        assert_eq!(body["tool_choice"], serde_json::json!({"type": "function", "name": "lookup"}));
+    }
+
+    #[test]
+    fn test_event_text() {
+        let usage = SharedUsage::new();
+        let text = |data: &str| event_text(&event(data), &usage).map(Result::unwrap);
+
+        assert_eq!(
+            text(r#"{"type":"response.output_text.delta","delta":"Hi"}"#),
+            Some((TokenKind::Content, "Hi".to_string()))
+        );
+        assert_eq!(
+            text(r#"{"type":"response.reasoning_summary_text.delta","delta":"Think"}"#),
+            Some((TokenKind::Thinking, "Think".to_string()))
+        );
+        assert_eq!(
+            text(r#"{"type":"response.output_item.added","item":{"type":"function_call","name":"f"}}"#),
+            Some((TokenKind::ToolCall, String::new()))
+        );
+        assert_eq!(text(r#"{"type":"response.output_item.added","item":{"type":"message"}}"#), None);
+        assert_eq!(text(r#"{"type":"response.output_text.done","text":"Hi"}"#), None);
+    }
+
+    #[test]
+    fn test_event_errors() {
+        let usage = SharedUsage::new();
+        let failed = event(r#"{"type":"response.failed","response":{"error":{"code":"server_error","message":"boom"}}}"#);
+        assert!(event_text(&failed, &usage).unwrap().is_err());
+        assert!(event_text(&event("not json"), &usage).unwrap().is_err());
+    }
+}
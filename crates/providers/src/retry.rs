@@ -0,0 +1,281 @@
+//! Retries of the initial request
+//!
+//! Adapters send requests through [`send`], which retries retryable failures
+//! ([`ProviderError::is_retryable`]) until the response headers arrive with a
+//! success status. The wait before each retry is taken from the response's
+//! `Retry-After` (or `retry-after-ms`) header, then from
+//! [`ProviderError::retry_delay`], and otherwise doubles from
+//! [`RetryPolicy::base_delay`].
+//!
+//! Streams are never reconnected once the response has started: an error
+//! after the headers ends the stream and is reported, so a latency sample
+//! never mixes tokens from two responses. Retries and the time spent waiting
+//! between attempts are recorded on the request's [`NetworkProbe`] and
+//! reported as [`RetryStats`].
+
+use crate::error::{ProviderError, Result};
+use crate::network::NetworkProbe;
+use reqwest::header::HeaderMap;
+use std::future::Future;
+use std::time::Duration;
+
+/// Backoff before the first retry when neither the response nor the error
+/// suggests a delay
+pub const DEFAULT_BASE_DELAY: Duration = Duration::from_secs(1);
+
+/// Longest wait between two attempts
+pub const DEFAULT_MAX_DELAY: Duration = Duration::from_secs(60);
+
+/// When and how long to retry a failed request
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Retries after the first attempt (0 disables retries)
+    pub max_retries: u32,
+    /// Backoff before the first retry, doubled for each retry after it
+    pub base_delay: Duration,
+    /// Upper bound on any wait, including server-requested ones
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new(3)
+    }
+}
+
+impl RetryPolicy {
+    /// Retry up to `max_retries` times with the default delays
+    pub fn new(max_retries: u32) -> Self {
+        Self {
+            max_retries,
+            base_delay: DEFAULT_BASE_DELAY,
+            max_delay: DEFAULT_MAX_DELAY,
+        }
+    }
+
+    /// Wait before retry number `retry` (1-based) after `error`
+    ///
+    /// `retry_after` is the delay requested by the server's response headers,
+    /// which takes precedence over the error's own suggestion.
+    pub fn delay(
+        &self,
+        retry: u32,
+        error: &ProviderError,
+        retry_after: Option<Duration>,
+    ) -> Duration {
+        retry_after
+            .or_else(|| error.retry_delay().map(Duration::from_secs))
+            .unwrap_or_else(|| {
+                self.base_delay
+                    .saturating_mul(2_u32.saturating_pow(retry.saturating_sub(1)))
+            })
+            .min(self.max_delay)
+    }
+}
+
+/// Retries made for a single request
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RetryStats {
+    /// Retries after the first attempt
+    pub retries: u32,
+    /// Total time spent waiting between attempts
+    pub backoff: Duration,
+}
+
+impl RetryStats {
+    /// Record a retry after waiting `backoff`
+    pub fn record(&mut self, backoff: Duration) {
+        self.retries += 1;
+        self.backoff += backoff;
+    }
+
+    /// Attempts made, including the first
+    pub fn attempts(&self) -> u32 {
+        self.retries + 1
+    }
+}
+
+/// Send `request`, retrying retryable failures under `policy`
+///
+/// Error responses are turned into errors by `parse_error`. Each retry is
+/// recorded on `network`, whose first-byte time then belongs to the attempt
+/// that succeeded.
+pub(crate) async fn send<E, Fut>(
+    policy: RetryPolicy,
+    network: &NetworkProbe,
+    request: reqwest::RequestBuilder,
+    parse_error: E,
+) -> Result<reqwest::Response>
+where
+    E: Fn(reqwest::Response) -> Fut,
+    Fut: Future<Output = ProviderError>,
+{
+    let mut retries = 0;
+    loop {
+        let attempt = request
+            .try_clone()
+            .ok_or_else(|| ProviderError::Other("Request body cannot be resent".to_string()))?;
+
+        let (error, retry_after) = match network.run(attempt.send()).await {
+            Ok(response) => {
+                network.first_byte();
+                if response.status().is_success() {
+                    return Ok(response);
+                }
+                let retry_after = retry_after(response.headers());
+                (parse_error(response).await, retry_after)
+            }
+            Err(e) => (ProviderError::from_reqwest(e), None),
+        };
+
+        if !error.is_retryable() || retries >= policy.max_retries {
+            return Err(error);
+        }
+
+        retries += 1;
+        let delay = policy.delay(retries, &error, retry_after);
+        tracing::warn!(
+            "Request failed (attempt {}/{}), retrying after {:?}: {}",
+            retries,
+            policy.max_retries + 1,
+            delay,
+            error
+        );
+
+        network.retried(delay);
+        tokio::time::sleep(delay).await;
+    }
+}
+
+/// Delay requested by `retry-after-ms` or `Retry-After` (in seconds)
+fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = |name: &str| headers.get(name)?.to_str().ok()?.trim().parse::<f64>().ok();
+
+    value("retry-after-ms")
+        .map(|ms| ms / 1000.0)
+        .or_else(|| value("retry-after"))
+        .filter(|secs| secs.is_finite() && *secs >= 0.0)
+        .map(Duration::from_secs_f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use llm_latency_lens_core::Clock;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn policy(max_retries: u32) -> RetryPolicy {
+        RetryPolicy {
+            max_retries,
+            base_delay: Duration::from_millis(10),
+            max_delay: Duration::from_millis(50),
+        }
+    }
+
+    #[test]
+    fn test_delay() {
+        let policy = policy(3);
+        let limited = ProviderError::rate_limit("slow down", None);
+        assert_eq!(policy.delay(1, &limited, None), Duration::from_millis(10));
+        assert_eq!(policy.delay(3, &limited, None), Duration::from_millis(40));
+        assert_eq!(policy.delay(5, &limited, None), Duration::from_millis(50));
+
+        // Server-requested delays win, up to the cap
+        let header = Some(Duration::from_millis(20));
+        assert_eq!(policy.delay(3, &limited, header), Duration::from_millis(20));
+        let unavailable = ProviderError::ServiceUnavailable("busy".to_string());
+        assert_eq!(
+            policy.delay(1, &unavailable, None),
+            Duration::from_millis(50)
+        );
+    }
+
+    #[test]
+    fn test_retry_after_headers() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(retry_after(&headers), None);
+
+        headers.insert("retry-after", "2".parse().unwrap());
+        assert_eq!(retry_after(&headers), Some(Duration::from_secs(2)));
+
+        headers.insert("retry-after-ms", "250".parse().unwrap());
+        assert_eq!(retry_after(&headers), Some(Duration::from_millis(250)));
+
+        // HTTP dates are not parsed
+        let mut dated = HeaderMap::new();
+        dated.insert(
+            "retry-after",
+            "Wed, 21 Oct 2015 07:28:00 GMT".parse().unwrap(),
+        );
+        assert_eq!(retry_after(&dated), None);
+    }
+
+    #[tokio::test]
+    async fn test_send_retries_rate_limits() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/chat"))
+            .respond_with(ResponseTemplate::new(429).insert_header("retry-after-ms", "20"))
+            .up_to_n_times(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/chat"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("ok"))
+            .mount(&server)
+            .await;
+
+        let network = NetworkProbe::new(&Clock::new());
+        let request = reqwest::Client::new()
+            .post(format!("{}/chat", server.uri()))
+            .body("{}");
+        let response = send(policy(3), &network, request, crate::error::parse_api_error)
+            .await
+            .unwrap();
+
+        assert_eq!(response.text().await.unwrap(), "ok");
+        let retries = network.retry_stats();
+        assert_eq!(retries.attempts(), 3);
+        assert_eq!(retries.backoff, Duration::from_millis(40));
+        assert!(network.timings().time_to_first_byte.unwrap() >= retries.backoff);
+    }
+
+    #[tokio::test]
+    async fn test_send_stops_at_max_retries() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(429))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let network = NetworkProbe::new(&Clock::new());
+        let request = reqwest::Client::new().post(server.uri()).body("{}");
+        let error = send(policy(1), &network, request, crate::error::parse_api_error)
+            .await
+            .unwrap_err();
+
+        assert!(matches!(error, ProviderError::RateLimitError { .. }));
+        assert_eq!(network.retry_stats().retries, 1);
+    }
+
+    #[tokio::test]
+    async fn test_send_does_not_retry_client_errors() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(401))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let network = NetworkProbe::new(&Clock::new());
+        let request = reqwest::Client::new().post(server.uri()).body("{}");
+        let error = send(policy(3), &network, request, crate::error::parse_api_error)
+            .await
+            .unwrap_err();
+
+        assert!(matches!(error, ProviderError::AuthenticationError(_)));
+        assert_eq!(network.retry_stats(), RetryStats::default());
+    }
+}
@@ -0,0 +1,317 @@
+//! Server-Sent Events decoder with chunk-arrival timestamps
+//!
+//! Decodes the raw response body as it is read from the network. Every read
+//! is timestamped when it arrives, and each event carries the timestamp of
+//! the read that completed it, so events delivered together in one read share
+//! an arrival time instead of being spread out by parsing and buffering.
+//!
+//! Reads and the events they completed are recorded on the request's
+//! [`NetworkProbe`] (see [`ReadStats`](crate::network::ReadStats)).
+//!
+//! Follows the WHATWG event stream format: `\n`, `\r\n` and `\r` line endings,
+//! `:` comments, and `data` lines joined with `\n`. Events without data are
+//! not dispatched.
+
+use crate::error::{ProviderError, Result};
+use crate::network::NetworkProbe;
+use bytes::Bytes;
+use futures::stream::BoxStream;
+use futures::{Stream, StreamExt};
+use llm_latency_lens_core::{Clock, Timestamp};
+use std::collections::VecDeque;
+
+/// A decoded SSE event
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SseEvent {
+    /// Event type (`event:` field; "message" when absent)
+    pub event: String,
+    /// Event data, with multiple `data:` lines joined by `\n`
+    pub data: String,
+    /// Last event ID (`id:` field)
+    pub id: Option<String>,
+    /// Arrival time of the network read that completed this event
+    pub received_at: Timestamp,
+    /// Index of that read within the response body
+    pub read: u64,
+}
+
+/// Incremental SSE decoder
+#[derive(Debug, Default)]
+pub struct SseDecoder {
+    /// Bytes of the current, incomplete line
+    line: Vec<u8>,
+    /// The previous chunk ended in `\r`, so a leading `\n` belongs to it
+    skip_lf: bool,
+    /// Whether the byte-order mark has been checked
+    started: bool,
+    event: Option<String>,
+    data: Option<String>,
+    id: Option<String>,
+}
+
+impl SseDecoder {
+    /// Create a new decoder
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Decode a chunk, returning the events it completed
+    ///
+    /// Events are stamped with `received_at` and `read`.
+    pub fn decode(
+        &mut self,
+        chunk: &[u8],
+        received_at: Timestamp,
+        read: u64,
+    ) -> Result<Vec<SseEvent>> {
+        let mut chunk = chunk;
+        if !self.started && !chunk.is_empty() {
+            self.started = true;
+            chunk = chunk.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(chunk);
+        }
+
+        let mut events = Vec::new();
+        for &byte in chunk {
+            let skip_lf = std::mem::take(&mut self.skip_lf);
+            match byte {
+                b'\n' if skip_lf => {}
+                b'\n' | b'\r' => {
+                    self.skip_lf = byte == b'\r';
+                    let line = std::mem::take(&mut self.line);
+                    if line.is_empty() {
+                        events.extend(self.dispatch(received_at, read));
+                    } else {
+                        self.process_line(&line)?;
+                    }
+                }
+                _ => self.line.push(byte),
+            }
+        }
+        Ok(events)
+    }
+
+    /// Whether a partial event is buffered (the body ended mid-event)
+    pub fn has_partial_event(&self) -> bool {
+        !self.line.is_empty() || self.data.is_some()
+    }
+
+    /// Finish the current event at a blank line
+    fn dispatch(&mut self, received_at: Timestamp, read: u64) -> Option<SseEvent> {
+        let event = self.event.take();
+        let data = self.data.take()?;
+        Some(SseEvent {
+            event: event.unwrap_or_else(|| "message".to_string()),
+            data,
+            id: self.id.clone(),
+            received_at,
+            read,
+        })
+    }
+
+    /// Apply one non-empty line
+    fn process_line(&mut self, line: &[u8]) -> Result<()> {
+        if line[0] == b':' {
+            return Ok(());
+        }
+
+        let line = std::str::from_utf8(line)
+            .map_err(|e| ProviderError::sse_parse(format!("Invalid UTF-8 in SSE line: {}", e)))?;
+        let (field, value) = match line.split_once(':') {
+            Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+            None => (line, ""),
+        };
+
+        match field {
+            "event" => self.event = Some(value.to_string()),
+            "data" => match self.data {
+                Some(ref mut data) => {
+                    data.push('\n');
+                    data.push_str(value);
+                }
+                None => self.data = Some(value.to_string()),
+            },
+            "id" if !value.contains('\0') => self.id = Some(value.to_string()),
+            // `retry` only matters for reconnecting, which is never done
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+/// Decode a response body into timed SSE events
+///
+/// Records each read and the first event on `network`.
+pub fn events<S>(
+    body: S,
+    clock: Clock,
+    network: NetworkProbe,
+) -> impl Stream<Item = Result<SseEvent>> + Send
+where
+    S: Stream<Item = reqwest::Result<Bytes>> + Send + 'static,
+{
+    let state = ReadState {
+        body: body.boxed(),
+        decoder: SseDecoder::new(),
+        clock,
+        network,
+        reads: 0,
+        pending: VecDeque::new(),
+        done: false,
+    };
+
+    futures::stream::unfold(state, |mut state| async move {
+        loop {
+            if let Some(item) = state.pending.pop_front() {
+                return Some((item, state));
+            }
+            if state.done {
+                return None;
+            }
+            state.read_next().await;
+        }
+    })
+}
+
+/// State carried between reads of the response body
+struct ReadState {
+    body: BoxStream<'static, reqwest::Result<Bytes>>,
+    decoder: SseDecoder,
+    clock: Clock,
+    network: NetworkProbe,
+    reads: u64,
+    pending: VecDeque<Result<SseEvent>>,
+    done: bool,
+}
+
+impl ReadState {
+    /// Read one chunk from the network and queue the events it completes
+    async fn read_next(&mut self) {
+        match self.body.next().await {
+            Some(Ok(chunk)) => {
+                let received_at = self.clock.now();
+                let read = self.reads;
+                self.reads += 1;
+
+                match self.decoder.decode(&chunk, received_at, read) {
+                    Ok(events) => {
+                        if !events.is_empty() {
+                            self.network.first_event_at(received_at);
+                        }
+                        self.network.record_read(events.len() as u64);
+                        self.pending.extend(events.into_iter().map(Ok));
+                    }
+                    Err(e) => {
+                        self.pending.push_back(Err(e));
+                        self.done = true;
+                    }
+                }
+            }
+            Some(Err(e)) => {
+                tracing::error!("SSE read error: {}", e);
+                self.pending.push_back(Err(ProviderError::streaming(format!(
+                    "Failed to read SSE stream: {}",
+                    e
+                ))));
+                self.done = true;
+            }
+            None => {
+                if self.decoder.has_partial_event() {
+                    tracing::debug!("SSE stream ended without a final blank line");
+                }
+                tracing::debug!("SSE stream completed");
+                self.done = true;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode_all(decoder: &mut SseDecoder, chunks: &[&[u8]]) -> Vec<SseEvent> {
+        let clock = Clock::new();
+        chunks
+            .iter()
+            .enumerate()
+            .flat_map(|(read, chunk)| decoder.decode(chunk, clock.now(), read as u64).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_decode_fields() {
+        let mut decoder = SseDecoder::new();
+        let events = decode_all(
+            &mut decoder,
+            &[
+                b"\xEF\xBB\xBF: keep-alive\nevent: message_start\nid: 7\ndata: {\"a\":1}\n\n",
+                b"data: one\ndata:two\n\n",
+            ],
+        );
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].event, "message_start");
+        assert_eq!(events[0].data, r#"{"a":1}"#);
+        assert_eq!(events[0].id.as_deref(), Some("7"));
+        assert_eq!(events[1].event, "message");
+        assert_eq!(events[1].data, "one\ntwo");
+        // The last event ID carries over
+        assert_eq!(events[1].id.as_deref(), Some("7"));
+    }
+
+    #[test]
+    fn test_decode_split_across_reads() {
+        let mut decoder = SseDecoder::new();
+        let events = decode_all(
+            &mut decoder,
+            &[b"data: hel", b"lo\r", b"\n\r", b"\ndata: \xC3", b"\xA9\r\r"],
+        );
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].data, "hello");
+        assert_eq!(events[0].read, 2);
+        assert_eq!(events[1].data, "é");
+        assert_eq!(events[1].read, 4);
+        assert!(!decoder.has_partial_event());
+    }
+
+    #[test]
+    fn test_events_in_one_read_share_arrival() {
+        let mut decoder = SseDecoder::new();
+        let events = decode_all(&mut decoder, &[b"data: a\n\ndata: b\n\n", b"data: c"]);
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].received_at, events[1].received_at);
+        assert_eq!(events[0].read, events[1].read);
+        assert!(decoder.has_partial_event());
+    }
+
+    #[test]
+    fn test_decode_skips_empty_events() {
+        let mut decoder = SseDecoder::new();
+        let events = decode_all(&mut decoder, &[b"event: ping\n\n: comment\n\nretry: 10\n\n"]);
+        assert!(events.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_events_record_reads() {
+        let clock = Clock::new();
+        let network = NetworkProbe::new(&clock);
+        let chunks: Vec<reqwest::Result<Bytes>> = vec![
+            Ok(Bytes::from_static(b"data: a\n\ndata: b\n\n")),
+            Ok(Bytes::from_static(b"data: c\n")),
+            Ok(Bytes::from_static(b"\n")),
+        ];
+
+        let events: Vec<_> = events(futures::stream::iter(chunks), clock, network.clone())
+            .collect()
+            .await;
+
+        assert_eq!(events.len(), 3);
+        let reads = network.read_stats();
+        assert_eq!(reads.reads, 3);
+        assert_eq!(reads.events, 3);
+        assert_eq!(reads.max_events_per_read, 2);
+        assert!(network.timings().time_to_first_event.is_some());
+    }
+}
@@ -0,0 +1,455 @@
+//! Local token counting for providers that report no usage
+//!
+//! Without reported usage, output tokens fall back to the number of streamed
+//! events, which undercounts when a provider packs several tokens into one
+//! event, and input tokens are unknown. A [`Tokenizer`] loaded from a
+//! vocabulary file on disk counts prompt and completion tokens locally
+//! instead, so throughput stays comparable across providers.
+//!
+//! Two vocabulary formats are supported:
+//!
+//! - **Byte-level BPE** in the tiktoken format (`cl100k_base.tiktoken`,
+//!   `o200k_base.tiktoken`): one base64-encoded token and its merge rank per
+//!   line. Text is split into words, numbers, punctuation and whitespace
+//!   the way the cl100k/o200k patterns do before merging.
+//! - **SentencePiece** unigram vocabularies (`tokenizer.vocab`): one piece
+//!   and its log-probability per line, tab-separated, with `▁` marking
+//!   spaces. Text is segmented into the most probable sequence of pieces.
+//!
+//! Counts are estimates: chat-format overhead and non-text content (images,
+//! documents, tool definitions) are not counted.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use base64::Engine;
+
+use crate::error::{ProviderError, Result};
+use crate::traits::Message;
+
+/// Counts tokens in text
+pub trait Tokenizer: Send + Sync + std::fmt::Debug {
+    /// Number of tokens `text` encodes to
+    fn count_tokens(&self, text: &str) -> u64;
+
+    /// Number of tokens in the text parts of a prompt
+    fn count_messages(&self, messages: &[Message]) -> u64 {
+        messages
+            .iter()
+            .map(|message| self.count_tokens(&message.text()))
+            .sum()
+    }
+}
+
+/// Load a tokenizer from a vocabulary file
+///
+/// Files with a `.vocab` extension are read as SentencePiece vocabularies,
+/// anything else as a tiktoken BPE vocabulary.
+pub fn load(path: &Path) -> Result<Box<dyn Tokenizer>> {
+    let content = std::fs::read_to_string(path).map_err(|e| {
+        ProviderError::ConfigError(format!(
+            "Failed to read tokenizer vocabulary {}: {}",
+            path.display(),
+            e
+        ))
+    })?;
+    if path.extension().and_then(|ext| ext.to_str()) == Some("vocab") {
+        Ok(Box::new(SentencePieceTokenizer::from_vocab(&content)?))
+    } else {
+        Ok(Box::new(BpeTokenizer::from_tiktoken(&content)?))
+    }
+}
+
+/// Byte-level BPE tokenizer (tiktoken vocabularies)
+#[derive(Debug, Clone)]
+pub struct BpeTokenizer {
+    ranks: HashMap<Vec<u8>, u32>,
+}
+
+impl BpeTokenizer {
+    /// Parse a tiktoken vocabulary (`<base64 token> <rank>` per line)
+    ///
+    /// The vocabulary must contain every single byte, so that any text can
+    /// be encoded.
+    pub fn from_tiktoken(content: &str) -> Result<Self> {
+        let invalid = |line: usize, reason: &str| {
+            ProviderError::ConfigError(format!(
+                "Invalid BPE vocabulary at line {}: {}",
+                line + 1,
+                reason
+            ))
+        };
+
+        let mut ranks = HashMap::new();
+        for (number, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let (token, rank) = line
+                .split_once(' ')
+                .ok_or_else(|| invalid(number, "expected a token and a rank"))?;
+            let token = base64::engine::general_purpose::STANDARD
+                .decode(token)
+                .map_err(|_| invalid(number, "token is not base64"))?;
+            let rank = rank
+                .trim()
+                .parse()
+                .map_err(|_| invalid(number, "rank is not a number"))?;
+            ranks.insert(token, rank);
+        }
+
+        if let Some(byte) = (0..=u8::MAX).find(|&b| !ranks.contains_key([b].as_slice())) {
+            return Err(ProviderError::ConfigError(format!(
+                "BPE vocabulary has no token for byte 0x{:02x}",
+                byte
+            )));
+        }
+        Ok(Self { ranks })
+    }
+
+    /// Number of tokens in the vocabulary
+    pub fn vocab_size(&self) -> usize {
+        self.ranks.len()
+    }
+
+    /// Encode text into token ranks
+    pub fn encode(&self, text: &str) -> Vec<u32> {
+        pre_tokenize(text)
+            .into_iter()
+            .flat_map(|piece| self.encode_piece(piece.as_bytes()))
+            .collect()
+    }
+
+    /// Merge the bytes of one pre-tokenized piece, lowest rank first
+    fn encode_piece(&self, piece: &[u8]) -> Vec<u32> {
+        if let Some(&rank) = self.ranks.get(piece) {
+            return vec![rank];
+        }
+
+        // Start offsets of the current parts, plus the end of the piece
+        let mut bounds: Vec<usize> = (0..=piece.len()).collect();
+        loop {
+            let merge = (0..bounds.len().saturating_sub(2))
+                .filter_map(|i| {
+                    let rank = self.ranks.get(&piece[bounds[i]..bounds[i + 2]])?;
+                    Some((*rank, i))
+                })
+                .min();
+            match merge {
+                Some((_, i)) => {
+                    bounds.remove(i + 1);
+                }
+                None => break,
+            }
+        }
+
+        bounds
+            .windows(2)
+            .map(|part| self.ranks[&piece[part[0]..part[1]]])
+            .collect()
+    }
+}
+
+impl Tokenizer for BpeTokenizer {
+    fn count_tokens(&self, text: &str) -> u64 {
+        self.encode(text).len() as u64
+    }
+}
+
+/// Split text into the pieces BPE merges run within
+///
+/// Follows the cl100k/o200k pre-tokenization patterns: English
+/// contractions, words with at most one leading symbol or space, numbers of
+/// up to three digits, punctuation runs with one leading space, newlines,
+/// and whitespace (a space before a word stays with the word).
+fn pre_tokenize(text: &str) -> Vec<&str> {
+    let chars: Vec<(usize, char)> = text.char_indices().collect();
+    let offset = |i: usize| chars.get(i).map_or(text.len(), |&(byte, _)| byte);
+
+    let mut pieces = Vec::new();
+    let mut start = 0;
+    while start < chars.len() {
+        let end = piece_end(&chars, start);
+        pieces.push(&text[offset(start)..offset(end)]);
+        start = end;
+    }
+    pieces
+}
+
+/// End (exclusive char index) of the piece starting at `start`
+fn piece_end(chars: &[(usize, char)], start: usize) -> usize {
+    let at = |i: usize| chars.get(i).map(|&(_, c)| c);
+    let run = |mut i: usize, matches: &dyn Fn(char) -> bool| {
+        while at(i).is_some_and(matches) {
+            i += 1;
+        }
+        i
+    };
+    let is_newline = |c: char| c == '\r' || c == '\n';
+    let is_symbol = |c: char| !c.is_whitespace() && !c.is_alphabetic() && !c.is_numeric();
+    let first = chars[start].1;
+
+    // Contractions
+    if first == '\'' {
+        for suffix in ["s", "t", "re", "ve", "m", "ll", "d"] {
+            let matched = suffix
+                .chars()
+                .enumerate()
+                .all(|(k, s)| at(start + 1 + k).is_some_and(|c| c.eq_ignore_ascii_case(&s)));
+            if matched {
+                return start + 1 + suffix.len();
+            }
+        }
+    }
+
+    // Words, with at most one leading non-letter, non-digit character
+    let word = if first.is_alphabetic() || first.is_numeric() || is_newline(first) {
+        start
+    } else {
+        start + 1
+    };
+    if at(word).is_some_and(char::is_alphabetic) {
+        return run(word, &|c| c.is_alphabetic());
+    }
+
+    // Numbers, in groups of up to three digits
+    if first.is_numeric() {
+        return run(start, &|c| c.is_numeric()).min(start + 3);
+    }
+
+    // Punctuation, with at most one leading space, and trailing newlines
+    let symbols = if first == ' ' { start + 1 } else { start };
+    if at(symbols).is_some_and(is_symbol) {
+        return run(run(symbols, &is_symbol), &is_newline);
+    }
+
+    // Whitespace up to and including the last newline
+    let end = run(start, &|c| c.is_whitespace());
+    if let Some(newline) = (start..end).rev().find(|&i| at(i).is_some_and(is_newline)) {
+        return newline + 1;
+    }
+
+    // Whitespace before a word leaves its last space to the word
+    if end < chars.len() && end - start > 1 {
+        end - 1
+    } else {
+        end
+    }
+}
+
+/// SentencePiece unigram tokenizer (`.vocab` files)
+#[derive(Debug, Clone)]
+pub struct SentencePieceTokenizer {
+    scores: HashMap<String, f32>,
+    max_piece_chars: usize,
+    byte_fallback: bool,
+    unknown_score: f32,
+}
+
+/// SentencePiece's marker for a space
+const SPACE: char = '\u{2581}';
+
+impl SentencePieceTokenizer {
+    /// Parse a SentencePiece vocabulary (`<piece>\t<score>` per line)
+    ///
+    /// Control pieces (`<unk>`, `<s>`, `</s>`, `<pad>`) are never matched.
+    /// Vocabularies with byte pieces (`<0x00>` ...) encode unknown
+    /// characters as one token per UTF-8 byte, otherwise as one token.
+    pub fn from_vocab(content: &str) -> Result<Self> {
+        let mut scores = HashMap::new();
+        let mut byte_fallback = false;
+        for (number, line) in content.lines().enumerate() {
+            if line.is_empty() {
+                continue;
+            }
+            let (piece, score) = line.split_once('\t').ok_or_else(|| {
+                ProviderError::ConfigError(format!(
+                    "Invalid SentencePiece vocabulary at line {}: expected a piece and a score",
+                    number + 1
+                ))
+            })?;
+            let score: f32 = score.trim().parse().map_err(|_| {
+                ProviderError::ConfigError(format!(
+                    "Invalid SentencePiece vocabulary at line {}: score is not a number",
+                    number + 1
+                ))
+            })?;
+
+            if is_byte_piece(piece) {
+                byte_fallback = true;
+            } else if !matches!(piece, "<unk>" | "<s>" | "</s>" | "<pad>") {
+                scores.insert(piece.to_string(), score);
+            }
+        }
+
+        if scores.is_empty() {
+            return Err(ProviderError::ConfigError(
+                "SentencePiece vocabulary has no pieces".to_string(),
+            ));
+        }
+
+        let max_piece_chars = scores.keys().map(|p| p.chars().count()).max().unwrap_or(1);
+        let lowest = scores.values().copied().fold(f32::INFINITY, f32::min);
+        Ok(Self {
+            scores,
+            max_piece_chars,
+            byte_fallback,
+            unknown_score: lowest - 10.0,
+        })
+    }
+
+    /// Number of pieces in the vocabulary (excluding control and byte pieces)
+    pub fn vocab_size(&self) -> usize {
+        self.scores.len()
+    }
+}
+
+impl Tokenizer for SentencePieceTokenizer {
+    fn count_tokens(&self, text: &str) -> u64 {
+        if text.is_empty() {
+            return 0;
+        }
+
+        // Spaces become `▁`, with one prepended as SentencePiece does
+        let chars: Vec<char> = std::iter::once(SPACE)
+            .chain(text.chars().map(|c| if c == ' ' { SPACE } else { c }))
+            .collect();
+
+        // Most probable segmentation ending at each position: (score, tokens)
+        let mut best: Vec<Option<(f32, u64)>> = vec![None; chars.len() + 1];
+        best[0] = Some((0.0, 0));
+        for start in 0..chars.len() {
+            let Some((score, tokens)) = best[start] else {
+                continue;
+            };
+            let mut update = |end: usize, candidate: (f32, u64)| {
+                if !best[end].is_some_and(|(current, _)| current >= candidate.0) {
+                    best[end] = Some(candidate);
+                }
+            };
+
+            let mut piece = String::new();
+            let mut matched_char = false;
+            for end in start + 1..=(start + self.max_piece_chars).min(chars.len()) {
+                piece.push(chars[end - 1]);
+                if let Some(&piece_score) = self.scores.get(&piece) {
+                    matched_char |= end == start + 1;
+                    update(end, (score + piece_score, tokens + 1));
+                }
+            }
+
+            if !matched_char {
+                let unknown = if self.byte_fallback {
+                    chars[start].len_utf8() as u64
+                } else {
+                    1
+                };
+                update(start + 1, (score + self.unknown_score, tokens + unknown));
+            }
+        }
+
+        best[chars.len()].map_or(0, |(_, tokens)| tokens)
+    }
+}
+
+/// Whether a piece is a byte-fallback piece such as `<0x0A>`
+fn is_byte_piece(piece: &str) -> bool {
+    piece.len() == 6
+        && piece.starts_with("<0x")
+        && piece.ends_with('>')
+        && u8::from_str_radix(&piece[3..5], 16).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::MessageRole;
+
+    /// Tiktoken vocabulary with every byte plus the given merges
+    fn tiktoken_vocab(merges: &[&str]) -> String {
+        let engine = base64::engine::general_purpose::STANDARD;
+        let bytes = (0..=u8::MAX).map(|b| vec![b]);
+        let merged = merges.iter().map(|m| m.as_bytes().to_vec());
+        bytes
+            .chain(merged)
+            .enumerate()
+            .map(|(rank, token)| format!("{} {}\n", engine.encode(token), rank))
+            .collect()
+    }
+
+    #[test]
+    fn test_pre_tokenize() {
+        assert_eq!(
+            pre_tokenize("Hello, world! It's 12345 tokens.\n\n  ok"),
+            vec![
+                "Hello", ",", " world", "!", " It", "'s", " ", "123", "45", " tokens", ".\n\n",
+                " ", " ok"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_bpe_merges_lowest_rank_first() {
+        let tokenizer = BpeTokenizer::from_tiktoken(&tiktoken_vocab(&["he", "ll", "hell"])).unwrap();
+        assert_eq!(tokenizer.vocab_size(), 259);
+
+        // "he" + "ll" -> "hell", then "o" on its own
+        assert_eq!(tokenizer.encode("hello"), vec![258, b'o' as u32]);
+        // " hello" is one piece, and there is no merge for " h"
+        assert_eq!(tokenizer.count_tokens(" hello"), 3);
+        assert_eq!(tokenizer.count_tokens(""), 0);
+
+        let messages = vec![
+            Message::new(MessageRole::System, "hello"),
+            Message::new(MessageRole::User, "hello"),
+        ];
+        assert_eq!(tokenizer.count_messages(&messages), 4);
+    }
+
+    #[test]
+    fn test_bpe_requires_every_byte() {
+        let err = BpeTokenizer::from_tiktoken("aGVsbG8= 0\n").unwrap_err();
+        assert!(err.to_string().contains("0x00"));
+        assert!(BpeTokenizer::from_tiktoken("not-a-line\n").is_err());
+    }
+
+    #[test]
+    fn test_sentencepiece_segmentation() {
+        let vocab = "<unk>\t0\n<s>\t0\n</s>\t0\n\u{2581}hello\t-1\n\u{2581}world\t-1\n\
+                     \u{2581}\t-2\nh\t-3\ne\t-3\nl\t-3\no\t-3\nw\t-3\nr\t-3\nd\t-3\n";
+        let tokenizer = SentencePieceTokenizer::from_vocab(vocab).unwrap();
+        assert_eq!(tokenizer.vocab_size(), 10);
+
+        assert_eq!(tokenizer.count_tokens("hello world"), 2);
+        // "▁hello", "▁", then the unknown "x"
+        assert_eq!(tokenizer.count_tokens("hello x"), 3);
+        assert_eq!(tokenizer.count_tokens(""), 0);
+    }
+
+    #[test]
+    fn test_sentencepiece_byte_fallback() {
+        let vocab = "<unk>\t0\n<0x00>\t0\n<0xC3>\t0\n<0xA9>\t0\n\u{2581}\t-1\na\t-2\n";
+        let tokenizer = SentencePieceTokenizer::from_vocab(vocab).unwrap();
+
+        // "▁", "a", then "é" as two UTF-8 bytes
+        assert_eq!(tokenizer.count_tokens("aé"), 4);
+    }
+
+    #[test]
+    fn test_load_by_extension() {
+        let dir = std::env::temp_dir().join(format!("tokenizer-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let bpe = dir.join("test.tiktoken");
+        std::fs::write(&bpe, tiktoken_vocab(&[])).unwrap();
+        assert_eq!(load(&bpe).unwrap().count_tokens("abc"), 3);
+
+        let spm = dir.join("test.vocab");
+        std::fs::write(&spm, "\u{2581}abc\t-1\n").unwrap();
+        assert_eq!(load(&spm).unwrap().count_tokens("abc"), 1);
+
+        assert!(load(&dir.join("missing.tiktoken")).is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -4,11 +4,19 @@
 //! The trait is designed to support streaming responses with fine-grained timing
 //! measurements for comprehensive latency analysis.
 
-use crate::error::Result;
+use crate::body::BodyStats;
+use crate::error::{ProviderError, Result};
+use crate::headers::ServerSignals;
+use crate::models::ModelCache;
+use crate::network::{NetworkProbe, NetworkTimings, ReadStats};
+use crate::pricing::{PriceTable, PricingMode};
+use crate::retry::RetryStats;
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use futures::Stream;
-use llm_latency_lens_core::{RequestId, SessionId, TimingEngine, TokenEvent};
+use llm_latency_lens_core::{RequestId, SessionId, TimingEngine, TokenEvent, TokenKind};
 use std::pin::Pin;
+use std::sync::{Arc, Mutex};
 
 /// Configuration for a streaming request
 #[derive(Debug, Clone)]
@@ -31,6 +39,74 @@ pub struct StreamingRequest {
     pub stop: Option<Vec<String>>,
     /// Request timeout in seconds
     pub timeout_secs: Option<u64>,
+    /// Token budget for extended thinking (None = thinking disabled)
+    pub thinking_budget: Option<u32>,
+    /// Tools the model may call
+    pub tools: Vec<Tool>,
+    /// How the model should choose among the tools
+    pub tool_choice: Option<ToolChoice>,
+    /// Response headers to redact in addition to
+    /// [`DEFAULT_REDACTED_HEADERS`](crate::headers::DEFAULT_REDACTED_HEADERS)
+    pub redact_headers: Vec<String>,
+}
+
+/// A tool (function) definition offered to the model
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Tool {
+    /// Tool name
+    pub name: String,
+    /// Description of what the tool does
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// JSON Schema of the tool arguments
+    #[serde(default = "empty_object_schema")]
+    pub parameters: serde_json::Value,
+}
+
+impl Tool {
+    /// Create a tool definition
+    pub fn new(name: impl Into<String>, parameters: serde_json::Value) -> Self {
+        Self {
+            name: name.into(),
+            description: None,
+            parameters,
+        }
+    }
+
+    /// Set the tool description
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+}
+
+fn empty_object_schema() -> serde_json::Value {
+    serde_json::json!({"type": "object", "properties": {}})
+}
+
+/// Tool selection strategy
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ToolChoice {
+    /// The model decides whether to call a tool
+    Auto,
+    /// The model must not call a tool
+    None,
+    /// The model must call at least one tool
+    Required,
+    /// The model must call the named tool
+    Tool(String),
+}
+
+impl From<&str> for ToolChoice {
+    /// Parse `auto`, `none`, `required` (or `any`), or a tool name
+    fn from(value: &str) -> Self {
+        match value {
+            "auto" => Self::Auto,
+            "none" => Self::None,
+            "required" | "any" => Self::Required,
+            name => Self::Tool(name.to_string()),
+        }
+    }
 }
 
 /// A message in the conversation
@@ -38,8 +114,144 @@ pub struct StreamingRequest {
 pub struct Message {
     /// Role of the message sender
     pub role: MessageRole,
-    /// Content of the message
-    pub content: String,
+    /// Content parts of the message
+    pub content: Vec<ContentPart>,
+    /// Cache the prompt up to and including this message (Anthropic `cache_control`)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub cache_breakpoint: bool,
+}
+
+impl Message {
+    /// Create a text-only message
+    pub fn new(role: MessageRole, text: impl Into<String>) -> Self {
+        Self {
+            role,
+            content: vec![ContentPart::text(text)],
+            cache_breakpoint: false,
+        }
+    }
+
+    /// Mark this message as the end of a cacheable prompt prefix
+    pub fn with_cache_breakpoint(mut self) -> Self {
+        self.cache_breakpoint = true;
+        self
+    }
+
+    /// Concatenated text of all text parts
+    pub fn text(&self) -> String {
+        self.content
+            .iter()
+            .filter_map(|part| match part {
+                ContentPart::Text { text } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Whether the message contains only text parts
+    pub fn is_text_only(&self) -> bool {
+        self.content
+            .iter()
+            .all(|part| matches!(part, ContentPart::Text { .. }))
+    }
+}
+
+/// One part of a message's content
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ContentPart {
+    /// Plain text
+    Text {
+        /// The text
+        text: String,
+    },
+    /// Image referenced by URL
+    ImageUrl {
+        /// Publicly reachable image URL
+        url: String,
+    },
+    /// Image sent inline as base64
+    Image {
+        /// MIME type, e.g. `image/png`
+        media_type: String,
+        /// Base64-encoded image bytes
+        data: String,
+    },
+    /// Document (PDF) sent inline as base64
+    Document {
+        /// MIME type, e.g. `application/pdf`
+        media_type: String,
+        /// Base64-encoded document bytes
+        data: String,
+    },
+}
+
+impl ContentPart {
+    /// Create a text part
+    pub fn text(text: impl Into<String>) -> Self {
+        Self::Text { text: text.into() }
+    }
+
+    /// Create an image part referencing a URL
+    pub fn image_url(url: impl Into<String>) -> Self {
+        Self::ImageUrl { url: url.into() }
+    }
+
+    /// Load an image or PDF from disk as an inline base64 part
+    ///
+    /// The media type is inferred from the file extension.
+    pub fn from_file(path: impl AsRef<std::path::Path>) -> Result<Self> {
+        use base64::Engine;
+
+        let path = path.as_ref();
+        let media_type = media_type_for_path(path).ok_or_else(|| {
+            ProviderError::ConfigError(format!(
+                "Unsupported attachment type: {} (expected png, jpg, gif, webp or pdf)",
+                path.display()
+            ))
+        })?;
+        let bytes = std::fs::read(path).map_err(|e| {
+            ProviderError::ConfigError(format!("Failed to read {}: {}", path.display(), e))
+        })?;
+        let data = base64::engine::general_purpose::STANDARD.encode(bytes);
+
+        Ok(if media_type == "application/pdf" {
+            Self::Document {
+                media_type: media_type.to_string(),
+                data,
+            }
+        } else {
+            Self::Image {
+                media_type: media_type.to_string(),
+                data,
+            }
+        })
+    }
+
+    /// Render an inline part as a `data:` URL (URL parts are returned unchanged)
+    pub fn to_url(&self) -> Option<String> {
+        match self {
+            Self::Text { .. } => None,
+            Self::ImageUrl { url } => Some(url.clone()),
+            Self::Image { media_type, data } | Self::Document { media_type, data } => {
+                Some(format!("data:{};base64,{}", media_type, data))
+            }
+        }
+    }
+}
+
+/// Infer the MIME type of a supported attachment from its file extension
+pub fn media_type_for_path(path: &std::path::Path) -> Option<&'static str> {
+    let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+    match ext.as_str() {
+        "png" => Some("image/png"),
+        "jpg" | "jpeg" => Some("image/jpeg"),
+        "gif" => Some("image/gif"),
+        "webp" => Some("image/webp"),
+        "pdf" => Some("application/pdf"),
+        _ => None,
+    }
 }
 
 /// Role of a message sender
@@ -62,6 +274,52 @@ pub struct StreamingResponse {
     pub token_stream: Pin<Box<dyn Stream<Item = Result<TokenEvent>> + Send>>,
     /// Request metadata
     pub metadata: ResponseMetadata,
+    /// Token usage reported by the provider while streaming
+    pub usage: SharedUsage,
+    /// Network-phase timings, recorded as the stream connects
+    pub network: NetworkProbe,
+}
+
+/// Token usage counts reported by a provider
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TokenUsage {
+    /// Input (prompt) tokens
+    pub input_tokens: Option<u64>,
+    /// Output (completion) tokens, excluding separately reported reasoning tokens
+    pub output_tokens: Option<u64>,
+    /// Thinking/reasoning tokens, when reported separately from the output
+    pub thinking_tokens: Option<u64>,
+    /// Input tokens written to the prompt cache
+    pub cache_creation_tokens: Option<u64>,
+    /// Input tokens served from the prompt cache
+    pub cache_read_tokens: Option<u64>,
+}
+
+/// Usage slot shared between a token stream and its consumer
+///
+/// Providers report usage inside the stream (often in the final chunk), after
+/// the [`StreamingResponse`] has already been handed out. The stream records
+/// the counts here as they arrive and the consumer reads them once the stream
+/// is drained.
+#[derive(Debug, Clone, Default)]
+pub struct SharedUsage(Arc<Mutex<TokenUsage>>);
+
+impl SharedUsage {
+    /// Create an empty usage slot
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Update the recorded usage
+    pub fn update(&self, f: impl FnOnce(&mut TokenUsage)) {
+        let mut usage = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        f(&mut usage);
+    }
+
+    /// Get a snapshot of the recorded usage
+    pub fn get(&self) -> TokenUsage {
+        *self.0.lock().unwrap_or_else(|e| e.into_inner())
+    }
 }
 
 /// Metadata about the response
@@ -73,12 +331,62 @@ pub struct ResponseMetadata {
     pub input_tokens: Option<u64>,
     /// Output token count (updated as tokens arrive)
     pub output_tokens: Option<u64>,
-    /// Thinking tokens (Claude extended thinking)
+    /// Thinking/reasoning tokens reported separately from the output tokens
     pub thinking_tokens: Option<u64>,
+    /// Input tokens written to the prompt cache (Anthropic)
+    pub cache_creation_tokens: Option<u64>,
+    /// Input tokens served from the prompt cache
+    pub cache_read_tokens: Option<u64>,
     /// Estimated cost in USD (if available)
     pub estimated_cost: Option<f64>,
-    /// Raw HTTP headers for debugging
+    /// Response headers, with sensitive values redacted
     pub headers: Vec<(String, String)>,
+    /// Request ID, processing time and rate limits reported by the server
+    pub server: ServerSignals,
+}
+
+impl ResponseMetadata {
+    /// Merge provider-reported usage, keeping existing counts the usage lacks
+    pub fn apply_usage(&mut self, usage: TokenUsage) {
+        if usage.input_tokens.is_some() {
+            self.input_tokens = usage.input_tokens;
+        }
+        if usage.output_tokens.is_some() {
+            self.output_tokens = usage.output_tokens;
+        }
+        if usage.thinking_tokens.is_some() {
+            self.thinking_tokens = usage.thinking_tokens;
+        }
+        if usage.cache_creation_tokens.is_some() {
+            self.cache_creation_tokens = usage.cache_creation_tokens;
+        }
+        if usage.cache_read_tokens.is_some() {
+            self.cache_read_tokens = usage.cache_read_tokens;
+        }
+    }
+
+    /// Token usage recorded in the metadata
+    pub fn usage(&self) -> TokenUsage {
+        TokenUsage {
+            input_tokens: self.input_tokens,
+            output_tokens: self.output_tokens,
+            thinking_tokens: self.thinking_tokens,
+            cache_creation_tokens: self.cache_creation_tokens,
+            cache_read_tokens: self.cache_read_tokens,
+        }
+    }
+
+    /// Price the request from the reported usage, unless already priced
+    pub(crate) fn estimate_cost(&mut self, price: impl FnOnce(&TokenUsage) -> Option<f64>) {
+        if self.estimated_cost.is_none() {
+            self.estimated_cost = price(&self.usage());
+        }
+    }
+
+    /// Whether the prompt was served from cache (None if the provider did not say)
+    pub fn cache_hit(&self) -> Option<bool> {
+        self.cache_read_tokens.map(|tokens| tokens > 0)
+    }
 }
 
 /// Result of a completed request with timing information
@@ -88,18 +396,137 @@ pub struct CompletionResult {
     pub request_id: RequestId,
     /// Complete generated text
     pub content: String,
+    /// Complete thinking text (empty unless extended thinking was enabled)
+    pub thinking: String,
     /// All token events in sequence
     pub token_events: Vec<TokenEvent>,
     /// Response metadata
     pub metadata: ResponseMetadata,
     /// Timing checkpoints
     pub timing_checkpoints: Vec<(String, std::time::Duration)>,
+    /// Network-phase timings (DNS, TCP connect, TLS, first byte)
+    pub network: NetworkTimings,
+    /// Response-body reads and the events they delivered
+    pub reads: ReadStats,
+    /// Retries of the initial request and the backoff between them
+    pub retries: RetryStats,
+    /// Body size, download and parse time (non-streaming requests only)
+    pub body: Option<BodyStats>,
 }
 
 impl CompletionResult {
+    /// Result of a non-streaming request, which has no token events
+    pub(crate) fn from_body(
+        request_id: RequestId,
+        content: String,
+        thinking: String,
+        metadata: ResponseMetadata,
+        network: &NetworkProbe,
+        body: BodyStats,
+    ) -> Self {
+        Self {
+            request_id,
+            content,
+            thinking,
+            token_events: Vec::new(),
+            metadata,
+            timing_checkpoints: network.checkpoints(),
+            network: network.timings(),
+            reads: network.read_stats(),
+            retries: network.retry_stats(),
+            body: Some(body),
+        }
+    }
+
+    /// Whether the response was streamed
+    pub fn is_streamed(&self) -> bool {
+        self.body.is_none()
+    }
+
     /// Calculate time to first token (TTFT)
+    ///
+    /// For a non-streaming request this is the time until the body was
+    /// parsed, since no text is available before then.
     pub fn ttft(&self) -> Option<std::time::Duration> {
-        self.token_events.first().map(|e| e.time_since_start)
+        self.token_events
+            .first()
+            .map(|e| e.time_since_start)
+            .or(self.body.map(|b| b.time_to_parsed))
+    }
+
+    /// Time to first token from the moment the response was requested on an
+    /// open session (realtime transports only)
+    ///
+    /// Unlike [`ttft`](Self::ttft), this excludes connection and session setup.
+    pub fn response_ttft(&self) -> Option<std::time::Duration> {
+        let requested = self.network.response_requested?;
+        Some(self.ttft()?.saturating_sub(requested))
+    }
+
+    /// Calculate time to first thinking token
+    pub fn time_to_first_thinking_token(&self) -> Option<std::time::Duration> {
+        self.token_events
+            .iter()
+            .find(|e| e.kind == TokenKind::Thinking)
+            .map(|e| e.time_since_start)
+    }
+
+    /// Calculate time spent in the thinking phase
+    ///
+    /// Measured from the first to the last thinking token.
+    pub fn thinking_duration(&self) -> Option<std::time::Duration> {
+        let mut thinking = self
+            .token_events
+            .iter()
+            .filter(|e| e.kind == TokenKind::Thinking);
+        let first = thinking.next()?;
+        let last = thinking.next_back().unwrap_or(first);
+        Some(last.time_since_start.saturating_sub(first.time_since_start))
+    }
+
+    /// Calculate time to first visible (non-thinking) token
+    pub fn time_to_first_visible_token(&self) -> Option<std::time::Duration> {
+        self.token_events
+            .iter()
+            .find(|e| e.kind == TokenKind::Content)
+            .map(|e| e.time_since_start)
+    }
+
+    /// Calculate the reasoning phase length
+    ///
+    /// For requests that reasoned (reasoning tokens were reported or thinking
+    /// text was streamed) this is the time to the first visible token. Models
+    /// that hide their reasoning produce nothing during this phase, so it also
+    /// includes prompt processing and network time.
+    pub fn reasoning_time(&self) -> Option<std::time::Duration> {
+        let reasoned = self.metadata.thinking_tokens.is_some_and(|t| t > 0)
+            || self.token_events.iter().any(|e| e.kind == TokenKind::Thinking);
+        if reasoned {
+            self.time_to_first_visible_token()
+        } else {
+            None
+        }
+    }
+
+    /// Calculate time to the first tool-call byte
+    pub fn time_to_first_tool_call(&self) -> Option<std::time::Duration> {
+        self.token_events
+            .iter()
+            .find(|e| e.kind == TokenKind::ToolCall)
+            .map(|e| e.time_since_start)
+    }
+
+    /// Calculate tool-argument generation time
+    ///
+    /// Measured from the first to the last tool-call event.
+    pub fn tool_call_duration(&self) -> Option<std::time::Duration> {
+        let mut tool_calls = self
+            .token_events
+            .iter()
+            .filter(|e| e.kind == TokenKind::ToolCall);
+        let first = tool_calls.next()?;
+        let last = tool_calls.next_back().unwrap_or(first);
+        Some(last.time_since_start.saturating_sub(first.time_since_start))
     }
 
     /// Calculate average inter-token latency
@@ -164,15 +591,31 @@ impl CompletionResult {
 
     /// Calculate total generation time
     pub fn total_generation_time(&self) -> Option<std::time::Duration> {
-        self.token_events.last().map(|e| e.time_since_start)
+        self.token_events
+            .last()
+            .map(|e| e.time_since_start)
+            .or(self.body.map(|b| b.time_to_parsed))
+    }
+
+    /// Number of output tokens, preferring the provider-reported count
+    ///
+    /// Falls back to the number of token events, which undercounts when a
+    /// provider packs several tokens into one event.
+    pub fn output_tokens(&self) -> u64 {
+        self.metadata
+            .output_tokens
+            .unwrap_or(self.token_events.len() as u64)
     }
 
     /// Calculate tokens per second
+    ///
+    /// Counts reasoning tokens as well, since they are generated too.
     pub fn tokens_per_second(&self) -> Option<f64> {
         if let Some(duration) = self.total_generation_time() {
             let secs = duration.as_secs_f64();
             if secs > 0.0 {
-                return Some(self.token_events.len() as f64 / secs);
+                let generated = self.output_tokens() + self.metadata.thinking_tokens.unwrap_or(0);
+                return Some(generated as f64 / secs);
             }
         }
         None
@@ -185,6 +628,9 @@ pub trait Provider: Send + Sync {
     /// Get the provider name
     fn name(&self) -> &'static str;
 
+    /// Provider recorded in request metrics
+    fn provider_kind(&self) -> llm_latency_lens_core::Provider;
+
     /// Check if the provider is properly configured
     async fn health_check(&self) -> Result<()>;
 
@@ -228,28 +674,94 @@ pub trait Provider: Send + Sync {
         use futures::StreamExt;
 
         let request_id = request.request_id;
+        let model = request.model.clone();
         let mut response = self.stream(request, timing_engine).await?;
 
         let mut token_events = Vec::new();
         let mut content = String::new();
+        let mut thinking = String::new();
 
         while let Some(event_result) = response.token_stream.next().await {
             let event = event_result?;
             if let Some(ref text) = event.content {
-                content.push_str(text);
+                match event.kind {
+                    TokenKind::Content => content.push_str(text),
+                    TokenKind::Thinking => thinking.push_str(text),
+                    // Tool-call fragments stay in token_events
+                    TokenKind::ToolCall => {}
+                }
             }
             token_events.push(event);
         }
 
+        let mut metadata = response.metadata;
+        metadata.apply_usage(response.usage.get());
+
+        // Price the request from the reported usage. The requested model name
+        // is used since some providers map it (e.g. Azure deployments).
+        metadata.estimate_cost(|usage| self.usage_cost(&model, usage));
+
         Ok(CompletionResult {
             request_id,
             content,
+            thinking,
             token_events,
-            metadata: response.metadata,
-            timing_checkpoints: Vec::new(), // Will be populated by provider
+            metadata,
+            timing_checkpoints: response.network.checkpoints(),
+            network: response.network.timings(),
+            reads: response.network.read_stats(),
+            retries: response.network.retry_stats(),
+            body: None,
         })
     }
 
+    /// Execute a non-streaming request (`stream: false`)
+    ///
+    /// The response arrives as one body, so the result has no token events.
+    /// Its [`body`](CompletionResult::body) records the body size and the
+    /// download and parse times, and the time until the body was parsed
+    /// stands in for the time to first token.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The request configuration
+    /// * `timing_engine` - Timing engine for high-precision measurements
+    ///
+    /// # Returns
+    ///
+    /// A `CompletionResult` with the complete response and timing data
+    async fn complete_non_streaming(
+        &self,
+        request: StreamingRequest,
+        timing_engine: &TimingEngine,
+    ) -> Result<CompletionResult>;
+
+    /// Price a request's token usage with `table`
+    ///
+    /// Prices are those valid at `at`. The default looks the model up under
+    /// the provider name; adapters that map model names (e.g. Azure
+    /// deployments) override it.
+    ///
+    /// # Returns
+    ///
+    /// Cost in USD, or None if the table has no price for the model
+    fn price_usage(
+        &self,
+        table: &PriceTable,
+        model: &str,
+        usage: &TokenUsage,
+        at: DateTime<Utc>,
+        mode: PricingMode,
+    ) -> Option<f64> {
+        table.cost(self.name(), model, usage, at, mode)
+    }
+
+    /// Price a request's token usage at current standard rates from the
+    /// bundled price table
+    fn usage_cost(&self, model: &str, usage: &TokenUsage) -> Option<f64> {
+        self.price_usage(PriceTable::bundled(), model, usage, Utc::now(), PricingMode::Standard)
+    }
+
     /// Calculate the cost of a request
     ///
     /// # Arguments
@@ -261,32 +773,81 @@ pub trait Provider: Send + Sync {
     /// # Returns
     ///
     /// Estimated cost in USD, or None if pricing is unavailable
-    fn calculate_cost(
-        &self,
-        model: &str,
-        input_tokens: u64,
-        output_tokens: u64,
-    ) -> Option<f64>;
+    fn calculate_cost(&self, model: &str, input_tokens: u64, output_tokens: u64) -> Option<f64> {
+        let usage = TokenUsage {
+            input_tokens: Some(input_tokens),
+            output_tokens: Some(output_tokens),
+            ..TokenUsage::default()
+        };
+        self.usage_cost(model, &usage)
+    }
 
     /// Get supported models for this provider
+    ///
+    /// A static list, used for validation until models are discovered with
+    /// [`discover_models`](Self::discover_models). An empty list accepts any
+    /// model.
     fn supported_models(&self) -> Vec<String>;
 
+    /// List the models available from the provider
+    ///
+    /// Adapters with a models endpoint query it; the default returns the
+    /// static [`supported_models`](Self::supported_models) list.
+    async fn list_models(&self) -> Result<Vec<String>> {
+        Ok(self.supported_models())
+    }
+
+    /// Cache of discovered models, for adapters that list them
+    fn model_cache(&self) -> Option<&ModelCache> {
+        None
+    }
+
+    /// List the provider's models and cache them for validation
+    ///
+    /// Returns the cached list while it is fresh.
+    async fn discover_models(&self) -> Result<Vec<String>> {
+        if let Some(models) = self.model_cache().and_then(ModelCache::get) {
+            return Ok(models);
+        }
+        let models = self.list_models().await?;
+        if let Some(cache) = self.model_cache() {
+            cache.set(models.clone());
+        }
+        Ok(models)
+    }
+
     /// Validate a model name
+    ///
+    /// Accepts models in the static list and models discovered from the
+    /// provider's models endpoint.
     fn validate_model(&self, model: &str) -> Result<()> {
         let supported = self.supported_models();
-        if supported.is_empty() || supported.contains(&model.to_string()) {
+        let discovered = self
+            .model_cache()
+            .and_then(ModelCache::get)
+            .unwrap_or_default();
+        if supported.is_empty() || supported.iter().chain(&discovered).any(|m| m == model) {
             Ok(())
         } else {
-            Err(crate::error::ProviderError::InvalidModel(format!(
+            Err(ProviderError::InvalidModel(format!(
                 "Model '{}' is not supported by {}. Supported models: {}",
                 model,
                 self.name(),
-                supported.join(", ")
+                known_models(supported, discovered).join(", ")
             )))
         }
     }
 }
 
+/// Static and discovered models, sorted and deduplicated
+pub(crate) fn known_models(supported: Vec<String>, discovered: Vec<String>) -> Vec<String> {
+    let mut known = supported;
+    known.extend(discovered);
+    known.sort();
+    known.dedup();
+    known
+}
+
 /// Helper to build a streaming request
 impl StreamingRequest {
     /// Create a new streaming request builder
@@ -307,6 +868,10 @@ pub struct StreamingRequestBuilder {
     top_p: Option<f32>,
     stop: Option<Vec<String>>,
     timeout_secs: Option<u64>,
+    thinking_budget: Option<u32>,
+    tools: Vec<Tool>,
+    tool_choice: Option<ToolChoice>,
+    redact_headers: Vec<String>,
 }
 
 impl StreamingRequestBuilder {
@@ -330,10 +895,35 @@ impl StreamingRequestBuilder {
 
     /// Add a message
     pub fn message(mut self, role: MessageRole, content: impl Into<String>) -> Self {
-        self.messages.push(Message {
-            role,
-            content: content.into(),
-        });
+        self.messages.push(Message::new(role, content));
+        self
+    }
+
+    /// Attach a content part (image or document) to the last user message
+    ///
+    /// Starts a new user message if there is none yet.
+    pub fn attachment(mut self, part: ContentPart) -> Self {
+        match self
+            .messages
+            .iter_mut()
+            .rev()
+            .find(|m| m.role == MessageRole::User)
+        {
+            Some(message) => message.content.push(part),
+            None => self.messages.push(Message {
+                role: MessageRole::User,
+                content: vec![part],
+                cache_breakpoint: false,
+            }),
+        }
+        self
+    }
+
+    /// Mark the most recently added message as a prompt-cache breakpoint
+    pub fn cache_breakpoint(mut self) -> Self {
+        if let Some(message) = self.messages.last_mut() {
+            message.cache_breakpoint = true;
+        }
         self
     }
 
@@ -373,6 +963,36 @@ impl StreamingRequestBuilder {
         self
     }
 
+    /// Enable extended thinking with the given token budget
+    pub fn thinking_budget(mut self, tokens: u32) -> Self {
+        self.thinking_budget = Some(tokens);
+        self
+    }
+
+    /// Add a tool definition
+    pub fn tool(mut self, tool: Tool) -> Self {
+        self.tools.push(tool);
+        self
+    }
+
+    /// Set the tool definitions
+    pub fn tools(mut self, tools: Vec<Tool>) -> Self {
+        self.tools = tools;
+        self
+    }
+
+    /// Set the tool choice
+    pub fn tool_choice(mut self, choice: ToolChoice) -> Self {
+        self.tool_choice = Some(choice);
+        self
+    }
+
+    /// Redact a response header when recording the response headers
+    pub fn redact_header(mut self, name: impl Into<String>) -> Self {
+        self.redact_headers.push(name.into());
+        self
+    }
+
     /// Build the request
     pub fn build(self) -> StreamingRequest {
         StreamingRequest {
@@ -385,6 +1005,10 @@ impl StreamingRequestBuilder {
             top_p: self.top_p,
             stop: self.stop,
             timeout_secs: self.timeout_secs,
+            thinking_budget: self.thinking_budget,
+            tools: self.tools,
+            tool_choice: self.tool_choice,
+            redact_headers: self.redact_headers,
         }
     }
 }
@@ -406,6 +1030,13 @@ mod tests {
         assert_eq!(request.messages.len(), 1);
         assert_eq!(request.max_tokens, Some(100));
         assert_eq!(request.temperature, Some(0.7));
+        assert_eq!(request.thinking_budget, None);
+
+        let request = StreamingRequest::builder()
+            .model("claude-3-7-sonnet-20250219")
+            .thinking_budget(2048)
+            .build();
+        assert_eq!(request.thinking_budget, Some(2048));
     }
 
     #[test]
@@ -415,10 +1046,12 @@ mod tests {
         let result = CompletionResult {
             request_id: RequestId::new(),
             content: "test".to_string(),
+            thinking: String::new(),
             token_events: vec![
                 TokenEvent {
                     request_id: RequestId::new(),
                     sequence: 0,
+                    kind: TokenKind::Content,
                     content: Some("Hello".to_string()),
                     timestamp_nanos: 1000000,
                     time_since_start: Duration::from_millis(100),
@@ -427,6 +1060,7 @@ mod tests {
                 TokenEvent {
                     request_id: RequestId::new(),
                     sequence: 1,
+                    kind: TokenKind::Content,
                     content: Some("World".to_string()),
                     timestamp_nanos: 2000000,
                     time_since_start: Duration::from_millis(150),
@@ -438,14 +1072,251 @@ mod tests {
                 input_tokens: Some(10),
                 output_tokens: Some(2),
                 thinking_tokens: None,
+                cache_creation_tokens: None,
+                cache_read_tokens: None,
                 estimated_cost: None,
                 headers: vec![],
+                server: ServerSignals::default(),
             },
             timing_checkpoints: vec![],
+            network: NetworkTimings::default(),
+            reads: ReadStats::default(),
+            retries: RetryStats::default(),
+            body: None,
         };
 
         assert_eq!(result.ttft(), Some(Duration::from_millis(100)));
         assert_eq!(result.total_generation_time(), Some(Duration::from_millis(150)));
         assert_eq!(result.avg_inter_token_latency(), Some(Duration::from_millis(50)));
     }
+
+    #[test]
+    fn test_completion_result_prefers_reported_output_tokens() {
+        use std::time::Duration;
+
+        let event = |sequence: u64, millis: u64| TokenEvent {
+            request_id: RequestId::new(),
+            sequence,
+            kind: TokenKind::Content,
+            content: Some("several tokens".to_string()),
+            timestamp_nanos: millis * 1_000_000,
+            time_since_start: Duration::from_millis(millis),
+            inter_token_latency: None,
+        };
+
+        let mut result = CompletionResult {
+            request_id: RequestId::new(),
+            content: "several tokens several tokens".to_string(),
+            thinking: String::new(),
+            token_events: vec![event(0, 250), event(1, 500)],
+            metadata: ResponseMetadata {
+                model: "test-model".to_string(),
+                input_tokens: Some(10),
+                output_tokens: Some(6),
+                thinking_tokens: None,
+                cache_creation_tokens: None,
+                cache_read_tokens: None,
+                estimated_cost: None,
+                headers: vec![],
+                server: ServerSignals::default(),
+            },
+            timing_checkpoints: vec![],
+            network: NetworkTimings::default(),
+            reads: ReadStats::default(),
+            retries: RetryStats::default(),
+            body: None,
+        };
+
+        assert_eq!(result.output_tokens(), 6);
+        assert_eq!(result.tokens_per_second(), Some(12.0));
+
+        result.metadata.output_tokens = None;
+        assert_eq!(result.output_tokens(), 2);
+        assert_eq!(result.tokens_per_second(), Some(4.0));
+    }
+
+    #[test]
+    fn test_completion_result_thinking_phase() {
+        use std::time::Duration;
+
+        let event = |sequence: u64, kind: TokenKind, millis: u64| TokenEvent {
+            request_id: RequestId::new(),
+            sequence,
+            kind,
+            content: Some("x".to_string()),
+            timestamp_nanos: millis * 1_000_000,
+            time_since_start: Duration::from_millis(millis),
+            inter_token_latency: None,
+        };
+
+        let mut result = CompletionResult {
+            request_id: RequestId::new(),
+            content: "xx".to_string(),
+            thinking: "xxx".to_string(),
+            token_events: vec![
+                event(0, TokenKind::Thinking, 200),
+                event(1, TokenKind::Thinking, 300),
+                event(2, TokenKind::Thinking, 700),
+                event(3, TokenKind::Content, 900),
+                event(4, TokenKind::Content, 950),
+            ],
+            metadata: ResponseMetadata {
+                model: "test-model".to_string(),
+                input_tokens: None,
+                output_tokens: None,
+                thinking_tokens: None,
+                cache_creation_tokens: None,
+                cache_read_tokens: None,
+                estimated_cost: None,
+                headers: vec![],
+                server: ServerSignals::default(),
+            },
+            timing_checkpoints: vec![],
+            network: NetworkTimings::default(),
+            reads: ReadStats::default(),
+            retries: RetryStats::default(),
+            body: None,
+        };
+
+        assert_eq!(result.ttft(), Some(Duration::from_millis(200)));
+        assert_eq!(result.time_to_first_thinking_token(), Some(Duration::from_millis(200)));
+        assert_eq!(result.thinking_duration(), Some(Duration::from_millis(500)));
+        assert_eq!(result.time_to_first_visible_token(), Some(Duration::from_millis(900)));
+
+        assert_eq!(result.reasoning_time(), Some(Duration::from_millis(900)));
+
+        result.token_events.retain(|e| e.kind == TokenKind::Content);
+        assert_eq!(result.time_to_first_thinking_token(), None);
+        assert_eq!(result.thinking_duration(), None);
+        assert_eq!(result.time_to_first_visible_token(), Some(Duration::from_millis(900)));
+        assert_eq!(result.reasoning_time(), None);
+
+        // Hidden reasoning is only visible through the reported token count
+        result.metadata.thinking_tokens = Some(500);
+        assert_eq!(result.reasoning_time(), Some(Duration::from_millis(900)));
+    }
+
+    #[test]
+    fn test_completion_result_tool_calls() {
+        use std::time::Duration;
+
+        let event = |sequence: u64, kind: TokenKind, millis: u64| TokenEvent {
+            request_id: RequestId::new(),
+            sequence,
+            kind,
+            content: Some("{".to_string()),
+            timestamp_nanos: millis * 1_000_000,
+            time_since_start: Duration::from_millis(millis),
+            inter_token_latency: None,
+        };
+
+        let result = CompletionResult {
+            request_id: RequestId::new(),
+            content: "{".to_string(),
+            thinking: String::new(),
+            token_events: vec![
+                event(0, TokenKind::Content, 100),
+                event(1, TokenKind::ToolCall, 300),
+                event(2, TokenKind::ToolCall, 350),
+                event(3, TokenKind::ToolCall, 800),
+            ],
+            metadata: ResponseMetadata {
+                model: "test-model".to_string(),
+                input_tokens: None,
+                output_tokens: None,
+                thinking_tokens: None,
+                cache_creation_tokens: None,
+                cache_read_tokens: None,
+                estimated_cost: None,
+                headers: vec![],
+                server: ServerSignals::default(),
+            },
+            timing_checkpoints: vec![],
+            network: NetworkTimings::default(),
+            reads: ReadStats::default(),
+            retries: RetryStats::default(),
+            body: None,
+        };
+
+        assert_eq!(result.time_to_first_tool_call(), Some(Duration::from_millis(300)));
+        assert_eq!(result.tool_call_duration(), Some(Duration::from_millis(500)));
+    }
+
+    #[test]
+    fn test_tool_choice_from_str() {
+        assert_eq!(ToolChoice::from("auto"), ToolChoice::Auto);
+        assert_eq!(ToolChoice::from("none"), ToolChoice::None);
+        assert_eq!(ToolChoice::from("any"), ToolChoice::Required);
+        assert_eq!(
+            ToolChoice::from("get_weather"),
+            ToolChoice::Tool("get_weather".to_string())
+        );
+    }
+
+    #[test]
+    fn test_attachment_joins_last_user_message() {
+        let request = StreamingRequest::builder()
+            .model("gpt-4o")
+            .message(MessageRole::System, "Describe images")
+            .message(MessageRole::User, "What is this?")
+            .attachment(ContentPart::image_url("https://example.com/cat.png"))
+            .build();
+
+        assert_eq!(request.messages.len(), 2);
+        let user = &request.messages[1];
+        assert_eq!(user.content.len(), 2);
+        assert_eq!(user.text(), "What is this?");
+        assert!(!user.is_text_only());
+        assert!(request.messages[0].is_text_only());
+    }
+
+    #[test]
+    fn test_content_part_from_file() {
+        let path = std::env::temp_dir().join(format!("lens-attach-{}.PNG", std::process::id()));
+        std::fs::write(&path, b"png").unwrap();
+        let part = ContentPart::from_file(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            part,
+            ContentPart::Image {
+                media_type: "image/png".to_string(),
+                data: "cG5n".to_string(),
+            }
+        );
+        assert_eq!(part.to_url().unwrap(), "data:image/png;base64,cG5n");
+
+        assert_eq!(
+            media_type_for_path(std::path::Path::new("report.pdf")),
+            Some("application/pdf")
+        );
+        assert!(ContentPart::from_file("notes.txt").is_err());
+    }
+
+    #[test]
+    fn test_apply_shared_usage() {
+        let usage = SharedUsage::new();
+        let handle = usage.clone();
+        handle.update(|u| {
+            u.input_tokens = Some(12);
+            u.output_tokens = Some(34);
+        });
+
+        let mut metadata = ResponseMetadata {
+            model: "test-model".to_string(),
+            input_tokens: None,
+            output_tokens: None,
+            thinking_tokens: Some(5),
+            cache_creation_tokens: None,
+            cache_read_tokens: None,
+            estimated_cost: None,
+            headers: vec![],
+            server: ServerSignals::default(),
+        };
+        metadata.apply_usage(usage.get());
+
+        assert_eq!(metadata.input_tokens, Some(12));
+        assert_eq!(metadata.output_tokens, Some(34));
+        assert_eq!(metadata.thinking_tokens, Some(5));
+    }
 }
@@ -8,7 +8,7 @@ use chrono::Utc;
 use llm_latency_lens_core::{Provider, RequestId, SessionId};
 use llm_latency_lens_metrics::{
     AggregatedMetrics, CollectorConfig, LatencyDistribution, MetricsCollector, RequestMetrics,
-    ThroughputStats,
+    ThroughputStats, TokenCountSource,
 };
 use serde::{Deserialize, Serialize};
 use std::sync::{Arc, Mutex};
@@ -62,6 +62,7 @@ pub enum JsProvider {
     AwsBedrock,
     AzureOpenAI,
     Generic,
+    Exec,
 }
 
 impl From<JsProvider> for Provider {
@@ -73,6 +74,7 @@ impl From<JsProvider> for Provider {
             JsProvider::AwsBedrock => Provider::AwsBedrock,
             JsProvider::AzureOpenAI => Provider::AzureOpenAI,
             JsProvider::Generic => Provider::Generic,
+            JsProvider::Exec => Provider::Exec,
         }
     }
 }
@@ -86,6 +88,7 @@ impl From<Provider> for JsProvider {
             Provider::AwsBedrock => JsProvider::AwsBedrock,
             Provider::AzureOpenAI => JsProvider::AzureOpenAI,
             Provider::Generic => JsProvider::Generic,
+            Provider::Exec => JsProvider::Exec,
         }
     }
 }
@@ -261,7 +264,7 @@ impl LatencyCollector {
             .lock()
             .map_err(|e| WasmError::from(format!("Lock error: {}", e)))?;
 
-        let aggregated = MetricsAggregator::aggregate(&*collector)
+        let aggregated = MetricsAggregator::aggregate(&collector)
             .map_err(|e| WasmError::from(e.to_string()))?;
 
         let js_aggregated = JsAggregatedMetrics::from(aggregated);
@@ -287,6 +290,7 @@ impl LatencyCollector {
             "aws-bedrock" | "awsbedrock" => Provider::AwsBedrock,
             "azure-openai" | "azureopenai" => Provider::AzureOpenAI,
             "generic" => Provider::Generic,
+            "exec" => Provider::Exec,
             _ => return Err(WasmError::from("Invalid provider name")),
         };
 
@@ -295,7 +299,7 @@ impl LatencyCollector {
             .lock()
             .map_err(|e| WasmError::from(format!("Lock error: {}", e)))?;
 
-        let aggregated = MetricsAggregator::aggregate_by_provider(&*collector, provider_enum)
+        let aggregated = MetricsAggregator::aggregate_by_provider(&collector, provider_enum)
             .map_err(|e| WasmError::from(e.to_string()))?;
 
         let js_aggregated = JsAggregatedMetrics::from(aggregated);
@@ -319,7 +323,7 @@ impl LatencyCollector {
             .lock()
             .map_err(|e| WasmError::from(format!("Lock error: {}", e)))?;
 
-        let aggregated = MetricsAggregator::aggregate_by_model(&*collector, model)
+        let aggregated = MetricsAggregator::aggregate_by_model(&collector, model)
             .map_err(|e| WasmError::from(e.to_string()))?;
 
         let js_aggregated = JsAggregatedMetrics::from(aggregated);
@@ -358,6 +362,7 @@ impl JsRequestMetrics {
             "aws-bedrock" | "awsbedrock" => Provider::AwsBedrock,
             "azure-openai" | "azureopenai" => Provider::AzureOpenAI,
             "generic" => Provider::Generic,
+            "exec" => Provider::Exec,
             _ => return Err(WasmError::from("Invalid provider name")),
         };
 
@@ -377,6 +382,39 @@ impl JsRequestMetrics {
             input_tokens: self.input_tokens,
             output_tokens: self.output_tokens,
             thinking_tokens: self.thinking_tokens,
+            input_token_source: TokenCountSource::Reported,
+            output_token_source: TokenCountSource::Reported,
+            cache_creation_tokens: None,
+            cache_read_tokens: None,
+            cache_hit: None,
+            time_to_first_thinking_token: None,
+            thinking_duration: None,
+            time_to_first_visible_token: None,
+            reasoning_time: None,
+            time_to_first_tool_call: None,
+            tool_call_duration: None,
+            dns_time: None,
+            tcp_connect_time: None,
+            tls_handshake_time: None,
+            time_to_first_byte: None,
+            time_to_first_event: None,
+            session_setup_time: None,
+            response_ttft: None,
+            provider_request_id: None,
+            server_processing_time: None,
+            rate_limit_remaining_requests: None,
+            rate_limit_remaining_tokens: None,
+            attempts: None,
+            retry_backoff: None,
+            network_reads: None,
+            stream_events: None,
+            max_events_per_read: None,
+            response_body_bytes: None,
+            body_download_time: None,
+            body_parse_time: None,
+            embedding_inputs: None,
+            embedding_vectors: None,
+            embedding_dimensions: None,
             tokens_per_second: self.tokens_per_second,
             cost_usd: self.cost_usd,
             success: self.success,
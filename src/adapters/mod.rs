@@ -11,7 +11,7 @@ use super::benchmarks::{BenchmarkConfig, BenchmarkResult};
 use super::config::Config;
 use super::orchestrator::{Orchestrator, OrchestratorConfig};
 use llm_latency_lens_metrics::{MetricsAggregator, MetricsCollector};
-use llm_latency_lens_providers::{create_provider, MessageRole, StreamingRequest};
+use llm_latency_lens_providers::{MessageRole, Provider, ProviderRegistry, StreamingRequest};
 
 /// Canonical trait for benchmark targets
 ///
@@ -95,22 +95,20 @@ impl BenchTarget for LLMTarget {
         // Get provider configuration
         let provider_config = config.get_provider(&self.provider)?;
 
-        let api_key = provider_config
-            .api_key
-            .as_ref()
-            .ok_or_else(|| anyhow::anyhow!("API key not found for provider: {}", self.provider))?;
-
         // Create provider
-        let provider = Arc::new(create_provider(&self.provider, api_key.clone())?);
+        let provider: Arc<dyn Provider> = config.create_provider(&self.provider)?.into();
 
         // Build request template
-        let request_template = StreamingRequest::builder()
+        let mut builder = StreamingRequest::builder()
             .model(&self.model)
             .message(MessageRole::User, &bench_config.default_prompt)
             .max_tokens(bench_config.max_tokens)
             .temperature(bench_config.temperature)
-            .timeout_secs(bench_config.timeout_secs)
-            .build();
+            .timeout_secs(bench_config.timeout_secs);
+        if let Some(budget) = provider_config.effective_thinking_budget() {
+            builder = builder.thinking_budget(budget);
+        }
+        let request_template = builder.build();
 
         // Create orchestrator
         let orchestrator_config = OrchestratorConfig {
@@ -119,9 +117,14 @@ impl BenchTarget for LLMTarget {
             rate_limit: bench_config.rate_limit,
             show_progress: bench_config.show_progress,
             shutdown_timeout: std::time::Duration::from_secs(30),
+            streaming: true,
         };
 
-        let orchestrator = Orchestrator::new(orchestrator_config, Arc::clone(&shutdown_signal));
+        let mut orchestrator = Orchestrator::new(orchestrator_config, Arc::clone(&shutdown_signal))
+            .with_pricing(config.pricing.table()?, config.pricing.mode());
+        if let Some(tokenizer) = config.tokenizer(&self.provider)? {
+            orchestrator = orchestrator.with_tokenizer(tokenizer);
+        }
         let session_id = orchestrator.session_id();
 
         // Create metrics collector
@@ -135,6 +138,7 @@ impl BenchTarget for LLMTarget {
                 rate_limit: bench_config.rate_limit,
                 show_progress: false,
                 shutdown_timeout: std::time::Duration::from_secs(30),
+                streaming: true,
             };
 
             let warmup_orchestrator =
@@ -175,17 +179,26 @@ impl BenchTarget for LLMTarget {
 /// A vector of boxed BenchTarget implementations
 pub fn all_targets(config: &Config) -> Vec<Box<dyn BenchTarget>> {
     let mut targets: Vec<Box<dyn BenchTarget>> = Vec::new();
+    let registry = ProviderRegistry::builtin();
 
     // Add targets based on configured providers
     for (provider_name, provider_config) in config.providers.iter() {
-        // Only add targets for providers with API keys configured
-        if provider_config.api_key.is_some() {
-            // Get default or recommended models for each provider
-            let models = get_default_models(provider_name);
-
-            for model in models {
-                targets.push(Box::new(LLMTarget::new(provider_name.clone(), model)));
-            }
+        let provider_type = provider_config.provider_type(provider_name);
+
+        // Skip providers missing an API key they need
+        if provider_config.api_key.is_none() && registry.requires_api_key(provider_type) {
+            continue;
+        }
+
+        // Get recommended models for the provider, falling back to the
+        // instance's default model
+        let mut models = get_default_models(provider_type);
+        if models.is_empty() {
+            models.extend(provider_config.default_model.clone());
+        }
+
+        for model in models {
+            targets.push(Box::new(LLMTarget::new(provider_name.clone(), model)));
         }
     }
 
@@ -229,6 +242,7 @@ pub fn create_targets(target_strings: &[String]) -> Vec<Box<dyn BenchTarget>> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::ProviderConfig;
 
     #[test]
     fn test_llm_target_creation() {
@@ -247,6 +261,15 @@ mod tests {
         assert_eq!(target.model(), "claude-3-5-sonnet-20241022");
     }
 
+    #[test]
+    fn test_llm_target_from_generic_string() {
+        let target = LLMTarget::from_target_string("generic:llama3.1:8b").unwrap();
+
+        assert_eq!(target.provider(), "generic");
+        assert_eq!(target.model(), "llama3.1:8b");
+        assert_eq!(target.id(), "generic:llama3.1:8b");
+    }
+
     #[test]
     fn test_llm_target_from_invalid_string() {
         assert!(LLMTarget::from_target_string("invalid").is_none());
@@ -264,6 +287,31 @@ mod tests {
         assert!(unknown_models.is_empty());
     }
 
+    #[test]
+    fn test_all_targets() {
+        let mut config = Config::default();
+        config
+            .providers
+            .insert("openai".to_string(), ProviderConfig::with_api_key("sk-test"));
+        config
+            .providers
+            .insert("anthropic".to_string(), ProviderConfig::default());
+        config.providers.insert(
+            "gateway".to_string(),
+            ProviderConfig {
+                provider_type: Some("generic".to_string()),
+                default_model: Some("llama3.1:8b".to_string()),
+                ..ProviderConfig::default()
+            },
+        );
+
+        let ids: Vec<String> = all_targets(&config).iter().map(|t| t.id()).collect();
+
+        assert!(ids.contains(&"openai:gpt-4o".to_string()));
+        assert!(ids.contains(&"gateway:llama3.1:8b".to_string()));
+        assert!(!ids.iter().any(|id| id.starts_with("anthropic:")));
+    }
+
     #[test]
     fn test_create_target() {
         let target = create_target("openai:gpt-4o").unwrap();
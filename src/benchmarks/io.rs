@@ -105,7 +105,7 @@ pub fn read_all_results() -> Result<Vec<BenchmarkResult>> {
     }
 
     // Sort by timestamp (newest first)
-    results.sort_by(|a, b| b.timestamp().cmp(&a.timestamp()));
+    results.sort_by_key(|r| std::cmp::Reverse(r.timestamp()));
 
     Ok(results)
 }
@@ -128,7 +128,7 @@ pub fn cleanup_old_results(keep_per_target: usize) -> Result<usize> {
 
     for (target_id, mut target_results) in by_target {
         // Sort by timestamp (newest first)
-        target_results.sort_by(|a, b| b.timestamp().cmp(&a.timestamp()));
+        target_results.sort_by_key(|r| std::cmp::Reverse(r.timestamp()));
 
         // Delete results beyond the keep limit
         for result in target_results.into_iter().skip(keep_per_target) {
@@ -177,11 +177,6 @@ pub fn read_summary() -> Result<Option<String>> {
 mod tests {
     use super::*;
     use serde_json::json;
-    use tempfile::TempDir;
-
-    fn setup_test_dir() -> TempDir {
-        tempfile::tempdir().expect("Failed to create temp dir")
-    }
 
     #[test]
     fn test_output_paths() {
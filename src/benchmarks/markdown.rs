@@ -3,7 +3,7 @@
 //! This module provides utilities for generating human-readable markdown
 //! summaries of benchmark results.
 
-use chrono::{DateTime, Utc};
+use chrono::Utc;
 use std::fmt::Write as FmtWrite;
 
 use super::result::BenchmarkResult;
@@ -23,7 +23,7 @@ use anyhow::{Context, Result};
 use std::sync::Arc;
 use tracing::info;
 
-use super::adapters::{all_targets, BenchTarget};
+use super::adapters::all_targets;
 use super::config::Config;
 
 /// Configuration for running all benchmarks
@@ -5,6 +5,7 @@
 //! benchmark-target repositories.
 
 use chrono::{DateTime, Utc};
+use llm_latency_lens_providers::{PriceTable, PricingMode, TokenUsage};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
@@ -107,6 +108,40 @@ impl BenchmarkResult {
         }
         Some((successful as f64 / total as f64) * 100.0)
     }
+
+    /// Re-cost the benchmark against a price table
+    ///
+    /// Prices the token totals in `metrics` with the prices that were in
+    /// effect at the result's timestamp, and stores the new cost in
+    /// `total_cost_usd`. Each successful request is priced at the average
+    /// usage, so tiered rates are chosen by the average prompt length.
+    ///
+    /// Returns None, leaving the metrics unchanged, when the target ID is
+    /// not `provider:model`, the result has no successful requests, or the
+    /// table has no price for the model.
+    pub fn recost(&mut self, table: &PriceTable, mode: PricingMode) -> Option<f64> {
+        let (provider, model) = self.target_id.split_once(':')?;
+        let requests = self.metrics.get("successful_requests")?.as_u64()?;
+        if requests == 0 {
+            return None;
+        }
+
+        let average = |key: &str| {
+            let total = self.metrics.get(key)?.as_u64()?;
+            Some((total + requests / 2) / requests)
+        };
+        let usage = TokenUsage {
+            input_tokens: average("total_input_tokens"),
+            output_tokens: average("total_output_tokens"),
+            thinking_tokens: average("total_thinking_tokens"),
+            cache_creation_tokens: average("total_cache_creation_tokens"),
+            cache_read_tokens: average("total_cache_read_tokens"),
+        };
+
+        let cost = table.cost(provider, model, &usage, self.timestamp, mode)? * requests as f64;
+        self.metrics.as_object_mut()?.insert("total_cost_usd".to_string(), cost.into());
+        Some(cost)
+    }
 }
 
 #[cfg(test)]
@@ -156,6 +191,34 @@ mod tests {
         assert!(result.get_metric("nonexistent").is_none());
     }
 
+    #[test]
+    fn test_recost_uses_prices_at_timestamp() {
+        let metrics = json!({
+            "total_requests": 10,
+            "successful_requests": 10,
+            "total_input_tokens": 10_000_000,
+            "total_output_tokens": 1_000_000,
+            "total_cost_usd": null
+        });
+        let table = PriceTable::bundled();
+
+        // gpt-4o was repriced from $5/$15 to $2.50/$10 on 2024-10-02
+        let at = "2024-09-01T00:00:00Z".parse().unwrap();
+        let mut result = BenchmarkResult::with_timestamp("openai:gpt-4o", metrics.clone(), at);
+        let cost = result.recost(table, PricingMode::Standard).unwrap();
+        assert!((cost - 65.0).abs() < 1e-9);
+        assert_eq!(result.get_metric("total_cost_usd"), Some(&json!(cost)));
+
+        let at = "2024-11-01T00:00:00Z".parse().unwrap();
+        let mut result = BenchmarkResult::with_timestamp("openai:gpt-4o", metrics.clone(), at);
+        let cost = result.recost(table, PricingMode::Batch).unwrap();
+        assert!((cost - 17.5).abs() < 1e-9);
+
+        let mut result = BenchmarkResult::new("openai:unknown-model", metrics);
+        assert!(result.recost(table, PricingMode::Standard).is_none());
+        assert_eq!(result.get_metric("total_cost_usd"), Some(&Value::Null));
+    }
+
     #[test]
     fn test_serialization() {
         let metrics = json!({"test": "value"});
@@ -1,6 +1,7 @@
 //! CLI argument parsing and command definitions
 
 use clap::{Parser, Subcommand};
+use llm_latency_lens::mock_server::{Distribution, DEFAULT_MODEL};
 use std::path::PathBuf;
 
 pub mod commands;
@@ -48,14 +49,26 @@ pub enum Commands {
     #[command(visible_alias = "comp")]
     Compare(CompareArgs),
 
+    /// Benchmark an embeddings endpoint
+    #[command(visible_alias = "emb")]
+    Embed(EmbedArgs),
+
     /// Validate API credentials and connectivity
     #[command(visible_alias = "val")]
     Validate(ValidateArgs),
 
+    /// List the models available from a provider
+    #[command(visible_alias = "ls")]
+    Models(ModelsArgs),
+
     /// Export metrics to different formats
     #[command(visible_alias = "exp")]
     Export(ExportArgs),
 
+    /// Serve mock OpenAI, Anthropic and Gemini endpoints for offline benchmarking
+    #[command(visible_alias = "mock")]
+    MockServer(MockServerArgs),
+
     /// Run all benchmarks for configured targets (canonical interface)
     #[command(visible_alias = "r")]
     Run(RunArgs),
@@ -104,6 +117,31 @@ pub struct ProfileArgs {
     #[arg(long, default_value = "120")]
     pub timeout: u64,
 
+    /// Extended thinking budget in tokens (Claude; overrides config)
+    #[arg(long)]
+    pub thinking_budget: Option<u32>,
+
+    /// JSON file with tool definitions (array of {name, description, parameters})
+    #[arg(long)]
+    pub tools: Option<PathBuf>,
+
+    /// Tool choice: auto, none, required, or a tool name
+    #[arg(long, requires = "tools")]
+    pub tool_choice: Option<String>,
+
+    /// Attach an image (png, jpg, gif, webp) or PDF file, or an image URL (repeatable)
+    #[arg(long = "attach", value_name = "FILE|URL")]
+    pub attachments: Vec<String>,
+
+    /// Mark the prompt as cacheable (Anthropic `cache_control`; OpenAI caches automatically)
+    #[arg(long)]
+    pub cache_prompt: bool,
+
+    /// Redact a response header in recorded output, on top of the default
+    /// credential and cookie headers (repeatable)
+    #[arg(long = "redact-header", value_name = "NAME")]
+    pub redact_headers: Vec<String>,
+
     /// Configuration file path
     #[arg(short, long)]
     pub config: Option<PathBuf>,
@@ -115,6 +153,11 @@ pub struct ProfileArgs {
     /// Show streaming output
     #[arg(long)]
     pub stream: bool,
+
+    /// Send a non-streaming (`stream: false`) request and time the complete
+    /// response body instead of the token stream
+    #[arg(long, conflicts_with = "stream")]
+    pub no_stream: bool,
 }
 
 /// Arguments for the benchmark command
@@ -172,8 +215,38 @@ pub struct BenchmarkArgs {
     #[arg(long, default_value = "120")]
     pub timeout: u64,
 
+    /// Extended thinking budget in tokens (Claude; overrides config)
+    #[arg(long)]
+    pub thinking_budget: Option<u32>,
+
+    /// JSON file with tool definitions (array of {name, description, parameters})
+    #[arg(long)]
+    pub tools: Option<PathBuf>,
+
+    /// Tool choice: auto, none, required, or a tool name
+    #[arg(long, requires = "tools")]
+    pub tool_choice: Option<String>,
+
+    /// Attach an image (png, jpg, gif, webp) or PDF file, or an image URL (repeatable)
+    #[arg(long = "attach", value_name = "FILE|URL")]
+    pub attachments: Vec<String>,
+
+    /// Mark the prompt as cacheable (Anthropic `cache_control`; OpenAI caches automatically)
+    #[arg(long)]
+    pub cache_prompt: bool,
+
+    /// Redact a response header in recorded output, on top of the default
+    /// credential and cookie headers (repeatable)
+    #[arg(long = "redact-header", value_name = "NAME")]
+    pub redact_headers: Vec<String>,
+
+    /// Vocabulary file for counting tokens locally when the provider reports
+    /// no usage (tiktoken BPE, or SentencePiece `.vocab`)
+    #[arg(long, value_name = "FILE")]
+    pub tokenizer: Option<PathBuf>,
+
     /// Configuration file path
-    #[arg(short, long)]
+    #[arg(long)]
     pub config: Option<PathBuf>,
 
     /// Output file for results
@@ -187,6 +260,10 @@ pub struct BenchmarkArgs {
     /// Show live progress
     #[arg(long, default_value = "true")]
     pub progress: bool,
+
+    /// Send non-streaming (`stream: false`) requests
+    #[arg(long)]
+    pub no_stream: bool,
 }
 
 /// Arguments for the compare command
@@ -235,6 +312,85 @@ pub struct CompareArgs {
     /// Metrics to compare (ttft, total, throughput, cost)
     #[arg(long, value_delimiter = ',', default_values = ["ttft", "total", "throughput"])]
     pub metrics: Vec<String>,
+
+    /// Response modes to run for each target (stream, no-stream); with both,
+    /// results are shown side by side
+    #[arg(
+        long,
+        value_delimiter = ',',
+        default_values = ["stream"],
+        value_parser = ["stream", "no-stream"]
+    )]
+    pub modes: Vec<String>,
+}
+
+/// Arguments for the embed command
+#[derive(Parser, Debug)]
+pub struct EmbedArgs {
+    /// Provider to use (openai, google, generic)
+    #[arg(short, long, env = "LLM_PROVIDER")]
+    pub provider: String,
+
+    /// Embedding model (e.g., text-embedding-3-small, text-embedding-004)
+    #[arg(short, long, env = "LLM_MODEL")]
+    pub model: String,
+
+    /// Input text (repeatable; inputs are cycled to fill each batch)
+    #[arg(short = 'P', long = "input", value_name = "TEXT")]
+    pub inputs: Vec<String>,
+
+    /// File with one input per line
+    #[arg(short = 'f', long, conflicts_with = "inputs")]
+    pub input_file: Option<PathBuf>,
+
+    /// Length in words of generated inputs, when no input is given
+    #[arg(long, default_value = "64")]
+    pub input_length: usize,
+
+    /// Inputs sent in each request
+    #[arg(short, long, default_value = "1")]
+    pub batch_size: usize,
+
+    /// Requested vector size, for models that can shorten their output
+    #[arg(long)]
+    pub dimensions: Option<u32>,
+
+    /// API key
+    #[arg(short = 'k', long, env = "LLM_API_KEY")]
+    pub api_key: Option<String>,
+
+    /// API endpoint URL
+    #[arg(short, long)]
+    pub endpoint: Option<String>,
+
+    /// Number of requests to run
+    #[arg(short, long, default_value = "10")]
+    pub requests: u32,
+
+    /// Number of concurrent requests
+    #[arg(short, long, default_value = "1")]
+    pub concurrency: u32,
+
+    /// Rate limit (requests per second, 0 = unlimited)
+    #[arg(long, default_value = "0")]
+    pub rate_limit: u32,
+
+    /// Redact a response header in recorded output, on top of the default
+    /// credential and cookie headers (repeatable)
+    #[arg(long = "redact-header", value_name = "NAME")]
+    pub redact_headers: Vec<String>,
+
+    /// Configuration file path
+    #[arg(long)]
+    pub config: Option<PathBuf>,
+
+    /// Output file for results
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+
+    /// Show live progress
+    #[arg(long, default_value = "true")]
+    pub progress: bool,
 }
 
 /// Arguments for the validate command
@@ -261,6 +417,30 @@ pub struct ValidateArgs {
     pub test_request: bool,
 }
 
+/// Arguments for the models command
+#[derive(Parser, Debug)]
+pub struct ModelsArgs {
+    /// Provider to list models for
+    #[arg(short, long, env = "LLM_PROVIDER")]
+    pub provider: String,
+
+    /// API key (can also use environment variables)
+    #[arg(short = 'k', long, env = "LLM_API_KEY")]
+    pub api_key: Option<String>,
+
+    /// API endpoint URL (optional, uses provider default)
+    #[arg(short, long)]
+    pub endpoint: Option<String>,
+
+    /// Configuration file path
+    #[arg(short, long)]
+    pub config: Option<PathBuf>,
+
+    /// Print the built-in model list without querying the provider
+    #[arg(long = "static")]
+    pub static_only: bool,
+}
+
 /// Arguments for the export command
 #[derive(Parser, Debug)]
 pub struct ExportArgs {
@@ -281,6 +461,55 @@ pub struct ExportArgs {
     pub pretty: bool,
 }
 
+/// Arguments for the mock-server command
+#[derive(Parser, Debug)]
+pub struct MockServerArgs {
+    /// Address to listen on
+    #[arg(long, default_value = "127.0.0.1")]
+    pub host: String,
+
+    /// Port to listen on (0 picks a free port)
+    #[arg(short, long, default_value = "8080")]
+    pub port: u16,
+
+    /// Time to first token in milliseconds: a number, or
+    /// uniform:MIN,MAX, normal:MEAN,STD_DEV or lognormal:MEDIAN,SIGMA
+    #[arg(long, default_value = "200")]
+    pub ttft: Distribution,
+
+    /// Inter-token latency in milliseconds (same forms as --ttft)
+    #[arg(long, default_value = "20")]
+    pub itl: Distribution,
+
+    /// Output length in tokens (same forms as --ttft), capped by max tokens
+    #[arg(long, default_value = "100")]
+    pub output_tokens: Distribution,
+
+    /// Fraction of requests failed with a 500
+    #[arg(long, default_value = "0")]
+    pub error_rate: f64,
+
+    /// Fraction of requests rejected with a 429
+    #[arg(long, default_value = "0")]
+    pub rate_limit_rate: f64,
+
+    /// Fraction of streams cut off midway
+    #[arg(long, default_value = "0")]
+    pub disconnect_rate: f64,
+
+    /// Retry-After seconds sent with 429 responses
+    #[arg(long, default_value = "1")]
+    pub retry_after: u64,
+
+    /// Model listed by the models endpoints (repeatable)
+    #[arg(long = "model", default_value = DEFAULT_MODEL)]
+    pub models: Vec<String>,
+
+    /// Seed for reproducible latencies and failures
+    #[arg(long)]
+    pub seed: Option<u64>,
+}
+
 /// Arguments for the run command (canonical benchmark interface)
 #[derive(Parser, Debug)]
 pub struct RunArgs {
@@ -359,7 +588,7 @@ mod tests {
 
     #[test]
     fn test_profile_args() {
-        let args = Cli::parse_from(&[
+        let args = Cli::parse_from([
             "llm-latency-lens",
             "profile",
             "--provider",
@@ -381,7 +610,7 @@ mod tests {
 
     #[test]
     fn test_benchmark_args() {
-        let args = Cli::parse_from(&[
+        let args = Cli::parse_from([
             "llm-latency-lens",
             "benchmark",
             "--provider",
@@ -408,7 +637,7 @@ mod tests {
 
     #[test]
     fn test_compare_args() {
-        let args = Cli::parse_from(&[
+        let args = Cli::parse_from([
             "llm-latency-lens",
             "compare",
             "openai:gpt-4o",
@@ -426,9 +655,83 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_models_args() {
+        let args = Cli::parse_from(["llm-latency-lens", "models", "-p", "anthropic", "--static"]);
+
+        if let Commands::Models(models) = args.command {
+            assert_eq!(models.provider, "anthropic");
+            assert!(models.static_only);
+        } else {
+            panic!("Expected Models command");
+        }
+    }
+
+    #[test]
+    fn test_mock_server_args() {
+        let args = Cli::parse_from([
+            "llm-latency-lens",
+            "mock-server",
+            "--port",
+            "0",
+            "--ttft",
+            "lognormal:300,0.5",
+            "--rate-limit-rate",
+            "0.1",
+            "--model",
+            "gpt-4o-mini",
+            "--model",
+            "claude-3-5-haiku-latest",
+        ]);
+
+        if let Commands::MockServer(mock) = args.command {
+            assert_eq!(mock.port, 0);
+            assert_eq!(
+                mock.ttft,
+                Distribution::LogNormal { median: 300.0, sigma: 0.5 }
+            );
+            assert_eq!(mock.itl, Distribution::fixed(20.0));
+            assert_eq!(mock.rate_limit_rate, 0.1);
+            assert_eq!(mock.models, vec!["gpt-4o-mini", "claude-3-5-haiku-latest"]);
+        } else {
+            panic!("Expected MockServer command");
+        }
+
+        assert!(Cli::try_parse_from(["llm-latency-lens", "mock", "--itl", "gamma:1,2"]).is_err());
+    }
+
+    #[test]
+    fn test_embed_args() {
+        let args = Cli::parse_from([
+            "llm-latency-lens",
+            "embed",
+            "--provider",
+            "openai",
+            "--model",
+            "text-embedding-3-small",
+            "--batch-size",
+            "32",
+            "--input-length",
+            "128",
+            "--dimensions",
+            "256",
+        ]);
+
+        if let Commands::Embed(embed) = args.command {
+            assert_eq!(embed.provider, "openai");
+            assert_eq!(embed.model, "text-embedding-3-small");
+            assert_eq!(embed.batch_size, 32);
+            assert_eq!(embed.input_length, 128);
+            assert_eq!(embed.dimensions, Some(256));
+            assert!(embed.inputs.is_empty());
+        } else {
+            panic!("Expected Embed command");
+        }
+    }
+
     #[test]
     fn test_global_flags() {
-        let args = Cli::parse_from(&[
+        let args = Cli::parse_from([
             "llm-latency-lens",
             "--json",
             "--quiet",
@@ -441,7 +744,7 @@ mod tests {
 
     #[test]
     fn test_verbose_flag() {
-        let args = Cli::parse_from(&[
+        let args = Cli::parse_from([
             "llm-latency-lens",
             "-vvv",
             "validate",
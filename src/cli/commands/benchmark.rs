@@ -11,9 +11,9 @@ use crate::config::Config;
 use crate::orchestrator::{Orchestrator, OrchestratorConfig};
 use llm_latency_lens_exporters::{Exporter, JsonExporter};
 use llm_latency_lens_metrics::{MetricsAggregator, MetricsCollector};
-use llm_latency_lens_providers::{create_provider, MessageRole, StreamingRequest};
+use llm_latency_lens_providers::{MessageRole, Provider, StreamingRequest, ToolChoice};
 
-use super::{read_prompt, write_output};
+use super::{read_attachments, read_prompt, read_tools, write_output};
 
 /// Run the benchmark command
 pub async fn run(
@@ -27,6 +27,9 @@ pub async fn run(
 
     // Merge CLI overrides
     config.merge_cli_overrides(&args.provider, args.api_key.clone(), args.endpoint.clone());
+    if let Some(ref path) = args.tokenizer {
+        config.get_or_create_provider(&args.provider).tokenizer = Some(path.clone());
+    }
 
     // Validate configuration
     config.validate().with_context(|| "Configuration validation failed")?;
@@ -34,16 +37,8 @@ pub async fn run(
     // Get provider configuration
     let provider_config = config.get_provider(&args.provider)?;
 
-    let api_key = provider_config
-        .api_key
-        .as_ref()
-        .context("API key not found for provider")?;
-
     // Create provider
-    let provider = Arc::new(
-        create_provider(&args.provider, api_key.clone())
-            .with_context(|| format!("Failed to create provider: {}", args.provider))?
-    );
+    let provider: Arc<dyn Provider> = config.create_provider(&args.provider)?.into();
 
     // Read prompt
     let prompt = read_prompt(&args.prompt, &args.prompt_file)
@@ -68,14 +63,35 @@ pub async fn run(
     }
 
     // Build request template
-    let request_template = StreamingRequest::builder()
+    let mut builder = StreamingRequest::builder()
         .model(args.model.clone())
         .message(MessageRole::User, prompt)
         .max_tokens(args.max_tokens)
         .temperature(args.temperature.unwrap_or(0.7))
-        .top_p(args.top_p)
-        .timeout_secs(args.timeout)
-        .build();
+        .timeout_secs(args.timeout);
+    if let Some(top_p) = args.top_p {
+        builder = builder.top_p(top_p);
+    }
+    if let Some(budget) = args
+        .thinking_budget
+        .or_else(|| provider_config.effective_thinking_budget())
+    {
+        builder = builder.thinking_budget(budget);
+    }
+    builder = builder.tools(read_tools(&args.tools).context("Failed to read tools")?);
+    if let Some(ref choice) = args.tool_choice {
+        builder = builder.tool_choice(ToolChoice::from(choice.as_str()));
+    }
+    for part in read_attachments(&args.attachments)? {
+        builder = builder.attachment(part);
+    }
+    if args.cache_prompt {
+        builder = builder.cache_breakpoint();
+    }
+    for name in &args.redact_headers {
+        builder = builder.redact_header(name);
+    }
+    let request_template = builder.build();
 
     // Create orchestrator
     let orchestrator_config = OrchestratorConfig {
@@ -84,9 +100,15 @@ pub async fn run(
         rate_limit: args.rate_limit,
         show_progress: args.progress && !quiet && !json_output,
         shutdown_timeout: std::time::Duration::from_secs(30),
+        streaming: !args.no_stream,
     };
 
-    let orchestrator = Orchestrator::new(orchestrator_config, shutdown_signal);
+    let price_table = config.pricing.table().context("Failed to load price table")?;
+    let mut orchestrator = Orchestrator::new(orchestrator_config, Arc::clone(&shutdown_signal))
+        .with_pricing(price_table, config.pricing.mode());
+    if let Some(tokenizer) = config.tokenizer(&args.provider)? {
+        orchestrator = orchestrator.with_tokenizer(tokenizer);
+    }
     let session_id = orchestrator.session_id();
 
     // Create metrics collector
@@ -109,12 +131,10 @@ pub async fn run(
             rate_limit: args.rate_limit,
             show_progress: false,
             shutdown_timeout: std::time::Duration::from_secs(30),
+            streaming: !args.no_stream,
         };
 
-        let warmup_orchestrator = Orchestrator::new(
-            warmup_config,
-            Arc::clone(&orchestrator.shutdown_signal),
-        );
+        let warmup_orchestrator = Orchestrator::new(warmup_config, Arc::clone(&shutdown_signal));
 
         let warmup_collector = Arc::new(
             MetricsCollector::with_defaults(warmup_orchestrator.session_id())?
@@ -260,6 +280,12 @@ pub async fn run(
             ];
 
             println!("{}", Table::new(throughput_rows));
+            if aggregated.estimated_token_requests > 0 {
+                println!(
+                    "   {} of {} requests had no reported usage; their token counts are estimated",
+                    aggregated.estimated_token_requests, aggregated.successful_requests
+                );
+            }
             println!();
 
             // Cost summary
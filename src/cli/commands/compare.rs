@@ -10,14 +10,14 @@ use crate::cli::CompareArgs;
 use crate::config::Config;
 use crate::orchestrator::{Orchestrator, OrchestratorConfig};
 use llm_latency_lens_metrics::{AggregatedMetrics, MetricsAggregator, MetricsCollector};
-use llm_latency_lens_providers::{create_provider, MessageRole, StreamingRequest};
+use llm_latency_lens_providers::{MessageRole, Provider, StreamingRequest};
 
 use super::{read_prompt, write_output};
 
 /// Run the compare command
 pub async fn run(
     args: CompareArgs,
-    mut config: Config,
+    config: Config,
     json_output: bool,
     quiet: bool,
     shutdown_signal: Arc<tokio::sync::Notify>,
@@ -32,14 +32,17 @@ pub async fn run(
         .targets
         .iter()
         .map(|t| {
-            let parts: Vec<&str> = t.split(':').collect();
-            if parts.len() != 2 {
-                anyhow::bail!(
+            // Split on the first colon only; model names may contain colons
+            // (e.g. 'generic:llama3.1:8b')
+            match t.split_once(':') {
+                Some((provider, model)) if !provider.is_empty() && !model.is_empty() => {
+                    Ok((provider.to_string(), model.to_string()))
+                }
+                _ => anyhow::bail!(
                     "Invalid target format '{}'. Expected 'provider:model' (e.g., 'openai:gpt-4o')",
                     t
-                );
+                ),
             }
-            Ok((parts[0].to_string(), parts[1].to_string()))
         })
         .collect();
 
@@ -63,11 +66,17 @@ pub async fn run(
             );
         }
         println!("   {} requests per configuration", args.requests);
+        if args.modes.len() > 1 {
+            println!("   Modes: {}", args.modes.join(", "));
+        }
         println!();
     }
 
-    // Results for each target
-    let mut results: Vec<(String, String, AggregatedMetrics)> = Vec::new();
+    // Results for each target and response mode
+    let mut results: Vec<(String, String, String, AggregatedMetrics)> = Vec::new();
+    let show_mode = args.modes.len() > 1;
+
+    let price_table = config.pricing.table().context("Failed to load price table")?;
 
     // Run benchmarks for each target
     for (provider_name, model) in &targets {
@@ -80,59 +89,59 @@ pub async fn run(
             );
         }
 
-        // Get provider configuration
-        let provider_config = config.get_provider(provider_name)
-            .with_context(|| format!("Provider '{}' not configured", provider_name))?;
-
-        let api_key = provider_config
-            .api_key
-            .as_ref()
-            .context("API key not found for provider")?;
-
         // Create provider
-        let provider = Arc::new(
-            create_provider(provider_name, api_key.clone())
-                .with_context(|| format!("Failed to create provider: {}", provider_name))?
-        );
+        let provider: Arc<dyn Provider> = config.create_provider(provider_name)?.into();
+        let tokenizer = config.tokenizer(provider_name)?;
 
         // Build request template
-        let request_template = StreamingRequest::builder()
+        let mut builder = StreamingRequest::builder()
             .model(model.clone())
             .message(MessageRole::User, prompt.clone())
             .max_tokens(args.max_tokens)
             .temperature(args.temperature.unwrap_or(0.7))
-            .top_p(args.top_p)
-            .timeout_secs(args.timeout)
-            .build();
-
-        // Create orchestrator
-        let orchestrator_config = OrchestratorConfig {
-            concurrency: 1, // Sequential for fair comparison
-            total_requests: args.requests,
-            rate_limit: 0,
-            show_progress: !quiet && !json_output,
-            shutdown_timeout: std::time::Duration::from_secs(30),
-        };
-
-        let orchestrator = Orchestrator::new(orchestrator_config, Arc::clone(&shutdown_signal));
-        let session_id = orchestrator.session_id();
+            .timeout_secs(args.timeout);
+        if let Some(top_p) = args.top_p {
+            builder = builder.top_p(top_p);
+        }
+        let request_template = builder.build();
+
+        // Run each response mode against the same provider
+        for mode in &args.modes {
+            // Create orchestrator
+            let orchestrator_config = OrchestratorConfig {
+                concurrency: 1, // Sequential for fair comparison
+                total_requests: args.requests,
+                rate_limit: 0,
+                show_progress: !quiet && !json_output,
+                shutdown_timeout: std::time::Duration::from_secs(30),
+                streaming: mode == "stream",
+            };
+
+            let mut orchestrator =
+                Orchestrator::new(orchestrator_config, Arc::clone(&shutdown_signal))
+                    .with_pricing(price_table.clone(), config.pricing.mode());
+            if let Some(ref tokenizer) = tokenizer {
+                orchestrator = orchestrator.with_tokenizer(Arc::clone(tokenizer));
+            }
+            let session_id = orchestrator.session_id();
 
-        // Create metrics collector
-        let collector = Arc::new(
-            MetricsCollector::with_defaults(session_id)
-                .context("Failed to create metrics collector")?
-        );
+            // Create metrics collector
+            let collector = Arc::new(
+                MetricsCollector::with_defaults(session_id)
+                    .context("Failed to create metrics collector")?
+            );
 
-        // Execute benchmark
-        let _summary = orchestrator
-            .execute(provider, request_template, Arc::clone(&collector))
-            .await?;
+            // Execute benchmark
+            let _summary = orchestrator
+                .execute(Arc::clone(&provider), request_template.clone(), Arc::clone(&collector))
+                .await?;
 
-        // Aggregate metrics
-        let aggregated = MetricsAggregator::aggregate(&collector)
-            .context("Failed to aggregate metrics")?;
+            // Aggregate metrics
+            let aggregated = MetricsAggregator::aggregate(&collector)
+                .context("Failed to aggregate metrics")?;
 
-        results.push((provider_name.clone(), model.clone(), aggregated));
+            results.push((provider_name.clone(), model.clone(), mode.clone(), aggregated));
+        }
 
         if !quiet {
             println!("{} Complete\n", "✓".bright_green());
@@ -143,10 +152,11 @@ pub async fn run(
     if json_output {
         let json_data: Vec<_> = results
             .iter()
-            .map(|(provider, model, metrics)| {
+            .map(|(provider, model, mode, metrics)| {
                 serde_json::json!({
                     "provider": provider,
                     "model": model,
+                    "mode": mode,
                     "metrics": {
                         "ttft": {
                             "mean_ms": metrics.ttft_distribution.mean.as_secs_f64() * 1000.0,
@@ -204,8 +214,8 @@ pub async fn run(
 
                 let ttft_rows: Vec<_> = results
                     .iter()
-                    .map(|(provider, model, metrics)| TtftRow {
-                        target: format!("{}:{}", provider, model),
+                    .map(|(provider, model, mode, metrics)| TtftRow {
+                        target: target_label(provider, model, mode, show_mode),
                         mean: format!("{:.2}", metrics.ttft_distribution.mean.as_secs_f64() * 1000.0),
                         p50: format!("{:.2}", metrics.ttft_distribution.p50.as_secs_f64() * 1000.0),
                         p95: format!("{:.2}", metrics.ttft_distribution.p95.as_secs_f64() * 1000.0),
@@ -236,8 +246,8 @@ pub async fn run(
 
                 let latency_rows: Vec<_> = results
                     .iter()
-                    .map(|(provider, model, metrics)| LatencyRow {
-                        target: format!("{}:{}", provider, model),
+                    .map(|(provider, model, mode, metrics)| LatencyRow {
+                        target: target_label(provider, model, mode, show_mode),
                         mean: format!("{:.2}", metrics.total_latency_distribution.mean.as_secs_f64() * 1000.0),
                         p50: format!("{:.2}", metrics.total_latency_distribution.p50.as_secs_f64() * 1000.0),
                         p95: format!("{:.2}", metrics.total_latency_distribution.p95.as_secs_f64() * 1000.0),
@@ -267,8 +277,8 @@ pub async fn run(
 
                 let throughput_rows: Vec<_> = results
                     .iter()
-                    .map(|(provider, model, metrics)| ThroughputRow {
-                        target: format!("{}:{}", provider, model),
+                    .map(|(provider, model, mode, metrics)| ThroughputRow {
+                        target: target_label(provider, model, mode, show_mode),
                         mean: format!("{:.2}", metrics.throughput.mean_tokens_per_second),
                         p50: format!("{:.2}", metrics.throughput.p50_tokens_per_second),
                         p95: format!("{:.2}", metrics.throughput.p95_tokens_per_second),
@@ -296,8 +306,8 @@ pub async fn run(
 
                 let cost_rows: Vec<_> = results
                     .iter()
-                    .map(|(provider, model, metrics)| CostRow {
-                        target: format!("{}:{}", provider, model),
+                    .map(|(provider, model, mode, metrics)| CostRow {
+                        target: target_label(provider, model, mode, show_mode),
                         total: metrics
                             .total_cost_usd
                             .map(|c| format!("${:.6}", c))
@@ -316,33 +326,43 @@ pub async fn run(
             // Winner analysis
             let fastest_ttft = results
                 .iter()
-                .min_by(|(_, _, a), (_, _, b)| {
+                .min_by(|(_, _, _, a), (_, _, _, b)| {
                     a.ttft_distribution.mean.cmp(&b.ttft_distribution.mean)
                 });
 
             let highest_throughput = results
                 .iter()
-                .max_by(|(_, _, a), (_, _, b)| {
+                .max_by(|(_, _, _, a), (_, _, _, b)| {
                     a.throughput.mean_tokens_per_second
                         .partial_cmp(&b.throughput.mean_tokens_per_second)
                         .unwrap_or(std::cmp::Ordering::Equal)
                 });
 
-            if let Some((provider, model, _)) = fastest_ttft {
+            let mode_note = |mode: &str| {
+                if show_mode {
+                    format!(", {}", mode)
+                } else {
+                    String::new()
+                }
+            };
+
+            if let Some((provider, model, mode, _)) = fastest_ttft {
                 println!(
-                    "{} Fastest TTFT: {} ({})",
+                    "{} Fastest TTFT: {} ({}{})",
                     "🏆".bright_yellow(),
                     model.bright_green().bold(),
-                    provider.bright_yellow()
+                    provider.bright_yellow(),
+                    mode_note(mode)
                 );
             }
 
-            if let Some((provider, model, _)) = highest_throughput {
+            if let Some((provider, model, mode, _)) = highest_throughput {
                 println!(
-                    "{} Highest throughput: {} ({})",
+                    "{} Highest throughput: {} ({}{})",
                     "🏆".bright_yellow(),
                     model.bright_green().bold(),
-                    provider.bright_yellow()
+                    provider.bright_yellow(),
+                    mode_note(mode)
                 );
             }
 
@@ -354,10 +374,11 @@ pub async fn run(
         if let Some(ref output_path) = args.output {
             let json_data: Vec<_> = results
                 .iter()
-                .map(|(provider, model, metrics)| {
+                .map(|(provider, model, mode, metrics)| {
                     serde_json::json!({
                         "provider": provider,
                         "model": model,
+                        "mode": mode,
                         "metrics": metrics,
                     })
                 })
@@ -373,3 +394,12 @@ pub async fn run(
 
     Ok(())
 }
+
+/// Table label for a target, with its response mode when several modes ran
+fn target_label(provider: &str, model: &str, mode: &str, show_mode: bool) -> String {
+    if show_mode {
+        format!("{}:{} ({})", provider, model, mode)
+    } else {
+        format!("{}:{}", provider, model)
+    }
+}
@@ -0,0 +1,194 @@
+//! Embed command implementation
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tracing::info;
+
+use crate::cli::EmbedArgs;
+use crate::config::Config;
+use crate::orchestrator::{Orchestrator, OrchestratorConfig};
+use llm_latency_lens_exporters::{ConsoleExporter, Exporter, JsonExporter};
+use llm_latency_lens_metrics::{MetricsAggregator, MetricsCollector};
+use llm_latency_lens_providers::{EmbeddingProvider, EmbeddingRequest};
+
+use super::write_output;
+
+/// Words used to generate inputs when none are given
+const FILLER_WORDS: &[&str] = &[
+    "latency", "vector", "model", "request", "token", "search", "index", "query",
+    "document", "semantic", "cluster", "measure", "network", "batch", "result", "signal",
+];
+
+/// Run the embed command
+pub async fn run(
+    args: EmbedArgs,
+    mut config: Config,
+    json_output: bool,
+    quiet: bool,
+    shutdown_signal: Arc<tokio::sync::Notify>,
+) -> Result<()> {
+    info!("Starting embed command");
+
+    if args.batch_size == 0 {
+        anyhow::bail!("--batch-size must be at least 1");
+    }
+
+    // Merge CLI overrides
+    config.merge_cli_overrides(&args.provider, args.api_key.clone(), args.endpoint.clone());
+
+    // Create provider
+    let provider: Arc<dyn EmbeddingProvider> =
+        Arc::from(config.create_embedding_provider(&args.provider)?);
+
+    // Fill the batch from the given inputs, or generate inputs
+    let inputs = batch_inputs(
+        read_inputs(&args.inputs, &args.input_file)?,
+        args.batch_size,
+        args.input_length,
+    );
+
+    if !quiet {
+        println!(
+            "{} Embedding with {} model {}",
+            "=>".bright_cyan().bold(),
+            args.provider.bright_yellow(),
+            args.model.bright_green()
+        );
+        println!(
+            "   {} requests of {} inputs with concurrency {}",
+            args.requests.to_string().bright_white().bold(),
+            args.batch_size.to_string().bright_white().bold(),
+            args.concurrency.to_string().bright_white().bold()
+        );
+        if args.rate_limit > 0 {
+            println!("   Rate limit: {} req/s", args.rate_limit);
+        }
+        println!();
+    }
+
+    // Build request template
+    let mut builder = EmbeddingRequest::builder()
+        .model(args.model.clone())
+        .inputs(inputs);
+    if let Some(dimensions) = args.dimensions {
+        builder = builder.dimensions(dimensions);
+    }
+    for name in &args.redact_headers {
+        builder = builder.redact_header(name);
+    }
+    let request_template = builder.build();
+
+    // Create orchestrator
+    let orchestrator_config = OrchestratorConfig {
+        concurrency: args.concurrency,
+        total_requests: args.requests,
+        rate_limit: args.rate_limit,
+        show_progress: args.progress && !quiet && !json_output,
+        shutdown_timeout: std::time::Duration::from_secs(30),
+        streaming: false,
+    };
+
+    let orchestrator = Orchestrator::new(orchestrator_config, shutdown_signal);
+
+    // Create metrics collector
+    let collector = Arc::new(
+        MetricsCollector::with_defaults(orchestrator.session_id())
+            .context("Failed to create metrics collector")?
+    );
+
+    // Execute benchmark
+    let summary = orchestrator
+        .execute_embeddings(provider, request_template, Arc::clone(&collector))
+        .await?;
+
+    // Aggregate metrics
+    let aggregated = MetricsAggregator::aggregate(&collector)
+        .context("Failed to aggregate metrics")?;
+
+    // Output results
+    if json_output {
+        let json_exporter = JsonExporter::new(!quiet);
+        let output = json_exporter.export(&aggregated)?;
+        write_output(&output, &args.output)?;
+    } else {
+        if !quiet {
+            println!();
+            println!("{}", ConsoleExporter::new().export(&aggregated)?);
+            println!(
+                "{} {}/{} requests succeeded ({:.2} req/s)",
+                "✓".bright_green().bold(),
+                summary.successful_requests,
+                summary.total_requests,
+                summary.requests_per_second
+            );
+        }
+
+        // Save to file if requested
+        if let Some(ref output_path) = args.output {
+            let json_exporter = JsonExporter::new(true);
+            let output = json_exporter.export(&aggregated)?;
+            std::fs::write(output_path, output)?;
+
+            if !quiet {
+                println!("Results saved to: {}", output_path.display());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Read inputs from `--input` arguments or a file with one input per line
+fn read_inputs(inputs: &[String], input_file: &Option<PathBuf>) -> Result<Vec<String>> {
+    let Some(file) = input_file else {
+        return Ok(inputs.to_vec());
+    };
+    let content = std::fs::read_to_string(file)
+        .map_err(|e| anyhow::anyhow!("Failed to read input file: {}", e))?;
+    Ok(content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+/// Build a batch of `batch_size` inputs
+///
+/// Given inputs are cycled to fill the batch; without any, inputs of
+/// `input_length` words are generated.
+fn batch_inputs(inputs: Vec<String>, batch_size: usize, input_length: usize) -> Vec<String> {
+    if inputs.is_empty() {
+        return (0..batch_size)
+            .map(|i| {
+                (0..input_length)
+                    .map(|w| FILLER_WORDS[(i + w) % FILLER_WORDS.len()])
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .collect();
+    }
+    inputs.into_iter().cycle().take(batch_size).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_batch_inputs_cycles_given_inputs() {
+        let inputs = vec!["a".to_string(), "b".to_string()];
+        assert_eq!(batch_inputs(inputs, 5, 64), vec!["a", "b", "a", "b", "a"]);
+    }
+
+    #[test]
+    fn test_batch_inputs_generates_inputs() {
+        let inputs = batch_inputs(Vec::new(), 3, 10);
+        assert_eq!(inputs.len(), 3);
+        assert!(inputs.iter().all(|input| input.split(' ').count() == 10));
+        // Inputs differ so servers cannot deduplicate the batch
+        assert_ne!(inputs[0], inputs[1]);
+    }
+}
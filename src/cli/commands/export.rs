@@ -13,7 +13,7 @@ use llm_latency_lens_metrics::AggregatedMetrics;
 use super::write_output;
 
 /// Run the export command
-pub async fn run(args: ExportArgs, json_output: bool, quiet: bool) -> Result<()> {
+pub async fn run(args: ExportArgs, _json_output: bool, quiet: bool) -> Result<()> {
     info!("Starting export command");
 
     // Read input file
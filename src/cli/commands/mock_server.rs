@@ -0,0 +1,124 @@
+//! Mock server command implementation
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use std::net::{SocketAddr, ToSocketAddrs};
+use std::sync::Arc;
+use tabled::{Table, Tabled};
+use tracing::info;
+
+use crate::cli::MockServerArgs;
+use llm_latency_lens::mock_server::{MockServer, MockServerConfig};
+
+/// Run the mock-server command
+pub async fn run(
+    args: MockServerArgs,
+    json_output: bool,
+    quiet: bool,
+    shutdown_signal: Arc<tokio::sync::Notify>,
+) -> Result<()> {
+    info!("Starting mock-server command");
+
+    let addr: SocketAddr = (args.host.as_str(), args.port)
+        .to_socket_addrs()
+        .with_context(|| format!("Invalid listen address {}:{}", args.host, args.port))?
+        .next()
+        .with_context(|| format!("{} did not resolve to an address", args.host))?;
+
+    let config = MockServerConfig {
+        ttft: args.ttft,
+        itl: args.itl,
+        output_tokens: args.output_tokens,
+        error_rate: args.error_rate,
+        rate_limit_rate: args.rate_limit_rate,
+        disconnect_rate: args.disconnect_rate,
+        retry_after: args.retry_after,
+        models: args.models,
+        seed: args.seed,
+    };
+
+    // Register for Ctrl+C before the server can be observed as ready
+    let shutdown = shutdown_signal.notified();
+    let server = MockServer::start(addr, config.clone()).await?;
+    let url = server.url();
+
+    // Base URLs to configure for each provider
+    let endpoints = [
+        ("openai", format!("{}/v1", url)),
+        ("generic", format!("{}/v1", url)),
+        ("anthropic", format!("{}/v1", url)),
+        ("google", format!("{}/v1beta", url)),
+        ("azure-openai", url.clone()),
+    ];
+
+    if json_output {
+        let ready = serde_json::json!({
+            "url": url,
+            "endpoints": endpoints
+                .iter()
+                .map(|(provider, endpoint)| (provider.to_string(), serde_json::json!(endpoint)))
+                .collect::<serde_json::Map<_, _>>(),
+            "config": config,
+        });
+        println!("{}", serde_json::to_string(&ready)?);
+    } else if quiet {
+        println!("{}", url);
+    } else {
+        #[derive(Tabled)]
+        struct EndpointRow {
+            #[tabled(rename = "Provider")]
+            provider: String,
+            #[tabled(rename = "Endpoint")]
+            endpoint: String,
+        }
+
+        println!(
+            "{} Mock server listening on {}",
+            "=>".bright_cyan().bold(),
+            url.bright_yellow()
+        );
+        println!(
+            "   TTFT {} ms, ITL {} ms, {} output tokens",
+            config.ttft, config.itl, config.output_tokens
+        );
+        println!(
+            "   Errors {:.1}%, rate limits {:.1}%, disconnects {:.1}%",
+            config.error_rate * 100.0,
+            config.rate_limit_rate * 100.0,
+            config.disconnect_rate * 100.0
+        );
+        println!();
+
+        let rows: Vec<_> = endpoints
+            .iter()
+            .map(|(provider, endpoint)| EndpointRow {
+                provider: provider.to_string(),
+                endpoint: endpoint.clone(),
+            })
+            .collect();
+        println!("{}", Table::new(rows));
+        println!();
+        println!("{}", "Press Ctrl+C to stop".bright_black());
+    }
+
+    shutdown.await;
+    let stats = server.stats();
+    drop(server);
+
+    if json_output {
+        println!("{}", serde_json::to_string(&stats)?);
+    } else if !quiet {
+        println!();
+        println!(
+            "{} Served {} request(s): {} completed, {} rate limited, {} errors, {} disconnects",
+            "✓".bright_green().bold(),
+            stats.requests,
+            stats.completed,
+            stats.rate_limited,
+            stats.errors,
+            stats.disconnects
+        );
+    }
+
+    Ok(())
+}
@@ -2,13 +2,16 @@
 
 pub mod benchmark;
 pub mod compare;
+pub mod embed;
 pub mod export;
+pub mod mock_server;
+pub mod models;
 pub mod profile;
 pub mod run;
 pub mod validate;
 
 use anyhow::Result;
-use std::path::Path;
+use llm_latency_lens_providers::{ContentPart, Tool};
 
 /// Read prompt from file or use provided string
 pub fn read_prompt(prompt: &Option<String>, prompt_file: &Option<std::path::PathBuf>) -> Result<String> {
@@ -22,6 +25,34 @@ pub fn read_prompt(prompt: &Option<String>, prompt_file: &Option<std::path::Path
     }
 }
 
+/// Read tool definitions from a JSON file, if one was given
+pub fn read_tools(tools_file: &Option<std::path::PathBuf>) -> Result<Vec<Tool>> {
+    let Some(file) = tools_file else {
+        return Ok(Vec::new());
+    };
+    let content = std::fs::read_to_string(file)
+        .map_err(|e| anyhow::anyhow!("Failed to read tools file: {}", e))?;
+    serde_json::from_str(&content).map_err(|e| anyhow::anyhow!("Invalid tools file: {}", e))
+}
+
+/// Load `--attach` arguments as message content parts
+///
+/// `http(s)://` arguments are sent as image URLs; anything else is read from
+/// disk and inlined as base64.
+pub fn read_attachments(attachments: &[String]) -> Result<Vec<ContentPart>> {
+    attachments
+        .iter()
+        .map(|attachment| {
+            if attachment.starts_with("http://") || attachment.starts_with("https://") {
+                Ok(ContentPart::image_url(attachment))
+            } else {
+                ContentPart::from_file(attachment)
+                    .map_err(|e| anyhow::anyhow!("Failed to attach {}: {}", attachment, e))
+            }
+        })
+        .collect()
+}
+
 /// Write output to file or stdout
 pub fn write_output(content: &str, output_path: &Option<std::path::PathBuf>) -> Result<()> {
     if let Some(path) = output_path {
@@ -0,0 +1,100 @@
+//! Models command implementation
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use tabled::{Table, Tabled};
+use tracing::info;
+
+use crate::cli::ModelsArgs;
+use crate::config::Config;
+
+/// Run the models command
+pub async fn run(
+    args: ModelsArgs,
+    mut config: Config,
+    json_output: bool,
+    quiet: bool,
+) -> Result<()> {
+    info!("Starting models command");
+
+    // Merge CLI overrides
+    config.merge_cli_overrides(&args.provider, args.api_key.clone(), args.endpoint.clone());
+
+    // Create provider
+    let provider = config.create_provider(&args.provider)?;
+    let supported = provider.supported_models();
+
+    let models = if args.static_only {
+        supported.clone()
+    } else {
+        if !quiet && !json_output {
+            println!(
+                "{} Listing models from {}...",
+                "=>".bright_cyan().bold(),
+                args.provider.bright_yellow()
+            );
+            println!();
+        }
+        provider
+            .list_models()
+            .await
+            .with_context(|| format!("Failed to list models for {}", args.provider))?
+    };
+
+    if json_output {
+        let json_data: Vec<_> = models
+            .iter()
+            .map(|model| {
+                serde_json::json!({
+                    "model": model,
+                    "static": supported.contains(model),
+                })
+            })
+            .collect();
+
+        let output = if quiet {
+            serde_json::to_string(&json_data)?
+        } else {
+            serde_json::to_string_pretty(&json_data)?
+        };
+
+        println!("{}", output);
+    } else if quiet {
+        for model in &models {
+            println!("{}", model);
+        }
+    } else if models.is_empty() {
+        println!("{} No models listed", "!".bright_yellow().bold());
+    } else {
+        #[derive(Tabled)]
+        struct ModelRow {
+            #[tabled(rename = "Model")]
+            model: String,
+            #[tabled(rename = "Static List")]
+            in_static_list: String,
+        }
+
+        let rows: Vec<_> = models
+            .iter()
+            .map(|model| ModelRow {
+                model: model.clone(),
+                in_static_list: if supported.contains(model) {
+                    "✓".bright_green().to_string()
+                } else {
+                    "-".bright_black().to_string()
+                },
+            })
+            .collect();
+
+        println!("{}", Table::new(rows));
+        println!();
+        println!(
+            "{} {} model(s) available from {}",
+            "✓".bright_green().bold(),
+            models.len(),
+            args.provider.bright_yellow()
+        );
+    }
+
+    Ok(())
+}
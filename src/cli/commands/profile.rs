@@ -8,11 +8,11 @@ use tracing::info;
 
 use crate::cli::ProfileArgs;
 use crate::config::Config;
+use crate::orchestrator::discover_model;
 use llm_latency_lens_core::TimingEngine;
-use llm_latency_lens_exporters::{Exporter, JsonExporter};
-use llm_latency_lens_providers::{create_provider, MessageRole, StreamingRequest};
+use llm_latency_lens_providers::{MessageRole, StreamingRequest, ToolChoice};
 
-use super::{read_prompt, write_output};
+use super::{read_attachments, read_prompt, read_tools, write_output};
 
 /// Run the profile command
 pub async fn run(
@@ -33,14 +33,9 @@ pub async fn run(
     // Get provider configuration
     let provider_config = config.get_provider(&args.provider)?;
 
-    let api_key = provider_config
-        .api_key
-        .as_ref()
-        .context("API key not found for provider")?;
-
     // Create provider
-    let provider = create_provider(&args.provider, api_key.clone())
-        .with_context(|| format!("Failed to create provider: {}", args.provider))?;
+    let provider = config.create_provider(&args.provider)?;
+    discover_model(provider.as_ref(), &args.model).await;
 
     // Read prompt
     let prompt = read_prompt(&args.prompt, &args.prompt_file)
@@ -56,31 +51,86 @@ pub async fn run(
     }
 
     // Build request
-    let request = StreamingRequest::builder()
+    let mut builder = StreamingRequest::builder()
         .model(args.model.clone())
         .message(MessageRole::User, prompt)
         .max_tokens(args.max_tokens)
         .temperature(args.temperature.unwrap_or(0.7))
-        .top_p(args.top_p)
-        .timeout_secs(args.timeout)
-        .build();
+        .timeout_secs(args.timeout);
+    if let Some(top_p) = args.top_p {
+        builder = builder.top_p(top_p);
+    }
+    if let Some(budget) = args
+        .thinking_budget
+        .or_else(|| provider_config.effective_thinking_budget())
+    {
+        builder = builder.thinking_budget(budget);
+    }
+    builder = builder.tools(read_tools(&args.tools).context("Failed to read tools")?);
+    if let Some(ref choice) = args.tool_choice {
+        builder = builder.tool_choice(ToolChoice::from(choice.as_str()));
+    }
+    for part in read_attachments(&args.attachments)? {
+        builder = builder.attachment(part);
+    }
+    if args.cache_prompt {
+        builder = builder.cache_breakpoint();
+    }
+    for name in &args.redact_headers {
+        builder = builder.redact_header(name);
+    }
+    let request = builder.build();
 
     // Create timing engine
     let timing_engine = TimingEngine::new();
 
+    let price_table = config.pricing.table().context("Failed to load price table")?;
+
     // Execute request
+    let requested_at = chrono::Utc::now();
     let start = std::time::Instant::now();
-    let result = provider.complete(request, &timing_engine).await
-        .context("Request failed")?;
+    let result = if args.no_stream {
+        provider.complete_non_streaming(request, &timing_engine).await
+    } else {
+        provider.complete(request, &timing_engine).await
+    }
+    .context("Request failed")?;
 
     let duration = start.elapsed();
 
+    // Price the request from the configured table
+    let cost = provider.price_usage(
+        &price_table,
+        &args.model,
+        &result.metadata.usage(),
+        requested_at,
+        config.pricing.mode(),
+    );
+
     // Calculate metrics
     let ttft = result.ttft().unwrap_or_default();
     let avg_inter_token = result.avg_inter_token_latency().unwrap_or_default();
     let median_inter_token = result.median_inter_token_latency().unwrap_or_default();
     let p95_inter_token = result.p95_inter_token_latency().unwrap_or_default();
     let tokens_per_second = result.tokens_per_second().unwrap_or(0.0);
+    let thinking_ttft = result.time_to_first_thinking_token();
+    let thinking_duration = result.thinking_duration();
+    let visible_ttft = result.time_to_first_visible_token();
+    let reasoning_time = result.reasoning_time();
+    let tool_call_ttft = result.time_to_first_tool_call();
+    let tool_call_duration = result.tool_call_duration();
+    let response_ttft = result.response_ttft();
+    let network = result.network;
+    let server = &result.metadata.server;
+    let reads = result.reads;
+    let retries = result.retries;
+    let body = result.body;
+    let response_headers: serde_json::Map<String, serde_json::Value> = result
+        .metadata
+        .headers
+        .iter()
+        .map(|(name, value)| (name.clone(), value.clone().into()))
+        .collect();
 
     // Prepare output
     if json_output {
@@ -94,11 +144,42 @@ pub async fn run(
             "input_tokens": result.metadata.input_tokens,
             "output_tokens": result.metadata.output_tokens,
             "thinking_tokens": result.metadata.thinking_tokens,
+            "cache_creation_tokens": result.metadata.cache_creation_tokens,
+            "cache_read_tokens": result.metadata.cache_read_tokens,
+            "cache_hit": result.metadata.cache_hit(),
+            "time_to_first_thinking_token_ms": thinking_ttft.map(|d| d.as_millis()),
+            "thinking_duration_ms": thinking_duration.map(|d| d.as_millis()),
+            "time_to_first_visible_token_ms": visible_ttft.map(|d| d.as_millis()),
+            "reasoning_time_ms": reasoning_time.map(|d| d.as_millis()),
+            "time_to_first_tool_call_ms": tool_call_ttft.map(|d| d.as_millis()),
+            "tool_call_duration_ms": tool_call_duration.map(|d| d.as_millis()),
+            "dns_ms": network.dns.map(|d| d.as_millis()),
+            "tcp_connect_ms": network.tcp_connect.map(|d| d.as_millis()),
+            "tls_handshake_ms": network.tls_handshake.map(|d| d.as_millis()),
+            "time_to_first_byte_ms": network.time_to_first_byte.map(|d| d.as_millis()),
+            "time_to_first_event_ms": network.time_to_first_event.map(|d| d.as_millis()),
+            "session_setup_ms": network.session_ready.map(|d| d.as_millis()),
+            "response_ttft_ms": response_ttft.map(|d| d.as_millis()),
+            "network_reads": reads.reads,
+            "stream_events": reads.events,
+            "max_events_per_read": reads.max_events_per_read,
+            "mean_events_per_read": reads.mean_events_per_read(),
+            "attempts": retries.attempts(),
+            "retry_backoff_ms": retries.backoff.as_millis(),
+            "streamed": result.is_streamed(),
+            "response_body_bytes": body.map(|b| b.size_bytes),
+            "body_download_ms": body.map(|b| b.download_time.as_millis()),
+            "body_parse_ms": body.map(|b| b.parse_time.as_millis()),
+            "provider_request_id": server.request_id,
+            "server_processing_ms": server.processing_time.map(|d| d.as_millis()),
+            "rate_limit_remaining_requests": server.rate_limit.requests_remaining,
+            "rate_limit_remaining_tokens": server.rate_limit.tokens_remaining,
+            "response_headers": response_headers,
             "avg_inter_token_latency_ms": avg_inter_token.as_millis(),
             "median_inter_token_latency_ms": median_inter_token.as_millis(),
             "p95_inter_token_latency_ms": p95_inter_token.as_millis(),
             "tokens_per_second": tokens_per_second,
-            "cost_usd": result.metadata.estimated_cost,
+            "cost_usd": cost,
             "content": result.content,
         });
 
@@ -123,7 +204,7 @@ pub async fn run(
                 value: String,
             }
 
-            let rows = vec![
+            let mut rows = vec![
                 MetricRow {
                     metric: "Provider".to_string(),
                     value: args.provider.clone(),
@@ -174,11 +255,151 @@ pub async fn run(
                 },
             ];
 
-            let mut table = Table::new(rows);
+            // Thinking-phase breakdown, only present with extended thinking
+            if let (Some(thinking_ttft), Some(thinking_duration)) = (thinking_ttft, thinking_duration) {
+                rows.push(MetricRow {
+                    metric: "Time to First Thinking Token".to_string(),
+                    value: format!("{:.2}ms", thinking_ttft.as_secs_f64() * 1000.0),
+                });
+                rows.push(MetricRow {
+                    metric: "Thinking Duration".to_string(),
+                    value: format!("{:.2}ms", thinking_duration.as_secs_f64() * 1000.0),
+                });
+                if let Some(visible_ttft) = visible_ttft {
+                    rows.push(MetricRow {
+                        metric: "Time to First Visible Token".to_string(),
+                        value: format!("{:.2}ms", visible_ttft.as_secs_f64() * 1000.0),
+                    });
+                }
+            }
+
+            // Reasoning phase, only for requests that reasoned
+            if let Some(reasoning_time) = reasoning_time {
+                rows.push(MetricRow {
+                    metric: "Reasoning Time".to_string(),
+                    value: format!("{:.2}ms", reasoning_time.as_secs_f64() * 1000.0),
+                });
+            }
+            if let Some(reasoning_tokens) = result.metadata.thinking_tokens {
+                rows.push(MetricRow {
+                    metric: "Reasoning Tokens".to_string(),
+                    value: reasoning_tokens.to_string(),
+                });
+            }
+
+            // Tool-call phase, only when the model called a tool
+            if let (Some(tool_call_ttft), Some(tool_call_duration)) = (tool_call_ttft, tool_call_duration) {
+                rows.push(MetricRow {
+                    metric: "Time to First Tool Call".to_string(),
+                    value: format!("{:.2}ms", tool_call_ttft.as_secs_f64() * 1000.0),
+                });
+                rows.push(MetricRow {
+                    metric: "Tool Argument Generation".to_string(),
+                    value: format!("{:.2}ms", tool_call_duration.as_secs_f64() * 1000.0),
+                });
+            }
+
+            // Prompt cache, only when the provider reported cache usage
+            if let Some(hit) = result.metadata.cache_hit() {
+                rows.push(MetricRow {
+                    metric: "Prompt Cache".to_string(),
+                    value: if hit {
+                        format!(
+                            "hit ({} tokens read)",
+                            result.metadata.cache_read_tokens.unwrap_or(0)
+                        )
+                    } else {
+                        "miss".to_string()
+                    },
+                });
+            }
+
+            // Network phases; connect and TLS are absent on a reused
+            // connection, session timings on non-realtime transports
+            for (metric, phase) in [
+                ("DNS Lookup", network.dns),
+                ("TCP Connect", network.tcp_connect),
+                ("TLS Handshake", network.tls_handshake),
+                ("Time to First Byte", network.time_to_first_byte),
+                ("Time to First Event", network.time_to_first_event),
+                ("Session Setup", network.session_ready),
+                ("Response TTFT", response_ttft),
+            ] {
+                if let Some(phase) = phase {
+                    rows.push(MetricRow {
+                        metric: metric.to_string(),
+                        value: format!("{:.2}ms", phase.as_secs_f64() * 1000.0),
+                    });
+                }
+            }
+
+            // How the body arrived; several events per read means buffering
+            if let Some(mean) = reads.mean_events_per_read() {
+                rows.push(MetricRow {
+                    metric: "Events per Read".to_string(),
+                    value: format!(
+                        "{:.2} ({} events in {} reads, max {})",
+                        mean, reads.events, reads.reads, reads.max_events_per_read
+                    ),
+                });
+            }
+
+            // Retried initial requests; the backoff is part of every timing above
+            if retries.retries > 0 {
+                rows.push(MetricRow {
+                    metric: "Attempts".to_string(),
+                    value: format!(
+                        "{} ({:.2}ms backoff)",
+                        retries.attempts(),
+                        retries.backoff.as_secs_f64() * 1000.0
+                    ),
+                });
+            }
+
+            // Complete response body, only for non-streaming requests
+            if let Some(body) = body {
+                rows.push(MetricRow {
+                    metric: "Response Body".to_string(),
+                    value: format!(
+                        "{} bytes (download {:.2}ms, parse {:.2}ms)",
+                        body.size_bytes,
+                        body.download_time.as_secs_f64() * 1000.0,
+                        body.parse_time.as_secs_f64() * 1000.0
+                    ),
+                });
+            }
+
+            // Server-reported signals, when the provider sent them
+            if let Some(processing_time) = server.processing_time {
+                rows.push(MetricRow {
+                    metric: "Server Processing".to_string(),
+                    value: format!("{:.2}ms", processing_time.as_secs_f64() * 1000.0),
+                });
+            }
+            if let Some(ref request_id) = server.request_id {
+                rows.push(MetricRow {
+                    metric: "Provider Request ID".to_string(),
+                    value: request_id.clone(),
+                });
+            }
+            if let Some(remaining) = server.rate_limit.requests_remaining {
+                rows.push(MetricRow {
+                    metric: "Rate Limit (Requests Left)".to_string(),
+                    value: remaining.to_string(),
+                });
+            }
+            if let Some(remaining) = server.rate_limit.tokens_remaining {
+                rows.push(MetricRow {
+                    metric: "Rate Limit (Tokens Left)".to_string(),
+                    value: remaining.to_string(),
+                });
+            }
+
+            let table = Table::new(rows);
             println!("{}", table);
             println!();
 
-            if let Some(cost) = result.metadata.estimated_cost {
+            if let Some(cost) = cost {
                 println!(
                     "{} Estimated cost: {}",
                     "=>".bright_cyan(),
@@ -202,6 +423,7 @@ pub async fn run(
                 "provider": args.provider,
                 "model": args.model,
                 "request_id": result.request_id.to_string(),
+                "provider_request_id": server.request_id,
                 "ttft_ms": ttft.as_millis(),
                 "total_duration_ms": duration.as_millis(),
                 "metrics": {
@@ -210,6 +432,24 @@ pub async fn run(
                     "median_inter_token": median_inter_token.as_millis(),
                     "p95_inter_token": p95_inter_token.as_millis(),
                     "tokens_per_second": tokens_per_second,
+                    "thinking_ttft": thinking_ttft.map(|d| d.as_millis()),
+                    "thinking_duration": thinking_duration.map(|d| d.as_millis()),
+                    "visible_ttft": visible_ttft.map(|d| d.as_millis()),
+                    "reasoning_time": reasoning_time.map(|d| d.as_millis()),
+                    "tool_call_ttft": tool_call_ttft.map(|d| d.as_millis()),
+                    "tool_call_duration": tool_call_duration.map(|d| d.as_millis()),
+                    "dns": network.dns.map(|d| d.as_millis()),
+                    "tcp_connect": network.tcp_connect.map(|d| d.as_millis()),
+                    "tls_handshake": network.tls_handshake.map(|d| d.as_millis()),
+                    "time_to_first_byte": network.time_to_first_byte.map(|d| d.as_millis()),
+                    "time_to_first_event": network.time_to_first_event.map(|d| d.as_millis()),
+                    "session_setup": network.session_ready.map(|d| d.as_millis()),
+                    "response_ttft": response_ttft.map(|d| d.as_millis()),
+                    "server_processing": server.processing_time.map(|d| d.as_millis()),
+                    "mean_events_per_read": reads.mean_events_per_read(),
+                    "body_download": body.map(|b| b.download_time.as_millis()),
+                    "body_parse": body.map(|b| b.parse_time.as_millis()),
+                    "cache_hit": result.metadata.cache_hit(),
                 },
                 "content": result.content,
             });
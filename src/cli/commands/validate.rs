@@ -8,7 +8,7 @@ use tracing::info;
 use crate::cli::ValidateArgs;
 use crate::config::Config;
 use llm_latency_lens_core::TimingEngine;
-use llm_latency_lens_providers::{create_provider, MessageRole, Provider, StreamingRequest};
+use llm_latency_lens_providers::{MessageRole, Provider, StreamingRequest};
 
 /// Run the validate command
 pub async fn run(
@@ -90,23 +90,20 @@ pub async fn run(
         };
 
         // Check API key
-        let api_key = match &provider_config.api_key {
-            Some(key) => {
-                result.api_key_present = true;
-                key.clone()
-            }
+        match &provider_config.api_key {
+            Some(_) => result.api_key_present = true,
             None => {
                 result.error = Some("API key not found".to_string());
                 results.push(result);
                 continue;
             }
-        };
+        }
 
         // Create provider and test connectivity
-        let provider = match create_provider(&provider_name, api_key) {
+        let provider = match config.create_provider(&provider_name) {
             Ok(p) => p,
             Err(e) => {
-                result.error = Some(format!("Failed to create provider: {}", e));
+                result.error = Some(format!("{:#}", e));
                 results.push(result);
                 continue;
             }
@@ -304,7 +301,11 @@ pub async fn run(
 
 /// Run a simple test request to validate the provider
 async fn run_test_request(provider: &dyn Provider) -> Result<()> {
-    let models = provider.supported_models();
+    // Servers without a static list report their models through discovery
+    let mut models = provider.supported_models();
+    if models.is_empty() {
+        models = provider.discover_models().await.unwrap_or_default();
+    }
     let model = models
         .first()
         .context("No supported models available")?
@@ -9,8 +9,14 @@ use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use std::time::Duration;
 
+use llm_latency_lens_providers::{
+    tokenizer, EmbeddingProvider, ModelPrice, PriceTable, PricingMode, Provider, ProviderRegistry,
+    Tokenizer,
+};
+
 // CLI import removed - not used in library context
 
 /// Main configuration structure
@@ -31,44 +37,17 @@ pub struct Config {
     /// Output preferences
     #[serde(default)]
     pub output: OutputConfig,
-}
-
-/// Provider-specific configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ProviderConfig {
-    /// API key
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub api_key: Option<String>,
-
-    /// API endpoint URL
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub endpoint: Option<String>,
 
-    /// Organization ID (for OpenAI)
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub organization: Option<String>,
-
-    /// API version (for Anthropic)
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub api_version: Option<String>,
-
-    /// Default model for this provider
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub default_model: Option<String>,
-
-    /// Request timeout in seconds
-    #[serde(default = "default_timeout")]
-    pub timeout_secs: u64,
-
-    /// Maximum retries
-    #[serde(default = "default_retries")]
-    pub max_retries: u32,
-
-    /// Enable extended thinking (Claude)
+    /// Model prices
     #[serde(default)]
-    pub extended_thinking: bool,
+    pub pricing: PricingConfig,
 }
 
+/// Provider-specific configuration
+///
+/// Defined alongside the provider registry, whose factories receive it.
+pub use llm_latency_lens_providers::ProviderConfig;
+
 /// Default settings
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DefaultSettings {
@@ -167,6 +146,49 @@ impl Default for OutputConfig {
     }
 }
 
+/// Pricing configuration
+///
+/// Prices come from the table bundled with the providers crate. A price
+/// table file and inline `prices` entries are applied over it, in that
+/// order; a model priced there takes its whole price history from them.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PricingConfig {
+    /// Price table file (TOML, or JSON with a `.json` extension)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file: Option<PathBuf>,
+
+    /// Inline price entries
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub prices: Vec<ModelPrice>,
+
+    /// Price requests at batch rates
+    #[serde(default)]
+    pub batch: bool,
+}
+
+impl PricingConfig {
+    /// Price table with the configured overrides applied
+    pub fn table(&self) -> Result<PriceTable> {
+        let mut table = PriceTable::bundled().clone();
+        if let Some(ref file) = self.file {
+            table = table.with_overrides(PriceTable::load(file)?);
+        }
+        if !self.prices.is_empty() {
+            table = table.with_overrides(PriceTable::new(self.prices.clone()));
+        }
+        Ok(table)
+    }
+
+    /// Billing mode to price requests with
+    pub fn mode(&self) -> PricingMode {
+        if self.batch {
+            PricingMode::Batch
+        } else {
+            PricingMode::Standard
+        }
+    }
+}
+
 impl Config {
     /// Load configuration from file and CLI arguments
     pub fn load(config_path: &Option<PathBuf>) -> Result<Self> {
@@ -243,20 +265,33 @@ impl Config {
             if let Ok(api_key) = std::env::var(&env_key) {
                 self.providers
                     .entry(provider.to_string())
-                    .or_insert_with(|| ProviderConfig {
-                        api_key: Some(api_key.clone()),
-                        endpoint: None,
-                        organization: None,
-                        api_version: None,
-                        default_model: None,
-                        timeout_secs: default_timeout(),
-                        max_retries: default_retries(),
-                        extended_thinking: false,
-                    })
+                    .or_default()
                     .api_key = Some(api_key.clone());
             }
         }
 
+        // OpenAI-compatible servers often run without auth, so the generic
+        // provider is registered by its base URL and the key is optional
+        if let Ok(base_url) = std::env::var("GENERIC_BASE_URL") {
+            let api_key = std::env::var("GENERIC_API_KEY").unwrap_or_default();
+            let generic = self.get_or_create_provider("generic");
+            generic.endpoint = Some(base_url);
+            if generic.api_key.is_none() || !api_key.is_empty() {
+                generic.api_key = Some(api_key);
+            }
+        }
+
+        // The exec provider is registered by its command; the key is optional
+        // and only passed through to the command
+        if let Ok(command) = std::env::var("EXEC_COMMAND") {
+            let api_key = std::env::var("EXEC_API_KEY").unwrap_or_default();
+            let exec = self.get_or_create_provider("exec");
+            exec.command = Some(command);
+            if exec.api_key.is_none() || !api_key.is_empty() {
+                exec.api_key = Some(api_key);
+            }
+        }
+
         // OpenAI organization
         if let Ok(org) = std::env::var("OPENAI_ORGANIZATION") {
             if let Some(openai) = self.providers.get_mut("openai") {
@@ -271,6 +306,26 @@ impl Config {
             }
         }
 
+        // The Responses API and Realtime modes use OpenAI's credentials
+        // unless configured. The default model names a Chat Completions
+        // model, and Realtime connects over WebSocket, so neither carries
+        // over (nor, for Realtime, does an HTTP endpoint).
+        if let Some(openai) = self.providers.get("openai").cloned() {
+            let openai = ProviderConfig {
+                default_model: None,
+                ..openai
+            };
+            self.providers
+                .entry("openai-responses".to_string())
+                .or_insert_with(|| openai.clone());
+            self.providers
+                .entry("openai-realtime".to_string())
+                .or_insert_with(|| ProviderConfig {
+                    endpoint: None,
+                    ..openai
+                });
+        }
+
         Ok(())
     }
 
@@ -281,20 +336,39 @@ impl Config {
             .with_context(|| format!("Provider '{}' not configured", provider))
     }
 
+    /// Create the provider configured under `name`
+    ///
+    /// The instance's `type` selects the adapter and defaults to its name.
+    pub fn create_provider(&self, name: &str) -> Result<Box<dyn Provider>> {
+        let provider_config = self.get_provider(name)?;
+        ProviderRegistry::builtin()
+            .create(provider_config.provider_type(name), provider_config)
+            .with_context(|| format!("Failed to create provider: {}", name))
+    }
+
+    /// Create the embeddings provider configured under `name`
+    pub fn create_embedding_provider(&self, name: &str) -> Result<Box<dyn EmbeddingProvider>> {
+        let provider_config = self.get_provider(name)?;
+        ProviderRegistry::builtin()
+            .create_embedding(provider_config.provider_type(name), provider_config)
+            .with_context(|| format!("Failed to create provider: {}", name))
+    }
+
+    /// Load the tokenizer configured for a provider instance, if any
+    pub fn tokenizer(&self, name: &str) -> Result<Option<Arc<dyn Tokenizer>>> {
+        let Some(ref path) = self.get_provider(name)?.tokenizer else {
+            return Ok(None);
+        };
+        let tokenizer = tokenizer::load(path)
+            .with_context(|| format!("Failed to load tokenizer for provider: {}", name))?;
+        Ok(Some(Arc::from(tokenizer)))
+    }
+
     /// Get or create provider configuration
     pub fn get_or_create_provider(&mut self, provider: &str) -> &mut ProviderConfig {
         self.providers
             .entry(provider.to_string())
-            .or_insert_with(|| ProviderConfig {
-                api_key: None,
-                endpoint: None,
-                organization: None,
-                api_version: None,
-                default_model: None,
-                timeout_secs: default_timeout(),
-                max_retries: default_retries(),
-                extended_thinking: false,
-            })
+            .or_default()
     }
 
     /// Validate configuration
@@ -305,8 +379,10 @@ impl Config {
         }
 
         // Validate provider configurations
+        let registry = ProviderRegistry::builtin();
         for (name, provider) in &self.providers {
-            if provider.api_key.is_none() {
+            let requires_api_key = registry.requires_api_key(provider.provider_type(name));
+            if provider.api_key.is_none() && requires_api_key {
                 anyhow::bail!(
                     "Provider '{}' is missing API key. Set it via config file or environment variable.",
                     name
@@ -378,10 +454,6 @@ fn default_timeout() -> u64 {
     120
 }
 
-fn default_retries() -> u32 {
-    3
-}
-
 fn default_burst() -> u32 {
     10
 }
@@ -436,6 +508,116 @@ api_version = "2023-06-01"
         );
     }
 
+    #[test]
+    fn test_extended_thinking_budget() {
+        let toml_content = r#"
+[providers.anthropic]
+api_key = "sk-ant-test"
+extended_thinking = true
+
+[providers.claude-deep]
+api_key = "sk-ant-test"
+extended_thinking = true
+thinking_budget = 8192
+
+[providers.openai]
+api_key = "sk-test"
+thinking_budget = 8192
+"#;
+
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(toml_content.as_bytes()).unwrap();
+
+        let config = Config::from_file(file.path()).unwrap();
+        assert_eq!(config.providers["anthropic"].effective_thinking_budget(), Some(1024));
+        assert_eq!(config.providers["claude-deep"].effective_thinking_budget(), Some(8192));
+        assert_eq!(config.providers["openai"].effective_thinking_budget(), None);
+    }
+
+    #[test]
+    fn test_openai_modes_inherit_credentials() {
+        let mut config = Config::default();
+        config.providers.insert(
+            "openai".to_string(),
+            ProviderConfig {
+                default_model: Some("gpt-4o-mini".to_string()),
+                ..ProviderConfig::with_api_key("sk-test")
+            },
+        );
+
+        config.apply_env_overrides().unwrap();
+
+        for mode in ["openai-responses", "openai-realtime"] {
+            assert!(config.providers[mode].api_key.is_some());
+            assert_eq!(config.providers[mode].default_model, None);
+        }
+        // The implicit entries add no targets of their own
+        let ids: Vec<String> = crate::adapters::all_targets(&config)
+            .iter()
+            .map(|t| t.id())
+            .collect();
+        assert!(ids.contains(&"openai:gpt-4o".to_string()));
+        assert!(!ids.iter().any(|id| id.starts_with("openai-re")));
+    }
+
+    #[test]
+    fn test_named_provider_instances() {
+        let toml_content = r#"
+[providers.gateway-a]
+type = "generic"
+endpoint = "http://gateway-a:8000/v1"
+
+[providers.gateway-b]
+type = "generic"
+endpoint = "http://gateway-b:8000/v1"
+headers = { X-Tenant = "benchmarks" }
+
+[providers.openai]
+api_key = "sk-test"
+"#;
+
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(toml_content.as_bytes()).unwrap();
+
+        let config = Config::from_file(file.path()).unwrap();
+        assert_eq!(config.create_provider("gateway-a").unwrap().name(), "generic");
+        assert_eq!(config.create_provider("gateway-b").unwrap().name(), "generic");
+        assert_eq!(config.create_provider("openai").unwrap().name(), "openai");
+        assert!(config.create_provider("gateway-c").is_err());
+        assert_eq!(
+            config.providers["gateway-b"].headers["X-Tenant"],
+            "benchmarks"
+        );
+    }
+
+    #[test]
+    fn test_pricing_overrides() {
+        let toml_content = r#"
+[pricing]
+batch = true
+
+[[pricing.prices]]
+provider = "generic"
+models = ["llama-3.1-70b"]
+effective_from = "2025-01-01"
+input = 0.60
+output = 0.80
+batch_discount = 0.25
+"#;
+
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(toml_content.as_bytes()).unwrap();
+
+        let config = Config::from_file(file.path()).unwrap();
+        assert_eq!(config.pricing.mode(), PricingMode::Batch);
+
+        let table = config.pricing.table().unwrap();
+        let at = chrono::Utc::now();
+        assert!(table.price("generic", "llama-3.1-70b", at).is_some());
+        // Bundled prices remain
+        assert!(table.price("openai", "gpt-4o", at).is_some());
+    }
+
     #[test]
     fn test_yaml_parsing() {
         let yaml_content = r#"
@@ -469,18 +651,32 @@ providers:
             "openai".to_string(),
             ProviderConfig {
                 api_key: Some("test".to_string()),
-                endpoint: None,
-                organization: None,
-                api_version: None,
-                default_model: None,
                 timeout_secs: 60,
                 max_retries: 3,
-                extended_thinking: false,
+                ..ProviderConfig::default()
             },
         );
 
         // Should pass
         assert!(config.validate().is_ok());
+
+        // Keyless provider types need no API key, keyed ones do
+        config.providers.insert(
+            "gateway".to_string(),
+            ProviderConfig {
+                provider_type: Some("openai-compatible".to_string()),
+                ..ProviderConfig::default()
+            },
+        );
+        assert!(config.validate().is_ok());
+        config.providers.insert(
+            "claude".to_string(),
+            ProviderConfig {
+                provider_type: Some("anthropic".to_string()),
+                ..ProviderConfig::default()
+            },
+        );
+        assert!(config.validate().is_err());
     }
 
     #[test]
@@ -490,13 +686,9 @@ providers:
             "test".to_string(),
             ProviderConfig {
                 api_key: Some("key".to_string()),
-                endpoint: None,
-                organization: None,
-                api_version: None,
-                default_model: None,
                 timeout_secs: 30,
                 max_retries: 2,
-                extended_thinking: false,
+                ..ProviderConfig::default()
             },
         );
 
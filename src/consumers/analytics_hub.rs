@@ -17,7 +17,8 @@
 
 use super::{ConsumerError, ConsumerResult, DataConsumer, RetryConfig};
 use crate::{
-    AggregatedMetrics, LatencyDistribution, RequestMetrics, SessionId, ThroughputStats,
+    AggregatedMetrics, EmbeddingStats, LatencyDistribution, ReasoningTokenStats, RequestMetrics,
+    RetryStats, SessionId, StreamReadStats, ThroughputStats,
 };
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
@@ -439,10 +440,30 @@ impl AnalyticsHubConsumer {
             total_latency_distribution: baseline
                 .total_latency_baseline
                 .to_latency_distribution(baseline.sample_count),
+            thinking_ttft_distribution: LatencyDistribution::empty(),
+            thinking_duration_distribution: LatencyDistribution::empty(),
+            visible_ttft_distribution: LatencyDistribution::empty(),
+            reasoning_time_distribution: LatencyDistribution::empty(),
+            reasoning_tokens: ReasoningTokenStats::empty(),
+            tool_call_ttft_distribution: LatencyDistribution::empty(),
+            tool_call_duration_distribution: LatencyDistribution::empty(),
+            ttft_cache_hit_distribution: LatencyDistribution::empty(),
+            ttft_cache_miss_distribution: LatencyDistribution::empty(),
+            time_to_first_byte_distribution: LatencyDistribution::empty(),
+            time_to_first_event_distribution: LatencyDistribution::empty(),
+            session_setup_distribution: LatencyDistribution::empty(),
+            response_ttft_distribution: LatencyDistribution::empty(),
+            server_processing_distribution: LatencyDistribution::empty(),
+            stream_reads: StreamReadStats::empty(),
+            retries: RetryStats::empty(),
+            embeddings: EmbeddingStats::empty(),
             throughput: baseline.throughput_baseline.to_throughput_stats(),
             total_input_tokens: 0, // Not tracked in baseline
             total_output_tokens: 0,
             total_thinking_tokens: None,
+            total_cache_creation_tokens: None,
+            total_cache_read_tokens: None,
+            estimated_token_requests: 0,
             total_cost_usd: baseline.cost_baseline.as_ref().map(|c| c.total_cost_usd),
             provider_breakdown: vec![(provider_enum, baseline.sample_count)],
             model_breakdown: vec![(baseline.model.clone(), baseline.sample_count)],
@@ -506,6 +527,7 @@ impl AnalyticsHubConsumer {
             "google" => Provider::Google,
             "aws-bedrock" | "bedrock" => Provider::AwsBedrock,
             "azure-openai" | "azure" => Provider::AzureOpenAI,
+            "exec" => Provider::Exec,
             _ => Provider::Generic,
         }
     }
@@ -187,7 +187,7 @@ impl MergedConsumer {
         }
 
         // Sort by timestamp for consistent ordering
-        all_metrics.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+        all_metrics.sort_by_key(|a| a.timestamp);
 
         Ok(all_metrics)
     }
@@ -19,6 +19,7 @@ use crate::{RequestMetrics, SessionId, RequestId};
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use llm_latency_lens_core::Provider;
+use llm_latency_lens_metrics::TokenCountSource;
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
@@ -165,6 +166,8 @@ pub struct ObservatoryConsumer {
     session_id: SessionId,
 }
 
+// `subscribe_telemetry_stream` is gated on a feature that is not defined yet
+#[allow(unexpected_cfgs)]
 impl ObservatoryConsumer {
     /// Create a new Observatory consumer with default configuration
     pub fn new() -> Self {
@@ -261,6 +264,39 @@ impl ObservatoryConsumer {
             input_tokens: span.attributes.gen_ai_usage_input_tokens.unwrap_or(0),
             output_tokens: span.attributes.gen_ai_usage_output_tokens.unwrap_or(0),
             thinking_tokens: None,
+            input_token_source: TokenCountSource::Reported,
+            output_token_source: TokenCountSource::Reported,
+            cache_creation_tokens: None,
+            cache_read_tokens: None,
+            cache_hit: None,
+            time_to_first_thinking_token: None,
+            thinking_duration: None,
+            time_to_first_visible_token: None,
+            reasoning_time: None,
+            time_to_first_tool_call: None,
+            tool_call_duration: None,
+            dns_time: None,
+            tcp_connect_time: None,
+            tls_handshake_time: None,
+            time_to_first_byte: None,
+            time_to_first_event: None,
+            session_setup_time: None,
+            response_ttft: None,
+            provider_request_id: None,
+            server_processing_time: None,
+            rate_limit_remaining_requests: None,
+            rate_limit_remaining_tokens: None,
+            attempts: None,
+            retry_backoff: None,
+            network_reads: None,
+            stream_events: None,
+            max_events_per_read: None,
+            response_body_bytes: None,
+            body_download_time: None,
+            body_parse_time: None,
+            embedding_inputs: None,
+            embedding_vectors: None,
+            embedding_dimensions: None,
             tokens_per_second,
             cost_usd: None,
             success: span.status.code == "OK",
@@ -290,6 +326,39 @@ impl ObservatoryConsumer {
             input_tokens: traced.input_tokens,
             output_tokens: traced.output_tokens,
             thinking_tokens: traced.thinking_tokens,
+            input_token_source: TokenCountSource::Reported,
+            output_token_source: TokenCountSource::Reported,
+            cache_creation_tokens: None,
+            cache_read_tokens: None,
+            cache_hit: None,
+            time_to_first_thinking_token: None,
+            thinking_duration: None,
+            time_to_first_visible_token: None,
+            reasoning_time: None,
+            time_to_first_tool_call: None,
+            tool_call_duration: None,
+            dns_time: None,
+            tcp_connect_time: None,
+            tls_handshake_time: None,
+            time_to_first_byte: None,
+            time_to_first_event: None,
+            session_setup_time: None,
+            response_ttft: None,
+            provider_request_id: None,
+            server_processing_time: None,
+            rate_limit_remaining_requests: None,
+            rate_limit_remaining_tokens: None,
+            attempts: None,
+            retry_backoff: None,
+            network_reads: None,
+            stream_events: None,
+            max_events_per_read: None,
+            response_body_bytes: None,
+            body_download_time: None,
+            body_parse_time: None,
+            embedding_inputs: None,
+            embedding_vectors: None,
+            embedding_dimensions: None,
             tokens_per_second,
             cost_usd: traced.cost_usd,
             success: traced.success,
@@ -305,6 +374,7 @@ impl ObservatoryConsumer {
             Some("google") | Some("Google") => Ok(Provider::Google),
             Some("aws-bedrock") | Some("bedrock") => Ok(Provider::AwsBedrock),
             Some("azure-openai") | Some("azure") => Ok(Provider::AzureOpenAI),
+            Some("exec") => Ok(Provider::Exec),
             Some(_) | None => Ok(Provider::Generic),
         }
     }
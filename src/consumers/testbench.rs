@@ -21,6 +21,7 @@ use super::{ConsumerError, ConsumerResult};
 use crate::{RequestMetrics, SessionId, RequestId};
 use chrono::{DateTime, Utc};
 use llm_latency_lens_core::Provider;
+use llm_latency_lens_metrics::TokenCountSource;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs::File;
@@ -279,7 +280,7 @@ impl TestBenchReader {
         let dir = dir.as_ref();
         if !dir.is_dir() {
             return Err(ConsumerError::IoError(std::io::Error::new(
-                std::io::ErrorKind::NotADirectory,
+                std::io::ErrorKind::InvalidInput,
                 format!("{} is not a directory", dir.display()),
             )));
         }
@@ -321,7 +322,7 @@ impl TestBenchReader {
         }
 
         // Sort by timestamp
-        all_metrics.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+        all_metrics.sort_by_key(|a| a.timestamp);
 
         Ok(all_metrics)
     }
@@ -373,6 +374,39 @@ impl TestBenchReader {
             input_tokens: tbm.input_tokens,
             output_tokens: tbm.output_tokens,
             thinking_tokens: tbm.thinking_tokens,
+            input_token_source: TokenCountSource::Reported,
+            output_token_source: TokenCountSource::Reported,
+            cache_creation_tokens: None,
+            cache_read_tokens: None,
+            cache_hit: None,
+            time_to_first_thinking_token: None,
+            thinking_duration: None,
+            time_to_first_visible_token: None,
+            reasoning_time: None,
+            time_to_first_tool_call: None,
+            tool_call_duration: None,
+            dns_time: None,
+            tcp_connect_time: None,
+            tls_handshake_time: None,
+            time_to_first_byte: None,
+            time_to_first_event: None,
+            session_setup_time: None,
+            response_ttft: None,
+            provider_request_id: None,
+            server_processing_time: None,
+            rate_limit_remaining_requests: None,
+            rate_limit_remaining_tokens: None,
+            attempts: None,
+            retry_backoff: None,
+            network_reads: None,
+            stream_events: None,
+            max_events_per_read: None,
+            response_body_bytes: None,
+            body_download_time: None,
+            body_parse_time: None,
+            embedding_inputs: None,
+            embedding_vectors: None,
+            embedding_dimensions: None,
             tokens_per_second,
             cost_usd: tbm.cost_usd,
             success: tbm.success,
@@ -388,6 +422,7 @@ impl TestBenchReader {
             "google" | "gemini" => Provider::Google,
             "aws-bedrock" | "bedrock" | "aws" => Provider::AwsBedrock,
             "azure-openai" | "azure" => Provider::AzureOpenAI,
+            "exec" => Provider::Exec,
             _ => Provider::Generic,
         }
     }
@@ -407,7 +442,7 @@ impl TestBenchReader {
         let mut failed_count = 0u64;
 
         for m in &metrics {
-            *providers.entry(m.provider.clone()).or_insert(0u64) += 1;
+            *providers.entry(m.provider).or_insert(0u64) += 1;
             *models.entry(m.model.clone()).or_insert(0u64) += 1;
             if m.success {
                 success_count += 1;
@@ -11,11 +11,8 @@
 //!
 //! #[tokio::main]
 //! async fn main() -> anyhow::Result<()> {
-//!     // Create a provider
-//!     let provider = OpenAIProvider::new("sk-...");
-//!
 //!     // Profile a single request
-//!     let metrics = ProfileBuilder::new(provider.clone())
+//!     let metrics = ProfileBuilder::new(OpenAIProvider::new("sk-..."))
 //!         .model("gpt-4o")
 //!         .prompt("Explain quantum computing")
 //!         .max_tokens(500)
@@ -26,7 +23,7 @@
 //!     println!("Total: {:?}", metrics.total_latency);
 //!
 //!     // Run a benchmark
-//!     let results = BenchmarkBuilder::new(provider)
+//!     let results = BenchmarkBuilder::new(OpenAIProvider::new("sk-..."))
 //!         .model("gpt-4o")
 //!         .prompt("Hello, world!")
 //!         .requests(10)
@@ -34,8 +31,8 @@
 //!         .execute()
 //!         .await?;
 //!
-//!     println!("Mean TTFT: {:?}", results.ttft_distribution.mean);
-//!     println!("P95 TTFT: {:?}", results.ttft_distribution.p95);
+//!     println!("Mean TTFT: {:?}", results.ttft_distribution().mean);
+//!     println!("P95 TTFT: {:?}", results.ttft_distribution().p95);
 //!
 //!     Ok(())
 //! }
@@ -45,6 +42,7 @@ pub mod adapters;
 pub mod benchmarks;
 pub mod config;
 pub mod consumers;
+pub mod mock_server;
 pub mod orchestrator;
 
 // Re-export core types for convenience
@@ -55,12 +53,14 @@ pub use llm_latency_lens_exporters::{
     ConsoleExporter, CsvExporter, Exporter, JsonExporter, PrometheusExporter,
 };
 pub use llm_latency_lens_metrics::{
-    AggregatedMetrics, CollectorConfig, LatencyDistribution, MetricsAggregator,
-    MetricsCollector, RequestMetrics, ThroughputStats,
+    AggregatedMetrics, CollectorConfig, EmbeddingStats, LatencyDistribution, MetricsAggregator,
+    MetricsCollector, ReasoningTokenStats, RequestMetrics, RetryStats, StreamReadStats,
+    ThroughputStats,
 };
 pub use llm_latency_lens_providers::{
-    AnthropicProvider, CompletionResult, GoogleProvider, Message, MessageRole,
-    OpenAIProvider, Provider, ResponseMetadata, StreamingRequest, StreamingResponse,
+    AnthropicProvider, CompletionResult, ContentPart, EmbeddingProvider, EmbeddingRequest,
+    EmbeddingResult, GoogleProvider, Message, MessageRole, OpenAIApi, OpenAIProvider, Provider,
+    ResponseMetadata, StreamingRequest, StreamingResponse,
 };
 
 // Re-export canonical benchmark interface
@@ -118,10 +118,7 @@ impl<P: Provider + 'static> ProfileBuilder<P> {
 
     /// Set a simple prompt
     pub fn prompt(mut self, prompt: impl Into<String>) -> Self {
-        self.messages = vec![Message {
-            role: MessageRole::User,
-            content: prompt.into(),
-        }];
+        self.messages = vec![Message::new(MessageRole::User, prompt)];
         self
     }
 
@@ -239,10 +236,7 @@ impl<P: Provider + 'static> BenchmarkBuilder<P> {
 
     /// Set a simple prompt
     pub fn prompt(mut self, prompt: impl Into<String>) -> Self {
-        self.messages = vec![Message {
-            role: MessageRole::User,
-            content: prompt.into(),
-        }];
+        self.messages = vec![Message::new(MessageRole::User, prompt)];
         self
     }
 
@@ -341,6 +335,7 @@ impl<P: Provider + 'static> BenchmarkBuilder<P> {
             rate_limit: self.rate_limit,
             show_progress: self.show_progress,
             shutdown_timeout: std::time::Duration::from_secs(30),
+            streaming: true,
         };
         let orchestrator = Orchestrator::new(config, shutdown);
 
@@ -398,8 +393,6 @@ impl BenchmarkResults {
 
 #[cfg(test)]
 mod tests {
-    use super::*;
-
     #[test]
     fn test_profile_builder() {
         // This is a compile-time test to ensure the builder API works
@@ -2,6 +2,9 @@
 //!
 //! This is the main entry point for the CLI application.
 
+// The profile command's JSON output nests deeper than `json!` expands by default
+#![recursion_limit = "256"]
+
 use anyhow::Result;
 use clap::Parser;
 use colored::Colorize;
@@ -9,11 +12,9 @@ use std::sync::Arc;
 use tokio::signal;
 use tracing::{error, info};
 
-mod adapters;
-mod benchmarks;
 mod cli;
-mod config;
-mod orchestrator;
+
+use llm_latency_lens::{benchmarks, config, orchestrator};
 
 use cli::{Cli, Commands};
 use config::Config;
@@ -48,13 +49,24 @@ async fn main() -> Result<()> {
             let config = Config::load(&args.config)?;
             cli::commands::compare::run(args, config, cli.json, cli.quiet, shutdown_signal).await
         }
+        Commands::Embed(args) => {
+            let config = Config::load(&args.config)?;
+            cli::commands::embed::run(args, config, cli.json, cli.quiet, shutdown_signal).await
+        }
         Commands::Validate(args) => {
             let config = Config::load(&args.config)?;
             cli::commands::validate::run(args, config, cli.json, cli.quiet).await
         }
+        Commands::Models(args) => {
+            let config = Config::load(&args.config)?;
+            cli::commands::models::run(args, config, cli.json, cli.quiet).await
+        }
         Commands::Export(args) => {
             cli::commands::export::run(args, cli.json, cli.quiet).await
         }
+        Commands::MockServer(args) => {
+            cli::commands::mock_server::run(args, cli.json, cli.quiet, shutdown_signal).await
+        }
         Commands::Run(args) => {
             let config = Config::load(&args.config)?;
             cli::commands::run::run(args, config, cli.json, cli.quiet).await
@@ -154,7 +166,7 @@ fn print_banner() {
         "LLM Latency Lens".bright_cyan().bold(),
         format!("v{}", env!("CARGO_PKG_VERSION")).bright_black(),
         "Enterprise-grade LLM performance profiler".bright_white(),
-        format!("Measure • Benchmark • Optimize").bright_green()
+        "Measure • Benchmark • Optimize".to_string().bright_green()
     );
 
     println!("{}", banner);
@@ -0,0 +1,400 @@
+//! Request parsing and response payloads of the mocked APIs
+
+use serde_json::{json, Value};
+
+/// An API the mock server speaks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Api {
+    /// OpenAI Chat Completions (also OpenAI-compatible servers and Azure)
+    OpenAI,
+    /// Anthropic Messages
+    Anthropic,
+    /// Gemini `generateContent`/`streamGenerateContent`
+    Gemini,
+}
+
+/// Failure injected in place of a completion
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Failure {
+    /// 429 with `Retry-After`
+    RateLimited,
+    /// 500
+    ServerError,
+}
+
+impl Failure {
+    /// HTTP status code
+    pub fn status(self) -> u16 {
+        match self {
+            Self::RateLimited => 429,
+            Self::ServerError => 500,
+        }
+    }
+}
+
+/// What a completion request asked for
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompletionRequest {
+    /// Requested model
+    pub model: String,
+    /// Whether the response is streamed
+    pub stream: bool,
+    /// Output token limit, if the request set one
+    pub max_tokens: Option<u64>,
+    /// Approximate prompt length in tokens
+    pub input_tokens: u64,
+    /// Report usage in a final stream chunk (OpenAI `stream_options`)
+    pub include_usage: bool,
+}
+
+impl CompletionRequest {
+    /// Read a request body; `model` and `stream` come from the URL for Gemini
+    pub fn parse(api: Api, body: &Value, model: Option<&str>, stream: bool) -> Self {
+        let u64_at = |pointer: &str| body.pointer(pointer).and_then(Value::as_u64);
+        let (prompt, max_tokens) = match api {
+            Api::OpenAI => (
+                &body["messages"],
+                u64_at("/max_completion_tokens").or_else(|| u64_at("/max_tokens")),
+            ),
+            Api::Anthropic => (&body["messages"], u64_at("/max_tokens")),
+            Api::Gemini => (
+                &body["contents"],
+                u64_at("/generationConfig/maxOutputTokens"),
+            ),
+        };
+
+        Self {
+            model: model
+                .map(str::to_string)
+                .or_else(|| body["model"].as_str().map(str::to_string))
+                .unwrap_or_default(),
+            stream: match api {
+                Api::Gemini => stream,
+                _ => body["stream"].as_bool().unwrap_or(false),
+            },
+            max_tokens,
+            // Roughly four characters per token
+            input_tokens: (text_len(prompt) as u64 / 4).max(1),
+            include_usage: body
+                .pointer("/stream_options/include_usage")
+                .and_then(Value::as_bool)
+                .unwrap_or(false),
+        }
+    }
+}
+
+/// Total length of the strings in a JSON value
+fn text_len(value: &Value) -> usize {
+    match value {
+        Value::String(s) => s.len(),
+        Value::Array(items) => items.iter().map(text_len).sum(),
+        Value::Object(fields) => fields.values().map(text_len).sum(),
+        _ => 0,
+    }
+}
+
+/// A generated completion, ready to be written in any API's format
+#[derive(Debug, Clone)]
+pub struct Completion {
+    /// Response ID
+    pub id: String,
+    /// Model echoed back
+    pub model: String,
+    /// Output tokens, in order
+    pub tokens: Vec<String>,
+    /// Prompt tokens reported in usage
+    pub input_tokens: u64,
+    /// Report usage in a final OpenAI stream chunk
+    pub include_usage: bool,
+}
+
+impl Completion {
+    fn text(&self) -> String {
+        self.tokens.concat()
+    }
+
+    fn output_tokens(&self) -> u64 {
+        self.tokens.len() as u64
+    }
+
+    /// Stream frames: the first carries the preamble and first token, one
+    /// frame per further token, then the closing events
+    pub fn stream_frames(&self, api: Api) -> Vec<String> {
+        let mut frames: Vec<String> = self
+            .tokens
+            .iter()
+            .map(|token| match api {
+                Api::OpenAI => data(&self.openai_chunk(json!({"content": token}), Value::Null)),
+                Api::Anthropic => event(
+                    "content_block_delta",
+                    &json!({
+                        "type": "content_block_delta",
+                        "index": 0,
+                        "delta": {"type": "text_delta", "text": token}
+                    }),
+                ),
+                Api::Gemini => data(&json!({
+                    "candidates": [{
+                        "content": {"role": "model", "parts": [{"text": token}]},
+                        "index": 0
+                    }]
+                })),
+            })
+            .collect();
+
+        let (preamble, trailer) = match api {
+            Api::OpenAI => {
+                let mut trailer = data(&self.openai_chunk(json!({}), json!("stop")));
+                if self.include_usage {
+                    trailer.push_str(&data(&json!({
+                        "id": self.id,
+                        "object": "chat.completion.chunk",
+                        "created": created(),
+                        "model": self.model,
+                        "choices": [],
+                        "usage": self.openai_usage()
+                    })));
+                }
+                trailer.push_str("data: [DONE]\n\n");
+                (
+                    data(
+                        &self
+                            .openai_chunk(json!({"role": "assistant", "content": ""}), Value::Null),
+                    ),
+                    trailer,
+                )
+            }
+            Api::Anthropic => (
+                event(
+                    "message_start",
+                    &json!({
+                        "type": "message_start",
+                        "message": {
+                            "id": self.id,
+                            "type": "message",
+                            "role": "assistant",
+                            "content": [],
+                            "model": self.model,
+                            "stop_reason": null,
+                            "usage": {"input_tokens": self.input_tokens, "output_tokens": 1}
+                        }
+                    }),
+                ) + &event(
+                    "content_block_start",
+                    &json!({
+                        "type": "content_block_start",
+                        "index": 0,
+                        "content_block": {"type": "text", "text": ""}
+                    }),
+                ),
+                event(
+                    "content_block_stop",
+                    &json!({"type": "content_block_stop", "index": 0}),
+                ) + &event(
+                    "message_delta",
+                    &json!({
+                        "type": "message_delta",
+                        "delta": {"stop_reason": "end_turn", "stop_sequence": null},
+                        "usage": {"output_tokens": self.output_tokens()}
+                    }),
+                ) + &event("message_stop", &json!({"type": "message_stop"})),
+            ),
+            Api::Gemini => (
+                String::new(),
+                data(&json!({
+                    "candidates": [{
+                        "content": {"role": "model", "parts": [{"text": ""}]},
+                        "finishReason": "STOP",
+                        "index": 0
+                    }],
+                    "usageMetadata": self.gemini_usage(),
+                    "modelVersion": self.model
+                })),
+            ),
+        };
+
+        if let Some(first) = frames.first_mut() {
+            first.insert_str(0, &preamble);
+        }
+        frames.push(trailer);
+        frames
+    }
+
+    /// Complete response body of a non-streaming request
+    pub fn body(&self, api: Api) -> Value {
+        match api {
+            Api::OpenAI => json!({
+                "id": self.id,
+                "object": "chat.completion",
+                "created": created(),
+                "model": self.model,
+                "choices": [{
+                    "index": 0,
+                    "message": {"role": "assistant", "content": self.text()},
+                    "finish_reason": "stop"
+                }],
+                "usage": self.openai_usage()
+            }),
+            Api::Anthropic => json!({
+                "id": self.id,
+                "type": "message",
+                "role": "assistant",
+                "model": self.model,
+                "content": [{"type": "text", "text": self.text()}],
+                "stop_reason": "end_turn",
+                "stop_sequence": null,
+                "usage": {"input_tokens": self.input_tokens, "output_tokens": self.output_tokens()}
+            }),
+            Api::Gemini => json!({
+                "candidates": [{
+                    "content": {"role": "model", "parts": [{"text": self.text()}]},
+                    "finishReason": "STOP",
+                    "index": 0
+                }],
+                "usageMetadata": self.gemini_usage(),
+                "modelVersion": self.model
+            }),
+        }
+    }
+
+    fn openai_chunk(&self, delta: Value, finish_reason: Value) -> Value {
+        json!({
+            "id": self.id,
+            "object": "chat.completion.chunk",
+            "created": created(),
+            "model": self.model,
+            "choices": [{"index": 0, "delta": delta, "finish_reason": finish_reason}]
+        })
+    }
+
+    fn openai_usage(&self) -> Value {
+        json!({
+            "prompt_tokens": self.input_tokens,
+            "completion_tokens": self.output_tokens(),
+            "total_tokens": self.input_tokens + self.output_tokens()
+        })
+    }
+
+    fn gemini_usage(&self) -> Value {
+        json!({
+            "promptTokenCount": self.input_tokens,
+            "candidatesTokenCount": self.output_tokens(),
+            "totalTokenCount": self.input_tokens + self.output_tokens()
+        })
+    }
+}
+
+/// Error body in the API's format
+pub fn error_body(api: Api, failure: Failure) -> Value {
+    let (message, openai_type, anthropic_type, gemini_status) = match failure {
+        Failure::RateLimited => (
+            "Rate limit reached (injected by mock server)",
+            "rate_limit_exceeded",
+            "rate_limit_error",
+            "RESOURCE_EXHAUSTED",
+        ),
+        Failure::ServerError => (
+            "Internal error (injected by mock server)",
+            "server_error",
+            "api_error",
+            "INTERNAL",
+        ),
+    };
+    match api {
+        Api::OpenAI => json!({"error": {"message": message, "type": openai_type, "code": null}}),
+        Api::Anthropic => json!({
+            "type": "error",
+            "error": {"type": anthropic_type, "message": message}
+        }),
+        Api::Gemini => json!({
+            "error": {"code": failure.status(), "message": message, "status": gemini_status}
+        }),
+    }
+}
+
+/// Models endpoint body in the API's format
+pub fn models_body(api: Api, models: &[String]) -> Value {
+    match api {
+        Api::Gemini => json!({
+            "models": models
+                .iter()
+                .map(|m| json!({
+                    "name": format!("models/{}", m),
+                    "supportedGenerationMethods": ["generateContent", "streamGenerateContent"]
+                }))
+                .collect::<Vec<_>>()
+        }),
+        // Anthropic lists models in the same shape
+        Api::OpenAI | Api::Anthropic => json!({
+            "object": "list",
+            "data": models
+                .iter()
+                .map(|m| json!({"id": m, "object": "model", "type": "model", "owned_by": "mock"}))
+                .collect::<Vec<_>>(),
+            "has_more": false
+        }),
+    }
+}
+
+fn data(value: &Value) -> String {
+    format!("data: {}\n\n", value)
+}
+
+fn event(name: &str, value: &Value) -> String {
+    format!("event: {}\ndata: {}\n\n", name, value)
+}
+
+fn created() -> i64 {
+    chrono::Utc::now().timestamp()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_request() {
+        let body = json!({
+            "model": "gpt-4o-mini",
+            "messages": [{"role": "user", "content": "Tell me a story about a lighthouse"}],
+            "stream": true,
+            "stream_options": {"include_usage": true},
+            "max_completion_tokens": 64
+        });
+        let request = CompletionRequest::parse(Api::OpenAI, &body, None, false);
+        assert_eq!(request.model, "gpt-4o-mini");
+        assert!(request.stream);
+        assert!(request.include_usage);
+        assert_eq!(request.max_tokens, Some(64));
+        assert!(request.input_tokens > 1);
+
+        let body = json!({
+            "contents": [{"role": "user", "parts": [{"text": "Hi"}]}],
+            "generationConfig": {"maxOutputTokens": 8}
+        });
+        let request = CompletionRequest::parse(Api::Gemini, &body, Some("gemini-1.5-flash"), true);
+        assert_eq!(request.model, "gemini-1.5-flash");
+        assert!(request.stream);
+        assert_eq!(request.max_tokens, Some(8));
+    }
+
+    #[test]
+    fn test_stream_frames() {
+        let completion = Completion {
+            id: "mock-1".to_string(),
+            model: "mock-model".to_string(),
+            tokens: vec!["Lorem".to_string(), " ipsum".to_string()],
+            input_tokens: 5,
+            include_usage: true,
+        };
+
+        let frames = completion.stream_frames(Api::Anthropic);
+        assert_eq!(frames.len(), 3);
+        assert!(frames[0].starts_with("event: message_start\n"));
+        assert!(frames[2].ends_with("event: message_stop\ndata: {\"type\":\"message_stop\"}\n\n"));
+
+        let frames = completion.stream_frames(Api::OpenAI);
+        assert!(frames[2].contains("\"completion_tokens\":2"));
+        assert!(frames[2].ends_with("data: [DONE]\n\n"));
+    }
+}
@@ -0,0 +1,202 @@
+//! Sampled latencies and output lengths
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// A distribution to sample from: milliseconds for latencies, tokens for
+/// output lengths
+///
+/// Parsed from a plain number (fixed), or `fixed:V`, `uniform:MIN,MAX`,
+/// `normal:MEAN,STD_DEV` and `lognormal:MEDIAN,SIGMA`. Samples below zero
+/// are clamped to zero.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Distribution {
+    /// Always the same value
+    Fixed {
+        /// The value
+        value: f64,
+    },
+    /// Uniform between `min` and `max`
+    Uniform {
+        /// Lower bound
+        min: f64,
+        /// Upper bound
+        max: f64,
+    },
+    /// Normal (Gaussian)
+    Normal {
+        /// Mean
+        mean: f64,
+        /// Standard deviation
+        std_dev: f64,
+    },
+    /// Log-normal, the usual shape of real TTFT and ITL: most samples near
+    /// the median with a long tail
+    LogNormal {
+        /// Median
+        median: f64,
+        /// Standard deviation of the underlying normal
+        sigma: f64,
+    },
+}
+
+impl Distribution {
+    /// A distribution that always yields `value`
+    pub fn fixed(value: f64) -> Self {
+        Self::Fixed { value }
+    }
+
+    /// Draw a value (never negative)
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        let value = match *self {
+            Self::Fixed { value } => value,
+            Self::Uniform { min, max } if max > min => rng.gen_range(min..=max),
+            Self::Uniform { min, .. } => min,
+            Self::Normal { mean, std_dev } => mean + std_dev * standard_normal(rng),
+            Self::LogNormal { median, sigma } => median * (sigma * standard_normal(rng)).exp(),
+        };
+        value.max(0.0)
+    }
+
+    /// Draw a latency, reading values as milliseconds
+    pub fn sample_duration<R: Rng + ?Sized>(&self, rng: &mut R) -> Duration {
+        Duration::from_secs_f64(self.sample(rng) / 1000.0)
+    }
+}
+
+/// Standard normal sample (Box-Muller)
+fn standard_normal<R: Rng + ?Sized>(rng: &mut R) -> f64 {
+    // 1 - [0, 1) keeps the logarithm finite
+    let u1: f64 = 1.0 - rng.gen::<f64>();
+    let u2: f64 = rng.gen();
+    (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+}
+
+impl FromStr for Distribution {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let number = |v: &str| {
+            v.trim()
+                .parse::<f64>()
+                .ok()
+                .filter(|v| v.is_finite() && *v >= 0.0)
+                .ok_or_else(|| format!("Invalid value '{}' in '{}'", v, s))
+        };
+        let pair = |args: &str| {
+            let (a, b) = args
+                .split_once(',')
+                .ok_or_else(|| format!("Expected two comma-separated values in '{}'", s))?;
+            Ok::<_, String>((number(a)?, number(b)?))
+        };
+
+        let Some((kind, args)) = s.split_once(':') else {
+            return Ok(Self::fixed(number(s)?));
+        };
+        match kind.trim() {
+            "fixed" => Ok(Self::fixed(number(args)?)),
+            "uniform" => {
+                let (min, max) = pair(args)?;
+                if max < min {
+                    return Err(format!("Uniform maximum is below the minimum in '{}'", s));
+                }
+                Ok(Self::Uniform { min, max })
+            }
+            "normal" => {
+                let (mean, std_dev) = pair(args)?;
+                Ok(Self::Normal { mean, std_dev })
+            }
+            "lognormal" => {
+                let (median, sigma) = pair(args)?;
+                Ok(Self::LogNormal { median, sigma })
+            }
+            other => Err(format!(
+                "Unknown distribution '{}' (expected fixed, uniform, normal or lognormal)",
+                other
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Distribution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Fixed { value } => write!(f, "{}", value),
+            Self::Uniform { min, max } => write!(f, "uniform:{},{}", min, max),
+            Self::Normal { mean, std_dev } => write!(f, "normal:{},{}", mean, std_dev),
+            Self::LogNormal { median, sigma } => write!(f, "lognormal:{},{}", median, sigma),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!("250".parse(), Ok(Distribution::fixed(250.0)));
+        assert_eq!("fixed:20".parse(), Ok(Distribution::fixed(20.0)));
+        assert_eq!(
+            "uniform:50,200".parse(),
+            Ok(Distribution::Uniform {
+                min: 50.0,
+                max: 200.0
+            })
+        );
+        assert_eq!(
+            "lognormal:300, 0.5".parse(),
+            Ok(Distribution::LogNormal {
+                median: 300.0,
+                sigma: 0.5
+            })
+        );
+        assert!("uniform:200,50".parse::<Distribution>().is_err());
+        assert!("normal:100".parse::<Distribution>().is_err());
+        assert!("gamma:1,2".parse::<Distribution>().is_err());
+        assert!("-5".parse::<Distribution>().is_err());
+
+        let normal = Distribution::Normal {
+            mean: 20.0,
+            std_dev: 5.0,
+        };
+        assert_eq!(normal.to_string().parse(), Ok(normal));
+    }
+
+    #[test]
+    fn test_sample() {
+        let mut rng = StdRng::seed_from_u64(7);
+        assert_eq!(Distribution::fixed(40.0).sample(&mut rng), 40.0);
+        assert_eq!(
+            Distribution::fixed(1500.0).sample_duration(&mut rng),
+            Duration::from_millis(1500)
+        );
+
+        let uniform = Distribution::Uniform {
+            min: 10.0,
+            max: 20.0,
+        };
+        assert!((0..100).all(|_| (10.0..=20.0).contains(&uniform.sample(&mut rng))));
+
+        // Samples are clamped at zero
+        let wide = Distribution::Normal {
+            mean: 0.0,
+            std_dev: 100.0,
+        };
+        assert!((0..100).all(|_| wide.sample(&mut rng) >= 0.0));
+
+        let lognormal = Distribution::LogNormal {
+            median: 100.0,
+            sigma: 0.5,
+        };
+        let mut samples: Vec<f64> = (0..2001).map(|_| lognormal.sample(&mut rng)).collect();
+        samples.sort_by(f64::total_cmp);
+        assert!((80.0..125.0).contains(&samples[1000]));
+    }
+}
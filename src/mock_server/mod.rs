@@ -0,0 +1,627 @@
+//! Mock LLM server for offline and CI benchmarking
+//!
+//! Serves the streaming and non-streaming completion endpoints of the
+//! OpenAI Chat Completions, Anthropic Messages and Gemini APIs, plus their
+//! models endpoints, so every HTTP adapter and the orchestrator can be run
+//! end to end without network access or API keys:
+//!
+//! | Route | API |
+//! |-------|-----|
+//! | `POST …/chat/completions` | OpenAI (also OpenAI-compatible servers and Azure deployments) |
+//! | `POST …/messages` | Anthropic |
+//! | `POST …/models/{model}:streamGenerateContent` and `:generateContent` | Gemini |
+//! | `GET …/models` | Models list in the caller's format |
+//!
+//! Time to first token, inter-token latency and output length are sampled
+//! from [`Distribution`]s; requests can be failed with injected 429 (with
+//! `Retry-After`) and 500 responses, and streams cut off midway. Any path
+//! prefix is accepted, so the server is used as the base URL of a provider
+//! (`http://HOST:PORT/v1` for OpenAI and Anthropic, `/v1beta` for Gemini).
+
+mod api;
+mod distribution;
+
+pub use distribution::Distribution;
+
+use anyhow::{Context, Result};
+use bytes::Bytes;
+use futures::channel::mpsc;
+use http_body_util::combinators::BoxBody;
+use http_body_util::{BodyExt, Full, StreamBody};
+use hyper::body::{Frame, Incoming};
+use hyper::header::{HeaderValue, CACHE_CONTROL, CONTENT_TYPE, RETRY_AFTER};
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper::{Method, Request, Response, StatusCode};
+use hyper_util::rt::TokioIo;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::convert::Infallible;
+use std::io;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+use tracing::{debug, warn};
+
+use api::{Api, Completion, CompletionRequest, Failure};
+
+/// Model listed and echoed back when none is configured or requested
+pub const DEFAULT_MODEL: &str = "mock-model";
+
+/// Pause after a failed accept before trying again
+const ACCEPT_ERROR_BACKOFF: Duration = Duration::from_millis(100);
+
+/// Words cycled through to make up the generated text
+const WORDS: &[&str] = &[
+    "Lorem",
+    "ipsum",
+    "dolor",
+    "sit",
+    "amet",
+    "consectetur",
+    "adipiscing",
+    "elit",
+    "sed",
+    "do",
+    "eiusmod",
+    "tempor",
+    "incididunt",
+    "ut",
+    "labore",
+    "et",
+    "dolore",
+    "magna",
+    "aliqua",
+];
+
+type Body = BoxBody<Bytes, io::Error>;
+
+/// Behaviour of the mock server
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MockServerConfig {
+    /// Time to first token, in milliseconds
+    pub ttft: Distribution,
+    /// Delay between consecutive tokens, in milliseconds
+    pub itl: Distribution,
+    /// Output length in tokens, capped by the request's max tokens
+    pub output_tokens: Distribution,
+    /// Fraction of completion requests failed with a 500
+    pub error_rate: f64,
+    /// Fraction of completion requests rejected with a 429
+    pub rate_limit_rate: f64,
+    /// Fraction of streams cut off after about half of their tokens
+    pub disconnect_rate: f64,
+    /// `Retry-After` seconds sent with 429 responses
+    pub retry_after: u64,
+    /// Models listed by the models endpoints
+    pub models: Vec<String>,
+    /// Seed for reproducible samples and failures
+    pub seed: Option<u64>,
+}
+
+impl Default for MockServerConfig {
+    fn default() -> Self {
+        Self {
+            ttft: Distribution::fixed(200.0),
+            itl: Distribution::fixed(20.0),
+            output_tokens: Distribution::fixed(100.0),
+            error_rate: 0.0,
+            rate_limit_rate: 0.0,
+            disconnect_rate: 0.0,
+            retry_after: 1,
+            models: vec![DEFAULT_MODEL.to_string()],
+            seed: None,
+        }
+    }
+}
+
+impl MockServerConfig {
+    /// Check that rates are fractions and at least one model is listed
+    pub fn validate(&self) -> Result<()> {
+        for (name, rate) in [
+            ("error rate", self.error_rate),
+            ("rate limit rate", self.rate_limit_rate),
+            ("disconnect rate", self.disconnect_rate),
+        ] {
+            if !(0.0..=1.0).contains(&rate) {
+                anyhow::bail!("Mock server {} must be between 0.0 and 1.0", name);
+            }
+        }
+        if self.models.is_empty() {
+            anyhow::bail!("Mock server needs at least one model");
+        }
+        Ok(())
+    }
+}
+
+/// Requests served so far
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MockServerStats {
+    /// Completion requests received
+    pub requests: u64,
+    /// Completions sent in full
+    pub completed: u64,
+    /// Requests rejected with a 429
+    pub rate_limited: u64,
+    /// Requests failed with a 500
+    pub errors: u64,
+    /// Streams cut off midway
+    pub disconnects: u64,
+}
+
+#[derive(Default)]
+struct Counters {
+    requests: AtomicU64,
+    completed: AtomicU64,
+    rate_limited: AtomicU64,
+    errors: AtomicU64,
+    disconnects: AtomicU64,
+}
+
+impl Counters {
+    fn snapshot(&self) -> MockServerStats {
+        MockServerStats {
+            requests: self.requests.load(Ordering::Relaxed),
+            completed: self.completed.load(Ordering::Relaxed),
+            rate_limited: self.rate_limited.load(Ordering::Relaxed),
+            errors: self.errors.load(Ordering::Relaxed),
+            disconnects: self.disconnects.load(Ordering::Relaxed),
+        }
+    }
+}
+
+fn bump(counter: &AtomicU64) {
+    counter.fetch_add(1, Ordering::Relaxed);
+}
+
+struct State {
+    config: MockServerConfig,
+    rng: Mutex<StdRng>,
+    counters: Counters,
+    next_id: AtomicU64,
+}
+
+/// How a completion request will be answered
+struct Plan {
+    failure: Option<Failure>,
+    disconnect: bool,
+    tokens: usize,
+    ttft: Duration,
+    itls: Vec<Duration>,
+}
+
+impl State {
+    fn plan(&self, request: &CompletionRequest) -> Plan {
+        let config = &self.config;
+        let mut rng = self.rng.lock().unwrap_or_else(|e| e.into_inner());
+
+        let failure = if rng.gen_bool(config.rate_limit_rate) {
+            Some(Failure::RateLimited)
+        } else if rng.gen_bool(config.error_rate) {
+            Some(Failure::ServerError)
+        } else {
+            None
+        };
+
+        let mut tokens = (config.output_tokens.sample(&mut *rng).round() as u64).max(1);
+        if let Some(max_tokens) = request.max_tokens {
+            tokens = tokens.min(max_tokens.max(1));
+        }
+        let tokens = tokens as usize;
+
+        Plan {
+            failure,
+            disconnect: request.stream && rng.gen_bool(config.disconnect_rate),
+            tokens,
+            ttft: config.ttft.sample_duration(&mut *rng),
+            itls: (1..tokens)
+                .map(|_| config.itl.sample_duration(&mut *rng))
+                .collect(),
+        }
+    }
+}
+
+/// A running mock server, stopped when dropped
+pub struct MockServer {
+    addr: SocketAddr,
+    state: Arc<State>,
+    task: JoinHandle<()>,
+}
+
+impl MockServer {
+    /// Bind `addr` (port 0 picks a free port) and start serving
+    pub async fn start(addr: SocketAddr, config: MockServerConfig) -> Result<Self> {
+        config.validate()?;
+
+        let listener = TcpListener::bind(addr)
+            .await
+            .with_context(|| format!("Failed to bind mock server to {}", addr))?;
+        let addr = listener.local_addr()?;
+
+        let rng = match config.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        let state = Arc::new(State {
+            config,
+            rng: Mutex::new(rng),
+            counters: Counters::default(),
+            next_id: AtomicU64::new(0),
+        });
+
+        let task = tokio::spawn(accept_loop(listener, Arc::clone(&state)));
+
+        Ok(Self { addr, state, task })
+    }
+
+    /// Address the server listens on
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Base URL of the server, without an API prefix
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// Requests served so far
+    pub fn stats(&self) -> MockServerStats {
+        self.state.counters.snapshot()
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+async fn accept_loop(listener: TcpListener, state: Arc<State>) {
+    loop {
+        let stream = match listener.accept().await {
+            Ok((stream, _)) => stream,
+            Err(e) => {
+                warn!("Mock server failed to accept a connection: {}", e);
+                // Usually out of file descriptors; back off instead of spinning
+                tokio::time::sleep(ACCEPT_ERROR_BACKOFF).await;
+                continue;
+            }
+        };
+
+        let state = Arc::clone(&state);
+        tokio::spawn(async move {
+            let service = service_fn(move |request| handle(Arc::clone(&state), request));
+            if let Err(e) = http1::Builder::new()
+                .serve_connection(TokioIo::new(stream), service)
+                .await
+            {
+                debug!("Mock server connection closed: {}", e);
+            }
+        });
+    }
+}
+
+async fn handle(
+    state: Arc<State>,
+    request: Request<Incoming>,
+) -> Result<Response<Body>, Infallible> {
+    let id = format!("mock-{}", state.next_id.fetch_add(1, Ordering::Relaxed) + 1);
+    let mut response = route(&state, request, &id).await;
+    if let Ok(value) = HeaderValue::from_str(&id) {
+        response.headers_mut().insert("x-request-id", value);
+    }
+    Ok(response)
+}
+
+async fn route(state: &Arc<State>, request: Request<Incoming>, id: &str) -> Response<Body> {
+    let path = request.uri().path().to_string();
+
+    if request.method() == Method::GET && path.ends_with("/models") {
+        let headers = request.headers();
+        let api = if headers.contains_key("x-goog-api-key") || path.starts_with("/v1beta") {
+            Api::Gemini
+        } else if headers.contains_key("anthropic-version") {
+            Api::Anthropic
+        } else {
+            Api::OpenAI
+        };
+        return json_response(StatusCode::OK, &api::models_body(api, &state.config.models));
+    }
+    if request.method() != Method::POST {
+        return not_found(&path);
+    }
+
+    let (api, model, stream) = if path.ends_with("/chat/completions") {
+        (Api::OpenAI, None, false)
+    } else if path.ends_with("/messages") {
+        (Api::Anthropic, None, false)
+    } else {
+        let Some((prefix, action)) = path.rsplit_once(':') else {
+            return not_found(&path);
+        };
+        let model = prefix.rsplit('/').next().map(str::to_string);
+        match action {
+            "streamGenerateContent" => (Api::Gemini, model, true),
+            "generateContent" => (Api::Gemini, model, false),
+            _ => return not_found(&path),
+        }
+    };
+
+    let body = match request.into_body().collect().await {
+        Ok(body) => serde_json::from_slice::<Value>(&body.to_bytes()).ok(),
+        Err(_) => None,
+    };
+    let Some(body) = body else {
+        return json_response(
+            StatusCode::BAD_REQUEST,
+            &json!({"error": {"message": "Request body is not valid JSON", "type": "invalid_request_error"}}),
+        );
+    };
+
+    let mut request = CompletionRequest::parse(api, &body, model.as_deref(), stream);
+    if request.model.is_empty() {
+        request.model = state.config.models[0].clone();
+    }
+    bump(&state.counters.requests);
+
+    let plan = state.plan(&request);
+    match plan.failure {
+        Some(Failure::RateLimited) => {
+            bump(&state.counters.rate_limited);
+            let mut response = json_response(
+                StatusCode::TOO_MANY_REQUESTS,
+                &api::error_body(api, Failure::RateLimited),
+            );
+            response
+                .headers_mut()
+                .insert(RETRY_AFTER, HeaderValue::from(state.config.retry_after));
+            return response;
+        }
+        Some(Failure::ServerError) => {
+            bump(&state.counters.errors);
+            return json_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                &api::error_body(api, Failure::ServerError),
+            );
+        }
+        None => {}
+    }
+
+    let completion = Completion {
+        id: id.to_string(),
+        model: request.model.clone(),
+        tokens: (0..plan.tokens)
+            .map(|i| match i {
+                0 => WORDS[0].to_string(),
+                _ => format!(" {}", WORDS[i % WORDS.len()]),
+            })
+            .collect(),
+        input_tokens: request.input_tokens,
+        include_usage: request.include_usage,
+    };
+
+    if !request.stream {
+        tokio::time::sleep(plan.ttft + plan.itls.iter().sum::<Duration>()).await;
+        bump(&state.counters.completed);
+        return json_response(StatusCode::OK, &completion.body(api));
+    }
+
+    let frames = completion.stream_frames(api);
+    let (tx, rx) = mpsc::unbounded();
+    let state = Arc::clone(state);
+    tokio::spawn(async move {
+        // Frame 0 waits for the first token, token frames for the gap since
+        // the previous one; the trailer follows the last token immediately
+        let delays: Vec<Duration> = std::iter::once(plan.ttft).chain(plan.itls).collect();
+        let cut_at = plan.disconnect.then_some(plan.tokens / 2);
+
+        for (i, frame) in frames.into_iter().enumerate() {
+            if let Some(delay) = delays.get(i) {
+                tokio::time::sleep(*delay).await;
+            }
+            if cut_at == Some(i) {
+                bump(&state.counters.disconnects);
+                let _ = tx.unbounded_send(Err(io::Error::new(
+                    io::ErrorKind::ConnectionAborted,
+                    "stream cut off by mock server",
+                )));
+                return;
+            }
+            if tx
+                .unbounded_send(Ok(Frame::data(Bytes::from(frame))))
+                .is_err()
+            {
+                // The client went away
+                return;
+            }
+        }
+        bump(&state.counters.completed);
+    });
+
+    let mut response = Response::new(BodyExt::boxed(StreamBody::new(rx)));
+    let headers = response.headers_mut();
+    headers.insert(CONTENT_TYPE, HeaderValue::from_static("text/event-stream"));
+    headers.insert(CACHE_CONTROL, HeaderValue::from_static("no-cache"));
+    response
+}
+
+fn json_response(status: StatusCode, body: &Value) -> Response<Body> {
+    let body = Full::new(Bytes::from(body.to_string()))
+        .map_err(|never| match never {})
+        .boxed();
+    let mut response = Response::new(body);
+    *response.status_mut() = status;
+    response
+        .headers_mut()
+        .insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    response
+}
+
+fn not_found(path: &str) -> Response<Body> {
+    json_response(
+        StatusCode::NOT_FOUND,
+        &json!({"error": {"message": format!("No mock route for {}", path), "type": "not_found"}}),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use llm_latency_lens_core::TimingEngine;
+    use llm_latency_lens_providers::{
+        AnthropicProvider, GoogleProvider, MessageRole, OpenAIProvider, Provider, ProviderError,
+        StreamingRequest,
+    };
+
+    fn instant(output_tokens: f64) -> MockServerConfig {
+        MockServerConfig {
+            ttft: Distribution::fixed(0.0),
+            itl: Distribution::fixed(0.0),
+            output_tokens: Distribution::fixed(output_tokens),
+            seed: Some(1),
+            ..Default::default()
+        }
+    }
+
+    async fn start(config: MockServerConfig) -> MockServer {
+        MockServer::start("127.0.0.1:0".parse().unwrap(), config)
+            .await
+            .unwrap()
+    }
+
+    fn request(max_tokens: u32) -> StreamingRequest {
+        StreamingRequest::builder()
+            .model("mock-model")
+            .message(MessageRole::User, "Count to ten")
+            .max_tokens(max_tokens)
+            .build()
+    }
+
+    async fn openai(server: &MockServer, max_retries: u32) -> OpenAIProvider {
+        let provider = OpenAIProvider::builder()
+            .api_key("mock")
+            .base_url(format!("{}/v1", server.url()))
+            .max_retries(max_retries)
+            .build();
+        provider.discover_models().await.unwrap();
+        provider
+    }
+
+    #[tokio::test]
+    async fn test_serves_every_api() {
+        let server = start(instant(5.0)).await;
+        let providers: Vec<Box<dyn Provider>> = vec![
+            Box::new(openai(&server, 0).await),
+            Box::new(
+                AnthropicProvider::builder()
+                    .api_key("mock")
+                    .base_url(format!("{}/v1", server.url()))
+                    .build(),
+            ),
+            Box::new(
+                GoogleProvider::builder()
+                    .api_key("mock")
+                    .base_url(format!("{}/v1beta", server.url()))
+                    .build(),
+            ),
+        ];
+
+        for provider in &providers {
+            // Adapters accept unlisted models once discovered, as the
+            // orchestrator does before benchmarking
+            assert_eq!(
+                provider.discover_models().await.unwrap(),
+                vec![DEFAULT_MODEL]
+            );
+
+            let timing = TimingEngine::new();
+            let streamed = provider.complete(request(64), &timing).await.unwrap();
+            assert_eq!(streamed.token_events.len(), 5, "{}", provider.name());
+            assert_eq!(streamed.content, "Lorem ipsum dolor sit amet");
+            assert_eq!(streamed.metadata.output_tokens, Some(5));
+            assert!(streamed.metadata.input_tokens.is_some());
+
+            let full = provider
+                .complete_non_streaming(request(3), &timing)
+                .await
+                .unwrap();
+            assert_eq!(full.content, "Lorem ipsum dolor", "{}", provider.name());
+            assert_eq!(full.metadata.output_tokens, Some(3));
+        }
+
+        let stats = server.stats();
+        assert_eq!(stats.requests, 6);
+        assert_eq!(stats.completed, 6);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limits_are_retried() {
+        let server = start(MockServerConfig {
+            rate_limit_rate: 1.0,
+            retry_after: 0,
+            ..instant(5.0)
+        })
+        .await;
+
+        let error = openai(&server, 2)
+            .await
+            .complete(request(64), &TimingEngine::new())
+            .await
+            .unwrap_err();
+        assert!(matches!(error, ProviderError::RateLimitError { .. }));
+
+        let stats = server.stats();
+        assert_eq!(stats.requests, 3);
+        assert_eq!(stats.rate_limited, 3);
+        assert_eq!(stats.completed, 0);
+    }
+
+    #[tokio::test]
+    async fn test_disconnect_fails_stream() {
+        let server = start(MockServerConfig {
+            disconnect_rate: 1.0,
+            ..instant(10.0)
+        })
+        .await;
+
+        let result = openai(&server, 0)
+            .await
+            .complete(request(64), &TimingEngine::new())
+            .await;
+        assert!(result.is_err());
+        assert_eq!(server.stats().disconnects, 1);
+    }
+
+    #[tokio::test]
+    async fn test_unknown_route() {
+        let server = start(instant(5.0)).await;
+        let response = reqwest::Client::new()
+            .post(format!("{}/v1/audio/speech", server.url()))
+            .body("{}")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 404);
+        assert!(response.headers().contains_key("x-request-id"));
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(MockServerConfig::default().validate().is_ok());
+        let config = MockServerConfig {
+            error_rate: 1.5,
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+        let config = MockServerConfig {
+            models: Vec::new(),
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+    }
+}
@@ -4,6 +4,7 @@ use anyhow::{Context, Result};
 use futures::stream::{FuturesUnordered, StreamExt};
 use governor::{Quota, RateLimiter};
 use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+use std::future::Future;
 use std::num::NonZeroU32;
 use std::sync::Arc;
 use std::time::Duration;
@@ -12,9 +13,10 @@ use tokio::time::Instant;
 use tracing::{debug, info, warn};
 
 use llm_latency_lens_core::{RequestId, SessionId, TimingEngine};
-use llm_latency_lens_metrics::{MetricsCollector, RequestMetrics};
+use llm_latency_lens_metrics::{MetricsCollector, RequestMetrics, TokenCountSource};
 use llm_latency_lens_providers::{
-    Provider, StreamingRequest,
+    EmbeddingProvider, EmbeddingRequest, PriceTable, PricingMode, Provider, StreamingRequest,
+    Tokenizer,
 };
 
 /// Configuration for the orchestrator
@@ -30,6 +32,8 @@ pub struct OrchestratorConfig {
     pub show_progress: bool,
     /// Graceful shutdown timeout
     pub shutdown_timeout: Duration,
+    /// Stream responses (false sends `stream: false` requests)
+    pub streaming: bool,
 }
 
 impl Default for OrchestratorConfig {
@@ -40,6 +44,7 @@ impl Default for OrchestratorConfig {
             rate_limit: 0,
             show_progress: true,
             shutdown_timeout: Duration::from_secs(30),
+            streaming: true,
         }
     }
 }
@@ -50,8 +55,13 @@ pub struct Orchestrator {
     timing_engine: Arc<TimingEngine>,
     session_id: SessionId,
     shutdown_signal: Arc<tokio::sync::Notify>,
+    pricing: Option<Pricing>,
+    tokenizer: Option<Arc<dyn Tokenizer>>,
 }
 
+/// Price table and billing mode requests are priced with
+type Pricing = (Arc<PriceTable>, PricingMode);
+
 impl Orchestrator {
     /// Create a new orchestrator
     pub fn new(
@@ -63,21 +73,108 @@ impl Orchestrator {
             timing_engine: Arc::new(TimingEngine::new()),
             session_id: SessionId::new(),
             shutdown_signal,
+            pricing: None,
+            tokenizer: None,
         }
     }
 
+    /// Price requests from the given table and billing mode
+    ///
+    /// Without a table, each request keeps the cost its provider estimated
+    /// from the bundled prices at standard rates.
+    pub fn with_pricing(mut self, table: PriceTable, mode: PricingMode) -> Self {
+        self.pricing = Some((Arc::new(table), mode));
+        self
+    }
+
+    /// Count tokens locally when the provider reports no usage
+    ///
+    /// Without a tokenizer, unreported output tokens fall back to the number
+    /// of streamed events and unreported input tokens are recorded as 0.
+    pub fn with_tokenizer(mut self, tokenizer: Arc<dyn Tokenizer>) -> Self {
+        self.tokenizer = Some(tokenizer);
+        self
+    }
+
     /// Get the session ID
     pub fn session_id(&self) -> SessionId {
         self.session_id
     }
 
     /// Execute multiple requests with the given provider
-    pub async fn execute<P: Provider + 'static>(
+    pub async fn execute<P: Provider + ?Sized + 'static>(
         &self,
         provider: Arc<P>,
         request_template: StreamingRequest,
         collector: Arc<MetricsCollector>,
     ) -> Result<ExecutionSummary> {
+        let session_id = self.session_id;
+        let streaming = self.config.streaming;
+        let pricing = self.pricing.clone();
+        let tokenizer = self.tokenizer.clone();
+
+        discover_model(provider.as_ref(), &request_template.model).await;
+
+        self.run(collector, move |timing_engine| {
+            let provider = Arc::clone(&provider);
+            let pricing = pricing.clone();
+            let tokenizer = tokenizer.clone();
+
+            // Clone request template and assign new ID
+            let mut request = request_template.clone();
+            request.request_id = RequestId::new();
+            request.session_id = session_id;
+
+            async move {
+                execute_single_request(
+                    provider.as_ref(),
+                    request,
+                    &timing_engine,
+                    streaming,
+                    pricing.as_ref(),
+                    tokenizer.as_deref(),
+                )
+                .await
+            }
+        })
+        .await
+    }
+
+    /// Execute multiple embeddings requests with the given provider
+    pub async fn execute_embeddings<P: EmbeddingProvider + ?Sized + 'static>(
+        &self,
+        provider: Arc<P>,
+        request_template: EmbeddingRequest,
+        collector: Arc<MetricsCollector>,
+    ) -> Result<ExecutionSummary> {
+        let session_id = self.session_id;
+
+        self.run(collector, move |timing_engine| {
+            let provider = Arc::clone(&provider);
+
+            // Clone request template and assign new ID
+            let mut request = request_template.clone();
+            request.request_id = RequestId::new();
+            request.session_id = session_id;
+
+            async move { execute_single_embedding(provider.as_ref(), request, &timing_engine).await }
+        })
+        .await
+    }
+
+    /// Run the configured number of requests with concurrency and rate limits
+    ///
+    /// `request` creates the future for one request; it is polled only once
+    /// the rate limiter and the concurrency semaphore allow it.
+    async fn run<F, Fut>(
+        &self,
+        collector: Arc<MetricsCollector>,
+        request: F,
+    ) -> Result<ExecutionSummary>
+    where
+        F: Fn(Arc<TimingEngine>) -> Fut,
+        Fut: Future<Output = Result<RequestMetrics>> + Send + 'static,
+    {
         info!(
             "Starting orchestration: {} requests with concurrency {}",
             self.config.total_requests, self.config.concurrency
@@ -120,32 +217,28 @@ impl Orchestrator {
         };
 
         // Track execution statistics
-        let mut summary = ExecutionSummary::default();
-        summary.total_requests = self.config.total_requests;
+        let mut summary = ExecutionSummary {
+            total_requests: self.config.total_requests,
+            ..Default::default()
+        };
 
         // Create tasks for all requests
         let mut tasks = FuturesUnordered::new();
 
         for i in 0..self.config.total_requests {
-            let provider = Arc::clone(&provider);
-            let timing_engine = Arc::clone(&self.timing_engine);
+            let request = request(Arc::clone(&self.timing_engine));
             let collector = Arc::clone(&collector);
             let semaphore = Arc::clone(&semaphore);
             let rate_limiter = rate_limiter.clone();
             let progress_bar = progress_bar.clone();
             let shutdown_signal = Arc::clone(&self.shutdown_signal);
 
-            // Clone request template and assign new ID
-            let mut request = request_template.clone();
-            request.request_id = RequestId::new();
-            request.session_id = self.session_id;
-
             let task = tokio::spawn(async move {
                 // Check for shutdown signal
                 tokio::select! {
                     _ = shutdown_signal.notified() => {
                         debug!("Request {} cancelled due to shutdown", i);
-                        return Err(anyhow::anyhow!("Cancelled"));
+                        Err(anyhow::anyhow!("Cancelled"))
                     }
                     result = async {
                         // Apply rate limiting
@@ -159,12 +252,7 @@ impl Orchestrator {
                         debug!("Starting request {}", i);
 
                         // Execute request
-                        let result = execute_single_request(
-                            provider.as_ref(),
-                            request,
-                            &timing_engine,
-                        )
-                        .await;
+                        let result = request.await;
 
                         // Record metrics
                         if let Ok(ref metrics) = result {
@@ -224,35 +312,67 @@ impl Orchestrator {
     }
 
     /// Execute a single request (useful for profiling)
-    pub async fn execute_single<P: Provider>(
+    pub async fn execute_single<P: Provider + ?Sized>(
         &self,
         provider: &P,
         request: StreamingRequest,
     ) -> Result<RequestMetrics> {
+        discover_model(provider, &request.model).await;
         execute_single_request(
             provider,
             request,
             &self.timing_engine,
+            self.config.streaming,
+            self.pricing.as_ref(),
+            self.tokenizer.as_deref(),
         )
         .await
     }
 }
 
+/// Discover the provider's models when its static list does not know `model`
+///
+/// Models released after the static list was written are accepted once
+/// discovered. Discovery failures are logged, leaving validation to the
+/// static list.
+pub async fn discover_model<P: Provider + ?Sized>(provider: &P, model: &str) {
+    if provider.validate_model(model).is_ok() {
+        return;
+    }
+    match provider.discover_models().await {
+        Ok(models) => debug!("Discovered {} {} models", models.len(), provider.name()),
+        Err(e) => warn!("Failed to list {} models: {}", provider.name(), e),
+    }
+}
+
 /// Execute a single request and return metrics
-async fn execute_single_request<P: Provider>(
+async fn execute_single_request<P: Provider + ?Sized>(
     provider: &P,
     request: StreamingRequest,
     timing_engine: &TimingEngine,
+    streaming: bool,
+    pricing: Option<&Pricing>,
+    tokenizer: Option<&dyn Tokenizer>,
 ) -> Result<RequestMetrics> {
     let request_id = request.request_id;
     let session_id = request.session_id;
     let model = request.model.clone();
 
+    // Keep the prompt for local token counting
+    let prompt = match tokenizer {
+        Some(_) => request.messages.clone(),
+        None => Vec::new(),
+    };
+
     let start_time = chrono::Utc::now();
     let start_instant = Instant::now();
 
     // Execute the request
-    let result = provider.complete(request, timing_engine).await?;
+    let result = if streaming {
+        provider.complete(request, timing_engine).await?
+    } else {
+        provider.complete_non_streaming(request, timing_engine).await?
+    };
 
     let total_latency = start_instant.elapsed();
 
@@ -266,25 +386,79 @@ async fn execute_single_request<P: Provider>(
         .filter_map(|e| e.inter_token_latency)
         .collect();
 
-    // Calculate throughput
+    // Get token counts from provider-reported usage. When the provider
+    // reports none, count them with the tokenizer, or fall back to the
+    // number of streamed events
+    let (input_tokens, input_token_source) = match (result.metadata.input_tokens, tokenizer) {
+        (Some(tokens), _) => (tokens, TokenCountSource::Reported),
+        (None, Some(tokenizer)) => (
+            tokenizer.count_messages(&prompt),
+            TokenCountSource::Tokenizer,
+        ),
+        (None, None) => (0, TokenCountSource::Unknown),
+    };
+    let (output_tokens, thinking_tokens, output_token_source) =
+        match (result.metadata.output_tokens, tokenizer) {
+            (Some(tokens), _) => (
+                tokens,
+                result.metadata.thinking_tokens,
+                TokenCountSource::Reported,
+            ),
+            (None, Some(tokenizer)) => {
+                let thinking =
+                    (!result.thinking.is_empty()).then(|| tokenizer.count_tokens(&result.thinking));
+                (
+                    tokenizer.count_tokens(&result.content),
+                    result.metadata.thinking_tokens.or(thinking),
+                    TokenCountSource::Tokenizer,
+                )
+            }
+            (None, None) => (
+                result.output_tokens(),
+                result.metadata.thinking_tokens,
+                TokenCountSource::StreamEvents,
+            ),
+        };
+
+    // Prompt-cache usage, when the provider reports it
+    let cache_creation_tokens = result.metadata.cache_creation_tokens;
+    let cache_read_tokens = result.metadata.cache_read_tokens;
+    let cache_hit = result.metadata.cache_hit();
+
+    // Thinking/reasoning-phase timings, kept apart from TTFT
+    let time_to_first_thinking_token = result.time_to_first_thinking_token();
+    let thinking_duration = result.thinking_duration();
+    let time_to_first_visible_token = result.time_to_first_visible_token();
+    let reasoning_time = result.reasoning_time();
+
+    // Tool-call timings
+    let time_to_first_tool_call = result.time_to_first_tool_call();
+    let tool_call_duration = result.tool_call_duration();
+
+    // How the response body arrived: many events per read means buffering
+    let reads = (result.reads.reads > 0).then_some(result.reads);
+
+    // Calculate throughput (reasoning tokens are generated too)
+    let generated_tokens = output_tokens + thinking_tokens.unwrap_or(0);
     let tokens_per_second = if total_latency.as_secs_f64() > 0.0 {
-        result.token_events.len() as f64 / total_latency.as_secs_f64()
+        generated_tokens as f64 / total_latency.as_secs_f64()
     } else {
         0.0
     };
 
-    // Get token counts from metadata
-    let input_tokens = result.metadata.input_tokens.unwrap_or(0);
-    let output_tokens = result.metadata.output_tokens.unwrap_or(result.token_events.len() as u64);
-    let thinking_tokens = result.metadata.thinking_tokens;
-
-    // Get cost
-    let cost_usd = result.metadata.estimated_cost;
+    // Get cost: from the configured price table when one is set, otherwise
+    // as estimated by the provider
+    let cost_usd = match pricing {
+        Some((table, mode)) => {
+            provider.price_usage(table, &model, &result.metadata.usage(), start_time, *mode)
+        }
+        None => result.metadata.estimated_cost,
+    };
 
     Ok(RequestMetrics {
         request_id,
         session_id,
-        provider: llm_latency_lens_core::Provider::OpenAI, // TODO: Get from provider
+        provider: provider.provider_kind(),
         model,
         timestamp: start_time,
         ttft,
@@ -293,6 +467,39 @@ async fn execute_single_request<P: Provider>(
         input_tokens,
         output_tokens,
         thinking_tokens,
+        input_token_source,
+        output_token_source,
+        cache_creation_tokens,
+        cache_read_tokens,
+        cache_hit,
+        time_to_first_thinking_token,
+        thinking_duration,
+        time_to_first_visible_token,
+        reasoning_time,
+        time_to_first_tool_call,
+        tool_call_duration,
+        dns_time: result.network.dns,
+        tcp_connect_time: result.network.tcp_connect,
+        tls_handshake_time: result.network.tls_handshake,
+        time_to_first_byte: result.network.time_to_first_byte,
+        time_to_first_event: result.network.time_to_first_event,
+        session_setup_time: result.network.session_ready,
+        response_ttft: result.response_ttft(),
+        provider_request_id: result.metadata.server.request_id.clone(),
+        server_processing_time: result.metadata.server.processing_time,
+        rate_limit_remaining_requests: result.metadata.server.rate_limit.requests_remaining,
+        rate_limit_remaining_tokens: result.metadata.server.rate_limit.tokens_remaining,
+        attempts: Some(result.retries.attempts()),
+        retry_backoff: Some(result.retries.backoff),
+        network_reads: reads.map(|r| r.reads),
+        stream_events: reads.map(|r| r.events),
+        max_events_per_read: reads.map(|r| r.max_events_per_read),
+        response_body_bytes: result.body.map(|b| b.size_bytes),
+        body_download_time: result.body.map(|b| b.download_time),
+        body_parse_time: result.body.map(|b| b.parse_time),
+        embedding_inputs: None,
+        embedding_vectors: None,
+        embedding_dimensions: None,
         tokens_per_second,
         cost_usd,
         success: true,
@@ -300,6 +507,85 @@ async fn execute_single_request<P: Provider>(
     })
 }
 
+/// Execute a single embeddings request and return metrics
+///
+/// Embeddings have no stream, so TTFT and total latency are both the time
+/// until the response body was parsed, and throughput is in input tokens.
+async fn execute_single_embedding<P: EmbeddingProvider + ?Sized>(
+    provider: &P,
+    request: EmbeddingRequest,
+    timing_engine: &TimingEngine,
+) -> Result<RequestMetrics> {
+    let request_id = request.request_id;
+    let session_id = request.session_id;
+    let model = request.model.clone();
+
+    let start_time = chrono::Utc::now();
+    let result = provider.embed(request, timing_engine).await?;
+
+    let latency = result.latency();
+    let (input_tokens, input_token_source) = match result.metadata.input_tokens {
+        Some(tokens) => (tokens, TokenCountSource::Reported),
+        None => (0, TokenCountSource::Unknown),
+    };
+    let tokens_per_second = if latency.as_secs_f64() > 0.0 {
+        input_tokens as f64 / latency.as_secs_f64()
+    } else {
+        0.0
+    };
+
+    Ok(RequestMetrics {
+        request_id,
+        session_id,
+        provider: provider.provider_kind(),
+        model,
+        timestamp: start_time,
+        ttft: latency,
+        total_latency: latency,
+        inter_token_latencies: Vec::new(),
+        input_tokens,
+        output_tokens: 0,
+        thinking_tokens: None,
+        input_token_source,
+        output_token_source: TokenCountSource::Reported,
+        cache_creation_tokens: None,
+        cache_read_tokens: None,
+        cache_hit: None,
+        time_to_first_thinking_token: None,
+        thinking_duration: None,
+        time_to_first_visible_token: None,
+        reasoning_time: None,
+        time_to_first_tool_call: None,
+        tool_call_duration: None,
+        dns_time: result.network.dns,
+        tcp_connect_time: result.network.tcp_connect,
+        tls_handshake_time: result.network.tls_handshake,
+        time_to_first_byte: result.network.time_to_first_byte,
+        time_to_first_event: None,
+        session_setup_time: None,
+        response_ttft: None,
+        provider_request_id: result.metadata.server.request_id.clone(),
+        server_processing_time: result.metadata.server.processing_time,
+        rate_limit_remaining_requests: result.metadata.server.rate_limit.requests_remaining,
+        rate_limit_remaining_tokens: result.metadata.server.rate_limit.tokens_remaining,
+        attempts: Some(result.retries.attempts()),
+        retry_backoff: Some(result.retries.backoff),
+        network_reads: None,
+        stream_events: None,
+        max_events_per_read: None,
+        response_body_bytes: Some(result.body.size_bytes),
+        body_download_time: Some(result.body.download_time),
+        body_parse_time: Some(result.body.parse_time),
+        embedding_inputs: Some(result.input_count as u64),
+        embedding_vectors: Some(result.vector_count() as u64),
+        embedding_dimensions: result.dimensions().map(|d| d as u64),
+        tokens_per_second,
+        cost_usd: result.metadata.estimated_cost,
+        success: true,
+        error: None,
+    })
+}
+
 /// Summary of orchestration execution
 #[derive(Debug, Clone, Default)]
 pub struct ExecutionSummary {
@@ -341,10 +627,12 @@ mod tests {
 
     #[test]
     fn test_execution_summary_success_rate() {
-        let mut summary = ExecutionSummary::default();
-        summary.total_requests = 100;
-        summary.successful_requests = 95;
-        summary.failed_requests = 5;
+        let summary = ExecutionSummary {
+            total_requests: 100,
+            successful_requests: 95,
+            failed_requests: 5,
+            ..Default::default()
+        };
 
         assert_eq!(summary.success_rate(), 95.0);
     }
@@ -363,4 +651,39 @@ mod tests {
 
         assert_eq!(orchestrator.config.concurrency, 1);
     }
+
+    #[tokio::test]
+    async fn test_execute_single_records_adapter_provider() {
+        use llm_latency_lens_providers::{GenericOpenAIProvider, MessageRole};
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "choices": [{ "index": 0, "message": { "role": "assistant", "content": "Hi" } }],
+                "usage": { "prompt_tokens": 3, "completion_tokens": 1 }
+            })))
+            .mount(&server)
+            .await;
+
+        let provider = GenericOpenAIProvider::builder()
+            .base_url(server.uri())
+            .build()
+            .unwrap();
+        let config = OrchestratorConfig {
+            show_progress: false,
+            streaming: false,
+            ..Default::default()
+        };
+        let orchestrator = Orchestrator::new(config, Arc::new(tokio::sync::Notify::new()));
+        let request = StreamingRequest::builder()
+            .model("llama3.1:8b")
+            .message(MessageRole::User, "Hello")
+            .build();
+
+        let metrics = orchestrator.execute_single(&provider, request).await.unwrap();
+
+        assert_eq!(metrics.provider, llm_latency_lens_core::Provider::Generic);
+    }
 }